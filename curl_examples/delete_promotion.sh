@@ -0,0 +1,5 @@
+#!/bin/bash
+
+# need to change promotion_id to a running one, and the PIN to the MANAGER_PIN the server was started with
+curl --location --request DELETE 'http://localhost:3030/promotions/6a0d5a8e-2b53-4f7c-9d1e-0b9c7a3e5f21' \
+--header 'X-Manager-Pin: 2468'
@@ -0,0 +1,6 @@
+#!/bin/bash
+
+# Each stream stays open until interrupted, run one at a time
+curl --no-buffer --location 'http://localhost:3030/orders/2/events'
+curl --no-buffer --location 'http://localhost:3030/events'
+curl --no-buffer --location 'http://localhost:3030/events' --header 'Last-Event-ID: 6'
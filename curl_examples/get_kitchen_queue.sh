@@ -0,0 +1,4 @@
+#!/bin/bash
+
+curl --location 'http://localhost:3030/kitchen/queue'
+curl --location 'http://localhost:3030/kitchen/queue?station=fryer'
@@ -0,0 +1,3 @@
+#!/bin/bash
+
+curl --location 'http://localhost:3030/menus'
@@ -0,0 +1,4 @@
+#!/bin/bash
+
+# need to change order_id to one returned by POST /orders
+curl --location 'http://localhost:3030/orders/by-id/b1f1e0c2-5d7e-4a43-9a51-2f4c1b7f3c9d?include_removed_items=true'
\ No newline at end of file
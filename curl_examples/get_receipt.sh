@@ -0,0 +1,6 @@
+#!/bin/bash
+
+curl --location 'http://localhost:3030/orders/2/receipt?format=text'
+curl --location 'http://localhost:3030/orders/2/receipt?format=text&width=32'
+curl --location 'http://localhost:3030/orders/2/receipt?format=html'
+curl --location 'http://localhost:3030/orders/2/receipt'
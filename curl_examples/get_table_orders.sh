@@ -0,0 +1,3 @@
+#!/bin/bash
+
+curl --location 'http://localhost:3030/tables/2/orders?include_removed_items=true'
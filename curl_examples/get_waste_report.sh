@@ -0,0 +1,5 @@
+#!/bin/bash
+
+# need to change the PIN to the MANAGER_PIN the server was started with
+curl --location 'http://localhost:3030/reports/waste' \
+--header 'X-Manager-Pin: 2468'
@@ -0,0 +1,3 @@
+#!/bin/bash
+
+curl --location --request POST 'http://localhost:3030/orders/2/checkout'
@@ -1,5 +1,6 @@
 #!/bin/bash
 
+# need to change menu_item_ids to ones returned by POST /menus
 curl --location 'http://localhost:3030/meal-items' \
 --header 'Content-Type: application/json' \
 --data '{
@@ -7,13 +8,11 @@ curl --location 'http://localhost:3030/meal-items' \
     "menu_items": [
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1e",
-            "name": "tea",
-            "price": "123"
+            "quantity": 1
         },
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1f",
-            "name": "cheese cake",
-            "price": "749"
+            "quantity": 1
         }
     ]
 }'
\ No newline at end of file
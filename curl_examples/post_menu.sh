@@ -0,0 +1,29 @@
+#!/bin/bash
+
+# need to change the PIN to the MANAGER_PIN the server was started with
+curl --location 'http://localhost:3030/menus' \
+--header 'Content-Type: application/json' \
+--header 'X-Manager-Pin: 2468' \
+--data '{
+    "name": "Lunch",
+    "currency": "USD",
+    "menu_items": [
+        {
+            "name": "Burger",
+            "price": "855",
+            "cooking_time_profile": {
+                "type": "triangular",
+                "min_minutes": 8,
+                "most_likely_minutes": 12,
+                "max_minutes": 20
+            },
+            "category": "mains"
+        },
+        {
+            "name": "Fries",
+            "price": "349",
+            "station": "fryer",
+            "category": "sides"
+        }
+    ]
+}'
@@ -1,29 +1,28 @@
 #!/bin/bash
 
+# need to change menu_item_ids to ones returned by POST /menus
 curl --location 'http://localhost:3030/orders' \
 --header 'Content-Type: application/json' \
 --data '{
     "table_id": 2,
+    "priority": "Rush",
+    "guests": 4,
     "menu_items": [
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1a",
-            "name": "Burger",
-            "price": "855"
+            "quantity": 1
         },
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1b",
-            "name": "Fries",
-            "price": "349"
+            "quantity": 1
         },
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1c",
-            "name": "Drink",
-            "price": "350"
+            "quantity": 1
         },
         {
             "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1d",
-            "name": "Ice Cream",
-            "price": "477"
+            "quantity": 1
         }
     ]
 }'
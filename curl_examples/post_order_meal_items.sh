@@ -0,0 +1,13 @@
+#!/bin/bash
+
+# need to change order_id to one returned by POST /orders, and menu_item_ids to ones returned by POST /menus
+curl --location 'http://localhost:3030/orders/by-id/b1f1e0c2-5d7e-4a43-9a51-2f4c1b7f3c9d/meal-items' \
+--header 'Content-Type: application/json' \
+--data '{
+    "menu_items": [
+        {
+            "menu_item_id": "433e36e8-f049-475a-8fa9-0b5453770f1e",
+            "quantity": 1
+        }
+    ]
+}'
\ No newline at end of file
@@ -0,0 +1,11 @@
+#!/bin/bash
+
+# amount is in minor units of the currency of the order, method can be cash, card or other,
+# reference is required for card payments, and check, ex. "ann", once the bill is split
+curl --location 'http://localhost:3030/orders/2/payments' \
+--header 'Content-Type: application/json' \
+--data '{
+    "method": "card",
+    "amount": "1250",
+    "reference": "VISA-4242-0001"
+}'
@@ -0,0 +1,19 @@
+#!/bin/bash
+
+# need to change the PIN to the MANAGER_PIN the server was started with
+curl --location 'http://localhost:3030/promotions' \
+--header 'Content-Type: application/json' \
+--header 'X-Manager-Pin: 2468' \
+--data '{
+    "name": "Mains 10% off",
+    "currency": "USD",
+    "min_spend": "2000",
+    "reward": {
+        "type": "percent_off",
+        "target": {
+            "type": "category",
+            "category": "mains"
+        },
+        "percent": 10
+    }
+}'
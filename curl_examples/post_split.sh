@@ -0,0 +1,17 @@
+#!/bin/bash
+
+# need to change meal_item_ids to ones of the order, see post_checkout.sh; or send {"guests": 3} to split it evenly
+curl --location 'http://localhost:3030/orders/2/split' \
+--header 'Content-Type: application/json' \
+--data '{
+    "checks": [
+        {
+            "name": "ann",
+            "meal_item_ids": ["ee5c8739-10a8-4b56-9c3c-7104dbfd286f"]
+        },
+        {
+            "name": "bob",
+            "meal_item_ids": ["5063168b-246b-4abb-9811-39b0c939cf51"]
+        }
+    ]
+}'
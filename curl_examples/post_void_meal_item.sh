@@ -0,0 +1,9 @@
+#!/bin/bash
+
+# need to change order_id and meal_item_id to ones being prepared, and the PIN to the MANAGER_PIN the server was started with
+curl --location 'http://localhost:3030/orders/by-id/b1f1e0c2-5d7e-4a43-9a51-2f4c1b7f3c9d/meal-items/ee5c8739-10a8-4b56-9c3c-7104dbfd286f/void' \
+--header 'Content-Type: application/json' \
+--header 'X-Manager-Pin: 2468' \
+--data '{
+    "reason": "guest left"
+}'
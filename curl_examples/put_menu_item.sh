@@ -0,0 +1,9 @@
+#!/bin/bash
+
+# need to change menu_id and menu_item_id, and the PIN to the MANAGER_PIN the server was started with
+curl --location --request PUT 'http://localhost:3030/menus/433e36e8-f049-475a-8fa9-0b5453770f10/menu-items/433e36e8-f049-475a-8fa9-0b5453770f1a' \
+--header 'Content-Type: application/json' \
+--header 'X-Manager-Pin: 2468' \
+--data '{
+    "price": "899"
+}'
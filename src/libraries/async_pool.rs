@@ -0,0 +1,140 @@
+use std::sync::{Arc, Condvar, Mutex};
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+use crate::libraries::clock::Clock;
+use crate::libraries::interrupt::Interrupt;
+use crate::libraries::job::{Job, JobInfo, JobStep};
+use crate::libraries::job_queue::{JobQueue, SchedulingPolicy};
+use crate::libraries::thread_pool::ThreadPoolDyn;
+use crate::libraries::worker::run_step_as;
+
+// Runs jobs as tokio tasks instead of one thread per worker. A semaphore holds a permit per worker, so no more jobs run
+// at the same time than there are workers, and a job waiting, ex. while cooking, awaits a timer on the clock without
+// holding a thread. Hundreds of workers and thousands of queued jobs only cost as many tasks.
+pub struct AsyncPool {
+    size: usize,
+    queue: Arc<Mutex<JobQueue>>,
+    workers: Arc<Semaphore>,
+    // Ids of the workers not running a job, one for every available permit
+    idle_worker_ids: Arc<Mutex<Vec<usize>>>,
+    // Number of tasks not done yet, the condvar wakes up the pool being dropped once there are none left
+    running: Arc<(Mutex<usize>, Condvar)>,
+    clock: Arc<dyn Clock>,
+    interrupt: Arc<Interrupt>,
+    runtime: Handle,
+}
+
+impl AsyncPool {
+    // Must be created within a tokio runtime, which runs the jobs from then on
+    pub fn new(size: usize, policy: SchedulingPolicy, clock: Arc<dyn Clock>) -> Self {
+        AsyncPool {
+            size,
+            queue: Arc::new(Mutex::new(JobQueue::new(policy))),
+            workers: Arc::new(Semaphore::new(size)),
+            idle_worker_ids: Arc::new(Mutex::new((0..size).rev().collect())),
+            running: Arc::new((Mutex::new(0), Condvar::new())),
+            clock,
+            interrupt: Arc::new(Interrupt::new()),
+            runtime: Handle::current(),
+        }
+    }
+}
+
+// Counts the task as done even if its job panics
+struct RunningGuard(Arc<(Mutex<usize>, Condvar)>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        let (count, done) = &*self.0;
+        *count.lock().unwrap() -= 1;
+        done.notify_all();
+    }
+}
+
+// Worker running a job, which is idle again once dropped, even if its job panics
+struct BusyWorker {
+    id: usize,
+    idle_worker_ids: Arc<Mutex<Vec<usize>>>,
+}
+
+impl BusyWorker {
+    fn take(idle_worker_ids: Arc<Mutex<Vec<usize>>>) -> Self {
+        let id = idle_worker_ids.lock().unwrap().pop().expect("a worker is idle for every permit");
+        BusyWorker { id, idle_worker_ids }
+    }
+}
+
+impl Drop for BusyWorker {
+    fn drop(&mut self) {
+        self.idle_worker_ids.lock().unwrap().push(self.id);
+    }
+}
+
+impl ThreadPoolDyn for AsyncPool {
+    // Every job gets a task waiting for a worker. Once it has one, the task runs whichever queued job should run next,
+    // which may not be the job it was spawned for, so the queue decides the order as with ThreadPool.
+    fn execute(&self, job: Job, info: JobInfo) {
+        self.queue.lock().unwrap().push(job, info);
+        *self.running.0.lock().unwrap() += 1;
+        let guard = RunningGuard(self.running.clone());
+
+        let queue = self.queue.clone();
+        let workers = self.workers.clone();
+        let idle_worker_ids = self.idle_worker_ids.clone();
+        let clock = self.clock.clone();
+        let interrupt = self.interrupt.clone();
+        self.runtime.spawn(async move {
+            let _guard = guard;
+            let _permit = workers.acquire().await.expect("worker semaphore is never closed");
+            // None once the job was taken off the queue without running, see cancel_queued_jobs
+            let next = queue.lock().unwrap().pop(clock.now());
+            if let Some((job, _)) = next {
+                let worker = BusyWorker::take(idle_worker_ids);
+                let id = worker.id;
+                println!("Worker {id} starts executing the job");
+                let job_interrupt = interrupt.child();
+                let mut step = run_step_as(id, &interrupt, &job_interrupt, job);
+                while let JobStep::Wait(duration, rest) = step {
+                    tokio::select! {
+                        _ = clock.sleep_async(duration) => {}
+                        _ = job_interrupt.interrupted() => {}
+                    }
+                    step = run_step_as(id, &interrupt, &job_interrupt, rest);
+                }
+                println!("Worker {id} finished the job");
+            }
+        });
+    }
+
+    fn queued_jobs(&self) -> Vec<JobInfo> {
+        self.queue.lock().unwrap().infos(self.clock.now())
+    }
+
+    fn worker_count(&self) -> usize {
+        self.size
+    }
+
+    fn cancel_queued_jobs(&self) -> Vec<JobInfo> {
+        self.queue.lock().unwrap().clear(self.clock.now())
+    }
+
+    fn interrupt(&self) {
+        self.interrupt.interrupt();
+    }
+}
+
+// Same as dropping ThreadPool, waits for every queued job to run, so it must not be dropped on a thread the runtime
+// needs to run them, ex. within a task of a single threaded runtime. Without workers, the queued jobs never run.
+impl Drop for AsyncPool {
+    fn drop(&mut self) {
+        println!("Shutting down {} workers", self.size);
+        if self.size == 0 {
+            return;
+        }
+        let (count, done) = &*self.running;
+        let mut count = count.lock().unwrap();
+        while *count > 0 {
+            count = done.wait(count).unwrap();
+        }
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod async_pool_test {
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use chrono::Utc;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+    use crate::libraries::async_pool::AsyncPool;
+    use crate::libraries::clock::{SystemClock, VirtualClock};
+    use crate::libraries::job::{Job, JobInfo, JobStep};
+    use crate::libraries::job_queue::SchedulingPolicy;
+    use crate::libraries::thread_pool::ThreadPoolDyn;
+    use crate::libraries::worker::current_worker_id;
+
+    fn job_info(priority: u8, duration_in_sec: u64) -> JobInfo {
+        JobInfo {
+            id: Uuid::new_v4(),
+            priority,
+            duration: Duration::from_secs(duration_in_sec),
+            requested_at: Utc::now(),
+        }
+    }
+
+    // Job waiting for the given time before it records that it is done
+    fn waiting_job(duration: Duration, done: Arc<AtomicUsize>) -> Job {
+        Box::new(move || JobStep::Wait(duration, Box::new(move || {
+            done.fetch_add(1, Ordering::SeqCst);
+            JobStep::Done
+        })))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_jobs_run_on_workers() {
+        let pool = AsyncPool::new(2, SchedulingPolicy::Fifo, Arc::new(SystemClock));
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        assert_eq!(2, pool.worker_count());
+
+        for _ in 0..4 {
+            let sender = sender.clone();
+            pool.execute(Box::new(move || {
+                sender.send(current_worker_id()).unwrap();
+                JobStep::Done
+            }), job_info(0, 1));
+        }
+
+        for _ in 0..4 {
+            let worker_id = receiver.recv().await.unwrap();
+            assert!(matches!(worker_id, Some(0) | Some(1)));
+        }
+        assert_eq!(None, current_worker_id());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_hundreds_of_workers_on_virtual_clock() {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let pool = AsyncPool::new(300, SchedulingPolicy::Fifo, clock.clone());
+        let done = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..1200 {
+            pool.execute(waiting_job(Duration::from_secs(10 * 60), done.clone()), job_info(0, 10 * 60));
+        }
+
+        // Given every worker is waiting on a job, the others stay queued until a worker is free
+        for round in 1..=4 {
+            clock.wait_for_sleepers(300);
+            assert_eq!(1200 - round * 300, pool.queued_jobs().len());
+            clock.advance(Duration::from_secs(10 * 60));
+        }
+
+        // Dropping the pool waits for every job to finish
+        drop(pool);
+        assert_eq!(1200, done.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_queued_jobs_run_by_priority() {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let pool = AsyncPool::new(1, SchedulingPolicy::ShortestFirst, clock.clone());
+        let ran = Arc::new(Mutex::new(Vec::new()));
+
+        // Given the only worker is busy, the jobs queued meanwhile run by priority, then by duration
+        pool.execute(waiting_job(Duration::from_secs(60), Arc::new(AtomicUsize::new(0))), job_info(0, 60));
+        clock.wait_for_sleepers(1);
+
+        let long = job_info(0, 15);
+        let short = job_info(0, 5);
+        let vip = job_info(2, 15);
+        for info in [long, short, vip] {
+            let ran = ran.clone();
+            pool.execute(Box::new(move || {
+                ran.lock().unwrap().push(info.id);
+                JobStep::Done
+            }), info);
+        }
+        assert_eq!(vec![vip.id, short.id, long.id], pool.queued_jobs().iter().map(|info| info.id).collect::<Vec<_>>());
+
+        clock.advance(Duration::from_secs(60));
+        drop(pool);
+        assert_eq!(vec![vip.id, short.id, long.id], *ran.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_queued_jobs() {
+        let clock = Arc::new(VirtualClock::new(Utc::now()));
+        let pool = AsyncPool::new(1, SchedulingPolicy::Fifo, clock.clone());
+        let done = Arc::new(AtomicUsize::new(0));
+
+        pool.execute(waiting_job(Duration::from_secs(60), done.clone()), job_info(0, 60));
+        clock.wait_for_sleepers(1);
+        let queued = job_info(0, 60);
+        pool.execute(waiting_job(Duration::from_secs(60), done.clone()), queued);
+
+        // Given the queued job is cancelled, only the running one finishes
+        assert_eq!(vec![queued], pool.cancel_queued_jobs());
+        clock.advance(Duration::from_secs(60));
+        drop(pool);
+        assert_eq!(1, done.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,171 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use crate::libraries::interrupt::Interrupt;
+#[cfg(test)]
+use std::sync::{Condvar, Mutex};
+#[cfg(test)]
+use chrono::TimeDelta;
+#[cfg(test)]
+use tokio::sync::watch;
+
+// Future resolving once the time asked for has passed on the clock
+pub type Sleep<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+// Source of time for the models and the workers, so the kitchen can run faster than the wall clock or, in tests, without waiting at all
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+    // Blocks the calling thread until the given time has passed on the clock, or until interrupted if that comes first
+    fn sleep(&self, duration: Duration, interrupt: &Interrupt);
+    // Same as sleep without blocking the calling thread, for tasks run by tokio
+    fn sleep_async(&self, duration: Duration) -> Sleep<'_>;
+    // Time the kitchen really takes for work of the given duration, ex. cooking a dish of 10 minutes
+    fn kitchen_duration(&self, duration: Duration) -> Duration {
+        duration
+    }
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration, interrupt: &Interrupt) {
+        interrupt.wait_timeout(duration)
+    }
+
+    fn sleep_async(&self, duration: Duration) -> Sleep<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+// Follows the wall clock, only the kitchen works the given times faster, ex. with 60 a minute of cooking takes a second.
+// Every time it gives is a wall clock time, so times stored before a restart stay in order with those after it.
+pub struct ScaledClock {
+    scale: f64,
+}
+
+impl ScaledClock {
+    pub fn new(scale: f64) -> Self {
+        ScaledClock {
+            scale,
+        }
+    }
+}
+
+impl Clock for ScaledClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration, interrupt: &Interrupt) {
+        interrupt.wait_timeout(duration)
+    }
+
+    fn sleep_async(&self, duration: Duration) -> Sleep<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn kitchen_duration(&self, duration: Duration) -> Duration {
+        duration.div_f64(self.scale)
+    }
+}
+
+#[cfg(test)]
+const INTERRUPT_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+// Only moves when advanced, so tests decide exactly when cooking finishes
+#[cfg(test)]
+pub struct VirtualClock {
+    state: Mutex<VirtualClockState>,
+    changed: Condvar,
+    // Tells the tasks sleeping asynchronously what time it is
+    now_sender: watch::Sender<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+struct VirtualClockState {
+    now: DateTime<Utc>,
+    // Times the sleeping threads wake up at
+    wake_times: Vec<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl VirtualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        VirtualClock {
+            state: Mutex::new(VirtualClockState { now, wake_times: Vec::new() }),
+            changed: Condvar::new(),
+            now_sender: watch::channel(now).0,
+        }
+    }
+
+    // Moves the clock forward, waking up every thread or task sleeping until then
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += TimeDelta::from_std(duration).unwrap();
+        let now = state.now;
+        state.wake_times.retain(|wake_time| *wake_time > now);
+        self.changed.notify_all();
+        self.now_sender.send_replace(now);
+    }
+
+    // Blocks until the given number of threads or tasks are sleeping, ex. until every worker has started cooking
+    pub fn wait_for_sleepers(&self, count: usize) {
+        let mut state = self.state.lock().unwrap();
+        while state.wake_times.len() != count {
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+impl Clock for VirtualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap().now
+    }
+
+    // The interrupt can't wake up the threads waiting for the clock to move, so they check it now and then
+    fn sleep(&self, duration: Duration, interrupt: &Interrupt) {
+        let mut state = self.state.lock().unwrap();
+        let wake_time = state.now + TimeDelta::from_std(duration).unwrap();
+        if wake_time <= state.now {
+            return;
+        }
+        state.wake_times.push(wake_time);
+        self.changed.notify_all();
+        while state.now < wake_time {
+            if interrupt.is_interrupted() {
+                if let Some(index) = state.wake_times.iter().position(|time| *time == wake_time) {
+                    state.wake_times.remove(index);
+                }
+                self.changed.notify_all();
+                return;
+            }
+            state = self.changed.wait_timeout(state, INTERRUPT_CHECK_INTERVAL).unwrap().0;
+        }
+    }
+
+    fn sleep_async(&self, duration: Duration) -> Sleep<'_> {
+        let mut now_receiver = self.now_sender.subscribe();
+        let wake_time = {
+            let mut state = self.state.lock().unwrap();
+            let wake_time = state.now + TimeDelta::from_std(duration).unwrap();
+            if wake_time > state.now {
+                state.wake_times.push(wake_time);
+                self.changed.notify_all();
+            }
+            wake_time
+        };
+        Box::pin(async move {
+            while *now_receiver.borrow_and_update() < wake_time {
+                if now_receiver.changed().await.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+}
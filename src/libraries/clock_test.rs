@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod clock_test {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use chrono::{TimeDelta, Utc};
+    use crate::libraries::clock::{Clock, ScaledClock, SystemClock, VirtualClock};
+    use crate::libraries::interrupt::Interrupt;
+
+    #[test]
+    fn test_scaled_clock() {
+        let clock = ScaledClock::new(600.0);
+        let before = Utc::now();
+
+        // Given the kitchen cooks 600 times faster, a minute of cooking takes a tenth of a second, while the time stays the wall clock's
+        assert_eq!(Duration::from_millis(100), clock.kitchen_duration(Duration::from_secs(60)));
+        let started = std::time::Instant::now();
+        clock.sleep(clock.kitchen_duration(Duration::from_secs(60)), &Interrupt::new());
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(clock.now() >= before);
+        assert!(clock.now() - before < TimeDelta::seconds(5));
+    }
+
+    #[test]
+    fn test_system_and_virtual_clocks_cook_at_their_own_pace() {
+        assert_eq!(Duration::from_secs(60), SystemClock.kitchen_duration(Duration::from_secs(60)));
+        assert_eq!(Duration::from_secs(60), VirtualClock::new(Utc::now()).kitchen_duration(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_virtual_clock_only_moves_when_advanced() {
+        let start = Utc::now();
+        let clock = VirtualClock::new(start);
+        assert_eq!(start, clock.now());
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(start + TimeDelta::seconds(90), clock.now());
+        clock.sleep(Duration::ZERO, &Interrupt::new());
+        assert_eq!(start + TimeDelta::seconds(90), clock.now());
+    }
+
+    #[test]
+    fn test_virtual_clock_wakes_sleepers_when_advanced() {
+        let start = Utc::now();
+        let clock = Arc::new(VirtualClock::new(start));
+
+        let short = { let clock = clock.clone(); thread::spawn(move || { clock.sleep(Duration::from_secs(60), &Interrupt::new()); clock.now() }) };
+        let long = { let clock = clock.clone(); thread::spawn(move || { clock.sleep(Duration::from_secs(120), &Interrupt::new()); clock.now() }) };
+        clock.wait_for_sleepers(2);
+
+        // Given the clock is advanced past the first wake up time only, the second thread keeps sleeping
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(start + TimeDelta::seconds(60), short.join().unwrap());
+        clock.wait_for_sleepers(1);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(start + TimeDelta::seconds(150), long.join().unwrap());
+        clock.wait_for_sleepers(0);
+    }
+
+    #[test]
+    fn test_sleep_interrupted() {
+        let interrupt = Arc::new(Interrupt::new());
+        let start = Utc::now();
+        let virtual_clock = Arc::new(VirtualClock::new(start));
+        let scaled_clock = Arc::new(ScaledClock::new(1.0));
+
+        let virtual_sleeper = { let (clock, interrupt) = (virtual_clock.clone(), interrupt.clone()); thread::spawn(move || clock.sleep(Duration::from_secs(60), &interrupt)) };
+        let scaled_sleeper = { let (clock, interrupt) = (scaled_clock.clone(), interrupt.clone()); thread::spawn(move || clock.sleep(Duration::from_secs(3600), &interrupt)) };
+        virtual_clock.wait_for_sleepers(1);
+
+        // Given both are interrupted, neither waits for its time to pass
+        let started = std::time::Instant::now();
+        interrupt.interrupt();
+        virtual_sleeper.join().unwrap();
+        scaled_sleeper.join().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(start, virtual_clock.now());
+        virtual_clock.wait_for_sleepers(0);
+        // Once interrupted, sleeping returns at once
+        scaled_clock.sleep(Duration::from_secs(3600), &interrupt);
+    }
+
+    #[test]
+    fn test_child_interrupt() {
+        let interrupt = Interrupt::new();
+        let first = interrupt.child();
+        let second = interrupt.child();
+
+        // Given only the first child is interrupted, the parent and the second child keep waiting
+        first.interrupt();
+        assert!(first.is_interrupted());
+        assert!(!second.is_interrupted());
+        assert!(!interrupt.is_interrupted());
+
+        // Given the parent is interrupted, so are its children, including those it gets afterwards
+        interrupt.interrupt();
+        assert!(second.is_interrupted());
+        assert!(interrupt.child().is_interrupted());
+        SystemClock.sleep(Duration::from_secs(3600), &second);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_virtual_clock_wakes_tasks_when_advanced() {
+        let start = Utc::now();
+        let clock = Arc::new(VirtualClock::new(start));
+
+        let sleeper = { let clock = clock.clone(); tokio::spawn(async move { clock.sleep_async(Duration::from_secs(60)).await; clock.now() }) };
+        clock.wait_for_sleepers(1);
+
+        // Given the task is sleeping without holding a thread, advancing the clock wakes it up
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(start + TimeDelta::seconds(60), sleeper.await.unwrap());
+        clock.wait_for_sleepers(0);
+        clock.sleep_async(Duration::ZERO).await;
+    }
+
+    #[tokio::test]
+    async fn test_scaled_clock_sleeps_asynchronously() {
+        let clock = ScaledClock::new(600.0);
+        let started = std::time::Instant::now();
+
+        clock.sleep_async(clock.kitchen_duration(Duration::from_secs(60))).await;
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
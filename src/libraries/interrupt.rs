@@ -0,0 +1,65 @@
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
+use tokio::sync::watch;
+
+// Set once the workers of a pool must stop waiting in their jobs, ex. when the kitchen is out of time to drain on shutdown.
+// Wakes up the workers waiting until then, whether they block a thread or await.
+pub struct Interrupt {
+    interrupted: Mutex<bool>,
+    changed: Condvar,
+    interrupted_sender: watch::Sender<bool>,
+    // Interrupted along with this one, ex. the interrupt of every job the pool is running
+    children: Mutex<Vec<Weak<Interrupt>>>,
+}
+
+impl Interrupt {
+    pub fn new() -> Self {
+        Interrupt {
+            interrupted: Mutex::new(false),
+            changed: Condvar::new(),
+            interrupted_sender: watch::channel(false).0,
+            children: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn interrupt(&self) {
+        *self.interrupted.lock().unwrap() = true;
+        self.changed.notify_all();
+        self.interrupted_sender.send_replace(true);
+        for child in self.children.lock().unwrap().drain(..) {
+            if let Some(child) = child.upgrade() {
+                child.interrupt();
+            }
+        }
+    }
+
+    // Interrupt which can be interrupted on its own, ex. to wake up a single job, and is interrupted along with this one
+    pub fn child(&self) -> Arc<Interrupt> {
+        let child = Arc::new(Interrupt::new());
+        let mut children = self.children.lock().unwrap();
+        if self.is_interrupted() {
+            child.interrupt();
+        } else {
+            children.retain(|child| child.strong_count() > 0);
+            children.push(Arc::downgrade(&child));
+        }
+        child
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        *self.interrupted.lock().unwrap()
+    }
+
+    // Blocks the calling thread for the given time on the wall clock, or until interrupted if that comes first
+    pub fn wait_timeout(&self, timeout: Duration) {
+        let interrupted = self.interrupted.lock().unwrap();
+        let _ = self.changed.wait_timeout_while(interrupted, timeout, |interrupted| !*interrupted).unwrap();
+    }
+
+    // Resolves once interrupted
+    pub async fn interrupted(&self) {
+        let mut interrupted_receiver = self.interrupted_sender.subscribe();
+        // The sender lives as long as self, so this never fails
+        let _ = interrupted_receiver.wait_for(|interrupted| *interrupted).await;
+    }
+}
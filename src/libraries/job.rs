@@ -1 +1,29 @@
-pub type Job = Box<dyn FnOnce() + Send + 'static>;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// A job never blocks for the time it waits, ex. while cooking: it returns the wait to the worker running it,
+// which sleeps on the clock, or awaits a timer without holding a thread, and then runs the rest of the job
+pub type Job = Box<dyn FnOnce() -> JobStep + Send + 'static>;
+
+// What the worker does once a step of the job has run
+pub enum JobStep {
+    Done,
+    // Waits for the given time on the clock, then runs the rest of the job; the worker stays busy meanwhile.
+    // Once the pool is interrupted, the rest runs at once and should stop the job, see is_interrupted.
+    // It also runs at once when only the job is interrupted, see current_job_interrupt.
+    Wait(Duration, Job),
+}
+
+// What the pool knows about a job when picking the next one to run
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JobInfo {
+    // Id of what the job works on, ex. the meal item being cooked
+    pub id: Uuid,
+    // Jobs of higher priority always run before those of lower priority, whatever the scheduling policy
+    pub priority: u8,
+    // Expected time the job takes to run
+    pub duration: Duration,
+    // When the work the job belongs to was requested, ex. when the order was placed
+    pub requested_at: DateTime<Utc>,
+}
@@ -0,0 +1,117 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use crate::libraries::job::{Job, JobInfo};
+
+// How the pool picks the next job among those of the same priority
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SchedulingPolicy {
+    // In the order the jobs were queued
+    #[default]
+    Fifo,
+    // Shortest job first, so a long job doesn't hold back the short ones queued behind it
+    ShortestFirst,
+    // Highest response ratio first, (time since requested + duration) / duration, so short jobs go first
+    // but long jobs move up as their request gets older and are never starved
+    OrderAge,
+}
+
+impl FromStr for SchedulingPolicy {
+    type Err = String;
+
+    // Accepts "fifo", "shortest-first", or "order-age"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fifo" => Ok(SchedulingPolicy::Fifo),
+            "shortest-first" => Ok(SchedulingPolicy::ShortestFirst),
+            "order-age" => Ok(SchedulingPolicy::OrderAge),
+            _ => Err(format!("invalid scheduling policy '{}'", s)),
+        }
+    }
+}
+
+struct QueuedJob {
+    seq: u64,
+    info: JobInfo,
+    job: Job,
+}
+
+// Jobs waiting for a worker. The next job is picked when a worker asks for one,
+// since with OrderAge the order of the jobs changes as time goes by.
+pub struct JobQueue {
+    policy: SchedulingPolicy,
+    jobs: Vec<QueuedJob>,
+    next_seq: u64,
+    closed: bool,
+}
+
+impl JobQueue {
+    pub fn new(policy: SchedulingPolicy) -> Self {
+        JobQueue {
+            policy,
+            jobs: Vec::new(),
+            next_seq: 0,
+            closed: false,
+        }
+    }
+
+    pub fn push(&mut self, job: Job, info: JobInfo) {
+        self.jobs.push(QueuedJob { seq: self.next_seq, info, job });
+        self.next_seq += 1;
+    }
+
+    // Removes and returns the job which should run next at the given time
+    pub fn pop(&mut self, now: DateTime<Utc>) -> Option<(Job, JobInfo)> {
+        let index = (0..self.jobs.len())
+            .min_by(|&a, &b| self.compare(&self.jobs[a], &self.jobs[b], now))?;
+        let queued_job = self.jobs.remove(index);
+        Some((queued_job.job, queued_job.info))
+    }
+
+    // Returns the info of every queued job, in the order they would run from the given time
+    pub fn infos(&self, now: DateTime<Utc>) -> Vec<JobInfo> {
+        let mut jobs: Vec<&QueuedJob> = self.jobs.iter().collect();
+        jobs.sort_by(|a, b| self.compare(a, b, now));
+        jobs.into_iter().map(|queued_job| queued_job.info).collect()
+    }
+
+    // Removes every job without running it, returns their info in the order they would have run at the given time
+    pub fn clear(&mut self, now: DateTime<Utc>) -> Vec<JobInfo> {
+        let infos = self.infos(now);
+        self.jobs.clear();
+        infos
+    }
+
+    // Once closed, the workers finish the queued jobs and stop
+    pub fn close(&mut self) {
+        self.closed = true;
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    // Ordering::Less means a runs before b
+    fn compare(&self, a: &QueuedJob, b: &QueuedJob, now: DateTime<Utc>) -> Ordering {
+        let by_policy = match self.policy {
+            SchedulingPolicy::Fifo => Ordering::Equal,
+            SchedulingPolicy::ShortestFirst => a.info.duration.cmp(&b.info.duration),
+            SchedulingPolicy::OrderAge => {
+                // Compares the response ratios without dividing: (wait_a + d_a) / d_a > (wait_b + d_b) / d_b
+                let (wait_a, duration_a) = response_ratio_terms(&a.info, now);
+                let (wait_b, duration_b) = response_ratio_terms(&b.info, now);
+                ((wait_b + duration_b) * duration_a).cmp(&((wait_a + duration_a) * duration_b))
+            }
+        };
+        b.info.priority.cmp(&a.info.priority)
+            .then(by_policy)
+            .then(a.seq.cmp(&b.seq))
+    }
+}
+
+// Returns the time since the job was requested and its duration, in milliseconds
+fn response_ratio_terms(info: &JobInfo, now: DateTime<Utc>) -> (i128, i128) {
+    let wait = (now - info.requested_at).num_milliseconds().max(0) as i128;
+    let duration = (info.duration.as_millis() as i128).max(1);
+    (wait, duration)
+}
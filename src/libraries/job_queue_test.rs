@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod job_queue_test {
+    use std::time::Duration;
+    use chrono::{TimeDelta, Utc};
+    use uuid::Uuid;
+    use crate::libraries::job::{JobInfo, JobStep};
+    use crate::libraries::job_queue::{JobQueue, SchedulingPolicy};
+
+    fn job_info(priority: u8, duration_in_sec: u64, requested_sec_ago: i64) -> JobInfo {
+        JobInfo {
+            id: Uuid::new_v4(),
+            priority,
+            duration: Duration::from_secs(duration_in_sec),
+            requested_at: Utc::now() - TimeDelta::seconds(requested_sec_ago),
+        }
+    }
+
+    fn queue_of(policy: SchedulingPolicy, infos: &[JobInfo]) -> JobQueue {
+        let mut queue = JobQueue::new(policy);
+        for info in infos.iter() {
+            queue.push(Box::new(|| JobStep::Done), *info);
+        }
+        queue
+    }
+
+    fn popped_ids(queue: &mut JobQueue) -> Vec<Uuid> {
+        let mut ids = Vec::new();
+        while let Some((_, info)) = queue.pop(Utc::now()) {
+            ids.push(info.id);
+        }
+        ids
+    }
+
+    #[test]
+    fn test_parse_scheduling_policy() {
+        assert_eq!(Ok(SchedulingPolicy::Fifo), "fifo".parse());
+        assert_eq!(Ok(SchedulingPolicy::ShortestFirst), "shortest-first".parse());
+        assert_eq!(Ok(SchedulingPolicy::OrderAge), "order-age".parse());
+        assert!("random".parse::<SchedulingPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_fifo() {
+        let long = job_info(0, 15, 0);
+        let short = job_info(0, 5, 0);
+        let mut queue = queue_of(SchedulingPolicy::Fifo, &[long, short]);
+
+        assert_eq!(vec![long.id, short.id], popped_ids(&mut queue));
+        assert!(queue.pop(Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_shortest_first() {
+        let long = job_info(0, 15, 0);
+        let short = job_info(0, 5, 0);
+        let other_short = job_info(0, 5, 0);
+        let mut queue = queue_of(SchedulingPolicy::ShortestFirst, &[long, short, other_short]);
+
+        // Jobs of the same duration keep the order they were queued in
+        assert_eq!(vec![short.id, other_short.id, long.id], queue.infos(Utc::now()).iter().map(|info| info.id).collect::<Vec<_>>());
+        assert_eq!(vec![short.id, other_short.id, long.id], popped_ids(&mut queue));
+    }
+
+    #[test]
+    fn test_order_age() {
+        // Given the long job was requested long ago, its response ratio (600 + 15) / 15 beats (1 + 5) / 5,
+        // while among the new jobs the short one goes first, (1 + 5) / 5 beats (1 + 15) / 15
+        let old_long = job_info(0, 15, 600);
+        let new_short = job_info(0, 5, 1);
+        let new_long = job_info(0, 15, 1);
+        let mut queue = queue_of(SchedulingPolicy::OrderAge, &[new_long, new_short, old_long]);
+
+        assert_eq!(vec![old_long.id, new_short.id, new_long.id], popped_ids(&mut queue));
+    }
+
+    #[test]
+    fn test_priority_jumps_the_queue() {
+        let normal = job_info(0, 5, 600);
+        let rush = job_info(1, 15, 0);
+        let vip = job_info(2, 15, 0);
+
+        for policy in [SchedulingPolicy::Fifo, SchedulingPolicy::ShortestFirst, SchedulingPolicy::OrderAge] {
+            let mut queue = queue_of(policy, &[normal, rush, vip]);
+            assert_eq!(vec![vip.id, rush.id, normal.id], popped_ids(&mut queue));
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let long = job_info(0, 15, 0);
+        let short = job_info(0, 5, 0);
+        let mut queue = queue_of(SchedulingPolicy::ShortestFirst, &[long, short]);
+
+        let cleared_ids: Vec<Uuid> = queue.clear(Utc::now()).iter().map(|info| info.id).collect();
+        assert_eq!(vec![short.id, long.id], cleared_ids);
+        assert!(queue.pop(Utc::now()).is_none());
+    }
+}
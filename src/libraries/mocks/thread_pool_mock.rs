@@ -1,18 +1,28 @@
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
+use crate::libraries::job::{Job, JobInfo};
 use crate::libraries::thread_pool::ThreadPoolDyn;
 
 pub struct MockThreadPool {
     counter: Arc<AtomicUsize>,
     threads: Arc<Mutex<Vec<thread::JoinHandle<()>>>>,
+    // Jobs are never run, so every job executed stays queued
+    queued_jobs: Mutex<Vec<JobInfo>>,
+    worker_count: usize,
 }
 
 impl MockThreadPool {
     pub fn new() -> Self {
+        MockThreadPool::with_worker_count(2)
+    }
+
+    pub fn with_worker_count(worker_count: usize) -> Self {
         MockThreadPool {
             counter: Arc::new(AtomicUsize::new(0)),
             threads: Arc::new(Mutex::new(Vec::new())), //plus main thread
+            queued_jobs: Mutex::new(Vec::new()),
+            worker_count,
         }
     }
 
@@ -29,7 +39,8 @@ impl MockThreadPool {
 }
 
 impl ThreadPoolDyn for MockThreadPool {
-    fn execute(&self, _: Box<dyn FnOnce() + Send + 'static>) {
+    fn execute(&self, _: Job, info: JobInfo) {
+        self.queued_jobs.lock().unwrap().push(info);
         let counter = self.counter.clone();
         let mut threads = self.threads.lock().unwrap();
         let handle = thread::spawn(move || {
@@ -37,4 +48,19 @@ impl ThreadPoolDyn for MockThreadPool {
         });
         threads.push(handle);
     }
-}
\ No newline at end of file
+
+    fn queued_jobs(&self) -> Vec<JobInfo> {
+        self.queued_jobs.lock().unwrap().clone()
+    }
+
+    fn worker_count(&self) -> usize {
+        self.worker_count
+    }
+
+    fn cancel_queued_jobs(&self) -> Vec<JobInfo> {
+        std::mem::take(&mut *self.queued_jobs.lock().unwrap())
+    }
+
+    // Jobs are never run, so none waits
+    fn interrupt(&self) {}
+}
@@ -1,4 +1,13 @@
+pub mod async_pool;
+pub mod clock;
+pub mod interrupt;
+pub mod shutdown;
 pub mod thread_pool;
 pub mod worker;
 pub mod job;
-pub mod mocks;
\ No newline at end of file
+pub mod job_queue;
+pub mod mocks;
+mod async_pool_test;
+mod clock_test;
+mod job_queue_test;
+mod thread_pool_test;
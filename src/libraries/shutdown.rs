@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Whether the server is shutting down. While it drains, it keeps answering reads but refuses every change.
+pub struct Shutdown {
+    draining: AtomicBool,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Shutdown {
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    pub fn start_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+}
+
+// Resolves once the process is asked to stop, by Ctrl-C or, on unix, by SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("failed to listen for Ctrl-C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
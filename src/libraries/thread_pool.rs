@@ -1,54 +1,83 @@
-use std::sync::{Arc, mpsc, Mutex};
-use std::sync::mpsc::Sender;
-use crate::libraries::job::Job;
+use std::sync::{Arc, Condvar, Mutex};
+use crate::libraries::clock::Clock;
+use crate::libraries::interrupt::Interrupt;
+use crate::libraries::job::{Job, JobInfo};
+use crate::libraries::job_queue::{JobQueue, SchedulingPolicy};
 use crate::libraries::worker::Worker;
 
-// F: FnOnce() + Send + 'static; means that F is a function or closure that can be executed once,
+// Jobs are FnOnce() + Send + 'static; means that a job is a function or closure that can be executed once,
 // can be sent between threads (Send), and does not capture any non-static references ('static).
 pub trait ThreadPoolDyn: Send + Sync {
-    fn execute(&self, job: Box<dyn FnOnce() + Send + 'static>);
+    fn execute(&self, job: Job, info: JobInfo);
+    // Returns the info of the jobs waiting for a worker, in the order they would run now
+    fn queued_jobs(&self) -> Vec<JobInfo>;
+    // Number of workers running jobs at the same time
+    fn worker_count(&self) -> usize;
+    // Takes the jobs waiting for a worker off the queue without running them, returns their info in the order they would have run
+    fn cancel_queued_jobs(&self) -> Vec<JobInfo>;
+    // Wakes up the workers waiting in a job, which run the rest of it at once, and those which wait later don't wait anymore.
+    // Jobs can tell, see is_interrupted, and are expected to stop, so the pool can be dropped without waiting for them.
+    fn interrupt(&self);
 }
 
+// Queue shared by the pool and its workers; the condvar wakes a worker up when a job is queued or the pool shuts down
+pub type SharedJobQueue = Arc<(Mutex<JobQueue>, Condvar)>;
+
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: Option<Sender<Job>>,
+    queue: SharedJobQueue,
+    clock: Arc<dyn Clock>,
+    interrupt: Arc<Interrupt>,
 }
 
 impl ThreadPool {
-    pub fn new(size: usize) -> Self {
-        let (sender, receiver) = mpsc::channel();
-        let receiver = Arc::new(Mutex::new(receiver));
+    // Jobs waiting in the queue are ordered by the time of the clock, ex. by how long they have waited
+    pub fn new(size: usize, policy: SchedulingPolicy, clock: Arc<dyn Clock>) -> Self {
+        let queue: SharedJobQueue = Arc::new((Mutex::new(JobQueue::new(policy)), Condvar::new()));
+        let interrupt = Arc::new(Interrupt::new());
 
         let mut workers = Vec::with_capacity(size);
         for id in 0..size {
-            let receiver = Arc::clone(&receiver);
-            let worker = Worker::new(id, receiver);
+            let queue = Arc::clone(&queue);
+            let worker = Worker::new(id, queue, clock.clone(), interrupt.clone());
             workers.push(worker);
         }
 
-        ThreadPool { workers, sender: Some(sender) }
+        ThreadPool { workers, queue, clock, interrupt }
     }
 }
 
 impl ThreadPoolDyn for ThreadPool {
-    fn execute(&self, job: Box<dyn FnOnce() + Send + 'static>) {
-        self.sender.as_ref().unwrap().send(job).unwrap()
+    fn execute(&self, job: Job, info: JobInfo) {
+        let (queue, job_available) = &*self.queue;
+        queue.lock().unwrap().push(job, info);
+        job_available.notify_one();
     }
-}
 
-/*
-self.sender.take()
-Purpose: The take() method is used to replace the value of self.sender with None and return the original value.
+    fn queued_jobs(&self) -> Vec<JobInfo> {
+        let (queue, _) = &*self.queue;
+        queue.lock().unwrap().infos(self.clock.now())
+    }
+
+    fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
 
-Why take()?:
-self.sender is an Option<mpsc::Sender<Job>>. By calling take(), we consume the Option and retrieve the Sender, leaving self.sender as None.
-This ensures that no more jobs can be sent to the channel because the sender is dropped, effectively closing the channel.
+    fn cancel_queued_jobs(&self) -> Vec<JobInfo> {
+        let (queue, _) = &*self.queue;
+        queue.lock().unwrap().clear(self.clock.now())
+    }
 
-Mutable Self: We need &mut self because take() modifies self.sender by replacing it with None.
-Dropping self.sender
+    fn interrupt(&self) {
+        self.interrupt.interrupt();
+    }
+}
 
-Why Drop?: By explicitly dropping the sender, we signal to all the worker threads that no more jobs will be sent.
-The worker threads will eventually encounter an error when trying to receive a job from the closed channel and will then exit their loop.
+/*
+Closing the queue
+Why close?: Once closed, no worker waits for new jobs anymore. Each worker keeps taking the jobs already queued
+and stops when the queue is empty, so every accepted job still runs before the pool is gone.
+notify_all wakes up every worker which is waiting on the condvar so it can see the queue is closed.
 
 Iterating Over Workers
 &mut self.workers: This mutable reference allows us to modify each worker's thread field.
@@ -59,7 +88,9 @@ This is essential for clean shutdown and ensures that all worker threads have co
 
 impl Drop for ThreadPool {
     fn drop(&mut self) {
-        drop(self.sender.take());
+        let (queue, job_available) = &*self.queue;
+        queue.lock().unwrap().close();
+        job_available.notify_all();
         for worker in &mut self.workers {
             println!("Shutting down worker {}", worker.id);
             if let Some(thread) = worker.thread.take() {
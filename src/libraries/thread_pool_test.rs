@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod thread_pool_test {
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::Duration;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::libraries::clock::SystemClock;
+    use crate::libraries::job::{JobInfo, JobStep};
+    use crate::libraries::job_queue::SchedulingPolicy;
+    use crate::libraries::thread_pool::{ThreadPool, ThreadPoolDyn};
+    use crate::libraries::worker::current_worker_id;
+
+    fn job_info(priority: u8, duration_in_sec: u64) -> JobInfo {
+        JobInfo {
+            id: Uuid::new_v4(),
+            priority,
+            duration: Duration::from_secs(duration_in_sec),
+            requested_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_jobs_run_on_workers() {
+        let pool = ThreadPool::new(2, SchedulingPolicy::Fifo, Arc::new(SystemClock));
+        let (sender, receiver) = mpsc::channel();
+        assert_eq!(2, pool.worker_count());
+
+        for _ in 0..4 {
+            let sender = sender.clone();
+            pool.execute(Box::new(move || {
+                sender.send(current_worker_id()).unwrap();
+                JobStep::Done
+            }), job_info(0, 1));
+        }
+
+        for _ in 0..4 {
+            let worker_id = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            assert!(matches!(worker_id, Some(0) | Some(1)));
+        }
+        assert_eq!(None, current_worker_id());
+    }
+
+    #[test]
+    fn test_queued_jobs_run_by_priority() {
+        let pool = ThreadPool::new(1, SchedulingPolicy::ShortestFirst, Arc::new(SystemClock));
+        let ran = Arc::new(Mutex::new(Vec::new()));
+
+        // Given the only worker is busy, the jobs queued meanwhile run by priority, then by duration
+        let (started_sender, started) = mpsc::channel();
+        let (release, release_receiver) = mpsc::channel::<()>();
+        pool.execute(Box::new(move || {
+            started_sender.send(()).unwrap();
+            release_receiver.recv().unwrap();
+            JobStep::Done
+        }), job_info(0, 1));
+        started.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        let long = job_info(0, 15);
+        let short = job_info(0, 5);
+        let vip = job_info(2, 15);
+        for info in [long, short, vip] {
+            let ran = ran.clone();
+            pool.execute(Box::new(move || {
+                ran.lock().unwrap().push(info.id);
+                JobStep::Done
+            }), info);
+        }
+        assert_eq!(vec![vip.id, short.id, long.id], pool.queued_jobs().iter().map(|info| info.id).collect::<Vec<_>>());
+
+        release.send(()).unwrap();
+        // Dropping the pool waits for the queued jobs to run
+        drop(pool);
+        assert_eq!(vec![vip.id, short.id, long.id], *ran.lock().unwrap());
+    }
+}
@@ -1,7 +1,47 @@
-use std::error::Error;
-use std::sync::{Arc, mpsc, Mutex};
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
 use std::thread;
-use crate::libraries::job::Job;
+use crate::libraries::clock::Clock;
+use crate::libraries::interrupt::Interrupt;
+use crate::libraries::job::{Job, JobStep};
+use crate::libraries::thread_pool::SharedJobQueue;
+
+thread_local! {
+    static CURRENT_WORKER_ID: Cell<Option<usize>> = const { Cell::new(None) };
+    static CURRENT_INTERRUPTED: Cell<bool> = const { Cell::new(false) };
+    static CURRENT_JOB_INTERRUPT: RefCell<Option<Arc<Interrupt>>> = const { RefCell::new(None) };
+}
+
+// Returns the id of the worker running the calling job, or None outside of a worker thread
+pub fn current_worker_id() -> Option<usize> {
+    CURRENT_WORKER_ID.with(|worker_id| worker_id.get())
+}
+
+// Whether the pool running the calling job was interrupted, ex. out of time to drain on shutdown.
+// The job should then stop waiting and leave its work to be picked up again, ex. on the next startup.
+pub fn is_interrupted() -> bool {
+    CURRENT_INTERRUPTED.with(|interrupted| interrupted.get())
+}
+
+// Interrupt of the calling job, or None outside of a worker thread. Interrupting it wakes the job up from its wait at once,
+// ex. once a manager voids the meal item it cooks; the pool interrupts it too, see is_interrupted to tell which one did.
+pub fn current_job_interrupt() -> Option<Arc<Interrupt>> {
+    CURRENT_JOB_INTERRUPT.with(|job_interrupt| job_interrupt.borrow().clone())
+}
+
+// Runs one step of a job as the given worker, so the step can tell which worker runs it, see current_worker_id,
+// whether its pool was interrupted, see is_interrupted, and what interrupts the job, see current_job_interrupt.
+// Steps never yield, so this also holds on threads shared by several workers, ex. those of a tokio runtime.
+pub(crate) fn run_step_as(worker_id: usize, interrupt: &Interrupt, job_interrupt: &Arc<Interrupt>, job: Job) -> JobStep {
+    let previous = CURRENT_WORKER_ID.with(|current| current.replace(Some(worker_id)));
+    let previous_interrupted = CURRENT_INTERRUPTED.with(|current| current.replace(interrupt.is_interrupted()));
+    let previous_job_interrupt = CURRENT_JOB_INTERRUPT.with(|current| current.replace(Some(job_interrupt.clone())));
+    let step = job();
+    CURRENT_WORKER_ID.with(|current| current.set(previous));
+    CURRENT_INTERRUPTED.with(|current| current.set(previous_interrupted));
+    CURRENT_JOB_INTERRUPT.with(|current| current.replace(previous_job_interrupt));
+    step
+}
 
 pub struct Worker {
     pub(crate) id: usize,
@@ -9,18 +49,36 @@ pub struct Worker {
 }
 
 impl Worker {
-    pub fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Self {
+    pub fn new(id: usize, queue: SharedJobQueue, clock: Arc<dyn Clock>, interrupt: Arc<Interrupt>) -> Self {
         let thread = thread::spawn(move || {
             loop {
-                let result = receiver.lock().unwrap().recv();
-                match result {
-                    Ok(job) => {
+                // The next job is picked only once the worker is free, so it follows the queue as it is at that time
+                let next = {
+                    let (queue, job_available) = &*queue;
+                    let mut queue = queue.lock().unwrap();
+                    loop {
+                        if let Some(next) = queue.pop(clock.now()) {
+                            break Some(next);
+                        }
+                        if queue.is_closed() {
+                            break None;
+                        }
+                        queue = job_available.wait(queue).unwrap();
+                    }
+                };
+                match next {
+                    Some((job, _)) => {
                         println!("Worker {id} starts executing the job");
-                        job();
+                        let job_interrupt = interrupt.child();
+                        let mut step = run_step_as(id, &interrupt, &job_interrupt, job);
+                        while let JobStep::Wait(duration, rest) = step {
+                            clock.sleep(duration, &job_interrupt);
+                            step = run_step_as(id, &interrupt, &job_interrupt, rest);
+                        }
                         println!("Worker {id} finished the job");
                     }
-                    Err(err) => {
-                        println!("Worker {id} encountered an error: {:?}", err.source());
+                    None => {
+                        println!("Worker {id} stops as the pool is shut down");
                         break;
                     }
                 }
@@ -32,4 +90,4 @@ impl Worker {
             thread: Some(thread),
         }
     }
-}
\ No newline at end of file
+}
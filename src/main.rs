@@ -1,16 +1,62 @@
+// Tests live in sibling `*_test.rs` files wrapping a module of the same name
+#![allow(clippy::module_inception)]
+
 use std::env;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use serde::Deserialize;
 use uuid::Uuid;
 use warp::{Filter};
-use crate::usecases::handlers::add_meal_items::{AddMealItemsHandler, AddMealItemsReq};
+use crate::usecases::handlers::add_meal_items::{AddMealItemsHandler, AddMealItemsReq, AddOrderMealItemsReq};
 use crate::usecases::handlers::add_order::{AddOrderHandler, AddOrderReq};
 use crate::usecases::handlers::query_meal_item::QueryMealItemHandler;
 use crate::usecases::handlers::query_order::QueryOrderHandler;
-use crate::usecases::handlers::remove_meal_items::{RemoveMealItemsHandler, RemoveMealItemsReq};
+use crate::usecases::handlers::remove_meal_items::{RemoveMealItemsHandler, RemoveMealItemsReq, RemoveOrderMealItemsReq};
 use crate::usecases::handlers::remove_order::{RemoveOrderHandler};
-use crate::libraries::thread_pool::{ThreadPool};
-use crate::repositories::order::OrderRepo;
+use crate::usecases::handlers::add_menu::{AddMenuHandler, AddMenuReq};
+use crate::usecases::handlers::query_menu::QueryMenuHandler;
+use crate::usecases::handlers::list_menus::ListMenusHandler;
+use crate::usecases::handlers::add_menu_items::{AddMenuItemsHandler, AddMenuItemsReq};
+use crate::usecases::handlers::update_menu_item::{UpdateMenuItemHandler, UpdateMenuItemReq};
+use crate::usecases::handlers::retire_menu_item::RetireMenuItemHandler;
+use crate::usecases::handlers::list_table_orders::ListTableOrdersHandler;
+use crate::usecases::handlers::query_kitchen_queue::QueryKitchenQueueHandler;
+use crate::usecases::handlers::shutdown::{accepting_changes, recover_shutting_down};
+use crate::usecases::handlers::manager::{manager_only, recover_not_manager};
+use crate::usecases::handlers::store::recover_store_failed;
+use crate::usecases::handlers::void_meal_item::{VoidMealItemHandler, VoidMealItemReq};
+use crate::usecases::handlers::query_waste_report::QueryWasteReportHandler;
+use crate::usecases::handlers::serve_meal_items::{ServeMealItemsHandler, ServeMealItemsReq, ServeOrderMealItemsReq};
+use crate::usecases::handlers::query_pass::QueryPassHandler;
+use crate::usecases::handlers::checkout::CheckoutHandler;
+use crate::usecases::handlers::add_payment::{AddPaymentHandler, AddPaymentReq};
+use crate::usecases::handlers::split_bill::{SplitBillHandler, SplitBillReq};
+use crate::usecases::handlers::query_receipt::QueryReceiptHandler;
+use crate::usecases::handlers::stream_order_events::StreamOrderEventsHandler;
+use crate::usecases::handlers::add_promotion::{to_promotion, AddPromotionHandler, AddPromotionReq};
+use crate::usecases::handlers::list_promotions::ListPromotionsHandler;
+use crate::usecases::handlers::remove_promotion::RemovePromotionHandler;
+use crate::usecases::jobs::drain_kitchen::drain_kitchen;
+use crate::usecases::jobs::kitchen::{parse_station_sizes, Kitchen};
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::usecases::jobs::resume_kitchen::resume_kitchen;
+use crate::libraries::job_queue::SchedulingPolicy;
+use crate::models::cooking_time::{CookingTimeEstimator, RandomCookingTimeEstimator};
+use crate::models::station::Station;
+use crate::models::tax::TaxPolicy;
+use crate::models::receipt::Restaurant;
+use crate::libraries::clock::{Clock, ScaledClock, SystemClock};
+use crate::libraries::shutdown::{shutdown_signal, Shutdown};
+use crate::libraries::async_pool::AsyncPool;
+use crate::libraries::thread_pool::{ThreadPool, ThreadPoolDyn};
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::repositories::order_sqlite::SqliteOrderRepo;
+use crate::repositories::order_log::{FsyncPolicy, OrderLogOptions};
+use crate::repositories::order_event::{OrderEventRepo, EVENT_HISTORY};
+use crate::repositories::promotion::PromotionRepo;
 
 mod models;
 mod repositories;
@@ -19,9 +65,237 @@ mod usecases;
 
 #[derive(Deserialize)]
 struct QueryOrderParams {
+    #[serde(default)]
     include_removed_items: bool,
 }
 
+#[derive(Deserialize)]
+struct KitchenQueueParams {
+    station: Option<Station>,
+}
+
+#[derive(Deserialize)]
+struct ReceiptParams {
+    // text, html or json
+    format: Option<String>,
+    // Columns of text receipts, 32 or 42
+    width: Option<String>,
+}
+
+// Orders are kept in memory unless ORDER_STORE=sqlite, then they are kept in the ORDER_DB_PATH file.
+// In memory, setting ORDER_LOG_DIR logs every change there so orders are rebuilt on startup.
+// Every change made from then on is published to the order events.
+fn order_repo_from_env(order_event_repo: Arc<OrderEventRepo>) -> Arc<dyn OrderRepository> {
+    match env::var("ORDER_STORE").as_deref() {
+        Err(_) | Ok("memory") => {
+            let dir = match env::var("ORDER_LOG_DIR") {
+                Ok(dir) => dir,
+                Err(_) => return Arc::new(OrderRepo::new().with_events(order_event_repo)),
+            };
+
+            let mut options = OrderLogOptions::default();
+            if let Ok(policy) = env::var("ORDER_LOG_FSYNC") {
+                match policy.parse::<FsyncPolicy>() {
+                    Ok(policy) => options.fsync_policy = policy,
+                    Err(err) => {
+                        eprintln!("Invalid ORDER_LOG_FSYNC: {}", err);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if let Ok(snapshot_every) = env::var("ORDER_SNAPSHOT_EVERY") {
+                match snapshot_every.parse::<usize>() {
+                    Ok(snapshot_every) if snapshot_every > 0 => options.snapshot_every = snapshot_every,
+                    _ => {
+                        eprintln!("Invalid ORDER_SNAPSHOT_EVERY: {}", snapshot_every);
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            match OrderRepo::with_log(Path::new(&dir), options) {
+                Ok(repo) => Arc::new(repo.with_events(order_event_repo)),
+                Err(err) => {
+                    eprintln!("Failed to rebuild orders from {}: {}", dir, err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Ok("sqlite") => {
+            let path = env::var("ORDER_DB_PATH").unwrap_or_else(|_| String::from("orders.db"));
+            match SqliteOrderRepo::open(&path) {
+                Ok(repo) => Arc::new(repo.with_events(order_event_repo)),
+                Err(err) => {
+                    eprintln!("Failed to open order database {}: {}", path, err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Ok(store) => {
+            eprintln!("Invalid order store: {}", store);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Chefs take meal items in the order they were queued unless KITCHEN_SCHEDULING_POLICY says otherwise
+fn scheduling_policy_from_env() -> SchedulingPolicy {
+    match env::var("KITCHEN_SCHEDULING_POLICY") {
+        Ok(policy) => match policy.parse::<SchedulingPolicy>() {
+            Ok(policy) => policy,
+            Err(err) => {
+                eprintln!("Invalid KITCHEN_SCHEDULING_POLICY: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => SchedulingPolicy::default(),
+    }
+}
+
+// Cooking times are random unless COOKING_TIME_SEED is given, in which case every run draws the same ones
+fn cooking_time_estimator_from_env() -> Arc<dyn CookingTimeEstimator> {
+    match env::var("COOKING_TIME_SEED") {
+        Ok(seed) => match seed.parse::<u64>() {
+            Ok(seed) => Arc::new(RandomCookingTimeEstimator::seeded(seed)),
+            Err(_) => {
+                eprintln!("Invalid COOKING_TIME_SEED: {}", seed);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => Arc::new(RandomCookingTimeEstimator::new()),
+    }
+}
+
+// The kitchen cooks at the wall clock's pace unless KITCHEN_TIME_SCALE says how many times faster, ex. with 60 a minute of cooking
+// takes a second; every time stored or answered stays a wall clock time
+fn clock_from_env() -> Arc<dyn Clock> {
+    let scale = match env::var("KITCHEN_TIME_SCALE") {
+        Ok(scale) => match scale.parse::<f64>() {
+            Ok(scale) if scale.is_finite() && scale > 0.0 => scale,
+            _ => {
+                eprintln!("Invalid KITCHEN_TIME_SCALE: {}", scale);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => 1.0,
+    };
+    if scale == 1.0 {
+        Arc::new(SystemClock)
+    } else {
+        Arc::new(ScaledClock::new(scale))
+    }
+}
+
+// Chefs are tokio tasks sharing the runtime threads unless KITCHEN_EXECUTOR=threads, which gives every chef its own thread
+fn thread_pool_from_env(size: usize, policy: SchedulingPolicy, clock: Arc<dyn Clock>) -> Arc<dyn ThreadPoolDyn> {
+    match env::var("KITCHEN_EXECUTOR").as_deref() {
+        Err(_) | Ok("tokio") => Arc::new(AsyncPool::new(size, policy, clock)),
+        Ok("threads") => Arc::new(ThreadPool::new(size, policy, clock)),
+        Ok(executor) => {
+            eprintln!("Invalid KITCHEN_EXECUTOR: {}", executor);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Every station has as many chefs as the first parameter says unless KITCHEN_STATIONS says otherwise, ex. grill=3,bar=1
+fn station_sizes_from_env() -> Vec<(Station, usize)> {
+    match env::var("KITCHEN_STATIONS") {
+        Ok(station_sizes) => match parse_station_sizes(&station_sizes) {
+            Ok(station_sizes) => station_sizes,
+            Err(err) => {
+                eprintln!("Invalid KITCHEN_STATIONS: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => Vec::new(),
+    }
+}
+
+// On shutdown the kitchen gets SHUTDOWN_DEADLINE_SECS, 30 by default, to cook what is queued before the rest is left for the next start
+fn shutdown_deadline_from_env() -> Duration {
+    match env::var("SHUTDOWN_DEADLINE_SECS") {
+        Ok(deadline) => match deadline.parse::<u64>() {
+            Ok(deadline) => Duration::from_secs(deadline),
+            Err(_) => {
+                eprintln!("Invalid SHUTDOWN_DEADLINE_SECS: {}", deadline);
+                std::process::exit(1);
+            }
+        },
+        Err(_) => Duration::from_secs(30),
+    }
+}
+
+// Voiding meal items, reading the waste report, adding, updating and retiring menus and menu items, and adding and removing
+// promotions take the MANAGER_PIN; without it, nobody can
+fn manager_pin_from_env() -> Option<Arc<String>> {
+    match env::var("MANAGER_PIN") {
+        Ok(pin) if pin.trim().is_empty() => {
+            eprintln!("Invalid MANAGER_PIN: it must not be blank");
+            std::process::exit(1);
+        }
+        Ok(pin) => Some(Arc::new(pin)),
+        Err(_) => {
+            eprintln!("MANAGER_PIN is not set: nobody can void meal items, read the waste report, or manage menus and promotions");
+            None
+        }
+    }
+}
+
+// Promotions running from startup are read from the JSON array in the PROMOTIONS_FILE, in the format of POST /promotions
+fn promotion_repo_from_env() -> Arc<PromotionRepo> {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let path = match env::var("PROMOTIONS_FILE") {
+        Ok(path) => path,
+        Err(_) => return promotion_repo,
+    };
+    let reqs = match std::fs::read_to_string(&path).map_err(|err| err.to_string())
+        .and_then(|promotions| serde_json::from_str::<Vec<AddPromotionReq>>(&promotions).map_err(|err| err.to_string())) {
+        Ok(reqs) => reqs,
+        Err(err) => {
+            eprintln!("Failed to read promotions from {}: {}", path, err);
+            std::process::exit(1);
+        }
+    };
+    for req in reqs {
+        match to_promotion(req) {
+            Ok(promotion) => promotion_repo.add(promotion),
+            Err(resp) => {
+                eprintln!("Invalid promotion in {}: {}", path, resp.error_message);
+                std::process::exit(1);
+            }
+        }
+    }
+    promotion_repo
+}
+
+// Taxes and service charge are read from the JSON object in the TAX_FILE, none are charged without it
+fn tax_policy_from_env() -> Arc<TaxPolicy> {
+    let path = match env::var("TAX_FILE") {
+        Ok(path) => path,
+        Err(_) => return Arc::new(TaxPolicy::default()),
+    };
+    let tax_policy = std::fs::read_to_string(&path).map_err(|err| err.to_string())
+        .and_then(|tax_policy| serde_json::from_str::<TaxPolicy>(&tax_policy).map_err(|err| err.to_string()))
+        .and_then(|tax_policy| tax_policy.validate().map(|_| tax_policy).map_err(|err| err.to_string()));
+    match tax_policy {
+        Ok(tax_policy) => Arc::new(tax_policy),
+        Err(err) => {
+            eprintln!("Failed to read taxes from {}: {}", path, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+// The header of receipts is RESTAURANT_NAME, with RESTAURANT_ADDRESS and RESTAURANT_PHONE under it if they are given
+fn restaurant_from_env() -> Arc<Restaurant> {
+    Arc::new(Restaurant {
+        name: env::var("RESTAURANT_NAME").unwrap_or_else(|_| String::from("Restaurant")),
+        address: env::var("RESTAURANT_ADDRESS").ok(),
+        phone: env::var("RESTAURANT_PHONE").ok(),
+    })
+}
+
 #[tokio::main]
 async fn main() {
     println!("Hello, Welcome to our restaurant!");
@@ -39,17 +313,75 @@ async fn main() {
         }
     }
 
-    let order_repo = Arc::new(OrderRepo::new());
-    let pool = Arc::new(ThreadPool::new(pool_size));
-    let add_order_handler = Arc::new(AddOrderHandler::new(order_repo.clone(), pool.clone()));
-    let query_order_handler = Arc::new(QueryOrderHandler::new(order_repo.clone()));
-    let remove_order_handler = Arc::new(RemoveOrderHandler::new(order_repo.clone()));
-    let add_meal_items_handler = Arc::new(AddMealItemsHandler::new(order_repo.clone(), pool.clone()));
+    let clock = clock_from_env();
+    let order_event_repo = Arc::new(OrderEventRepo::new(EVENT_HISTORY));
+    let order_repo = order_repo_from_env(order_event_repo.clone());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let promotion_repo = promotion_repo_from_env();
+    let tax_policy = tax_policy_from_env();
+    let restaurant = restaurant_from_env();
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let cooking_time_estimator = cooking_time_estimator_from_env();
+    let station_sizes = station_sizes_from_env();
+    let scheduling_policy = scheduling_policy_from_env();
+    let kitchen = Arc::new(Kitchen::new(|station| {
+        let size = station_sizes.iter()
+            .find(|(sized_station, _)| *sized_station == station)
+            .map_or(pool_size, |(_, size)| *size);
+        thread_pool_from_env(size, scheduling_policy, clock.clone())
+    }));
+    match resume_kitchen(order_repo.clone(), kitchen_repo.clone(), &kitchen, clock.clone()) {
+        Ok(0) => {}
+        Ok(resumed) => println!("{} meal items queued again from the last run", resumed),
+        Err(err) => {
+            eprintln!("Failed to resume the kitchen: {}", err);
+            std::process::exit(1);
+        }
+    }
+    let shutdown = Arc::new(Shutdown::new());
+    let shutdown_deadline = shutdown_deadline_from_env();
+    let manager_pin = manager_pin_from_env();
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), clock.clone()));
+    let add_order_handler = Arc::new(AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), promotion_repo.clone(), tax_policy.clone(), cooking_time_estimator.clone(), kitchen_repo.clone(), kitchen.clone(), ready_time_estimator.clone(), clock.clone()));
+    let query_order_handler = Arc::new(QueryOrderHandler::new(order_repo.clone(), ready_time_estimator.clone()));
+    let remove_order_handler = Arc::new(RemoveOrderHandler::new(order_repo.clone(), clock.clone()));
+    let add_meal_items_handler = Arc::new(AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), promotion_repo.clone(), tax_policy.clone(), cooking_time_estimator.clone(), kitchen_repo.clone(), kitchen.clone(), ready_time_estimator.clone(), clock.clone()));
     let query_meal_item_handler = Arc::new(QueryMealItemHandler::new(order_repo.clone()));
-    let remove_meal_items_handler = Arc::new(RemoveMealItemsHandler::new(order_repo.clone()));
+    let remove_meal_items_handler = Arc::new(RemoveMealItemsHandler::new(order_repo.clone(), promotion_repo.clone(), tax_policy.clone(), clock.clone()));
+    let list_table_orders_handler = Arc::new(ListTableOrdersHandler::new(order_repo.clone(), ready_time_estimator.clone()));
+    let query_kitchen_queue_handler = Arc::new(QueryKitchenQueueHandler::new(order_repo.clone(), kitchen_repo.clone(), kitchen.clone(), ready_time_estimator.clone(), clock.clone()));
+    let query_order_by_id_handler = query_order_handler.clone();
+    let remove_order_by_id_handler = remove_order_handler.clone();
+    let add_order_meal_items_handler = add_meal_items_handler.clone();
+    let query_order_meal_item_handler = query_meal_item_handler.clone();
+    let remove_order_meal_items_handler = remove_meal_items_handler.clone();
+    let void_meal_item_handler = Arc::new(VoidMealItemHandler::new(order_repo.clone(), promotion_repo.clone(), tax_policy.clone(), kitchen_repo.clone(), clock.clone()));
+    let query_waste_report_handler = Arc::new(QueryWasteReportHandler::new(order_repo.clone()));
+    let serve_meal_items_handler = Arc::new(ServeMealItemsHandler::new(order_repo.clone(), clock.clone()));
+    let serve_order_meal_items_handler = serve_meal_items_handler.clone();
+    let query_pass_handler = Arc::new(QueryPassHandler::new(order_repo.clone(), clock.clone()));
+    let checkout_handler = Arc::new(CheckoutHandler::new(order_repo.clone(), promotion_repo.clone(), tax_policy.clone(), clock.clone()));
+    let add_payment_handler = Arc::new(AddPaymentHandler::new(order_repo.clone(), clock.clone()));
+    let split_bill_handler = Arc::new(SplitBillHandler::new(order_repo.clone(), clock.clone()));
+    let query_receipt_handler = Arc::new(QueryReceiptHandler::new(order_repo.clone(), restaurant.clone(), clock.clone()));
+    let stream_order_events_handler = Arc::new(StreamOrderEventsHandler::new(order_event_repo.clone()));
+    let stream_table_order_events_handler = stream_order_events_handler.clone();
+
+    let add_menu_handler = Arc::new(AddMenuHandler::new(menu_repo.clone()));
+    let query_menu_handler = Arc::new(QueryMenuHandler::new(menu_repo.clone()));
+    let list_menus_handler = Arc::new(ListMenusHandler::new(menu_repo.clone()));
+    let add_menu_items_handler = Arc::new(AddMenuItemsHandler::new(menu_repo.clone()));
+    let update_menu_item_handler = Arc::new(UpdateMenuItemHandler::new(menu_repo.clone()));
+    let retire_menu_item_handler = Arc::new(RetireMenuItemHandler::new(menu_repo.clone()));
+
+    let add_promotion_handler = Arc::new(AddPromotionHandler::new(promotion_repo.clone()));
+    let list_promotions_handler = Arc::new(ListPromotionsHandler::new(promotion_repo.clone()));
+    let remove_promotion_handler = Arc::new(RemovePromotionHandler::new(promotion_repo.clone()));
 
     let add_order = warp::post()
         .and(warp::path("orders"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
         .and(warp::body::json())
         .and_then(move |req: AddOrderReq| {
             let handler = add_order_handler.clone();
@@ -77,6 +409,7 @@ async fn main() {
 
     let add_meal_items = warp::post()
         .and(warp::path("meal-items"))
+        .and(accepting_changes(shutdown.clone()))
         .and(warp::body::json())
         .and_then(move |req: AddMealItemsReq| {
             let handler = add_meal_items_handler.clone();
@@ -85,6 +418,7 @@ async fn main() {
 
     let remove_meal_items = warp::delete()
         .and(warp::path("meal-items"))
+        .and(accepting_changes(shutdown.clone()))
         .and(warp::body::json())
         .and_then(move |req: RemoveMealItemsReq| {
             let handler = remove_meal_items_handler.clone();
@@ -94,17 +428,400 @@ async fn main() {
     let remove_order = warp::delete()
         .and(warp::path("orders"))
         .and(warp::path::param())
+        .and(accepting_changes(shutdown.clone()))
         .and_then(move |table_id: u32| {
             let handler = remove_order_handler.clone();
             async move { handler.handle(table_id) }
         });
 
-    let routes = add_order
+    let query_order_by_id = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(warp::query::<QueryOrderParams>())
+        .and_then(move |order_id: Uuid, params: QueryOrderParams| {
+            let handler = query_order_by_id_handler.clone();
+            async move { handler.handle_by_id(order_id, params.include_removed_items) }
+        });
+
+    let remove_order_by_id = warp::delete()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and_then(move |order_id: Uuid| {
+            let handler = remove_order_by_id_handler.clone();
+            async move { handler.handle_by_id(order_id) }
+        });
+
+    let add_order_meal_items = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path("meal-items"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |order_id: Uuid, req: AddOrderMealItemsReq| {
+            let handler = add_order_meal_items_handler.clone();
+            async move { handler.handle_by_id(order_id, req) }
+        });
+
+    let query_order_meal_item = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path("meal-items"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and_then(move |order_id: Uuid, meal_item_id: Uuid| {
+            let handler = query_order_meal_item_handler.clone();
+            async move { handler.handle_by_id(order_id, meal_item_id) }
+        });
+
+    let remove_order_meal_items = warp::delete()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path("meal-items"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |order_id: Uuid, req: RemoveOrderMealItemsReq| {
+            let handler = remove_order_meal_items_handler.clone();
+            async move { handler.handle_by_id(order_id, req) }
+        });
+
+    let void_meal_item = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path("meal-items"))
+        .and(warp::path::param())
+        .and(warp::path("void"))
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |order_id: Uuid, meal_item_id: Uuid, req: VoidMealItemReq| {
+            let handler = void_meal_item_handler.clone();
+            async move { handler.handle_by_id(order_id, meal_item_id, req) }
+        });
+
+    let query_waste_report = warp::get()
+        .and(warp::path("reports"))
+        .and(warp::path("waste"))
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and_then(move || {
+            let handler = query_waste_report_handler.clone();
+            async move { handler.handle() }
+        });
+
+    let serve_meal_items = warp::post()
+        .and(warp::path("meal-items"))
+        .and(warp::path("served"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |req: ServeMealItemsReq| {
+            let handler = serve_meal_items_handler.clone();
+            async move { handler.handle(req) }
+        });
+
+    let serve_order_meal_items = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path("by-id"))
+        .and(warp::path::param())
+        .and(warp::path("meal-items"))
+        .and(warp::path("served"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |order_id: Uuid, req: ServeOrderMealItemsReq| {
+            let handler = serve_order_meal_items_handler.clone();
+            async move { handler.handle_by_id(order_id, req) }
+        });
+
+    let checkout = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("checkout"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and_then(move |table_id: u32| {
+            let handler = checkout_handler.clone();
+            async move { handler.handle(table_id) }
+        });
+
+    let add_payment = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("payments"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |table_id: u32, req: AddPaymentReq| {
+            let handler = add_payment_handler.clone();
+            async move { handler.handle(table_id, req) }
+        });
+
+    let split_bill = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("split"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |table_id: u32, req: SplitBillReq| {
+            let handler = split_bill_handler.clone();
+            async move { handler.handle(table_id, req) }
+        });
+
+    let query_receipt = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("receipt"))
+        .and(warp::path::end())
+        .and(warp::query::<ReceiptParams>())
+        .and_then(move |table_id: u32, params: ReceiptParams| {
+            let handler = query_receipt_handler.clone();
+            async move { handler.handle(table_id, params.format, params.width) }
+        });
+
+    let stream_table_order_events = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("last-event-id"))
+        .and_then(move |table_id: u32, last_event_id: Option<String>| {
+            let handler = stream_table_order_events_handler.clone();
+            async move { handler.handle(Some(table_id), last_event_id) }
+        });
+
+    let stream_order_events = warp::get()
+        .and(warp::path("events"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("last-event-id"))
+        .and_then(move |last_event_id: Option<String>| {
+            let handler = stream_order_events_handler.clone();
+            async move { handler.handle(None, last_event_id) }
+        });
+
+    let query_pass = warp::get()
+        .and(warp::path("kitchen"))
+        .and(warp::path("pass"))
+        .and(warp::path::end())
+        .and_then(move || {
+            let handler = query_pass_handler.clone();
+            async move { handler.handle() }
+        });
+
+    let list_table_orders = warp::get()
+        .and(warp::path("tables"))
+        .and(warp::path::param())
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .and(warp::query::<QueryOrderParams>())
+        .and_then(move |table_id: u32, params: QueryOrderParams| {
+            let handler = list_table_orders_handler.clone();
+            async move { handler.handle(table_id, params.include_removed_items) }
+        });
+
+    let query_kitchen_queue = warp::get()
+        .and(warp::path("kitchen"))
+        .and(warp::path("queue"))
+        .and(warp::path::end())
+        .and(warp::query::<KitchenQueueParams>())
+        .and_then(move |params: KitchenQueueParams| {
+            let handler = query_kitchen_queue_handler.clone();
+            async move { handler.handle(params.station) }
+        });
+
+    let add_menu = warp::post()
+        .and(warp::path("menus"))
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |req: AddMenuReq| {
+            let handler = add_menu_handler.clone();
+            async move { handler.handle(req) }
+        });
+
+    let list_menus = warp::get()
+        .and(warp::path("menus"))
+        .and(warp::path::end())
+        .and_then(move || {
+            let handler = list_menus_handler.clone();
+            async move { handler.handle() }
+        });
+
+    let query_menu = warp::get()
+        .and(warp::path("menus"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and_then(move |menu_id: Uuid| {
+            let handler = query_menu_handler.clone();
+            async move { handler.handle(menu_id) }
+        });
+
+    let add_menu_items = warp::post()
+        .and(warp::path("menus"))
+        .and(warp::path::param())
+        .and(warp::path("menu-items"))
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |menu_id: Uuid, req: AddMenuItemsReq| {
+            let handler = add_menu_items_handler.clone();
+            async move { handler.handle(menu_id, req) }
+        });
+
+    let update_menu_item = warp::put()
+        .and(warp::path("menus"))
+        .and(warp::path::param())
+        .and(warp::path("menu-items"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |menu_id: Uuid, menu_item_id: Uuid, req: UpdateMenuItemReq| {
+            let handler = update_menu_item_handler.clone();
+            async move { handler.handle(menu_id, menu_item_id, req) }
+        });
+
+    let retire_menu_item = warp::delete()
+        .and(warp::path("menus"))
+        .and(warp::path::param())
+        .and(warp::path("menu-items"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and_then(move |menu_id: Uuid, menu_item_id: Uuid| {
+            let handler = retire_menu_item_handler.clone();
+            async move { handler.handle(menu_id, menu_item_id) }
+        });
+
+    let add_promotion = warp::post()
+        .and(warp::path("promotions"))
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and(warp::body::json())
+        .and_then(move |req: AddPromotionReq| {
+            let handler = add_promotion_handler.clone();
+            async move { handler.handle(req) }
+        });
+
+    let list_promotions = warp::get()
+        .and(warp::path("promotions"))
+        .and(warp::path::end())
+        .and_then(move || {
+            let handler = list_promotions_handler.clone();
+            async move { handler.handle() }
+        });
+
+    let remove_promotion = warp::delete()
+        .and(warp::path("promotions"))
+        .and(warp::path::param())
+        .and(warp::path::end())
+        .and(manager_only(manager_pin.clone()))
+        .and(accepting_changes(shutdown.clone()))
+        .and_then(move |promotion_id: Uuid| {
+            let handler = remove_promotion_handler.clone();
+            async move { handler.handle(promotion_id) }
+        });
+
+    // Routes are boxed in groups, the type of a single chain of all of them is too deep for the compiler.
+    // Routes under /orders/{table-id}/ come before query_order, which takes any path after the table id.
+    let order_by_id_routes = query_order_by_id
+        .or(remove_order_by_id)
+        .or(add_order_meal_items)
+        .or(query_order_meal_item)
+        .or(remove_order_meal_items)
+        .or(void_meal_item)
+        .or(serve_order_meal_items)
+        .boxed();
+
+    let table_order_routes = add_order
+        .or(query_receipt)
+        .or(stream_table_order_events)
         .or(query_order)
+        .or(checkout)
+        .or(add_payment)
+        .or(split_bill)
+        .or(remove_order)
+        .or(list_table_orders)
+        .boxed();
+
+    let meal_item_routes = serve_meal_items
         .or(add_meal_items)
         .or(query_meal_item)
         .or(remove_meal_items)
-        .or(remove_order);
+        .boxed();
+
+    let kitchen_routes = query_kitchen_queue
+        .or(query_pass)
+        .or(query_waste_report)
+        .or(stream_order_events)
+        .boxed();
 
-    warp::serve(routes).run(([127, 0, 0, 1], 3030)).await;
+    let menu_routes = add_menu
+        .or(list_menus)
+        .or(query_menu)
+        .or(add_menu_items)
+        .or(update_menu_item)
+        .or(retire_menu_item)
+        .boxed();
+
+    let promotion_routes = add_promotion
+        .or(list_promotions)
+        .or(remove_promotion)
+        .boxed();
+
+    let routes = order_by_id_routes
+        .or(table_order_routes)
+        .or(meal_item_routes)
+        .or(kitchen_routes)
+        .or(menu_routes)
+        .or(promotion_routes)
+        .recover(recover_shutting_down)
+        .recover(recover_not_manager)
+        .recover(recover_store_failed);
+
+    // On Ctrl-C or SIGTERM, changes are turned away while the kitchen drains, then the server stops
+    // once the streams of order events are ended
+    let drain_order_event_repo = order_event_repo.clone();
+    let drain_kitchen_pools = kitchen.clone();
+    let drain_kitchen_repo = kitchen_repo.clone();
+    let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(([127, 0, 0, 1], 3030), async move {
+        shutdown_signal().await;
+        println!("Shutting down, no changes are accepted");
+        shutdown.start_draining();
+        let checkpointed = tokio::task::spawn_blocking(move || drain_kitchen(&drain_kitchen_pools, &drain_kitchen_repo, shutdown_deadline))
+            .await
+            .unwrap_or_default();
+        if !checkpointed.is_empty() {
+            println!("{} meal items left waiting until the next start", checkpointed.len());
+        }
+        drain_order_event_repo.close();
+    });
+    server.await;
+
+    // Dropping the last reference to the kitchen drops the pool of every station, which waits for its chefs to finish
+    // the meal items they are cooking, or to stop them once interrupted by drain_kitchen, away from the runtime as the
+    // chefs may need it to finish. The orders are saved once no chef changes them anymore.
+    drop(ready_time_estimator);
+    let _ = tokio::task::spawn_blocking(move || drop(kitchen)).await;
+    if let Err(err) = order_repo.flush() {
+        eprintln!("Failed to save orders: {}", err);
+    }
+    println!("Goodbye!");
 }
@@ -0,0 +1,85 @@
+use std::sync::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// How long a menu item takes to cook, every meal item ordered from it gets its own cooking time drawn from the profile
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CookingTimeProfile {
+    // Always the same time, ex. a drink poured in a minute
+    Fixed { minutes: u32 },
+    // Any time between min and max, each as likely as the other
+    Range { min_minutes: u32, max_minutes: u32 },
+    // Any time between min and max, most likely around most_likely, ex. a steak usually done in 12 but taking 8 to 20
+    Triangular { min_minutes: u32, most_likely_minutes: u32, max_minutes: u32 },
+}
+
+impl Default for CookingTimeProfile {
+    fn default() -> Self {
+        CookingTimeProfile::Range { min_minutes: 5, max_minutes: 15 }
+    }
+}
+
+impl CookingTimeProfile {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            CookingTimeProfile::Fixed { .. } => Ok(()),
+            CookingTimeProfile::Range { min_minutes, max_minutes } if min_minutes > max_minutes =>
+                Err(format!("min_minutes {} is greater than max_minutes {}", min_minutes, max_minutes)),
+            CookingTimeProfile::Range { .. } => Ok(()),
+            CookingTimeProfile::Triangular { min_minutes, most_likely_minutes, max_minutes }
+                if min_minutes > most_likely_minutes || most_likely_minutes > max_minutes =>
+                Err(format!("most_likely_minutes {} is not between min_minutes {} and max_minutes {}", most_likely_minutes, min_minutes, max_minutes)),
+            CookingTimeProfile::Triangular { .. } => Ok(()),
+        }
+    }
+}
+
+// Picks the cooking time of a meal item from the profile of its menu item
+pub trait CookingTimeEstimator: Send + Sync {
+    fn cooking_time_in_min(&self, profile: &CookingTimeProfile) -> u32;
+}
+
+pub struct RandomCookingTimeEstimator {
+    rng: Mutex<StdRng>,
+}
+
+impl RandomCookingTimeEstimator {
+    pub fn new() -> Self {
+        RandomCookingTimeEstimator {
+            rng: Mutex::new(StdRng::from_entropy()),
+        }
+    }
+
+    // The same seed draws the same cooking times in the same order, ex. to replay a demo
+    pub fn seeded(seed: u64) -> Self {
+        RandomCookingTimeEstimator {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl CookingTimeEstimator for RandomCookingTimeEstimator {
+    fn cooking_time_in_min(&self, profile: &CookingTimeProfile) -> u32 {
+        let mut rng = self.rng.lock().unwrap();
+        match *profile {
+            CookingTimeProfile::Fixed { minutes } => minutes,
+            CookingTimeProfile::Range { min_minutes, max_minutes } => rng.gen_range(min_minutes..=max_minutes.max(min_minutes)),
+            CookingTimeProfile::Triangular { min_minutes, most_likely_minutes, max_minutes } => {
+                if min_minutes >= max_minutes {
+                    return min_minutes;
+                }
+                // Inverse of the cumulative distribution, which rises linearly to most_likely and falls linearly after it
+                let (min, most_likely, max) = (min_minutes as f64, most_likely_minutes as f64, max_minutes as f64);
+                let u: f64 = rng.gen();
+                let minutes = if u < (most_likely - min) / (max - min) {
+                    min + (u * (max - min) * (most_likely - min)).sqrt()
+                } else {
+                    max - ((1.0 - u) * (max - min) * (max - most_likely)).sqrt()
+                };
+                minutes.round() as u32
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod cooking_time_test {
+    use crate::models::cooking_time::{CookingTimeEstimator, CookingTimeProfile, RandomCookingTimeEstimator};
+
+    #[test]
+    fn test_validate() {
+        assert!(CookingTimeProfile::Fixed { minutes: 1 }.validate().is_ok());
+        assert!(CookingTimeProfile::Range { min_minutes: 5, max_minutes: 5 }.validate().is_ok());
+        assert!(CookingTimeProfile::Range { min_minutes: 6, max_minutes: 5 }.validate().is_err());
+        assert!(CookingTimeProfile::Triangular { min_minutes: 5, most_likely_minutes: 8, max_minutes: 15 }.validate().is_ok());
+        assert!(CookingTimeProfile::Triangular { min_minutes: 5, most_likely_minutes: 16, max_minutes: 15 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let profile: CookingTimeProfile = serde_json::from_str(r#"{"type": "triangular", "min_minutes": 8, "most_likely_minutes": 12, "max_minutes": 20}"#).unwrap();
+        assert_eq!(CookingTimeProfile::Triangular { min_minutes: 8, most_likely_minutes: 12, max_minutes: 20 }, profile);
+        assert!(serde_json::from_str::<CookingTimeProfile>(r#"{"type": "normal", "minutes": 8}"#).is_err());
+    }
+
+    #[test]
+    fn test_cooking_time_in_profile() {
+        let estimator = RandomCookingTimeEstimator::new();
+        let range = CookingTimeProfile::default();
+        let triangular = CookingTimeProfile::Triangular { min_minutes: 8, most_likely_minutes: 12, max_minutes: 20 };
+
+        for _ in 0..100 {
+            assert_eq!(3, estimator.cooking_time_in_min(&CookingTimeProfile::Fixed { minutes: 3 }));
+            assert!((5..=15).contains(&estimator.cooking_time_in_min(&range)));
+            assert!((8..=20).contains(&estimator.cooking_time_in_min(&triangular)));
+        }
+        assert_eq!(7, estimator.cooking_time_in_min(&CookingTimeProfile::Triangular { min_minutes: 7, most_likely_minutes: 7, max_minutes: 7 }));
+    }
+
+    #[test]
+    fn test_seeded_estimators_draw_the_same_cooking_times() {
+        let estimator = RandomCookingTimeEstimator::seeded(42);
+        let other_estimator = RandomCookingTimeEstimator::seeded(42);
+        let profile = CookingTimeProfile::Range { min_minutes: 1, max_minutes: 60 };
+
+        let cooking_times: Vec<u32> = (0..10).map(|_| estimator.cooking_time_in_min(&profile)).collect();
+        let other_cooking_times: Vec<u32> = (0..10).map(|_| other_estimator.cooking_time_in_min(&profile)).collect();
+        assert_eq!(cooking_times, other_cooking_times);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// ISO-4217 currencies the restaurant accepts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+    Krw,
+    Sgd,
+    Twd,
+}
+
+impl Currency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Jpy => "JPY",
+            Currency::Krw => "KRW",
+            Currency::Sgd => "SGD",
+            Currency::Twd => "TWD",
+        }
+    }
+
+    // Number of minor units digits as defined by ISO-4217, ex. 2 for USD cents, 0 for JPY
+    pub fn exponent(&self) -> u32 {
+        match self {
+            Currency::Jpy | Currency::Krw => 0,
+            _ => 2,
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "USD" => Ok(Currency::Usd),
+            "EUR" => Ok(Currency::Eur),
+            "GBP" => Ok(Currency::Gbp),
+            "JPY" => Ok(Currency::Jpy),
+            "KRW" => Ok(Currency::Krw),
+            "SGD" => Ok(Currency::Sgd),
+            "TWD" => Ok(Currency::Twd),
+            _ => Err(format!("unknown currency '{}'", s)),
+        }
+    }
+}
@@ -1,15 +1,24 @@
 use std::fmt;
+use std::str::FromStr;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use rand::{Rng};
+use serde::{Deserialize, Serialize};
+use crate::models::cooking_time::CookingTimeEstimator;
 use crate::models::menu::MenuItem;
 use crate::models::price::Price;
+use crate::models::station::Station;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum MealItemStatus {
     Received,
     Preparing,
-    Completed,
+    // Cooked and waiting on the pass for a waiter to take it to the table.
+    // Meal items stored as Completed before pickups were tracked were cooked, but nothing says they reached their table.
+    #[serde(alias = "Completed")]
+    ReadyForPickup,
+    Served,
+    // Stopped by a manager while being prepared, see MealItem::void
+    Voided,
 }
 
 impl fmt::Display for MealItemStatus {
@@ -17,12 +26,29 @@ impl fmt::Display for MealItemStatus {
         let s = match self {
             MealItemStatus::Received => "Received",
             MealItemStatus::Preparing => "Preparing",
-            MealItemStatus::Completed => "Completed",
+            MealItemStatus::ReadyForPickup => "ReadyForPickup",
+            MealItemStatus::Served => "Served",
+            MealItemStatus::Voided => "Voided",
         };
         write!(f, "{}", s)
     }
 }
 
+impl FromStr for MealItemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Received" => Ok(MealItemStatus::Received),
+            "Preparing" => Ok(MealItemStatus::Preparing),
+            "ReadyForPickup" | "Completed" => Ok(MealItemStatus::ReadyForPickup),
+            "Served" => Ok(MealItemStatus::Served),
+            "Voided" => Ok(MealItemStatus::Voided),
+            _ => Err(format!("unknown meal item status '{}'", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct MealItem {
     meal_item_id: Uuid,
@@ -32,26 +58,67 @@ pub struct MealItem {
     cooking_time_in_min: u32,
     is_removed: bool,
     status: MealItemStatus,
+    // Why a manager voided the meal item, none unless it is voided
+    void_reason: Option<String>,
+    // When the meal item reached the pass, none until it is cooked
+    ready_time: Option<DateTime<Utc>>,
 }
 
 impl MealItem {
-    pub fn create(menu_item: MenuItem) -> MealItem {
+    // The cooking time is picked by the estimator from the cooking time profile of the menu item
+    pub fn create(menu_item: MenuItem, cooking_time_estimator: &dyn CookingTimeEstimator, now: DateTime<Utc>) -> MealItem {
+        let cooking_time_in_min = cooking_time_estimator.cooking_time_in_min(&menu_item.cooking_time_profile());
         MealItem {
             meal_item_id: Uuid::new_v4(),
             menu_item,
-            creation_time: Utc::now(),
-            update_time: Utc::now(),
-            cooking_time_in_min: rand::thread_rng().gen_range(5..=15), //For flexibility, we can pass in function for generating cooking time
+            creation_time: now,
+            update_time: now,
+            cooking_time_in_min,
             is_removed: false,
             status: MealItemStatus::Received,
+            void_reason: None,
+            ready_time: None,
+        }
+    }
+
+    // Rebuilds a meal item previously persisted by a repository
+    pub fn restore(meal_item_id: Uuid, menu_item: MenuItem, cooking_time_in_min: u32, is_removed: bool,
+                   status: MealItemStatus, creation_time: DateTime<Utc>, update_time: DateTime<Utc>) -> MealItem {
+        MealItem {
+            meal_item_id,
+            menu_item,
+            creation_time,
+            update_time,
+            cooking_time_in_min,
+            is_removed,
+            status,
+            void_reason: None,
+            ready_time: None,
         }
     }
 
-    pub fn remove(&mut self) {
-        self.update_time = Utc::now();
+    pub fn with_void_reason(mut self, void_reason: Option<String>) -> Self {
+        self.void_reason = void_reason;
+        self
+    }
+
+    pub fn with_ready_time(mut self, ready_time: Option<DateTime<Utc>>) -> Self {
+        self.ready_time = ready_time;
+        self
+    }
+
+    pub fn remove(&mut self, now: DateTime<Utc>) {
+        self.update_time = now;
         self.is_removed = true;
     }
 
+    // Stops the meal item while it is being prepared; unlike removing it, what was cooked so far is wasted
+    pub fn void(&mut self, reason: String, now: DateTime<Utc>) {
+        self.status = MealItemStatus::Voided;
+        self.void_reason = Some(reason);
+        self.update_time = now;
+    }
+
     pub fn id(&self) -> Uuid {
         self.meal_item_id
     }
@@ -68,16 +135,66 @@ impl MealItem {
         self.is_removed
     }
 
+    pub fn is_voided(&self) -> bool {
+        self.status == MealItemStatus::Voided
+    }
+
+    pub fn void_reason(&self) -> Option<String> {
+        self.void_reason.clone()
+    }
+
+    // Cost of the ingredients thrown away once the meal item is voided, valued at its menu price
+    pub fn wasted_cost(&self) -> Option<Price> {
+        if self.is_voided() { Some(self.price()) } else { None }
+    }
+
+    // Whether the kitchen is done with the meal item, whether or not it was taken to the table yet
+    pub fn is_cooked(&self) -> bool {
+        matches!(self.status, MealItemStatus::ReadyForPickup | MealItemStatus::Served)
+    }
+
+    // Falls back to the last update for meal items served before ready times were kept
+    pub fn ready_time(&self) -> Option<DateTime<Utc>> {
+        match self.status {
+            MealItemStatus::ReadyForPickup | MealItemStatus::Served => Some(self.ready_time.unwrap_or(self.update_time)),
+            _ => None,
+        }
+    }
+
     pub fn get_status(&self) -> MealItemStatus {
         self.status
     }
 
-    pub fn update_state(&mut self, status: MealItemStatus) {
+    pub fn update_state(&mut self, status: MealItemStatus, now: DateTime<Utc>) {
+        if status == MealItemStatus::ReadyForPickup {
+            self.ready_time = Some(now);
+        }
         self.status = status;
-        self.update_time = Utc::now();
+        self.update_time = now;
     }
 
     pub fn get_name(&self) -> String {
         self.menu_item.get_name()
     }
+
+    // Station of the kitchen cooking the meal item
+    pub fn station(&self) -> Station {
+        self.menu_item.station()
+    }
+
+    pub fn category(&self) -> Option<String> {
+        self.menu_item.category()
+    }
+
+    pub fn get_menu_item(&self) -> MenuItem {
+        self.menu_item.clone()
+    }
+
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        self.creation_time
+    }
+
+    pub fn update_time(&self) -> DateTime<Utc> {
+        self.update_time
+    }
 }
\ No newline at end of file
@@ -1,27 +1,87 @@
 #[cfg(test)]
 mod meal_test {
+    use std::str::FromStr;
+    use chrono::{TimeDelta, Utc};
+    use crate::models::currency::Currency;
+    use crate::models::cooking_time::{CookingTimeProfile, RandomCookingTimeEstimator};
     use crate::models::meal::{MealItem, MealItemStatus};
     use crate::models::menu::MenuItem;
+    use crate::models::price::Price;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    #[test]
+    fn test_create_from_cooking_time_profile() {
+        let menu_item = MenuItem::new(String::from("coke"), Price::from_minor_units(150, Currency::Usd).unwrap())
+            .with_cooking_time_profile(CookingTimeProfile::Fixed { minutes: 1 });
+        let meal_item = MealItem::create(menu_item, &RandomCookingTimeEstimator::new(), Utc::now());
+        assert_eq!(1, meal_item.cooking_time_in_min());
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(7), Utc::now());
+        assert_eq!(7, meal_item.cooking_time_in_min());
+    }
 
     #[test]
     fn test_remove() {
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let mut meal_item = MealItem::create(menu_item);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
         assert!(!meal_item.is_removed());
 
-        meal_item.remove();
+        meal_item.remove(Utc::now());
 
         assert!(meal_item.is_removed());
     }
 
     #[test]
     fn test_update_status() {
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let mut meal_item = MealItem::create(menu_item);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
         assert_eq!(MealItemStatus::Received, meal_item.get_status());
 
-        meal_item.update_state(MealItemStatus::Completed);
+        meal_item.update_state(MealItemStatus::ReadyForPickup, Utc::now());
+
+        assert_eq!(MealItemStatus::ReadyForPickup, meal_item.get_status());
+    }
+
+    #[test]
+    fn test_void() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        assert_eq!(None, meal_item.wasted_cost());
+
+        meal_item.void(String::from("guest left"), Utc::now());
 
-        assert_eq!(MealItemStatus::Completed, meal_item.get_status());
+        assert!(meal_item.is_voided());
+        assert!(!meal_item.is_removed());
+        assert_eq!(Some(String::from("guest left")), meal_item.void_reason());
+        assert_eq!(Some(Price::from_minor_units(345, Currency::Usd).unwrap()), meal_item.wasted_cost());
+    }
+
+    #[test]
+    fn test_ready_time() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        assert_eq!(None, meal_item.ready_time());
+        assert!(!meal_item.is_cooked());
+
+        // The meal item keeps the time it reached the pass once it is served
+        let ready_time = Utc::now();
+        meal_item.update_state(MealItemStatus::ReadyForPickup, ready_time);
+        meal_item.update_state(MealItemStatus::Served, ready_time + TimeDelta::minutes(3));
+        assert!(meal_item.is_cooked());
+        assert_eq!(Some(ready_time), meal_item.ready_time());
+        assert_eq!(ready_time + TimeDelta::minutes(3), meal_item.update_time());
+    }
+
+    #[test]
+    fn test_status_from_str() {
+        assert_eq!(Ok(MealItemStatus::ReadyForPickup), MealItemStatus::from_str("ReadyForPickup"));
+        assert_eq!(Ok(MealItemStatus::Served), MealItemStatus::from_str("Served"));
+        // Meal items stored as completed before pickups were tracked are read as waiting on the pass
+        assert_eq!(Ok(MealItemStatus::ReadyForPickup), MealItemStatus::from_str("Completed"));
+        assert_eq!(MealItemStatus::ReadyForPickup, serde_json::from_str::<MealItemStatus>("\"Completed\"").unwrap());
+        assert!(MealItemStatus::from_str("Plated").is_err());
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,24 @@
 use uuid::Uuid;
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::currency::Currency;
 use crate::models::price::Price;
+use crate::models::station::Station;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Menu {
     menu_id: Uuid,
     name: String,
+    currency: Currency,
     menu_items: Vec<MenuItem>,
 }
 
 impl Menu {
-    pub fn new(name: String, menu_items: Vec<MenuItem>) -> Self {
+    // All menu item prices are expected to be in the currency of the menu
+    pub fn new(name: String, currency: Currency, menu_items: Vec<MenuItem>) -> Self {
         Menu {
             menu_id: Uuid::new_v4(),
             name,
+            currency,
             menu_items,
         }
     }
@@ -22,9 +28,57 @@ impl Menu {
         true
     }
 
+    pub fn update_menu_item(&mut self, menu_item_id: Uuid, name: Option<String>, price: Option<Price>,
+                            cooking_time_profile: Option<CookingTimeProfile>, station: Option<Station>, category: Option<String>) -> bool {
+        if let Some(menu_item) = self.menu_items.iter_mut().find(|item| item.id() == menu_item_id) {
+            if let Some(name) = name {
+                menu_item.name = name;
+            }
+            if let Some(price) = price {
+                menu_item.price = price;
+            }
+            if let Some(cooking_time_profile) = cooking_time_profile {
+                menu_item.cooking_time_profile = cooking_time_profile;
+            }
+            if let Some(station) = station {
+                menu_item.station = station;
+            }
+            if let Some(category) = category {
+                menu_item.category = Some(category);
+            }
+            return true;
+        }
+        false
+    }
+
+    // Menu items are retired instead of deleted, so meal items already ordered can still refer to them
+    pub fn retire_menu_item(&mut self, menu_item_id: Uuid) -> bool {
+        if let Some(menu_item) = self.menu_items.iter_mut().find(|item| item.id() == menu_item_id) {
+            menu_item.is_retired = true;
+            return true;
+        }
+        false
+    }
+
     pub fn id(&self) -> Uuid {
         self.menu_id
     }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn get_menu_items(&self) -> Vec<MenuItem> {
+        self.menu_items.clone()
+    }
+
+    pub fn get_menu_item(&self, menu_item_id: Uuid) -> Option<MenuItem> {
+        self.menu_items.iter().find(|item| item.id() == menu_item_id).cloned()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -32,25 +86,58 @@ pub struct MenuItem {
     menu_item_id: Uuid,
     name: String,
     price: Price,
+    cooking_time_profile: CookingTimeProfile,
+    station: Station,
+    // Ex. drinks or mains, which promotions can target; none if the menu item isn't in any category
+    category: Option<String>,
+    is_retired: bool,
 }
 
 impl MenuItem {
-    pub fn new(name: String, price: String) -> Self {
+    pub fn new(name: String, price: Price) -> Self {
         MenuItem {
             menu_item_id: Uuid::new_v4(),
             name,
-            price: Price::from_string(price),
+            price,
+            cooking_time_profile: CookingTimeProfile::default(),
+            station: Station::default(),
+            category: None,
+            is_retired: false,
         }
     }
 
-    pub fn create(id: Uuid, name: String, price: String) -> Self {
+    pub fn with_cooking_time_profile(mut self, cooking_time_profile: CookingTimeProfile) -> Self {
+        self.cooking_time_profile = cooking_time_profile;
+        self
+    }
+
+    pub fn with_station(mut self, station: Station) -> Self {
+        self.station = station;
+        self
+    }
+
+    pub fn with_category(mut self, category: Option<String>) -> Self {
+        self.category = category;
+        self
+    }
+
+    // Rebuilds the copy of a menu item kept by a persisted meal item, which already has its cooking time
+    pub fn restore(menu_item_id: Uuid, name: String, price: Price) -> Self {
         MenuItem {
-            menu_item_id: id,
+            menu_item_id,
             name,
-            price: Price::from_string(price),
+            price,
+            cooking_time_profile: CookingTimeProfile::default(),
+            station: Station::default(),
+            category: None,
+            is_retired: false,
         }
     }
 
+    pub fn id(&self) -> Uuid {
+        self.menu_item_id
+    }
+
     pub fn price(&self) -> Price {
         self.price
     }
@@ -58,5 +145,20 @@ impl MenuItem {
     pub fn get_name(&self) -> String {
         self.name.clone()
     }
-}
 
+    pub fn cooking_time_profile(&self) -> CookingTimeProfile {
+        self.cooking_time_profile
+    }
+
+    pub fn station(&self) -> Station {
+        self.station
+    }
+
+    pub fn category(&self) -> Option<String> {
+        self.category.clone()
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.is_retired
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod menu_test {
+    use crate::models::currency::Currency;
+    use crate::models::menu::{Menu, MenuItem};
+    use crate::models::price::Price;
+
+    #[test]
+    fn test_add_menu_items() {
+        let mut menu = Menu::new(String::from("lunch"), Currency::Usd, vec![]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+
+        menu.add_menu_items(vec![menu_item.clone()]);
+
+        assert_eq!(vec![menu_item], menu.get_menu_items());
+    }
+
+    #[test]
+    fn test_update_menu_item() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+
+        let updated = menu.update_menu_item(menu_item.id(), None, Some(Price::from_minor_units(399, Currency::Usd).unwrap()), None, None, None);
+        assert!(updated);
+
+        let updated_menu_item = menu.get_menu_item(menu_item.id()).unwrap();
+        assert_eq!("fries", updated_menu_item.get_name());
+        assert_eq!("399", updated_menu_item.price().to_string());
+    }
+
+    #[test]
+    fn test_retire_menu_item() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+        assert!(!menu.get_menu_item(menu_item.id()).unwrap().is_retired());
+
+        assert!(menu.retire_menu_item(menu_item.id()));
+        assert!(menu.get_menu_item(menu_item.id()).unwrap().is_retired());
+
+        let unknown_menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        assert!(!menu.retire_menu_item(unknown_menu_item.id()));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::models::cooking_time::{CookingTimeEstimator, CookingTimeProfile};
+
+// Ignores the profile, every meal item gets the same cooking time
+pub struct MockCookingTimeEstimator {
+    cooking_time_in_min: u32,
+}
+
+impl MockCookingTimeEstimator {
+    pub fn new(cooking_time_in_min: u32) -> Self {
+        MockCookingTimeEstimator {
+            cooking_time_in_min,
+        }
+    }
+}
+
+impl CookingTimeEstimator for MockCookingTimeEstimator {
+    fn cooking_time_in_min(&self, _: &CookingTimeProfile) -> u32 {
+        self.cooking_time_in_min
+    }
+}
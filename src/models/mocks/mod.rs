@@ -0,0 +1,2 @@
+#[cfg(test)]
+pub mod cooking_time_mock;
\ No newline at end of file
@@ -2,6 +2,25 @@ pub mod menu;
 pub mod meal;
 pub mod order;
 pub mod price;
+pub mod currency;
+pub mod cooking_time;
+pub mod station;
+pub mod payment;
+pub mod split;
+pub mod promotion;
+pub mod tax;
+pub mod receipt;
+pub mod order_event;
+pub mod mocks;
 mod price_test;
 mod order_test;
-mod meal_test;
\ No newline at end of file
+mod meal_test;
+mod menu_test;
+mod cooking_time_test;
+mod station_test;
+mod payment_test;
+mod split_test;
+mod promotion_test;
+mod tax_test;
+mod receipt_test;
+mod order_event_test;
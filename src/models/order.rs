@@ -1,18 +1,26 @@
 use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::models::meal::{MealItem, MealItemStatus};
-use crate::models::menu::MenuItem;
-use crate::models::price::Price;
+use crate::models::currency::Currency;
+use crate::models::payment::{Payment, PaymentError};
+use crate::models::price::{Price, PriceError};
+use crate::models::promotion::AppliedPromotion;
+use crate::models::split::{guest_check_name, BillSplit, SplitError, SubCheck, MAX_GUESTS};
+use crate::models::tax::{Charges, TaxPolicy};
 
 #[derive(Copy, Clone, Debug, Serialize, PartialEq)]
 pub enum OrderStatus {
     Received,
     Preparing,
+    // Every meal item is cooked, some of them still wait on the pass
     Completed,
+    // Every meal item reached the table
+    Served,
     Canceled,
 }
 
@@ -22,60 +30,260 @@ impl fmt::Display for OrderStatus {
             OrderStatus::Received => "Received",
             OrderStatus::Preparing => "Preparing",
             OrderStatus::Completed => "Completed",
+            OrderStatus::Served => "Served",
             OrderStatus::Canceled => "Canceled",
         };
         write!(f, "{}", s)
     }
 }
 
+// Meal items of rush and VIP orders are cooked ahead of those of normal orders
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum OrderPriority {
+    #[default]
+    Normal,
+    Rush,
+    Vip,
+}
+
+impl fmt::Display for OrderPriority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            OrderPriority::Normal => "Normal",
+            OrderPriority::Rush => "Rush",
+            OrderPriority::Vip => "Vip",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for OrderPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Normal" => Ok(OrderPriority::Normal),
+            "Rush" => Ok(OrderPriority::Rush),
+            "Vip" => Ok(OrderPriority::Vip),
+            _ => Err(format!("unknown order priority '{}'", s)),
+        }
+    }
+}
+
+// Number of non-removed meal items in each status, and the cooking time of those not cooked yet.
+// Voided meal items are left out like removed ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct MealItemTally {
+    received: u32,
+    preparing: u32,
+    ready_for_pickup: u32,
+    served: u32,
+    remaining_cooking_time_in_min: u32,
+}
+
+impl MealItemTally {
+    fn add(&mut self, meal_item: &MealItem) {
+        if meal_item.is_removed() {
+            return;
+        }
+        match meal_item.get_status() {
+            MealItemStatus::Received => self.received += 1,
+            MealItemStatus::Preparing => self.preparing += 1,
+            MealItemStatus::ReadyForPickup => self.ready_for_pickup += 1,
+            MealItemStatus::Served => self.served += 1,
+            MealItemStatus::Voided => return,
+        }
+        if !meal_item.is_cooked() {
+            self.remaining_cooking_time_in_min += meal_item.cooking_time_in_min();
+        }
+    }
+
+    fn subtract(&mut self, meal_item: &MealItem) {
+        if meal_item.is_removed() {
+            return;
+        }
+        match meal_item.get_status() {
+            MealItemStatus::Received => self.received -= 1,
+            MealItemStatus::Preparing => self.preparing -= 1,
+            MealItemStatus::ReadyForPickup => self.ready_for_pickup -= 1,
+            MealItemStatus::Served => self.served -= 1,
+            MealItemStatus::Voided => return,
+        }
+        if !meal_item.is_cooked() {
+            self.remaining_cooking_time_in_min -= meal_item.cooking_time_in_min();
+        }
+    }
+
+    fn order_status(&self) -> OrderStatus {
+        if self.received + self.preparing + self.ready_for_pickup + self.served == 0 {
+            OrderStatus::Canceled
+        } else if self.preparing > 0 {
+            OrderStatus::Preparing
+        } else if self.received > 0 {
+            OrderStatus::Received
+        } else if self.ready_for_pickup > 0 {
+            OrderStatus::Completed
+        } else {
+            OrderStatus::Served
+        }
+    }
+}
+
+// A meal item charged on the order, along with what promotions took off it
+struct ChargedMealItem {
+    meal_item_id: Uuid,
+    category: Option<String>,
+    price: Price,
+    discount: Price,
+}
+
+// Meal items must only be changed through the order, which keeps the tally of their statuses up to date,
+// so reading the order status doesn't lock every meal item.
 #[derive(Clone, Debug)]
 pub struct Order {
     order_id: Uuid,
     table_id: u32,
+    priority: OrderPriority,
+    // Number of guests at the table, if the waiter gave it, for the service charge of large parties
+    guests: Option<u32>,
     meal_items: DashMap<Uuid, Arc<Mutex<MealItem>>>,
-    total_cooking_time_in_min: u32,
-    total_price: Price,
+    tally: MealItemTally,
+    // Sum of the prices of the meal items neither removed nor voided, before promotions
+    subtotal: Price,
+    // Promotions taken off the subtotal, dropped whenever the meal items charged change until the order is priced again
+    promotions: Vec<AppliedPromotion>,
+    // Taxes and service charge the order was last priced with, kept once the order is closed
+    tax_policy: TaxPolicy,
+    // Oldest first, they never add up to more than the total price
+    payments: Vec<Payment>,
+    // How the bill is split into checks, dropped whenever the meal items charged change
+    split: Option<BillSplit>,
+    // When the order was paid in full, after which its meal items can't be changed anymore
+    closed_time: Option<DateTime<Utc>>,
     creation_time: DateTime<Utc>,
     update_time: DateTime<Utc>,
 }
 
 impl Order {
-    pub fn new(table_id: u32, menu_items: Vec<MenuItem>) -> Self {
+    // Every meal item of the order has to be priced in the currency of the order
+    pub fn new(table_id: u32, currency: Currency, meal_items: Vec<MealItem>, now: DateTime<Utc>) -> Result<Self, PriceError> {
         let mut order = Order {
             order_id: Uuid::new_v4(),
             table_id,
+            priority: OrderPriority::Normal,
+            guests: None,
             meal_items: Default::default(),
-            total_cooking_time_in_min: 0,
-            total_price: Default::default(),
-            creation_time: Utc::now(),
-            update_time: Utc::now(),
+            tally: MealItemTally::default(),
+            subtotal: Price::zero(currency),
+            promotions: Vec::new(),
+            tax_policy: TaxPolicy::default(),
+            payments: Vec::new(),
+            split: None,
+            closed_time: None,
+            creation_time: now,
+            update_time: now,
         };
-        order.add_meal_items_by_menu_items(menu_items);
-        order
+        order.add_meal_items(meal_items, now)?;
+        Ok(order)
     }
 
-    fn add_meal_items_by_menu_items(&mut self, menu_items: Vec<MenuItem>) -> bool {
-        for menu_item in menu_items.iter() {
-            let meal_item = MealItem::create(menu_item.clone());
-            self.total_price.add(meal_item.price());
-            self.total_cooking_time_in_min += meal_item.cooking_time_in_min();
-            self.meal_items.insert(meal_item.id(), Arc::new(Mutex::new(meal_item)));
+    // Rebuilds an order previously persisted by a repository, totals are derived from its non-removed, non-voided meal items
+    pub fn restore(order_id: Uuid, table_id: u32, priority: OrderPriority, currency: Currency, meal_items: Vec<MealItem>,
+                   creation_time: DateTime<Utc>, update_time: DateTime<Utc>) -> Result<Self, PriceError> {
+        let mut order = Order {
+            order_id,
+            table_id,
+            priority,
+            guests: None,
+            meal_items: Default::default(),
+            tally: MealItemTally::default(),
+            subtotal: Price::zero(currency),
+            promotions: Vec::new(),
+            tax_policy: TaxPolicy::default(),
+            payments: Vec::new(),
+            split: None,
+            closed_time: None,
+            creation_time,
+            update_time,
+        };
+        for meal_item in meal_items.into_iter() {
+            if !meal_item.is_removed() && !meal_item.is_voided() {
+                order.subtotal = order.subtotal.checked_add(meal_item.price())?;
+            }
+            order.tally.add(&meal_item);
+            order.meal_items.insert(meal_item.id(), Arc::new(Mutex::new(meal_item)));
         }
-        self.update_time = Utc::now();
-        true
+        Ok(order)
+    }
+
+    // Clones of an order share its meal items; this copy has its own, so changing it leaves the order as it is
+    pub fn deep_clone(&self) -> Self {
+        let meal_items = self.meal_items.iter()
+            .map(|entry| (*entry.key(), Arc::new(Mutex::new(entry.value().lock().unwrap().clone()))))
+            .collect();
+        Order { meal_items, ..self.clone() }
+    }
+
+    pub fn with_priority(mut self, priority: OrderPriority) -> Self {
+        self.priority = priority;
+        self
     }
 
-    pub fn add_meal_items(&mut self, meal_items: Vec<MealItem>) -> bool {
-        for meal_item in meal_items.iter() {
-            self.total_price.add(meal_item.price());
-            self.total_cooking_time_in_min += meal_item.cooking_time_in_min();
-            self.meal_items.insert(meal_item.id(), Arc::new(Mutex::new(meal_item.clone())));
+    pub fn with_guests(mut self, guests: Option<u32>) -> Self {
+        self.guests = guests;
+        self
+    }
+
+    pub fn with_tax_policy(mut self, tax_policy: TaxPolicy) -> Self {
+        self.tax_policy = tax_policy;
+        self
+    }
+
+    pub fn with_payments(mut self, payments: Vec<Payment>, closed_time: Option<DateTime<Utc>>) -> Self {
+        self.payments = payments;
+        self.closed_time = closed_time;
+        self
+    }
+
+    pub fn with_promotions(mut self, promotions: Vec<AppliedPromotion>) -> Self {
+        self.promotions = promotions;
+        self
+    }
+
+    pub fn with_split(mut self, split: Option<BillSplit>) -> Self {
+        self.split = split;
+        self
+    }
+
+    // Either all meal items are added or, if the total price can't be computed, none of them.
+    // Returns whether they were added, which they can't be to a closed order, nor to one canceled by removing or voiding
+    // every meal item. The table has moved on to another order by then, which is the only one taking meal items.
+    pub fn add_meal_items(&mut self, meal_items: Vec<MealItem>, now: DateTime<Utc>) -> Result<bool, PriceError> {
+        if self.is_closed() || (!self.meal_items.is_empty() && self.get_order_status() == OrderStatus::Canceled) {
+            return Ok(false);
         }
-        self.update_time = Utc::now();
-        true
+        let subtotal = meal_items.iter()
+            .try_fold(self.subtotal, |total, meal_item| total.checked_add(meal_item.price()))?;
+
+        // Meal items added after the bill was split are on none of its checks
+        if !meal_items.is_empty() {
+            self.split = None;
+            self.promotions.clear();
+        }
+        for meal_item in meal_items.into_iter() {
+            self.tally.add(&meal_item);
+            self.meal_items.insert(meal_item.id(), Arc::new(Mutex::new(meal_item)));
+        }
+        self.subtotal = subtotal;
+        self.update_time = now;
+        Ok(true)
     }
 
-    pub fn remove_meal_items(&mut self, meal_item_ids: Vec<Uuid>) -> Vec<Uuid> {
+    // Meal items of a closed order can't be removed
+    pub fn remove_meal_items(&mut self, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Vec<Uuid> {
+        if self.is_closed() {
+            return meal_item_ids;
+        }
         let mut non_removable_items = Vec::new();
         for meal_item_id in meal_item_ids.iter() {
             if let Some(meal_item) = self.meal_items.get(meal_item_id) {
@@ -85,26 +293,338 @@ impl Order {
                     continue;
                 }
 
-                match meal_item.get_status() {
-                    MealItemStatus::Preparing | MealItemStatus::Completed => {
-                        non_removable_items.push(meal_item_id.clone());
-                        continue;
-                    }
-                    _ => {}
+                if meal_item.get_status() != MealItemStatus::Received || !self.covers_payments_without(meal_item.price()) {
+                    non_removable_items.push(*meal_item_id);
+                    continue;
                 }
 
-                self.total_price.deduct(meal_item.price());
-                self.total_cooking_time_in_min -= meal_item.cooking_time_in_min();
+                self.subtotal = self.subtotal.checked_sub(meal_item.price())
+                    .expect("subtotal always covers every non-removed meal item");
+                self.tally.subtract(&meal_item);
+                self.split = None;
+                self.promotions.clear();
 
-                meal_item.remove();
+                meal_item.remove(now);
             } else {
-                non_removable_items.push(meal_item_id.clone());
+                non_removable_items.push(*meal_item_id);
             }
         }
-        self.update_time = Utc::now();
+        self.update_time = now;
         non_removable_items
     }
 
+    // Removes every meal item, unless some of them are already being prepared, cooked or served, or the order is closed
+    pub fn remove(&mut self, now: DateTime<Utc>) -> bool {
+        if self.is_closed() {
+            return false;
+        }
+        match self.get_order_status() {
+            OrderStatus::Preparing | OrderStatus::Completed | OrderStatus::Served => { return false; }
+            _ => {}
+        }
+
+        let meal_item_ids = self.meal_items.iter().map(|entry| *entry.key()).collect();
+        self.remove_meal_items(meal_item_ids, now);
+        true
+    }
+
+    // Returns false if the meal item doesn't belong to the order. A voided meal item keeps its status,
+    // ex. when the chef preparing it finishes before noticing it was voided.
+    pub fn update_meal_item_status(&mut self, meal_item_id: Uuid, meal_item_status: MealItemStatus, now: DateTime<Utc>) -> bool {
+        let meal_item_arc = match self.get_meal_item(meal_item_id) {
+            Some(meal_item_arc) => meal_item_arc,
+            None => return false,
+        };
+        let mut meal_item = meal_item_arc.lock().unwrap();
+        if meal_item.is_voided() {
+            return true;
+        }
+        self.tally.subtract(&meal_item);
+        meal_item.update_state(meal_item_status, now);
+        self.tally.add(&meal_item);
+        self.update_time = now;
+        true
+    }
+
+    // Voids the meal item being prepared, which is then no longer charged. Returns None if the meal item doesn't belong
+    // to the order, otherwise whether it was voided, which only meal items being prepared of an open order can be.
+    pub fn void_meal_item(&mut self, meal_item_id: Uuid, reason: String, now: DateTime<Utc>) -> Option<bool> {
+        let meal_item_arc = self.get_meal_item(meal_item_id)?;
+        let mut meal_item = meal_item_arc.lock().unwrap();
+        if self.is_closed() || meal_item.is_removed() || meal_item.get_status() != MealItemStatus::Preparing
+            || !self.covers_payments_without(meal_item.price()) {
+            return Some(false);
+        }
+        self.subtotal = self.subtotal.checked_sub(meal_item.price())
+            .expect("subtotal always covers every non-removed meal item");
+        self.tally.subtract(&meal_item);
+        self.split = None;
+        self.promotions.clear();
+        meal_item.void(reason, now);
+        self.update_time = now;
+        Some(true)
+    }
+
+    // Marks the meal items waiting on the pass as served. Returns those which could not be served, as they don't belong
+    // to the order or are not ready for pickup; meal items already served are left as they are.
+    pub fn serve_meal_items(&mut self, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Vec<Uuid> {
+        let mut non_servable_items = Vec::new();
+        for meal_item_id in meal_item_ids.iter() {
+            let meal_item_arc = match self.get_meal_item(*meal_item_id) {
+                Some(meal_item_arc) => meal_item_arc,
+                None => {
+                    non_servable_items.push(*meal_item_id);
+                    continue;
+                }
+            };
+            let mut meal_item = meal_item_arc.lock().unwrap();
+            match meal_item.get_status() {
+                MealItemStatus::Served => continue,
+                MealItemStatus::ReadyForPickup => {}
+                _ => {
+                    non_servable_items.push(*meal_item_id);
+                    continue;
+                }
+            }
+            self.tally.subtract(&meal_item);
+            meal_item.update_state(MealItemStatus::Served, now);
+            self.tally.add(&meal_item);
+        }
+        self.update_time = now;
+        non_servable_items
+    }
+
+    // A meal item can only stop being charged if what was paid so far is still covered by the subtotal without it,
+    // as its promotions are dropped along with it
+    fn covers_payments_without(&self, price: Price) -> bool {
+        self.subtotal.minor_units() - price.minor_units() >= self.get_amount_paid().minor_units()
+    }
+
+    // Records part or all of the bill as paid; once nothing is left to pay, the order is closed
+    pub fn add_payment(&mut self, payment: Payment) -> Result<(), PaymentError> {
+        if self.is_closed() {
+            return Err(PaymentError::OrderClosed);
+        }
+        if payment.amount().currency() != self.get_currency() {
+            return Err(PaymentError::CurrencyMismatch(self.get_currency(), payment.amount().currency()));
+        }
+        // Nothing is left to pay on an order whose meal items are all voided or free, so a payment of zero settles it
+        if payment.amount().minor_units() == 0 && self.get_balance_due().minor_units() > 0 {
+            return Err(PaymentError::ZeroAmount);
+        }
+        let balance_due = match (&self.split, payment.check()) {
+            (None, None) => self.get_balance_due(),
+            (None, Some(check)) => return Err(PaymentError::UnknownCheck(check)),
+            (Some(_), None) => return Err(PaymentError::CheckMissing),
+            (Some(_), Some(check)) => match self.get_sub_checks().into_iter().find(|sub_check| sub_check.name() == check) {
+                Some(sub_check) => sub_check.balance_due(),
+                None => return Err(PaymentError::UnknownCheck(check)),
+            },
+        };
+        if payment.amount().minor_units() > balance_due.minor_units() {
+            return Err(PaymentError::ExceedsBalance(balance_due));
+        }
+
+        let now = payment.time();
+        self.payments.push(payment);
+        if self.get_balance_due().minor_units() == 0 {
+            self.closed_time = Some(now);
+        }
+        self.update_time = now;
+        Ok(())
+    }
+
+    // Replaces the promotions taken off the order, ex. with those worked out by promotion::apply_promotions, and the taxes
+    // and service charge it is charged. Returns whether they changed; a closed order keeps its pricing, and so does an order
+    // whose total would fall below what was paid or whose promotions don't match the meal items charged.
+    pub fn apply_pricing(&mut self, promotions: Vec<AppliedPromotion>, tax_policy: TaxPolicy, now: DateTime<Utc>) -> bool {
+        if self.is_closed() || (promotions == self.promotions && tax_policy == self.tax_policy) {
+            return false;
+        }
+        let charged = |meal_item_id: &Uuid| self.get_meal_item(*meal_item_id)
+            .is_some_and(|meal_item_arc| {
+                let meal_item = meal_item_arc.lock().unwrap();
+                !meal_item.is_removed() && !meal_item.is_voided()
+            });
+        if !promotions.iter().flat_map(|promotion| promotion.meal_item_savings()).all(|(meal_item_id, _)| charged(&meal_item_id)) {
+            return false;
+        }
+        let total = self.charged_meal_items(&promotions)
+            .and_then(|charged| self.total_of(&charged, &self.charges_of(&charged, &tax_policy)?));
+        match total {
+            Ok(total) if total.minor_units() >= self.get_amount_paid().minor_units() => {}
+            _ => return false,
+        }
+        // The checks of a split bill no longer add up to the total
+        self.split = None;
+        self.promotions = promotions;
+        self.tax_policy = tax_policy;
+        self.update_time = now;
+        true
+    }
+
+    // Meal items charged on the order, oldest first, each with the savings of the promotions on it and its share of those
+    // on the whole order, in proportion to what is left to pay on it
+    fn charged_meal_items(&self, promotions: &[AppliedPromotion]) -> Result<Vec<ChargedMealItem>, PriceError> {
+        let currency = self.get_currency();
+        let mut meal_items: Vec<MealItem> = self.get_meal_items().iter()
+            .map(|meal_item_arc| meal_item_arc.lock().unwrap().clone())
+            .filter(|meal_item| !meal_item.is_removed() && !meal_item.is_voided())
+            .collect();
+        meal_items.sort_by_key(|meal_item| (meal_item.creation_time(), meal_item.id()));
+        let mut charged = meal_items.into_iter()
+            .map(|meal_item| {
+                let discount = promotions.iter()
+                    .flat_map(|promotion| promotion.meal_item_savings())
+                    .filter(|(meal_item_id, _)| *meal_item_id == meal_item.id())
+                    .try_fold(Price::zero(currency), |discount, (_, saving)| discount.checked_add(saving))?;
+                Ok(ChargedMealItem { meal_item_id: meal_item.id(), category: meal_item.category(), price: meal_item.price(), discount })
+            })
+            .collect::<Result<Vec<ChargedMealItem>, PriceError>>()?;
+
+        let order_discount = promotions.iter()
+            .filter(|promotion| promotion.is_on_order())
+            .try_fold(Price::zero(currency), |discount, promotion| discount.checked_add(promotion.saved()))?;
+        if order_discount.minor_units() > 0 {
+            let weights: Vec<i64> = charged.iter().map(|meal_item| meal_item.price.minor_units() - meal_item.discount.minor_units()).collect();
+            for (meal_item, share) in charged.iter_mut().zip(order_discount.allocate(&weights)?) {
+                meal_item.discount = meal_item.discount.checked_add(share)?;
+            }
+        }
+        for meal_item in charged.iter() {
+            meal_item.price.checked_sub(meal_item.discount)?;
+        }
+        Ok(charged)
+    }
+
+    fn charges_of(&self, charged: &[ChargedMealItem], tax_policy: &TaxPolicy) -> Result<Charges, PriceError> {
+        let meal_items: Vec<(Option<String>, Price)> = charged.iter()
+            .map(|meal_item| Ok((meal_item.category.clone(), meal_item.price.checked_sub(meal_item.discount)?)))
+            .collect::<Result<Vec<_>, PriceError>>()?;
+        tax_policy.charge(&meal_items, self.guests, self.get_currency())
+    }
+
+    // What the meal items cost once promotions are taken off, plus the taxes added on top and the service charge
+    fn total_of(&self, charged: &[ChargedMealItem], charges: &Charges) -> Result<Price, PriceError> {
+        charged.iter()
+            .try_fold(Price::zero(self.get_currency()), |total, meal_item| total.checked_add(meal_item.price.checked_sub(meal_item.discount)?))?
+            .checked_add(charges.tax_added())?
+            .checked_add(charges.service_charge())
+    }
+
+    // Splits the bill into checks paid on their own. The bill can be split again, ex. after meal items changed,
+    // as long as every check paid in part keeps its name and is still charged at least what it paid.
+    pub fn split_bill(&mut self, split: BillSplit, now: DateTime<Utc>) -> Result<(), SplitError> {
+        if self.is_closed() {
+            return Err(SplitError::OrderClosed);
+        }
+        let sub_checks = self.sub_checks_of(&split)?;
+        for payment in self.payments.iter() {
+            let check = payment.check().ok_or(SplitError::PaidBeforeSplit)?;
+            let sub_check = sub_checks.iter().find(|sub_check| sub_check.name() == check)
+                .ok_or_else(|| SplitError::PaidCheckMissing(check.clone()))?;
+            if sub_check.amount_paid().minor_units() > sub_check.total().minor_units() {
+                return Err(SplitError::CheckOverpaid(check));
+            }
+        }
+        self.split = Some(split);
+        self.update_time = now;
+        Ok(())
+    }
+
+    // Charges each check its share of the total price, so the checks always add up to it. A check gets the savings
+    // of promotions on its meal items, its share of those on the whole order, and the taxes and service charge of its meal items.
+    fn sub_checks_of(&self, split: &BillSplit) -> Result<Vec<SubCheck>, SplitError> {
+        let currency = self.get_currency();
+        let charged = self.charged_meal_items(&self.promotions)?;
+        let charges = self.charges_of(&charged, &self.tax_policy)?;
+        let shares = match split {
+            BillSplit::ByMealItem { checks } => {
+                if checks.is_empty() {
+                    return Err(SplitError::NoChecks);
+                }
+                let taxes_added = charges.meal_item_taxes_added();
+                let service_charges = charges.meal_item_service_charges();
+                let mut assigned = Vec::new();
+                let mut shares = Vec::new();
+                for check in checks.iter() {
+                    let name = check.name.trim().to_string();
+                    if name.is_empty() {
+                        return Err(SplitError::EmptyCheckName);
+                    }
+                    if shares.iter().any(|(share_name, _, _, _, _, _)| *share_name == name) {
+                        return Err(SplitError::DuplicateCheckName(name));
+                    }
+                    let (mut subtotal, mut discount, mut tax, mut service_charge) =
+                        (Price::zero(currency), Price::zero(currency), Price::zero(currency), Price::zero(currency));
+                    for meal_item_id in check.meal_item_ids.iter() {
+                        let position = charged.iter().position(|meal_item| meal_item.meal_item_id == *meal_item_id)
+                            .ok_or(SplitError::UnknownMealItem(*meal_item_id))?;
+                        if assigned.contains(meal_item_id) {
+                            return Err(SplitError::DuplicateMealItem(*meal_item_id));
+                        }
+                        assigned.push(*meal_item_id);
+                        subtotal = subtotal.checked_add(charged[position].price)?;
+                        discount = discount.checked_add(charged[position].discount)?;
+                        tax = tax.checked_add(taxes_added[position])?;
+                        service_charge = service_charge.checked_add(service_charges[position])?;
+                    }
+                    shares.push((name, check.meal_item_ids.clone(), subtotal, discount, tax, service_charge));
+                }
+                if let Some(meal_item) = charged.iter().find(|meal_item| !assigned.contains(&meal_item.meal_item_id)) {
+                    return Err(SplitError::MissingMealItem(meal_item.meal_item_id));
+                }
+                shares
+            }
+            BillSplit::Evenly { guests } => {
+                if *guests == 0 || *guests > MAX_GUESTS {
+                    return Err(SplitError::InvalidGuests(*guests));
+                }
+                // The subtotal, what is left once promotions are taken off, and the total are each shared evenly, so every guest
+                // pays the same but for a minor unit. What is between them is the discount, and the taxes and service charge.
+                let total = self.total_of(&charged, &charges)?;
+                let tax_added = charges.tax_added();
+                let net = total.checked_sub(tax_added)?.checked_sub(charges.service_charge())?;
+                let subtotals = self.subtotal.split_evenly(*guests)?;
+                let nets = net.split_evenly(*guests)?;
+                let totals = total.split_evenly(*guests)?;
+                let surcharges: Vec<i64> = totals.iter().zip(nets.iter()).map(|(total, net)| total.minor_units() - net.minor_units()).collect();
+                let taxes = if tax_added.minor_units() > 0 { tax_added.allocate(&surcharges)? } else { vec![Price::zero(currency); *guests as usize] };
+                subtotals.into_iter()
+                    .zip(nets)
+                    .zip(totals)
+                    .zip(taxes)
+                    .zip(1..)
+                    .map(|((((subtotal, net), total), tax), position)| {
+                        let service_charge = total.checked_sub(net)?.checked_sub(tax)?;
+                        Ok((guest_check_name(position), Vec::new(), subtotal, subtotal.checked_sub(net)?, tax, service_charge))
+                    })
+                    .collect::<Result<Vec<_>, PriceError>>()?
+            }
+        };
+
+        shares.into_iter()
+            .map(|(name, meal_item_ids, subtotal, discount, tax, service_charge)| {
+                let amount_paid = self.payments.iter()
+                    .filter(|payment| payment.check().as_deref() == Some(name.as_str()))
+                    .try_fold(Price::zero(currency), |paid, payment| paid.checked_add(payment.amount()))?;
+                Ok(SubCheck::new(name, meal_item_ids, subtotal, discount, tax, service_charge, amount_paid))
+            })
+            .collect()
+    }
+
+    pub fn get_split(&self) -> Option<BillSplit> {
+        self.split.clone()
+    }
+
+    // Checks of the split bill, none unless the bill is split
+    pub fn get_sub_checks(&self) -> Vec<SubCheck> {
+        match &self.split {
+            Some(split) => self.sub_checks_of(split).expect("the split is checked when made and dropped when meal items change"),
+            None => Vec::new(),
+        }
+    }
+
     pub fn get_meal_items(&self) -> Vec<Arc<Mutex<MealItem>>> {
         self.meal_items.iter().map(|entry| entry.value().clone()).collect()
     }
@@ -113,55 +633,105 @@ impl Order {
         self.meal_items.get(&meal_item_id).map(|item| item.clone())
     }
 
+    pub fn id(&self) -> Uuid {
+        self.order_id
+    }
+
     pub fn get_table_id(&self) -> u32 {
         self.table_id
     }
 
+    pub fn get_priority(&self) -> OrderPriority {
+        self.priority
+    }
+
+    pub fn get_guests(&self) -> Option<u32> {
+        self.guests
+    }
+
+    pub fn get_tax_policy(&self) -> TaxPolicy {
+        self.tax_policy.clone()
+    }
+
+    pub fn get_subtotal(&self) -> Price {
+        self.subtotal
+    }
+
+    pub fn get_promotions(&self) -> Vec<AppliedPromotion> {
+        self.promotions.clone()
+    }
+
+    // Sum of what every promotion saved, never more than the subtotal
+    pub fn get_discount(&self) -> Price {
+        self.promotions.iter()
+            .try_fold(Price::zero(self.get_currency()), |discount, promotion| discount.checked_add(promotion.saved()))
+            .expect("promotions never save more than the subtotal")
+    }
+
+    // Taxes and service charge of the order under the policy it was last priced with
+    pub fn get_charges(&self) -> Charges {
+        self.charged_meal_items(&self.promotions)
+            .and_then(|charged| self.charges_of(&charged, &self.tax_policy))
+            .expect("promotions are checked when applied and dropped when meal items change")
+    }
+
+    // What the order is charged, which is the subtotal less the promotions, plus the taxes added on top and the service charge
     pub fn get_total_price(&self) -> Price {
-        self.total_price
+        self.charged_meal_items(&self.promotions)
+            .and_then(|charged| self.total_of(&charged, &self.charges_of(&charged, &self.tax_policy)?))
+            .expect("promotions are checked when applied and dropped when meal items change")
     }
 
-    pub fn is_active(&self) -> bool {
-        let order_status = self.get_order_status();
-        match order_status {
-            OrderStatus::Received | OrderStatus::Preparing => true,
-            _ => false,
-        }
+    pub fn get_payments(&self) -> Vec<Payment> {
+        self.payments.clone()
     }
 
-    // TODO: improve to have and update order_status on write
-    pub fn get_order_status(&self) -> OrderStatus {
-        let mut has_preparing = false;
-        let mut has_received = false;
-        let mut all_removed = true;
-        for item_arc in self.meal_items.iter() {
-            let item = item_arc.lock().unwrap();
-
-            if item.is_removed() {
-                continue;
-            }
+    pub fn get_amount_paid(&self) -> Price {
+        self.payments.iter()
+            .try_fold(Price::zero(self.get_currency()), |paid, payment| paid.checked_add(payment.amount()))
+            .expect("payments never add up to more than the total price")
+    }
 
-            all_removed = false;
+    pub fn get_balance_due(&self) -> Price {
+        self.get_total_price().checked_sub(self.get_amount_paid())
+            .expect("payments never add up to more than the total price")
+    }
 
-            match item.get_status() {
-                MealItemStatus::Received => {
-                    has_received = true;
-                }
-                MealItemStatus::Preparing => {
-                    has_preparing = true;
-                }
-                MealItemStatus::Completed => {}
-            }
-        }
+    pub fn is_closed(&self) -> bool {
+        self.closed_time.is_some()
+    }
 
-        if all_removed {
-            OrderStatus::Canceled
-        } else if has_preparing {
-            OrderStatus::Preparing
-        } else if has_received {
-            OrderStatus::Received
-        } else {
-            OrderStatus::Completed
-        }
+    pub fn closed_time(&self) -> Option<DateTime<Utc>> {
+        self.closed_time
+    }
+
+    // The table of an open order can't take a new one until the order is paid in full or canceled
+    pub fn is_open(&self) -> bool {
+        !self.is_closed() && self.get_order_status() != OrderStatus::Canceled
+    }
+
+    pub fn get_currency(&self) -> Currency {
+        self.subtotal.currency()
+    }
+
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        self.creation_time
     }
-}
\ No newline at end of file
+
+    pub fn update_time(&self) -> DateTime<Utc> {
+        self.update_time
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self.get_order_status(), OrderStatus::Received | OrderStatus::Preparing)
+    }
+
+    pub fn get_order_status(&self) -> OrderStatus {
+        self.tally.order_status()
+    }
+
+    // Sum of the cooking times of the non-removed meal items which are not cooked yet
+    pub fn get_remaining_cooking_time_in_min(&self) -> u32 {
+        self.tally.remaining_cooking_time_in_min
+    }
+}
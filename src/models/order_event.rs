@@ -0,0 +1,221 @@
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+use crate::models::meal::MealItemStatus;
+use crate::models::order::{Order, OrderStatus};
+use crate::models::promotion::AppliedPromotion;
+use crate::models::split::BillSplit;
+use crate::models::tax::TaxPolicy;
+
+// What changed in an order, pushed to the clients following it
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderChange {
+    OrderAdded { meal_item_ids: Vec<Uuid>, status: OrderStatus },
+    MealItemsAdded { meal_item_ids: Vec<Uuid> },
+    MealItemsRemoved { meal_item_ids: Vec<Uuid> },
+    MealItemStatusChanged { meal_item_id: Uuid, from: MealItemStatus, to: MealItemStatus },
+    // The status of the order follows from those of its meal items, so it changes along with them
+    OrderStatusChanged { from: OrderStatus, to: OrderStatus },
+    OrderRemoved,
+    // The promotions taken off the order or its taxes changed, and so did its total
+    OrderPriced,
+    BillSplit,
+    PaymentAdded { payment_id: Uuid },
+    // Paid in full
+    OrderClosed,
+}
+
+impl OrderChange {
+    // Name of the change, which is the name of its server-sent event
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrderChange::OrderAdded { .. } => "order_added",
+            OrderChange::MealItemsAdded { .. } => "meal_items_added",
+            OrderChange::MealItemsRemoved { .. } => "meal_items_removed",
+            OrderChange::MealItemStatusChanged { .. } => "meal_item_status_changed",
+            OrderChange::OrderStatusChanged { .. } => "order_status_changed",
+            OrderChange::OrderRemoved => "order_removed",
+            OrderChange::OrderPriced => "order_priced",
+            OrderChange::BillSplit => "bill_split",
+            OrderChange::PaymentAdded { .. } => "payment_added",
+            OrderChange::OrderClosed => "order_closed",
+        }
+    }
+
+    pub fn added(order: &OrderState) -> Self {
+        OrderChange::OrderAdded {
+            meal_item_ids: order.meal_items.iter().map(|meal_item| meal_item.meal_item_id).collect(),
+            status: order.status,
+        }
+    }
+
+    // Every change between two states of the same order: meal items added, then removed, then whose status changed
+    // in the order they were ordered, then the status of the order, and last its bill
+    pub fn between(before: &OrderState, after: &OrderState) -> Vec<Self> {
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut status_changes = Vec::new();
+        for meal_item in after.meal_items.iter() {
+            match before.meal_items.iter().find(|previous| previous.meal_item_id == meal_item.meal_item_id) {
+                None => added.push(meal_item.meal_item_id),
+                Some(previous) if !previous.is_removed && meal_item.is_removed => removed.push(meal_item.meal_item_id),
+                Some(previous) if previous.status != meal_item.status => {
+                    status_changes.push(OrderChange::MealItemStatusChanged { meal_item_id: meal_item.meal_item_id, from: previous.status, to: meal_item.status });
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut changes = Vec::new();
+        if !added.is_empty() {
+            changes.push(OrderChange::MealItemsAdded { meal_item_ids: added });
+        }
+        if !removed.is_empty() {
+            changes.push(OrderChange::MealItemsRemoved { meal_item_ids: removed });
+        }
+        changes.extend(status_changes);
+        if before.status != after.status {
+            changes.push(OrderChange::OrderStatusChanged { from: before.status, to: after.status });
+        }
+        if before.promotions != after.promotions || before.tax_policy != after.tax_policy {
+            changes.push(OrderChange::OrderPriced);
+        }
+        // A split dropped along with the meal items it was made of tells nothing the changes of the meal items don't
+        if after.split.is_some() && before.split != after.split {
+            changes.push(OrderChange::BillSplit);
+        }
+        for payment_id in after.payment_ids.iter().filter(|payment_id| !before.payment_ids.contains(payment_id)) {
+            changes.push(OrderChange::PaymentAdded { payment_id: *payment_id });
+        }
+        if !before.is_closed && after.is_closed {
+            changes.push(OrderChange::OrderClosed);
+        }
+        changes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct MealItemState {
+    meal_item_id: Uuid,
+    is_removed: bool,
+    status: MealItemStatus,
+}
+
+// The statuses of an order and of its meal items, and what its bill is made of, at a point in time.
+// Orders share their meal items with their clones, so telling what a change did takes copying the statuses before it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderState {
+    order_id: Uuid,
+    table_id: u32,
+    status: OrderStatus,
+    // In the order they were ordered
+    meal_items: Vec<MealItemState>,
+    promotions: Vec<AppliedPromotion>,
+    tax_policy: TaxPolicy,
+    split: Option<BillSplit>,
+    payment_ids: Vec<Uuid>,
+    is_closed: bool,
+}
+
+impl OrderState {
+    pub fn of(order: &Order) -> Self {
+        let mut meal_items: Vec<(DateTime<Utc>, MealItemState)> = order.get_meal_items().iter()
+            .map(|meal_item_arc| {
+                let meal_item = meal_item_arc.lock().unwrap();
+                (meal_item.creation_time(), MealItemState { meal_item_id: meal_item.id(), is_removed: meal_item.is_removed(), status: meal_item.get_status() })
+            })
+            .collect();
+        meal_items.sort_by_key(|(creation_time, meal_item)| (*creation_time, meal_item.meal_item_id));
+        OrderState {
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            status: order.get_order_status(),
+            meal_items: meal_items.into_iter().map(|(_, meal_item)| meal_item).collect(),
+            promotions: order.get_promotions(),
+            tax_policy: order.get_tax_policy(),
+            split: order.get_split(),
+            payment_ids: order.get_payments().iter().map(|payment| payment.id()).collect(),
+            is_closed: order.is_closed(),
+        }
+    }
+
+    pub fn order_id(&self) -> Uuid {
+        self.order_id
+    }
+
+    pub fn table_id(&self) -> u32 {
+        self.table_id
+    }
+}
+
+// Id of an event: the run of the server which published it, then its number in that run, ex. 3054127789-42.
+// Numbers start over every run, so an id from another run can't tell which events a client got.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventId {
+    pub epoch: u32,
+    pub number: u64,
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.epoch, self.number)
+    }
+}
+
+impl FromStr for EventId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid event id '{}'", s);
+        let (epoch, number) = s.split_once('-').ok_or_else(invalid)?;
+        Ok(EventId {
+            epoch: epoch.parse().map_err(|_| invalid())?,
+            number: number.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+// A change of an order, numbered in the order changes were made across all orders, so clients can resume after the last one they got
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderEvent {
+    event_id: EventId,
+    order_id: Uuid,
+    table_id: u32,
+    change: OrderChange,
+    time: DateTime<Utc>,
+}
+
+impl OrderEvent {
+    pub fn new(event_id: EventId, order_id: Uuid, table_id: u32, change: OrderChange, time: DateTime<Utc>) -> Self {
+        OrderEvent {
+            event_id,
+            order_id,
+            table_id,
+            change,
+            time,
+        }
+    }
+
+    pub fn id(&self) -> EventId {
+        self.event_id
+    }
+
+    pub fn order_id(&self) -> Uuid {
+        self.order_id
+    }
+
+    pub fn table_id(&self) -> u32 {
+        self.table_id
+    }
+
+    pub fn change(&self) -> OrderChange {
+        self.change.clone()
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod order_event_test {
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::{Order, OrderStatus};
+    use crate::models::order_event::{OrderChange, OrderState};
+    use crate::models::price::Price;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn opened_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    fn meal_item(name: &str, time: DateTime<Utc>) -> MealItem {
+        let menu_item = MenuItem::new(String::from(name), Price::from_minor_units(500, Currency::Usd).unwrap());
+        MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), time)
+    }
+
+    #[test]
+    fn test_order_change_added() {
+        let fries = meal_item("fries", opened_time());
+        let burger = meal_item("burger", opened_time() + TimeDelta::minutes(1));
+        let order = Order::new(1, Currency::Usd, vec![burger.clone(), fries.clone()], opened_time()).unwrap();
+
+        let state = OrderState::of(&order);
+
+        assert_eq!((order.id(), 1), (state.order_id(), state.table_id()));
+        let expected = OrderChange::OrderAdded { meal_item_ids: vec![fries.id(), burger.id()], status: OrderStatus::Received };
+        assert_eq!(expected, OrderChange::added(&state));
+    }
+
+    #[test]
+    fn test_order_change_between_meal_items_added_and_removed() {
+        let fries = meal_item("fries", opened_time());
+        let burger = meal_item("burger", opened_time());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], opened_time()).unwrap();
+        let before = OrderState::of(&order);
+
+        let soup = meal_item("soup", opened_time() + TimeDelta::minutes(1));
+        order.add_meal_items(vec![soup.clone()], opened_time() + TimeDelta::minutes(1)).unwrap();
+        order.remove_meal_items(vec![burger.id()], opened_time() + TimeDelta::minutes(1));
+
+        let expected = vec![
+            OrderChange::MealItemsAdded { meal_item_ids: vec![soup.id()] },
+            OrderChange::MealItemsRemoved { meal_item_ids: vec![burger.id()] },
+        ];
+        assert_eq!(expected, OrderChange::between(&before, &OrderState::of(&order)));
+    }
+
+    #[test]
+    fn test_order_change_between_status_changed() {
+        let fries = meal_item("fries", opened_time());
+        let burger = meal_item("burger", opened_time());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], opened_time()).unwrap();
+        let before = OrderState::of(&order);
+
+        order.update_meal_item_status(fries.id(), MealItemStatus::Preparing, opened_time() + TimeDelta::minutes(1));
+
+        let expected = vec![
+            OrderChange::MealItemStatusChanged { meal_item_id: fries.id(), from: MealItemStatus::Received, to: MealItemStatus::Preparing },
+            OrderChange::OrderStatusChanged { from: OrderStatus::Received, to: OrderStatus::Preparing },
+        ];
+        assert_eq!(expected, OrderChange::between(&before, &OrderState::of(&order)));
+        assert!(OrderChange::between(&OrderState::of(&order), &OrderState::of(&order)).is_empty());
+    }
+
+    #[test]
+    fn test_order_change_serialize() {
+        let fries = meal_item("fries", opened_time());
+        let change = OrderChange::MealItemStatusChanged { meal_item_id: fries.id(), from: MealItemStatus::Received, to: MealItemStatus::Preparing };
+
+        let actual = serde_json::to_value(&change).unwrap();
+
+        assert_eq!("meal_item_status_changed", actual["type"]);
+        assert_eq!(change.name(), actual["type"]);
+        assert_eq!(fries.id().to_string(), actual["meal_item_id"]);
+        assert_eq!(serde_json::to_value(MealItemStatus::Preparing).unwrap(), actual["to"]);
+        assert_eq!("order_removed", serde_json::to_value(OrderChange::OrderRemoved).unwrap()["type"]);
+    }
+}
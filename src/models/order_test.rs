@@ -1,29 +1,38 @@
 #[cfg(test)]
 mod order_test {
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
     use crate::models::meal::{MealItem, MealItemStatus};
     use crate::models::menu::MenuItem;
+    use crate::models::price::{Price, PriceError};
     use crate::models::order::{Order, OrderStatus};
+    use crate::models::payment::{Payment, PaymentError, PaymentMethod};
+    use crate::models::promotion::AppliedPromotion;
+    use crate::models::split::{BillSplit, CheckStatus, NamedCheck, SplitError};
+    use crate::models::tax::{ServiceCharge, Tax, TaxPolicy};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
 
     #[test]
     fn test_add_meal_items() {
         let mut expected_meal_items = Vec::new();
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
         expected_meal_items.push(meal_item);
 
-        let menu_item = MenuItem::new(String::from("burger"), String::from("789"));
-        let meal_item = MealItem::create(menu_item);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
         expected_meal_items.push(meal_item.clone());
 
         let mut actual_meal_items = order.get_meal_items();
         assert_eq!(expected_meal_items.len(), actual_meal_items.len());
 
-        expected_meal_items.sort_by(|a, b| a.id().cmp(&b.id()));
-        actual_meal_items.sort_by(|a, b| a.lock().unwrap().id().cmp(&b.lock().unwrap().id()));
+        expected_meal_items.sort_by_key(|a| a.id());
+        actual_meal_items.sort_by_key(|a| a.lock().unwrap().id());
 
         for (expected, actual) in expected_meal_items.iter().zip(actual_meal_items.iter()) {
             assert_eq!(expected.clone(), actual.lock().unwrap().clone());
@@ -32,25 +41,25 @@ mod order_test {
 
     #[test]
     fn test_remove_meal_items() {
-        let mut order = Order::new(1, vec![]);
-        let menu_item_one = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item_one = MealItem::create(menu_item_one);
-        let menu_item_two = MenuItem::new(String::from("burger"), String::from("789"));
-        let mut meal_item_two = MealItem::create(menu_item_two);
-        meal_item_two.update_state(MealItemStatus::Preparing);
-        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone()]);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let menu_item_one = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item_one = MealItem::create(menu_item_one, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item_two = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let mut meal_item_two = MealItem::create(menu_item_two, &MockCookingTimeEstimator::new(10), Utc::now());
+        meal_item_two.update_state(MealItemStatus::Preparing, Utc::now());
+        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone()], Utc::now()).unwrap();
         let meal_item_ids = vec![meal_item_one.id(), meal_item_two.id()];
 
         for meal_item_arc in order.get_meal_items().iter() {
             assert!(!meal_item_arc.lock().unwrap().is_removed());
         }
 
-        order.remove_meal_items(meal_item_ids);
+        order.remove_meal_items(meal_item_ids, Utc::now());
 
         for meal_item_arc in order.get_meal_items().iter() {
             let meal_item = meal_item_arc.lock().unwrap();
             match meal_item.get_status() {
-                MealItemStatus::Preparing | MealItemStatus::Completed => {
+                MealItemStatus::Preparing | MealItemStatus::ReadyForPickup => {
                     assert!(!meal_item.is_removed());
                 }
                 _ => {
@@ -60,14 +69,44 @@ mod order_test {
         }
     }
 
+    #[test]
+    fn test_deep_clone() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+
+        let mut copy = order.deep_clone();
+        assert!(copy.update_meal_item_status(meal_item.id(), MealItemStatus::Preparing, Utc::now()));
+
+        // The meal items of the copy are its own, so the order is left as it was
+        let original = order.get_meal_item(meal_item.id()).unwrap();
+        assert_eq!(MealItemStatus::Received, original.lock().unwrap().get_status());
+        assert_eq!(OrderStatus::Received, order.get_order_status());
+        assert_eq!(MealItemStatus::Preparing, copy.get_meal_item(meal_item.id()).unwrap().lock().unwrap().get_status());
+    }
+
+    #[test]
+    fn test_add_meal_items_to_canceled_order() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+        order.remove_meal_items(vec![meal_item.id()], Utc::now());
+        assert_eq!(OrderStatus::Canceled, order.get_order_status());
+
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        assert_eq!(Ok(false), order.add_meal_items(vec![meal_item.clone()], Utc::now()));
+        assert!(order.get_meal_item(meal_item.id()).is_none());
+        assert_eq!(OrderStatus::Canceled, order.get_order_status());
+    }
+
     #[test]
     fn test_get_meal_items() {
         let mut expected_meal_items = Vec::new();
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
         expected_meal_items.push(meal_item);
 
         let actual_meal_items = order.get_meal_items();
@@ -80,10 +119,10 @@ mod order_test {
 
     #[test]
     fn test_get_meal_item() {
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
         if let Some(meal_item_arc) = order.get_meal_item(meal_item.id()) {
             assert_eq!(meal_item, meal_item_arc.lock().unwrap().clone());
@@ -94,26 +133,390 @@ mod order_test {
 
     #[test]
     fn test_get_order_status() {
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let mut meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
         assert_eq!(OrderStatus::Received, order.get_order_status());
 
-        meal_item.update_state(MealItemStatus::Preparing);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let mut preparing_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        preparing_meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        order.add_meal_items(vec![preparing_meal_item.clone()], Utc::now()).unwrap();
         assert_eq!(OrderStatus::Preparing, order.get_order_status());
 
-        for meal_item_arc in order.get_meal_items() {
-            meal_item_arc.lock().unwrap().update_state(MealItemStatus::Completed);
-        }
+        assert!(order.update_meal_item_status(meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()));
+        assert!(order.update_meal_item_status(preparing_meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()));
         assert_eq!(OrderStatus::Completed, order.get_order_status());
 
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let mut order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+        assert!(order.remove(Utc::now()));
+        assert_eq!(OrderStatus::Canceled, order.get_order_status());
+    }
+
+    #[test]
+    fn test_get_remaining_cooking_time_in_min() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item_one = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+        let meal_item_two = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+        let meal_item_three = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone(), meal_item_three.clone()], Utc::now()).unwrap();
+        assert_eq!(meal_item_one.cooking_time_in_min() + meal_item_two.cooking_time_in_min() + meal_item_three.cooking_time_in_min(),
+                   order.get_remaining_cooking_time_in_min());
 
-        for meal_item_arc in order.get_meal_items() {
-            meal_item_arc.lock().unwrap().remove();
-        }
+        // Preparing items still count, completed and removed ones don't
+        order.update_meal_item_status(meal_item_one.id(), MealItemStatus::Preparing, Utc::now());
+        order.update_meal_item_status(meal_item_two.id(), MealItemStatus::ReadyForPickup, Utc::now());
+        order.remove_meal_items(vec![meal_item_three.id()], Utc::now());
+        assert_eq!(meal_item_one.cooking_time_in_min(), order.get_remaining_cooking_time_in_min());
+        assert_eq!(OrderStatus::Preparing, order.get_order_status());
+
+        assert!(!order.update_meal_item_status(Uuid::new_v4(), MealItemStatus::ReadyForPickup, Utc::now()));
+    }
+
+    #[test]
+    fn test_add_meal_items_price_overflow() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("caviar"), Price::from_minor_units(i64::MAX, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+
+        assert_eq!(Err(PriceError::Overflow), order.add_meal_items(vec![meal_item], Utc::now()));
+        assert_eq!(1, order.get_meal_items().len());
+        assert_eq!("345", order.get_total_price().to_string());
+    }
+
+    #[test]
+    fn test_add_meal_items_currency_mismatch() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("ramen"), Price::from_minor_units(980, Currency::Jpy).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+
+        assert_eq!(Err(PriceError::CurrencyMismatch(Currency::Usd, Currency::Jpy)), order.add_meal_items(vec![meal_item], Utc::now()));
+        assert_eq!(1, order.get_meal_items().len());
+        assert_eq!(Currency::Usd, order.get_total_price().currency());
+    }
+
+    #[test]
+    fn test_void_meal_item() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let received_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let preparing_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![received_meal_item.clone(), preparing_meal_item.clone()], Utc::now()).unwrap();
+        order.update_meal_item_status(preparing_meal_item.id(), MealItemStatus::Preparing, Utc::now());
+
+        // Given only meal items being prepared can be voided, the received one is left as is
+        assert_eq!(Some(false), order.void_meal_item(received_meal_item.id(), String::from("guest left"), Utc::now()));
+        assert_eq!(None, order.void_meal_item(Uuid::new_v4(), String::from("guest left"), Utc::now()));
+        assert_eq!(Some(true), order.void_meal_item(preparing_meal_item.id(), String::from("guest left"), Utc::now()));
+
+        // The voided meal item is no longer charged nor cooked, and can't be voided, removed, or completed anymore
+        assert_eq!(Price::from_minor_units(345, Currency::Usd).unwrap(), order.get_total_price());
+        assert_eq!(10, order.get_remaining_cooking_time_in_min());
+        assert_eq!(OrderStatus::Received, order.get_order_status());
+        assert_eq!(Some(false), order.void_meal_item(preparing_meal_item.id(), String::from("again"), Utc::now()));
+        assert_eq!(vec![preparing_meal_item.id()], order.remove_meal_items(vec![preparing_meal_item.id()], Utc::now()));
+        assert!(order.update_meal_item_status(preparing_meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()));
+        assert_eq!(MealItemStatus::Voided, order.get_meal_item(preparing_meal_item.id()).unwrap().lock().unwrap().get_status());
+
+        // Once the rest is removed, nothing is left to serve
+        assert!(order.remove(Utc::now()));
         assert_eq!(OrderStatus::Canceled, order.get_order_status());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_serve_meal_items() {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+        order.update_meal_item_status(fries.id(), MealItemStatus::ReadyForPickup, Utc::now());
+        order.update_meal_item_status(burger.id(), MealItemStatus::Preparing, Utc::now());
+
+        // Given only meal items waiting on the pass can be served, the burger and the unknown meal item are left as they are
+        let unknown_meal_item_id = Uuid::new_v4();
+        assert_eq!(vec![burger.id(), unknown_meal_item_id],
+                   order.serve_meal_items(vec![fries.id(), burger.id(), unknown_meal_item_id], Utc::now()));
+        assert_eq!(MealItemStatus::Served, order.get_meal_item(fries.id()).unwrap().lock().unwrap().get_status());
+        assert_eq!(OrderStatus::Preparing, order.get_order_status());
+        assert_eq!(15, order.get_remaining_cooking_time_in_min());
+
+        // The order is completed once everything is cooked, and served once everything reached the table
+        order.update_meal_item_status(burger.id(), MealItemStatus::ReadyForPickup, Utc::now());
+        assert_eq!(OrderStatus::Completed, order.get_order_status());
+        assert_eq!(0, order.get_remaining_cooking_time_in_min());
+        assert!(order.serve_meal_items(vec![fries.id(), burger.id()], Utc::now()).is_empty());
+        assert_eq!(OrderStatus::Served, order.get_order_status());
+        assert!(!order.remove(Utc::now()));
+    }
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    #[test]
+    fn test_add_payment() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(345));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(789));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+        assert!(order.is_open());
+
+        assert_eq!(Err(PaymentError::ZeroAmount), order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(0), Utc::now())));
+        let yen = Price::from_minor_units(500, Currency::Jpy).unwrap();
+        assert_eq!(Err(PaymentError::CurrencyMismatch(Currency::Usd, Currency::Jpy)),
+                   order.add_payment(Payment::new(PaymentMethod::Cash, None, yen, Utc::now())));
+        assert_eq!(Err(PaymentError::ExceedsBalance(usd(1134))), order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(1135), Utc::now())));
+
+        // Given 800 is paid, the fries can't be removed anymore as the burger alone doesn't cover it
+        assert!(order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(800), Utc::now())).is_ok());
+        assert_eq!((usd(800), usd(334)), (order.get_amount_paid(), order.get_balance_due()));
+        assert!(!order.is_closed());
+        assert_eq!(vec![fries.id()], order.remove_meal_items(vec![fries.id()], Utc::now()));
+
+        // Once paid in full, the order is closed and its meal items are locked
+        let closed_time = Utc::now();
+        assert!(order.add_payment(Payment::new(PaymentMethod::Card, Some(String::from("VISA-0042")), usd(334), closed_time)).is_ok());
+        assert!(order.is_closed());
+        assert!(!order.is_open());
+        assert_eq!(Some(closed_time), order.closed_time());
+        assert_eq!(Err(PaymentError::OrderClosed), order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(1), Utc::now())));
+        assert_eq!(vec![burger.id()], order.remove_meal_items(vec![burger.id()], Utc::now()));
+        assert!(!order.remove(Utc::now()));
+        order.update_meal_item_status(burger.id(), MealItemStatus::Preparing, Utc::now());
+        assert_eq!(Some(false), order.void_meal_item(burger.id(), String::from("guest left"), Utc::now()));
+        let soda = MealItem::create(MenuItem::new(String::from("soda"), usd(150)), &MockCookingTimeEstimator::new(1), Utc::now());
+        assert_eq!(Ok(false), order.add_meal_items(vec![soda.clone()], Utc::now()));
+        assert!(order.get_meal_item(soda.id()).is_none());
+        assert_eq!(usd(1134), order.get_total_price());
+    }
+
+    #[test]
+    fn test_add_zero_payment_to_order_with_nothing_due() {
+        let steak = MealItem::create(MenuItem::new(String::from("steak"), usd(2500)), &MockCookingTimeEstimator::new(20), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![steak.clone()], Utc::now()).unwrap();
+        order.update_meal_item_status(steak.id(), MealItemStatus::Preparing, Utc::now());
+        assert_eq!(Some(true), order.void_meal_item(steak.id(), String::from("guest left"), Utc::now()));
+        assert_eq!(usd(0), order.get_balance_due());
+
+        // Given nothing is due, no payment can ever close the order but one of zero, which settles it
+        assert_eq!(Err(PaymentError::ExceedsBalance(usd(0))), order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(1), Utc::now())));
+        let closed_time = Utc::now();
+        assert!(order.add_payment(Payment::new(PaymentMethod::Other, Some(String::from("comped")), usd(0), closed_time)).is_ok());
+        assert_eq!(Some(closed_time), order.closed_time());
+    }
+
+    fn check(name: &str, meal_items: &[&MealItem]) -> NamedCheck {
+        NamedCheck {
+            name: String::from(name),
+            meal_item_ids: meal_items.iter().map(|meal_item| meal_item.id()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_split_bill_by_meal_item() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(345));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(789));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let menu_item = MenuItem::new(String::from("salad"), usd(500));
+        let salad = MealItem::create(menu_item, &MockCookingTimeEstimator::new(5), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone(), salad.clone()], Utc::now()).unwrap();
+        order.remove_meal_items(vec![salad.id()], Utc::now());
+
+        // Every charged meal item has to be on exactly one check
+        let split = |checks| BillSplit::ByMealItem { checks };
+        assert_eq!(Err(SplitError::NoChecks), order.split_bill(split(vec![]), Utc::now()));
+        assert_eq!(Err(SplitError::EmptyCheckName), order.split_bill(split(vec![check(" ", &[&fries, &burger])]), Utc::now()));
+        assert_eq!(Err(SplitError::DuplicateCheckName(String::from("ann"))),
+                   order.split_bill(split(vec![check("ann", &[&fries]), check("ann", &[&burger])]), Utc::now()));
+        assert_eq!(Err(SplitError::UnknownMealItem(salad.id())),
+                   order.split_bill(split(vec![check("ann", &[&fries, &salad]), check("bob", &[&burger])]), Utc::now()));
+        assert_eq!(Err(SplitError::DuplicateMealItem(fries.id())),
+                   order.split_bill(split(vec![check("ann", &[&fries]), check("bob", &[&burger, &fries])]), Utc::now()));
+        assert_eq!(Err(SplitError::MissingMealItem(burger.id())), order.split_bill(split(vec![check("ann", &[&fries])]), Utc::now()));
+        assert!(order.get_sub_checks().is_empty());
+
+        order.split_bill(split(vec![check("ann", &[&fries]), check("bob", &[&burger])]), Utc::now()).unwrap();
+        let sub_checks = order.get_sub_checks();
+        assert_eq!((String::from("ann"), vec![fries.id()], usd(345)), (sub_checks[0].name(), sub_checks[0].meal_item_ids(), sub_checks[0].total()));
+        assert_eq!((String::from("bob"), vec![burger.id()], usd(789)), (sub_checks[1].name(), sub_checks[1].meal_item_ids(), sub_checks[1].total()));
+
+        // Payments of a split bill go to their check
+        assert_eq!(Err(PaymentError::CheckMissing), order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(345), Utc::now())));
+        let payment = |check: &str, amount| Payment::new(PaymentMethod::Cash, None, usd(amount), Utc::now()).with_check(Some(String::from(check)));
+        assert_eq!(Err(PaymentError::UnknownCheck(String::from("cid"))), order.add_payment(payment("cid", 100)));
+        assert_eq!(Err(PaymentError::ExceedsBalance(usd(345))), order.add_payment(payment("ann", 346)));
+        order.add_payment(payment("ann", 345)).unwrap();
+        order.add_payment(payment("bob", 89)).unwrap();
+        let statuses: Vec<_> = order.get_sub_checks().iter().map(|sub_check| (sub_check.status(), sub_check.balance_due())).collect();
+        assert_eq!(vec![(CheckStatus::Paid, usd(0)), (CheckStatus::PartiallyPaid, usd(700))], statuses);
+        assert!(!order.is_closed());
+
+        order.add_payment(payment("bob", 700)).unwrap();
+        assert!(order.is_closed());
+        assert_eq!(Err(SplitError::OrderClosed), order.split_bill(BillSplit::Evenly { guests: 2 }, Utc::now()));
+    }
+
+    #[test]
+    fn test_split_bill_evenly() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(345));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(789));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+
+        assert_eq!(Err(SplitError::InvalidGuests(0)), order.split_bill(BillSplit::Evenly { guests: 0 }, Utc::now()));
+        assert_eq!(Err(SplitError::InvalidGuests(101)), order.split_bill(BillSplit::Evenly { guests: 101 }, Utc::now()));
+
+        // 1134 among 4 guests leaves 2 cents over, which go to the first guests
+        order.split_bill(BillSplit::Evenly { guests: 4 }, Utc::now()).unwrap();
+        let shares: Vec<_> = order.get_sub_checks().iter().map(|sub_check| (sub_check.name(), sub_check.total())).collect();
+        assert_eq!(vec![(String::from("guest-1"), usd(284)), (String::from("guest-2"), usd(284)),
+                        (String::from("guest-3"), usd(283)), (String::from("guest-4"), usd(283))], shares);
+
+        let payment = |check: &str, amount| Payment::new(PaymentMethod::Cash, None, usd(amount), Utc::now()).with_check(Some(String::from(check)));
+        order.add_payment(payment("guest-1", 284)).unwrap();
+
+        // Adding a meal item drops the split, which can be made again as long as guest-1 is still charged what they paid
+        let menu_item = MenuItem::new(String::from("soda"), usd(200));
+        let soda = MealItem::create(menu_item, &MockCookingTimeEstimator::new(1), Utc::now());
+        order.add_meal_items(vec![soda.clone()], Utc::now()).unwrap();
+        assert_eq!(None, order.get_split());
+        assert_eq!(Err(SplitError::PaidCheckMissing(String::from("guest-1"))),
+                   order.split_bill(BillSplit::ByMealItem { checks: vec![check("ann", &[&fries, &burger, &soda])] }, Utc::now()));
+        assert_eq!(Err(SplitError::CheckOverpaid(String::from("guest-1"))),
+                   order.split_bill(BillSplit::ByMealItem { checks: vec![check("guest-1", &[&soda]), check("bob", &[&fries, &burger])] }, Utc::now()));
+        order.split_bill(BillSplit::Evenly { guests: 2 }, Utc::now()).unwrap();
+        let shares: Vec<_> = order.get_sub_checks().iter().map(|sub_check| (sub_check.total(), sub_check.balance_due())).collect();
+        assert_eq!(vec![(usd(667), usd(383)), (usd(667), usd(667))], shares);
+
+        // A bill paid in part as a whole can't be split
+        let mut order = Order::new(2, Currency::Usd, vec![fries.clone()], Utc::now()).unwrap();
+        order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(100), Utc::now())).unwrap();
+        assert_eq!(Err(SplitError::PaidBeforeSplit), order.split_bill(BillSplit::Evenly { guests: 2 }, Utc::now()));
+    }
+
+    #[test]
+    fn test_apply_promotions() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(345));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(789));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+
+        let fries_half_off = AppliedPromotion::new(Uuid::new_v4(), String::from("half fries"), usd(173), vec![(fries.id(), usd(173))]);
+        let dollar_off = AppliedPromotion::new(Uuid::new_v4(), String::from("dollar off"), usd(100), vec![]);
+        assert!(order.apply_pricing(vec![fries_half_off.clone(), dollar_off.clone()], TaxPolicy::default(), Utc::now()));
+        assert_eq!(usd(1134), order.get_subtotal());
+        assert_eq!(usd(273), order.get_discount());
+        assert_eq!(usd(861), order.get_total_price());
+        assert_eq!(usd(861), order.get_balance_due());
+        assert!(!order.apply_pricing(vec![fries_half_off.clone(), dollar_off.clone()], TaxPolicy::default(), Utc::now()));
+
+        // Promotions saving more than the subtotal, or on meal items not charged, are refused
+        let too_much = AppliedPromotion::new(Uuid::new_v4(), String::from("too much"), usd(1135), vec![]);
+        assert!(!order.apply_pricing(vec![too_much], TaxPolicy::default(), Utc::now()));
+        let unknown = AppliedPromotion::new(Uuid::new_v4(), String::from("unknown"), usd(10), vec![(Uuid::new_v4(), usd(10))]);
+        assert!(!order.apply_pricing(vec![unknown], TaxPolicy::default(), Utc::now()));
+        assert_eq!(usd(861), order.get_total_price());
+
+        // Changing the meal items drops the promotions until the order is priced again
+        order.remove_meal_items(vec![fries.id()], Utc::now());
+        assert!(order.get_promotions().is_empty());
+        assert_eq!(usd(789), order.get_total_price());
+        assert!(!order.apply_pricing(vec![fries_half_off], TaxPolicy::default(), Utc::now()));
+
+        // The total can't fall below what was paid, and a closed order keeps its promotions
+        order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(750), Utc::now())).unwrap();
+        assert!(!order.apply_pricing(vec![dollar_off.clone()], TaxPolicy::default(), Utc::now()));
+        order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(39), Utc::now())).unwrap();
+        assert!(order.is_closed());
+        let cent_off = AppliedPromotion::new(Uuid::new_v4(), String::from("cent off"), usd(1), vec![]);
+        assert!(!order.apply_pricing(vec![cent_off], TaxPolicy::default(), Utc::now()));
+    }
+
+    #[test]
+    fn test_apply_pricing_with_taxes() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(400)).with_category(Some(String::from("sides")));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(800)).with_category(Some(String::from("mains")));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap().with_guests(Some(6));
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "10".parse().unwrap(), categories: None }],
+            service_charge: Some(ServiceCharge { rate: "10".parse().unwrap(), above_guests: 5 }),
+            ..TaxPolicy::default()
+        };
+        let fries_off = AppliedPromotion::new(Uuid::new_v4(), String::from("fries off"), usd(100), vec![(fries.id(), usd(100))]);
+        let order_off = AppliedPromotion::new(Uuid::new_v4(), String::from("order off"), usd(110), vec![]);
+        assert!(order.apply_pricing(vec![fries_off.clone(), order_off.clone()], tax_policy.clone(), Utc::now()));
+
+        // Taxes and the service charge are on what is left once promotions are taken off, 990
+        let charges = order.get_charges();
+        assert_eq!(usd(99), charges.tax_added());
+        assert_eq!(usd(99), charges.service_charge());
+        assert_eq!(usd(1188), order.get_total_price());
+
+        // A check gets the taxes and service charge of its meal items
+        order.split_bill(BillSplit::ByMealItem { checks: vec![check("ann", &[&fries]), check("bob", &[&burger])] }, Utc::now()).unwrap();
+        let checks: Vec<_> = order.get_sub_checks().iter()
+            .map(|sub_check| (sub_check.subtotal(), sub_check.discount(), sub_check.tax(), sub_check.service_charge(), sub_check.total()))
+            .collect();
+        assert_eq!(vec![(usd(400), usd(130), usd(27), usd(27), usd(324)), (usd(800), usd(80), usd(72), usd(72), usd(864))], checks);
+
+        // Split evenly, every guest pays the same but for a minor unit
+        order.split_bill(BillSplit::Evenly { guests: 4 }, Utc::now()).unwrap();
+        let checks: Vec<_> = order.get_sub_checks().iter()
+            .map(|sub_check| (sub_check.subtotal(), sub_check.discount(), sub_check.tax(), sub_check.service_charge(), sub_check.total()))
+            .collect();
+        assert_eq!(vec![(usd(300), usd(52), usd(25), usd(24), usd(297)), (usd(300), usd(52), usd(24), usd(25), usd(297)),
+                        (usd(300), usd(53), usd(25), usd(25), usd(297)), (usd(300), usd(53), usd(25), usd(25), usd(297))], checks);
+
+        // The total can't fall below what was paid when the taxes change, and a closed order keeps its pricing
+        order.apply_pricing(vec![fries_off.clone(), order_off.clone()], TaxPolicy::default(), Utc::now());
+        order.apply_pricing(vec![fries_off.clone(), order_off.clone()], tax_policy.clone(), Utc::now());
+        order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(1000), Utc::now())).unwrap();
+        assert!(!order.apply_pricing(vec![fries_off.clone(), order_off.clone()], TaxPolicy::default(), Utc::now()));
+        order.add_payment(Payment::new(PaymentMethod::Cash, None, usd(188), Utc::now())).unwrap();
+        assert!(order.is_closed());
+        assert!(!order.apply_pricing(vec![], TaxPolicy::default(), Utc::now()));
+        assert_eq!((tax_policy, usd(1188)), (order.get_tax_policy(), order.get_total_price()));
+    }
+
+    #[test]
+    fn test_split_bill_with_promotions() {
+        let menu_item = MenuItem::new(String::from("fries"), usd(400));
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), usd(800));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+        let fries_off = AppliedPromotion::new(Uuid::new_v4(), String::from("fries off"), usd(100), vec![(fries.id(), usd(100))]);
+        let order_off = AppliedPromotion::new(Uuid::new_v4(), String::from("order off"), usd(110), vec![]);
+        order.apply_pricing(vec![fries_off, order_off], TaxPolicy::default(), Utc::now());
+        assert_eq!(usd(990), order.get_total_price());
+
+        // A check gets the savings on its meal items, and a share of those on the order in proportion to what is left on it
+        order.split_bill(BillSplit::ByMealItem { checks: vec![check("ann", &[&fries]), check("bob", &[&burger])] }, Utc::now()).unwrap();
+        let checks: Vec<_> = order.get_sub_checks().iter().map(|sub_check| (sub_check.subtotal(), sub_check.discount(), sub_check.total())).collect();
+        assert_eq!(vec![(usd(400), usd(130), usd(270)), (usd(800), usd(80), usd(720))], checks);
+
+        // Split evenly, every guest pays the same but for a minor unit
+        order.split_bill(BillSplit::Evenly { guests: 4 }, Utc::now()).unwrap();
+        let checks: Vec<_> = order.get_sub_checks().iter().map(|sub_check| (sub_check.subtotal(), sub_check.discount(), sub_check.total())).collect();
+        assert_eq!(vec![(usd(300), usd(52), usd(248)), (usd(300), usd(52), usd(248)), (usd(300), usd(53), usd(247)), (usd(300), usd(53), usd(247))], checks);
+
+        // Pricing the order again drops the split, as its checks no longer add up to the total
+        order.apply_pricing(vec![], TaxPolicy::default(), Utc::now());
+        assert_eq!(None, order.get_split());
+        assert_eq!(usd(1200), order.get_total_price());
+    }
+}
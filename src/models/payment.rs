@@ -0,0 +1,139 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::currency::Currency;
+use crate::models::price::Price;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentMethod {
+    Cash,
+    // Paid by card, the reference of the card terminal is kept with the payment
+    Card,
+    Other,
+}
+
+impl PaymentMethod {
+    pub const ALL: [PaymentMethod; 3] = [PaymentMethod::Cash, PaymentMethod::Card, PaymentMethod::Other];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::Card => "card",
+            PaymentMethod::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for PaymentMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for PaymentMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PaymentMethod::ALL.into_iter()
+            .find(|method| method.name() == s)
+            .ok_or_else(|| format!("unknown payment method '{}'", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaymentError {
+    ZeroAmount,
+    CurrencyMismatch(Currency, Currency),
+    // The payment is more than what is left to pay
+    ExceedsBalance(Price),
+    OrderClosed,
+    // The bill is split, so the payment has to name the check it pays
+    CheckMissing,
+    UnknownCheck(String),
+}
+
+impl fmt::Display for PaymentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaymentError::ZeroAmount => write!(f, "payment amount must be more than zero while a balance is due"),
+            PaymentError::CurrencyMismatch(expected, actual) => write!(f, "order is paid in {}, not in {}", expected, actual),
+            PaymentError::ExceedsBalance(balance) => write!(f, "payment is more than the balance due of {} {}", balance, balance.currency()),
+            PaymentError::OrderClosed => write!(f, "order is already paid in full"),
+            PaymentError::CheckMissing => write!(f, "the bill is split, the payment has to name its check"),
+            PaymentError::UnknownCheck(name) => write!(f, "the bill has no check '{}'", name),
+        }
+    }
+}
+
+impl Error for PaymentError {}
+
+// Part or all of the bill of an order, paid at once
+#[derive(Clone, Debug, PartialEq)]
+pub struct Payment {
+    payment_id: Uuid,
+    method: PaymentMethod,
+    // Ex. the card terminal's transaction reference, or what was used to pay by other means
+    reference: Option<String>,
+    // Check of the split bill the payment is made on, none if the bill is paid as a whole
+    check: Option<String>,
+    amount: Price,
+    time: DateTime<Utc>,
+}
+
+impl Payment {
+    pub fn new(method: PaymentMethod, reference: Option<String>, amount: Price, now: DateTime<Utc>) -> Self {
+        Payment {
+            payment_id: Uuid::new_v4(),
+            method,
+            reference,
+            check: None,
+            amount,
+            time: now,
+        }
+    }
+
+    // Rebuilds a payment previously persisted by a repository
+    pub fn restore(payment_id: Uuid, method: PaymentMethod, reference: Option<String>, amount: Price, time: DateTime<Utc>) -> Self {
+        Payment {
+            payment_id,
+            method,
+            reference,
+            check: None,
+            amount,
+            time,
+        }
+    }
+
+    pub fn with_check(mut self, check: Option<String>) -> Self {
+        self.check = check;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.payment_id
+    }
+
+    pub fn method(&self) -> PaymentMethod {
+        self.method
+    }
+
+    pub fn reference(&self) -> Option<String> {
+        self.reference.clone()
+    }
+
+    pub fn check(&self) -> Option<String> {
+        self.check.clone()
+    }
+
+    pub fn amount(&self) -> Price {
+        self.amount
+    }
+
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
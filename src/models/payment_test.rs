@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod payment_test {
+    use crate::models::currency::Currency;
+    use crate::models::payment::{PaymentError, PaymentMethod};
+    use crate::models::price::Price;
+
+    #[test]
+    fn test_parse_and_display_payment_method() {
+        for method in PaymentMethod::ALL {
+            assert_eq!(Ok(method), method.to_string().parse());
+        }
+        assert_eq!(Ok(PaymentMethod::Card), "card".parse());
+        assert!("cheque".parse::<PaymentMethod>().is_err());
+        assert_eq!("\"cash\"", serde_json::to_string(&PaymentMethod::Cash).unwrap());
+    }
+
+    #[test]
+    fn test_display_payment_error() {
+        let balance = Price::from_minor_units(1250, Currency::Usd).unwrap();
+        assert_eq!("payment is more than the balance due of 1250 USD", PaymentError::ExceedsBalance(balance).to_string());
+        assert_eq!("order is paid in USD, not in EUR", PaymentError::CurrencyMismatch(Currency::Usd, Currency::Eur).to_string());
+    }
+}
@@ -1,29 +1,177 @@
-#[derive(Clone, Debug, Copy, PartialEq)]
-pub struct Price(f64);
+use std::error::Error;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::models::currency::Currency;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PriceError {
+    Invalid(String),
+    Negative,
+    Overflow,
+    DivisionByZero,
+    CurrencyMismatch(Currency, Currency),
+}
+
+impl fmt::Display for PriceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PriceError::Invalid(price) => write!(f, "'{}' is not a valid price in minor units", price),
+            PriceError::Negative => write!(f, "price cannot be negative"),
+            PriceError::Overflow => write!(f, "price is too large"),
+            PriceError::DivisionByZero => write!(f, "price cannot be divided by zero"),
+            PriceError::CurrencyMismatch(expected, actual) => write!(f, "cannot combine {} with {}", expected, actual),
+        }
+    }
+}
+
+impl Error for PriceError {}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    // Rounds towards zero, ex. 1.5 -> 1
+    Down,
+    // Rounds away from zero, ex. 1.1 -> 2
+    Up,
+    // Rounds half away from zero, ex. 1.5 -> 2, 2.5 -> 3
+    #[default]
+    HalfUp,
+    // Rounds half to the nearest even number, ex. 1.5 -> 2, 2.5 -> 2
+    HalfEven,
+}
+
+// Price is kept as an integer amount of minor units, ex. cents, of its currency so arithmetic is exact.
+// It is never negative; every operation which could go below zero, overflow, or mix currencies returns an error.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct Price {
+    minor_units: i64,
+    currency: Currency,
+}
 
 impl Price {
-    pub fn from_string(price: String) -> Self {
-        let price_in_cents = price.parse().unwrap_or(0.0);
-        let price: f64 = price_in_cents / 100.0;
-        Price(price)
+    pub fn zero(currency: Currency) -> Self {
+        Price { minor_units: 0, currency }
+    }
+
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Result<Self, PriceError> {
+        if minor_units < 0 {
+            return Err(PriceError::Negative);
+        }
+        Ok(Price { minor_units, currency })
+    }
+
+    // Parses a price given in minor units of the currency, ex. "5095" for 50.95 USD or for 5095 JPY
+    pub fn parse(price: &str, currency: Currency) -> Result<Self, PriceError> {
+        let minor_units: i64 = price.trim().parse()
+            .map_err(|_| PriceError::Invalid(price.to_string()))?;
+        Price::from_minor_units(minor_units, currency)
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
     }
 
-    pub fn to_string(self) -> String {
-        let price_in_cents: u64 = (self.0 * 100.0) as u64;
-        price_in_cents.to_string()
+    pub fn currency(&self) -> Currency {
+        self.currency
     }
 
-    pub fn add(&mut self, other: Price) {
-        self.0 += other.0;
+    // Formats the amount in major units using the currency exponent, ex. "50.95" for USD, "5095" for JPY
+    pub fn to_major_units_string(self) -> String {
+        let exponent = self.currency.exponent();
+        if exponent == 0 {
+            return self.minor_units.to_string();
+        }
+        let divisor = 10_i64.pow(exponent);
+        format!("{}.{:0width$}", self.minor_units / divisor, self.minor_units % divisor, width = exponent as usize)
     }
 
-    pub fn deduct(&mut self, other: Price) {
-        self.0 -= other.0;
+    pub fn checked_add(self, other: Price) -> Result<Price, PriceError> {
+        self.ensure_same_currency(other)?;
+        let minor_units = self.minor_units.checked_add(other.minor_units).ok_or(PriceError::Overflow)?;
+        Price::from_minor_units(minor_units, self.currency)
+    }
+
+    pub fn checked_sub(self, other: Price) -> Result<Price, PriceError> {
+        self.ensure_same_currency(other)?;
+        let minor_units = self.minor_units.checked_sub(other.minor_units).ok_or(PriceError::Overflow)?;
+        Price::from_minor_units(minor_units, self.currency)
+    }
+
+    // Price of the given quantity, ex. of 3 meal items of the same menu item
+    pub fn checked_mul(self, quantity: u32) -> Result<Price, PriceError> {
+        let minor_units = self.minor_units.checked_mul(quantity as i64).ok_or(PriceError::Overflow)?;
+        Price::from_minor_units(minor_units, self.currency)
+    }
+
+    // Multiplies the price by numerator / denominator, ex. 15 / 100 for 15%, rounding the result with the given mode
+    pub fn mul_ratio(self, numerator: u64, denominator: u64, rounding_mode: RoundingMode) -> Result<Price, PriceError> {
+        if denominator == 0 {
+            return Err(PriceError::DivisionByZero);
+        }
+        let dividend = (self.minor_units as i128) * (numerator as i128);
+        let divisor = denominator as i128;
+        let quotient = dividend / divisor;
+        let remainder = dividend % divisor;
+
+        let round_up = match rounding_mode {
+            RoundingMode::Down => false,
+            RoundingMode::Up => remainder > 0,
+            RoundingMode::HalfUp => remainder * 2 >= divisor,
+            RoundingMode::HalfEven => remainder * 2 > divisor || (remainder * 2 == divisor && quotient % 2 == 1),
+        };
+        let minor_units = if round_up { quotient + 1 } else { quotient };
+
+        let minor_units = i64::try_from(minor_units).map_err(|_| PriceError::Overflow)?;
+        Price::from_minor_units(minor_units, self.currency)
+    }
+
+    // Splits the price into as many parts as given, which differ by at most one minor unit and always add up to the price.
+    // The first parts get the minor units left over, ex. 100 in 3 parts is 34, 33 and 33
+    pub fn split_evenly(self, parts: u32) -> Result<Vec<Price>, PriceError> {
+        if parts == 0 {
+            return Err(PriceError::DivisionByZero);
+        }
+        let share = self.minor_units / parts as i64;
+        let remainder = self.minor_units % parts as i64;
+        (0..parts as i64)
+            .map(|part| Price::from_minor_units(if part < remainder { share + 1 } else { share }, self.currency))
+            .collect()
+    }
+
+    // Splits the price in proportion to the weights, ex. the prices of the meal items it is shared by. Every part is rounded down
+    // to the minor unit and the minor units left over go to the parts rounded down the most, the first ones on a tie,
+    // so the parts always add up to the price.
+    pub fn allocate(self, weights: &[i64]) -> Result<Vec<Price>, PriceError> {
+        let total_weight: i64 = weights.iter().sum();
+        if total_weight <= 0 || weights.iter().any(|weight| *weight < 0) {
+            return Err(PriceError::DivisionByZero);
+        }
+        let mut parts = weights.iter()
+            .map(|weight| self.mul_ratio(*weight as u64, total_weight as u64, RoundingMode::Down))
+            .collect::<Result<Vec<Price>, PriceError>>()?;
+        let allocated: i64 = parts.iter().map(|part| part.minor_units).sum();
+
+        // Remainder of every part left out by rounding it down, largest first
+        let mut remainders: Vec<(i128, usize)> = weights.iter().enumerate()
+            .map(|(index, weight)| ((self.minor_units as i128 * *weight as i128) % total_weight as i128, index))
+            .collect();
+        remainders.sort_by(|(remainder, index), (other_remainder, other_index)| other_remainder.cmp(remainder).then(index.cmp(other_index)));
+        for (_, index) in remainders.into_iter().take((self.minor_units - allocated) as usize) {
+            parts[index].minor_units += 1;
+        }
+        Ok(parts)
+    }
+
+    fn ensure_same_currency(&self, other: Price) -> Result<(), PriceError> {
+        if self.currency != other.currency {
+            return Err(PriceError::CurrencyMismatch(self.currency, other.currency));
+        }
+        Ok(())
     }
 }
 
-impl Default for Price {
-    fn default() -> Self {
-        Price(0.0)
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.minor_units)
     }
-}
\ No newline at end of file
+}
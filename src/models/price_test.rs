@@ -1,30 +1,119 @@
 #[cfg(test)]
 mod price_test {
-    use crate::models::price::Price;
+    use crate::models::currency::Currency;
+    use crate::models::price::{Price, PriceError, RoundingMode};
 
     #[test]
-    fn test_from_string() {
-        let price = Price::from_string("345".to_string());
-        assert_eq!("345", price.to_string());
+    fn test_parse() {
+        let price = Price::parse("345", Currency::Usd).unwrap();
+        assert_eq!(Price::from_minor_units(345, Currency::Usd).unwrap(), price);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(Err(PriceError::Invalid("abc".to_string())), Price::parse("abc", Currency::Usd));
+        assert_eq!(Err(PriceError::Invalid("34.5".to_string())), Price::parse("34.5", Currency::Usd));
+        assert_eq!(Err(PriceError::Negative), Price::parse("-345", Currency::Usd));
     }
 
     #[test]
     fn test_to_string() {
-        let price = Price::from_string("345".to_string());
+        let price = Price::parse("345", Currency::Usd).unwrap();
         assert_eq!("345", price.to_string());
     }
 
     #[test]
-    fn test_add() {
-        let mut price = Price::from_string("345".to_string());
-        price.add(Price::from_string("123".to_string()));
-        assert_eq!("468", price.to_string());
+    fn test_checked_add() {
+        let price = Price::parse("345", Currency::Usd).unwrap();
+        let total = price.checked_add(Price::parse("123", Currency::Usd).unwrap()).unwrap();
+        assert_eq!("468", total.to_string());
+
+        let max = Price::from_minor_units(i64::MAX, Currency::Usd).unwrap();
+        assert_eq!(Err(PriceError::Overflow), max.checked_add(price));
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        let price = Price::parse("345", Currency::Usd).unwrap();
+        let total = price.checked_sub(Price::parse("123", Currency::Usd).unwrap()).unwrap();
+        assert_eq!("222", total.to_string());
+
+        assert_eq!(Err(PriceError::Negative), total.checked_sub(price));
     }
 
     #[test]
-    fn test_deduct() {
-        let mut price = Price::from_string("345".to_string());
-        price.deduct(Price::from_string("123".to_string()));
-        assert_eq!("222", price.to_string());
+    fn test_checked_mul() {
+        let price = Price::parse("345", Currency::Usd).unwrap();
+        assert_eq!("1035", price.checked_mul(3).unwrap().to_string());
+
+        let max = Price::from_minor_units(i64::MAX, Currency::Usd).unwrap();
+        assert_eq!(Err(PriceError::Overflow), max.checked_mul(2));
+    }
+
+    #[test]
+    fn test_mul_ratio() {
+        // 15% of 1.25 is 0.1875
+        let price = Price::parse("125", Currency::Usd).unwrap();
+        assert_eq!("18", price.mul_ratio(15, 100, RoundingMode::Down).unwrap().to_string());
+        assert_eq!("19", price.mul_ratio(15, 100, RoundingMode::Up).unwrap().to_string());
+        assert_eq!("19", price.mul_ratio(15, 100, RoundingMode::HalfUp).unwrap().to_string());
+        assert_eq!("19", price.mul_ratio(15, 100, RoundingMode::HalfEven).unwrap().to_string());
+
+        // half of 0.25 and 0.35 are ties
+        let price = Price::parse("25", Currency::Usd).unwrap();
+        assert_eq!("12", price.mul_ratio(1, 2, RoundingMode::Down).unwrap().to_string());
+        assert_eq!("13", price.mul_ratio(1, 2, RoundingMode::HalfUp).unwrap().to_string());
+        assert_eq!("12", price.mul_ratio(1, 2, RoundingMode::HalfEven).unwrap().to_string());
+        let price = Price::parse("35", Currency::Usd).unwrap();
+        assert_eq!("18", price.mul_ratio(1, 2, RoundingMode::HalfEven).unwrap().to_string());
+
+        assert_eq!(Err(PriceError::DivisionByZero), price.mul_ratio(1, 0, RoundingMode::HalfUp));
+    }
+
+    #[test]
+    fn test_currency_mismatch() {
+        let usd = Price::parse("345", Currency::Usd).unwrap();
+        let jpy = Price::parse("345", Currency::Jpy).unwrap();
+
+        assert_eq!(Err(PriceError::CurrencyMismatch(Currency::Usd, Currency::Jpy)), usd.checked_add(jpy));
+        assert_eq!(Err(PriceError::CurrencyMismatch(Currency::Jpy, Currency::Usd)), jpy.checked_sub(usd));
+    }
+
+    #[test]
+    fn test_to_major_units_string() {
+        assert_eq!("3.45", Price::parse("345", Currency::Usd).unwrap().to_major_units_string());
+        assert_eq!("0.05", Price::parse("5", Currency::Eur).unwrap().to_major_units_string());
+        assert_eq!("345", Price::parse("345", Currency::Jpy).unwrap().to_major_units_string());
+    }
+
+    #[test]
+    fn test_split_evenly() {
+        let price = Price::parse("100", Currency::Usd).unwrap();
+        let parts: Vec<i64> = price.split_evenly(3).unwrap().iter().map(|part| part.minor_units()).collect();
+        assert_eq!(vec![34, 33, 33], parts);
+
+        let parts: Vec<i64> = price.split_evenly(7).unwrap().iter().map(|part| part.minor_units()).collect();
+        assert_eq!(100, parts.iter().sum::<i64>());
+        assert_eq!(vec![15, 15, 14, 14, 14, 14, 14], parts);
+
+        assert_eq!(Err(PriceError::DivisionByZero), price.split_evenly(0));
+    }
+
+    #[test]
+    fn test_allocate() {
+        let price = Price::parse("100", Currency::Usd).unwrap();
+        let parts: Vec<i64> = price.allocate(&[1, 1, 1]).unwrap().iter().map(|part| part.minor_units()).collect();
+        assert_eq!(vec![34, 33, 33], parts);
+
+        // 100 * 2 / 7 = 28.57 and 100 * 5 / 7 = 71.43, so the minor unit left over goes to the first part
+        let parts: Vec<i64> = price.allocate(&[2, 5]).unwrap().iter().map(|part| part.minor_units()).collect();
+        assert_eq!(vec![29, 71], parts);
+
+        let parts: Vec<i64> = price.allocate(&[345, 0, 789]).unwrap().iter().map(|part| part.minor_units()).collect();
+        assert_eq!(vec![30, 0, 70], parts);
+
+        assert_eq!(Err(PriceError::DivisionByZero), price.allocate(&[0, 0]));
+        assert_eq!(Err(PriceError::DivisionByZero), price.allocate(&[]));
+        assert_eq!(Err(PriceError::DivisionByZero), price.allocate(&[2, -1]));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,338 @@
+use std::error::Error;
+use std::fmt;
+use uuid::Uuid;
+use crate::models::currency::Currency;
+use crate::models::meal::MealItem;
+use crate::models::order::Order;
+use crate::models::price::{Price, PriceError, RoundingMode};
+
+// What a promotion discounts
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromotionTarget {
+    // Every meal item ordered from the menu item
+    MenuItem(Uuid),
+    // Every meal item whose menu item is in the category
+    Category(String),
+    // What is left to pay for the whole order, once promotions listed before it are taken off
+    Order,
+}
+
+impl PromotionTarget {
+    fn matches(&self, meal_item: &MealItem) -> bool {
+        match self {
+            PromotionTarget::MenuItem(menu_item_id) => meal_item.get_menu_item().id() == *menu_item_id,
+            PromotionTarget::Category(category) => meal_item.category().as_deref() == Some(category.as_str()),
+            PromotionTarget::Order => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reward {
+    // Percent off the price of every meal item targeted, or off the order
+    PercentOff { target: PromotionTarget, percent: u32 },
+    // Amount off every meal item targeted, down to free, or off the order once
+    AmountOff { target: PromotionTarget, amount: Price },
+    // For every buy + get meal items targeted, the get cheapest of them are free
+    BuyXGetY { target: PromotionTarget, buy: u32, get: u32 },
+    // Every full set of meal items ordered from the menu items is charged the combo price
+    ComboPrice { menu_item_ids: Vec<Uuid>, price: Price },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromotionError {
+    EmptyName,
+    EmptyCategory,
+    InvalidPercent(u32),
+    InvalidBuyXGetY,
+    // Buy-X-get-Y rewards meal items, so it can't target the order as a whole
+    BuyXGetYOnOrder,
+    ComboTooSmall,
+    ZeroAmount,
+    CurrencyMismatch(Currency, Currency),
+}
+
+impl fmt::Display for PromotionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromotionError::EmptyName => write!(f, "promotion has to be named"),
+            PromotionError::EmptyCategory => write!(f, "category targeted has to be named"),
+            PromotionError::InvalidPercent(percent) => write!(f, "{}% is not between 1% and 100%", percent),
+            PromotionError::InvalidBuyXGetY => write!(f, "buy and get have to be at least 1"),
+            PromotionError::BuyXGetYOnOrder => write!(f, "buy-x-get-y has to target a menu item or a category"),
+            PromotionError::ComboTooSmall => write!(f, "combo has to be made of at least 2 menu items"),
+            PromotionError::ZeroAmount => write!(f, "amount has to be more than zero"),
+            PromotionError::CurrencyMismatch(expected, actual) => write!(f, "promotion is in {}, not in {}", expected, actual),
+        }
+    }
+}
+
+impl Error for PromotionError {}
+
+// A rule taking money off orders in its currency, once they spend at least the minimum spend if there is one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Promotion {
+    promotion_id: Uuid,
+    name: String,
+    currency: Currency,
+    reward: Reward,
+    min_spend: Option<Price>,
+}
+
+impl Promotion {
+    pub fn new(name: String, currency: Currency, reward: Reward, min_spend: Option<Price>) -> Result<Self, PromotionError> {
+        if name.trim().is_empty() {
+            return Err(PromotionError::EmptyName);
+        }
+        let target = match &reward {
+            Reward::PercentOff { target, .. } | Reward::AmountOff { target, .. } | Reward::BuyXGetY { target, .. } => Some(target),
+            Reward::ComboPrice { .. } => None,
+        };
+        if let Some(PromotionTarget::Category(category)) = target {
+            if category.trim().is_empty() {
+                return Err(PromotionError::EmptyCategory);
+            }
+        }
+        let amounts = match &reward {
+            Reward::PercentOff { percent, .. } => {
+                if *percent == 0 || *percent > 100 {
+                    return Err(PromotionError::InvalidPercent(*percent));
+                }
+                vec![]
+            }
+            Reward::AmountOff { amount, .. } => {
+                if amount.minor_units() == 0 {
+                    return Err(PromotionError::ZeroAmount);
+                }
+                vec![*amount]
+            }
+            Reward::BuyXGetY { target, buy, get } => {
+                if *buy == 0 || *get == 0 {
+                    return Err(PromotionError::InvalidBuyXGetY);
+                }
+                if *target == PromotionTarget::Order {
+                    return Err(PromotionError::BuyXGetYOnOrder);
+                }
+                vec![]
+            }
+            Reward::ComboPrice { menu_item_ids, price } => {
+                if menu_item_ids.len() < 2 {
+                    return Err(PromotionError::ComboTooSmall);
+                }
+                vec![*price]
+            }
+        };
+        if let Some(amount) = amounts.into_iter().chain(min_spend).find(|amount| amount.currency() != currency) {
+            return Err(PromotionError::CurrencyMismatch(currency, amount.currency()));
+        }
+        Ok(Promotion {
+            promotion_id: Uuid::new_v4(),
+            name: name.trim().to_string(),
+            currency,
+            reward,
+            min_spend,
+        })
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.promotion_id
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn get_currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn reward(&self) -> Reward {
+        self.reward.clone()
+    }
+
+    pub fn min_spend(&self) -> Option<Price> {
+        self.min_spend
+    }
+}
+
+// A promotion taken off an order, along with how much it saved on each meal item it took.
+// Promotions on the whole order take no meal item.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppliedPromotion {
+    promotion_id: Uuid,
+    name: String,
+    saved: Price,
+    meal_item_savings: Vec<(Uuid, Price)>,
+}
+
+impl AppliedPromotion {
+    pub fn new(promotion_id: Uuid, name: String, saved: Price, meal_item_savings: Vec<(Uuid, Price)>) -> Self {
+        AppliedPromotion {
+            promotion_id,
+            name,
+            saved,
+            meal_item_savings,
+        }
+    }
+
+    pub fn promotion_id(&self) -> Uuid {
+        self.promotion_id
+    }
+
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn saved(&self) -> Price {
+        self.saved
+    }
+
+    pub fn meal_item_savings(&self) -> Vec<(Uuid, Price)> {
+        self.meal_item_savings.clone()
+    }
+
+    pub fn is_on_order(&self) -> bool {
+        self.meal_item_savings.is_empty()
+    }
+}
+
+// Meal item charged on the order, along with whether a promotion already discounted it
+struct Candidate {
+    meal_item: MealItem,
+    is_discounted: bool,
+}
+
+// Works out which promotions the order gets and how much each saves. Promotions are tried in the order given:
+// a meal item is discounted by the first promotion on it only, and promotions on the order are taken off what is left to pay.
+// Promotions in another currency, or whose minimum spend the order doesn't reach before discounts, are skipped.
+pub fn apply_promotions(promotions: &[Promotion], order: &Order) -> Vec<AppliedPromotion> {
+    let mut candidates: Vec<Candidate> = order.get_meal_items().iter()
+        .map(|meal_item_arc| meal_item_arc.lock().unwrap().clone())
+        .filter(|meal_item| !meal_item.is_removed() && !meal_item.is_voided())
+        .map(|meal_item| Candidate { meal_item, is_discounted: false })
+        .collect();
+    candidates.sort_by_key(|candidate| (candidate.meal_item.creation_time(), candidate.meal_item.id()));
+
+    let subtotal = order.get_subtotal();
+    let mut left_to_pay = subtotal;
+    let mut applied = Vec::new();
+    for promotion in promotions.iter() {
+        if promotion.currency != order.get_currency()
+            || promotion.min_spend.is_some_and(|min_spend| subtotal.minor_units() < min_spend.minor_units()) {
+            continue;
+        }
+        let (saved, candidate_savings) = match apply_reward(&promotion.reward, &candidates, left_to_pay)
+            .and_then(|(saved, candidate_savings)| cap_saving(saved, candidate_savings, left_to_pay)) {
+            Ok(saving) => saving,
+            Err(_) => continue,
+        };
+        if saved.minor_units() == 0 {
+            continue;
+        }
+        // Only a promotion taken keeps the meal items it discounts from those after it
+        let mut meal_item_savings = Vec::new();
+        for (index, saving) in candidate_savings {
+            candidates[index].is_discounted = true;
+            meal_item_savings.push((candidates[index].meal_item.id(), saving));
+        }
+        left_to_pay = left_to_pay.checked_sub(saved).expect("promotions never save more than what is left to pay");
+        applied.push(AppliedPromotion::new(promotion.promotion_id, promotion.get_name(), saved, meal_item_savings));
+    }
+    applied
+}
+
+// Meal items discounted after a promotion on the order can't bring what is left to pay below zero,
+// so their savings are cut down in proportion to fit
+fn cap_saving(saved: Price, candidate_savings: Vec<(usize, Price)>, left_to_pay: Price) -> Result<(Price, Vec<(usize, Price)>), PriceError> {
+    if saved.minor_units() <= left_to_pay.minor_units() {
+        return Ok((saved, candidate_savings));
+    }
+    if candidate_savings.is_empty() {
+        return Ok((left_to_pay, candidate_savings));
+    }
+    let weights: Vec<i64> = candidate_savings.iter().map(|(_, saving)| saving.minor_units()).collect();
+    let savings = left_to_pay.allocate(&weights)?;
+    Ok((left_to_pay, candidate_savings.into_iter().zip(savings).map(|((index, _), saving)| (index, saving)).collect()))
+}
+
+// Returns how much the reward saves and how much on each candidate it would take, by index, which no other promotion
+// can then discount once the promotion is taken
+fn apply_reward(reward: &Reward, candidates: &[Candidate], left_to_pay: Price) -> Result<(Price, Vec<(usize, Price)>), PriceError> {
+    let mut saved = Price::zero(left_to_pay.currency());
+    let mut candidate_savings = Vec::new();
+    let matching = |target: &PromotionTarget| candidates.iter().enumerate()
+        .filter(|(_, candidate)| !candidate.is_discounted && target.matches(&candidate.meal_item))
+        .collect::<Vec<(usize, &Candidate)>>();
+    match reward {
+        Reward::PercentOff { target: PromotionTarget::Order, percent } => {
+            saved = left_to_pay.mul_ratio(*percent as u64, 100, RoundingMode::HalfUp)?;
+        }
+        Reward::AmountOff { target: PromotionTarget::Order, amount } => {
+            saved = if amount.minor_units() < left_to_pay.minor_units() { *amount } else { left_to_pay };
+        }
+        Reward::PercentOff { target, percent } => {
+            for (index, candidate) in matching(target) {
+                let saving = candidate.meal_item.price().mul_ratio(*percent as u64, 100, RoundingMode::HalfUp)?;
+                saved = saved.checked_add(saving)?;
+                candidate_savings.push((index, saving));
+            }
+        }
+        Reward::AmountOff { target, amount } => {
+            for (index, candidate) in matching(target) {
+                let price = candidate.meal_item.price();
+                let saving = if amount.minor_units() < price.minor_units() { *amount } else { price };
+                saved = saved.checked_add(saving)?;
+                candidate_savings.push((index, saving));
+            }
+        }
+        Reward::BuyXGetY { target, buy, get } => {
+            // The most expensive meal items are grouped first, so the guest always pays for the dearest of every group
+            let mut matching = matching(target);
+            matching.sort_by_key(|(_, candidate)| std::cmp::Reverse(candidate.meal_item.price().minor_units()));
+            let group_size = (*buy + *get) as usize;
+            for group in matching.chunks_exact(group_size) {
+                for (position, (index, candidate)) in group.iter().enumerate() {
+                    let price = candidate.meal_item.price();
+                    let saving = if position < *buy as usize { Price::zero(price.currency()) } else { price };
+                    saved = saved.checked_add(saving)?;
+                    candidate_savings.push((*index, saving));
+                }
+            }
+        }
+        Reward::ComboPrice { menu_item_ids: combo, price } => {
+            // Meal items already in a set of this combo
+            let mut taken: Vec<usize> = Vec::new();
+            loop {
+                let mut set: Vec<usize> = Vec::new();
+                for menu_item_id in combo.iter() {
+                    let found = (0..candidates.len()).find(|index| {
+                        let candidate = &candidates[*index];
+                        !candidate.is_discounted && !taken.contains(index) && !set.contains(index) && candidate.meal_item.get_menu_item().id() == *menu_item_id
+                    });
+                    match found {
+                        Some(index) => set.push(index),
+                        None => break,
+                    }
+                }
+                if set.len() < combo.len() {
+                    break;
+                }
+                let set_price = set.iter()
+                    .try_fold(Price::zero(price.currency()), |total, index| total.checked_add(candidates[*index].meal_item.price()))?;
+                // A combo dearer than its meal items ordered on their own saves nothing
+                if set_price.minor_units() <= price.minor_units() {
+                    break;
+                }
+                // The saving is shared by the meal items of the set in proportion to their prices
+                let set_saving = set_price.checked_sub(*price)?;
+                let weights: Vec<i64> = set.iter().map(|index| candidates[*index].meal_item.price().minor_units()).collect();
+                saved = saved.checked_add(set_saving)?;
+                for (index, saving) in set.into_iter().zip(set_saving.allocate(&weights)?) {
+                    taken.push(index);
+                    candidate_savings.push((index, saving));
+                }
+            }
+        }
+    }
+    Ok((saved, candidate_savings))
+}
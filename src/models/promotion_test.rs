@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod promotion_test {
+    use chrono::{TimeDelta, Utc};
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::meal::MealItem;
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::models::promotion::{apply_promotions, Promotion, PromotionError, PromotionTarget, Reward};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    // Meal items are created a second apart, so they are always discounted in the order given
+    fn create_meal_items(menu_items: &[&MenuItem]) -> Vec<MealItem> {
+        let now = Utc::now();
+        menu_items.iter().zip(0..)
+            .map(|(menu_item, position)| MealItem::create((*menu_item).clone(), &MockCookingTimeEstimator::new(10), now + TimeDelta::seconds(position)))
+            .collect()
+    }
+
+    fn promotion(name: &str, reward: Reward, min_spend: Option<Price>) -> Promotion {
+        Promotion::new(String::from(name), Currency::Usd, reward, min_spend).unwrap()
+    }
+
+    fn savings(order: &Order, promotions: &[Promotion]) -> Vec<(String, Price)> {
+        apply_promotions(promotions, order).iter().map(|applied| (applied.get_name(), applied.saved())).collect()
+    }
+
+    #[test]
+    fn test_new_promotion() {
+        let order_target = PromotionTarget::Order;
+        let new = |name: &str, reward| Promotion::new(String::from(name), Currency::Usd, reward, None);
+        assert_eq!(Err(PromotionError::EmptyName), new(" ", Reward::PercentOff { target: order_target.clone(), percent: 10 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::InvalidPercent(0)), new("a", Reward::PercentOff { target: order_target.clone(), percent: 0 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::InvalidPercent(101)), new("a", Reward::PercentOff { target: order_target.clone(), percent: 101 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::EmptyCategory),
+                   new("a", Reward::PercentOff { target: PromotionTarget::Category(String::from(" ")), percent: 10 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::ZeroAmount), new("a", Reward::AmountOff { target: order_target.clone(), amount: usd(0) }).map(|_| ()));
+        assert_eq!(Err(PromotionError::InvalidBuyXGetY),
+                   new("a", Reward::BuyXGetY { target: PromotionTarget::MenuItem(Uuid::new_v4()), buy: 0, get: 1 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::BuyXGetYOnOrder), new("a", Reward::BuyXGetY { target: order_target.clone(), buy: 1, get: 1 }).map(|_| ()));
+        assert_eq!(Err(PromotionError::ComboTooSmall),
+                   new("a", Reward::ComboPrice { menu_item_ids: vec![Uuid::new_v4()], price: usd(500) }).map(|_| ()));
+        let eur = Price::from_minor_units(100, Currency::Eur).unwrap();
+        assert_eq!(Err(PromotionError::CurrencyMismatch(Currency::Usd, Currency::Eur)),
+                   new("a", Reward::AmountOff { target: order_target.clone(), amount: eur }).map(|_| ()));
+        assert_eq!(Err(PromotionError::CurrencyMismatch(Currency::Usd, Currency::Eur)),
+                   Promotion::new(String::from("a"), Currency::Usd, Reward::PercentOff { target: order_target, percent: 10 }, Some(eur)).map(|_| ()));
+    }
+
+    #[test]
+    fn test_apply_percent_and_amount_off() {
+        let fries = MenuItem::new(String::from("fries"), usd(345)).with_category(Some(String::from("sides")));
+        let burger = MenuItem::new(String::from("burger"), usd(789));
+        let meal_items = create_meal_items(&[&fries, &fries, &burger]);
+        let order = Order::new(1, Currency::Usd, meal_items.clone(), Utc::now()).unwrap();
+
+        // 10% of 345 is 34.5, rounded half up to 35 on each meal item
+        let sides_off = promotion("sides 10% off", Reward::PercentOff { target: PromotionTarget::Category(String::from("sides")), percent: 10 }, None);
+        let burger_off = promotion("burger 1 off", Reward::AmountOff { target: PromotionTarget::MenuItem(burger.id()), amount: usd(100) }, None);
+        let applied = apply_promotions(&[sides_off.clone(), burger_off.clone()], &order);
+        assert_eq!(2, applied.len());
+        assert_eq!((sides_off.id(), usd(70)), (applied[0].promotion_id(), applied[0].saved()));
+        assert_eq!(vec![(meal_items[0].id(), usd(35)), (meal_items[1].id(), usd(35))], applied[0].meal_item_savings());
+        assert_eq!(vec![(meal_items[2].id(), usd(100))], applied[1].meal_item_savings());
+
+        // Promotions on the order come off what is left to pay, once those before them are taken off
+        let order_off = promotion("5% off", Reward::PercentOff { target: PromotionTarget::Order, percent: 5 }, None);
+        let applied = apply_promotions(&[sides_off, burger_off, order_off], &order);
+        assert!(applied[2].is_on_order());
+        // 5% of 1309 is 65.45, rounded to 65
+        assert_eq!(usd(65), applied[2].saved());
+    }
+
+    #[test]
+    fn test_apply_buy_x_get_y() {
+        let soda = MenuItem::new(String::from("soda"), usd(200)).with_category(Some(String::from("drinks")));
+        let juice = MenuItem::new(String::from("juice"), usd(300)).with_category(Some(String::from("drinks")));
+        let meal_items = create_meal_items(&[&soda, &juice, &soda, &juice, &soda]);
+        let order = Order::new(1, Currency::Usd, meal_items.clone(), Utc::now()).unwrap();
+
+        // Buy 1 get 1 on 5 drinks makes 2 groups, the dearest first: the cheaper of juice and juice, then of soda and soda, are free
+        let drinks = promotion("drinks 1+1", Reward::BuyXGetY { target: PromotionTarget::Category(String::from("drinks")), buy: 1, get: 1 }, None);
+        let applied = apply_promotions(&[drinks], &order);
+        assert_eq!(usd(500), applied[0].saved());
+        assert_eq!(vec![(meal_items[1].id(), usd(0)), (meal_items[3].id(), usd(300)), (meal_items[0].id(), usd(0)), (meal_items[2].id(), usd(200))],
+                   applied[0].meal_item_savings());
+
+        // The last soda is in no group, so another promotion can still discount it
+        let soda_off = promotion("soda 1 off", Reward::AmountOff { target: PromotionTarget::MenuItem(soda.id()), amount: usd(100) }, None);
+        let drinks = promotion("drinks 1+1", Reward::BuyXGetY { target: PromotionTarget::Category(String::from("drinks")), buy: 1, get: 1 }, None);
+        let applied = apply_promotions(&[drinks, soda_off], &order);
+        assert_eq!(vec![(meal_items[4].id(), usd(100))], applied[1].meal_item_savings());
+    }
+
+    #[test]
+    fn test_apply_promotions_after_one_saving_nothing() {
+        let burger = MenuItem::new(String::from("burger"), usd(800)).with_category(Some(String::from("mains")));
+        let water = MenuItem::new(String::from("water"), usd(0)).with_category(Some(String::from("mains")));
+        let meal_items = create_meal_items(&[&burger, &water]);
+        let order = Order::new(1, Currency::Usd, meal_items.clone(), Utc::now()).unwrap();
+
+        // Given the free water is the one given away, buy 1 get 1 saves nothing, so it leaves the burger to the next promotion
+        let mains = promotion("mains 1+1", Reward::BuyXGetY { target: PromotionTarget::Category(String::from("mains")), buy: 1, get: 1 }, None);
+        let burger_off = promotion("burger 1 off", Reward::AmountOff { target: PromotionTarget::MenuItem(burger.id()), amount: usd(100) }, None);
+        let applied = apply_promotions(&[mains, burger_off.clone()], &order);
+        assert_eq!(1, applied.len());
+        assert_eq!((burger_off.id(), usd(100)), (applied[0].promotion_id(), applied[0].saved()));
+        assert_eq!(vec![(meal_items[0].id(), usd(100))], applied[0].meal_item_savings());
+    }
+
+    #[test]
+    fn test_apply_combo_price() {
+        let burger = MenuItem::new(String::from("burger"), usd(800));
+        let fries = MenuItem::new(String::from("fries"), usd(400));
+        let soda = MenuItem::new(String::from("soda"), usd(200));
+        let meal_items = create_meal_items(&[&burger, &fries, &burger, &soda, &fries]);
+        let order = Order::new(1, Currency::Usd, meal_items.clone(), Utc::now()).unwrap();
+
+        // Two full sets of burger and fries are ordered, the soda is in none of them
+        let combo = promotion("burger and fries", Reward::ComboPrice { menu_item_ids: vec![burger.id(), fries.id()], price: usd(1000) }, None);
+        let applied = apply_promotions(&[combo], &order);
+        assert_eq!(usd(400), applied[0].saved());
+        // The 200 saved on each set is shared in proportion to the prices, 800 to 400
+        assert_eq!(vec![(meal_items[0].id(), usd(133)), (meal_items[1].id(), usd(67)), (meal_items[2].id(), usd(133)), (meal_items[4].id(), usd(67))],
+                   applied[0].meal_item_savings());
+
+        // A combo dearer than its meal items saves nothing
+        let dear = promotion("dear combo", Reward::ComboPrice { menu_item_ids: vec![burger.id(), fries.id()], price: usd(1200) }, None);
+        assert!(apply_promotions(&[dear], &order).is_empty());
+    }
+
+    #[test]
+    fn test_apply_promotions_min_spend_and_currency() {
+        let burger = MenuItem::new(String::from("burger"), usd(800));
+        let order = Order::new(1, Currency::Usd, create_meal_items(&[&burger]), Utc::now()).unwrap();
+
+        let big_spender = promotion("big spender", Reward::AmountOff { target: PromotionTarget::Order, amount: usd(200) }, Some(usd(1000)));
+        let small_spender = promotion("small spender", Reward::AmountOff { target: PromotionTarget::Order, amount: usd(100) }, Some(usd(800)));
+        let eur = Promotion::new(String::from("euro"), Currency::Eur,
+                                 Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None).unwrap();
+        assert_eq!(vec![(String::from("small spender"), usd(100))], savings(&order, &[big_spender, small_spender, eur]));
+    }
+
+    #[test]
+    fn test_apply_promotions_first_one_wins() {
+        let burger = MenuItem::new(String::from("burger"), usd(800)).with_category(Some(String::from("mains")));
+        let order = Order::new(1, Currency::Usd, create_meal_items(&[&burger]), Utc::now()).unwrap();
+
+        let mains_off = promotion("mains half off", Reward::PercentOff { target: PromotionTarget::Category(String::from("mains")), percent: 50 }, None);
+        let burger_off = promotion("burger 1 off", Reward::AmountOff { target: PromotionTarget::MenuItem(burger.id()), amount: usd(100) }, None);
+        assert_eq!(vec![(String::from("mains half off"), usd(400))], savings(&order, &[mains_off.clone(), burger_off.clone()]));
+        assert_eq!(vec![(String::from("burger 1 off"), usd(100))], savings(&order, &[burger_off, mains_off]));
+
+        // Nothing is ever taken off below zero
+        let all_off = promotion("all off", Reward::AmountOff { target: PromotionTarget::Order, amount: usd(1000) }, None);
+        let more_off = promotion("more off", Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None);
+        assert_eq!(vec![(String::from("all off"), usd(800))], savings(&order, &[all_off, more_off]));
+    }
+}
@@ -0,0 +1,376 @@
+use chrono::{DateTime, Utc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use uuid::Uuid;
+use crate::models::order::Order;
+use crate::models::payment::Payment;
+use crate::models::price::Price;
+use crate::models::promotion::AppliedPromotion;
+use crate::models::tax::{Charges, TaxPricing};
+
+// Name and contact details printed at the top of every receipt
+#[derive(Clone, Debug, PartialEq)]
+pub struct Restaurant {
+    pub name: String,
+    pub address: Option<String>,
+    pub phone: Option<String>,
+}
+
+// Widths of the thermal printers receipts are printed on, in columns of a monospaced font
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceiptWidth {
+    // 58mm paper
+    Narrow,
+    // 80mm paper
+    #[default]
+    Wide,
+}
+
+impl ReceiptWidth {
+    pub fn from_columns(columns: u32) -> Option<Self> {
+        match columns {
+            32 => Some(ReceiptWidth::Narrow),
+            42 => Some(ReceiptWidth::Wide),
+            _ => None,
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        match self {
+            ReceiptWidth::Narrow => 32,
+            ReceiptWidth::Wide => 42,
+        }
+    }
+}
+
+// A meal item charged on the receipt, which is neither removed nor voided
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReceiptItem {
+    pub meal_item_id: Uuid,
+    pub name: String,
+    pub price: Price,
+}
+
+// What the table was charged for and paid, as of the time it is printed
+#[derive(Clone, Debug)]
+pub struct Receipt {
+    restaurant: Restaurant,
+    order_id: Uuid,
+    table_id: u32,
+    guests: Option<u32>,
+    meal_items: Vec<ReceiptItem>,
+    subtotal: Price,
+    promotions: Vec<AppliedPromotion>,
+    discount: Price,
+    charges: Charges,
+    total_price: Price,
+    payments: Vec<Payment>,
+    amount_paid: Price,
+    balance_due: Price,
+    creation_time: DateTime<Utc>,
+    closed_time: Option<DateTime<Utc>>,
+    printed_time: DateTime<Utc>,
+}
+
+// A line of the receipt, laid out once for both the text and the HTML receipt
+#[derive(Clone, Debug, PartialEq)]
+enum Row {
+    Title(String),
+    Centered(String),
+    Rule,
+    DoubleRule,
+    // A label on the left and an amount or a time on the right
+    Columns(String, String),
+    Note(String),
+}
+
+impl Receipt {
+    pub fn new(order: &Order, restaurant: &Restaurant, printed_time: DateTime<Utc>) -> Self {
+        let mut meal_items = Vec::new();
+        for meal_item_arc in order.get_meal_items() {
+            let meal_item = meal_item_arc.lock().unwrap();
+            if meal_item.is_removed() || meal_item.is_voided() {
+                continue;
+            }
+            meal_items.push((meal_item.creation_time(), ReceiptItem {
+                meal_item_id: meal_item.id(),
+                name: meal_item.get_name(),
+                price: meal_item.price(),
+            }));
+        }
+        // Meal items are listed in the order they were ordered, those ordered together by id so every print lists them the same way
+        meal_items.sort_by_key(|(creation_time, meal_item)| (*creation_time, meal_item.meal_item_id));
+
+        Receipt {
+            restaurant: restaurant.clone(),
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            guests: order.get_guests(),
+            meal_items: meal_items.into_iter().map(|(_, meal_item)| meal_item).collect(),
+            subtotal: order.get_subtotal(),
+            promotions: order.get_promotions(),
+            discount: order.get_discount(),
+            charges: order.get_charges(),
+            total_price: order.get_total_price(),
+            payments: order.get_payments(),
+            amount_paid: order.get_amount_paid(),
+            balance_due: order.get_balance_due(),
+            creation_time: order.creation_time(),
+            closed_time: order.closed_time(),
+            printed_time,
+        }
+    }
+
+    pub fn restaurant(&self) -> Restaurant {
+        self.restaurant.clone()
+    }
+
+    pub fn order_id(&self) -> Uuid {
+        self.order_id
+    }
+
+    pub fn table_id(&self) -> u32 {
+        self.table_id
+    }
+
+    pub fn guests(&self) -> Option<u32> {
+        self.guests
+    }
+
+    pub fn meal_items(&self) -> Vec<ReceiptItem> {
+        self.meal_items.clone()
+    }
+
+    pub fn subtotal(&self) -> Price {
+        self.subtotal
+    }
+
+    pub fn promotions(&self) -> Vec<AppliedPromotion> {
+        self.promotions.clone()
+    }
+
+    pub fn discount(&self) -> Price {
+        self.discount
+    }
+
+    pub fn charges(&self) -> Charges {
+        self.charges.clone()
+    }
+
+    pub fn total_price(&self) -> Price {
+        self.total_price
+    }
+
+    pub fn payments(&self) -> Vec<Payment> {
+        self.payments.clone()
+    }
+
+    pub fn amount_paid(&self) -> Price {
+        self.amount_paid
+    }
+
+    pub fn balance_due(&self) -> Price {
+        self.balance_due
+    }
+
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        self.creation_time
+    }
+
+    pub fn closed_time(&self) -> Option<DateTime<Utc>> {
+        self.closed_time
+    }
+
+    pub fn printed_time(&self) -> DateTime<Utc> {
+        self.printed_time
+    }
+
+    // Plain text for thermal printers, every line at most as wide as the paper, ex. CJK characters take two columns.
+    // Long names wrap onto the next lines, with their amount on the last one.
+    pub fn to_text(&self, width: ReceiptWidth) -> String {
+        let width = width.columns();
+        let mut lines = Vec::new();
+        for row in self.rows() {
+            match row {
+                Row::Title(text) | Row::Centered(text) => {
+                    lines.extend(wrap(&text, width).into_iter().map(|line| center(&line, width)));
+                }
+                Row::Rule => lines.push("-".repeat(width)),
+                Row::DoubleRule => lines.push("=".repeat(width)),
+                Row::Columns(label, value) => lines.extend(columns(&label, &value, width)),
+                Row::Note(text) => lines.extend(wrap(&text, width - 2).into_iter().map(|line| format!("  {}", line))),
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    // A standalone HTML page laid out like the text receipt, for printing from a browser or sending by email
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>Receipt of table {}</title>\n", self.table_id));
+        html.push_str("<style>body{font-family:monospace;max-width:24em;margin:1em auto}table{width:100%;border-collapse:collapse}\
+                       td.amount{text-align:right;white-space:nowrap}td.center{text-align:center}td.note{padding-left:1em}\
+                       hr{border:none;border-top:1px dashed}hr.double{border-top:3px double}</style>\n");
+        html.push_str("</head>\n<body>\n<table>\n");
+        for row in self.rows() {
+            let cells = match row {
+                Row::Title(text) => format!("<td colspan=\"2\" class=\"center\"><strong>{}</strong></td>", escape_html(&text)),
+                Row::Centered(text) => format!("<td colspan=\"2\" class=\"center\">{}</td>", escape_html(&text)),
+                Row::Rule => String::from("<td colspan=\"2\"><hr></td>"),
+                Row::DoubleRule => String::from("<td colspan=\"2\"><hr class=\"double\"></td>"),
+                Row::Columns(label, value) => format!("<td>{}</td><td class=\"amount\">{}</td>", escape_html(&label), escape_html(&value)),
+                Row::Note(text) => format!("<td colspan=\"2\" class=\"note\">{}</td>", escape_html(&text)),
+            };
+            html.push_str(&format!("<tr>{}</tr>\n", cells));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+        html
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = vec![Row::Title(self.restaurant.name.clone())];
+        rows.extend(self.restaurant.address.iter().map(|address| Row::Centered(address.clone())));
+        rows.extend(self.restaurant.phone.iter().map(|phone| Row::Centered(format!("Tel {}", phone))));
+        rows.push(Row::DoubleRule);
+
+        rows.push(Row::Columns(format!("Table {}", self.table_id), self.guests.map(|guests| format!("Guests {}", guests)).unwrap_or_default()));
+        // The first group of the order id is enough to find the order, and fits the narrowest paper
+        rows.push(Row::Columns(String::from("Order"), self.order_id.to_string()[..8].to_string()));
+        rows.push(Row::Columns(String::from("Opened"), format_time(self.creation_time)));
+        rows.push(Row::Rule);
+
+        // Meal items of the same name and price are printed on one line, where the first of them is listed, ex. "2 x Soda"
+        let mut lines: Vec<(&str, Price, u32)> = Vec::new();
+        for meal_item in self.meal_items.iter() {
+            match lines.iter_mut().find(|(name, price, _)| *name == meal_item.name && *price == meal_item.price) {
+                Some((_, _, quantity)) => *quantity += 1,
+                None => lines.push((&meal_item.name, meal_item.price, 1)),
+            }
+        }
+        rows.extend(lines.into_iter().map(|(name, price, quantity)| {
+            let label = if quantity > 1 { format!("{} x {}", quantity, name) } else { name.to_string() };
+            let line_price = price.checked_mul(quantity).expect("meal items on the receipt fit in the order subtotal");
+            Row::Columns(label, line_price.to_major_units_string())
+        }));
+        rows.push(Row::Rule);
+
+        rows.push(Row::Columns(String::from("Subtotal"), self.subtotal.to_major_units_string()));
+        rows.extend(self.promotions.iter().map(|promotion| Row::Columns(promotion.get_name(), format!("-{}", promotion.saved().to_major_units_string()))));
+        let included = self.charges.pricing() == TaxPricing::Inclusive;
+        if !included {
+            rows.extend(self.charges.tax_lines().iter().map(|tax_line| {
+                Row::Columns(format!("{} {}%", tax_line.get_name(), tax_line.rate()), tax_line.amount().to_major_units_string())
+            }));
+        }
+        if self.charges.service_charge().minor_units() > 0 {
+            rows.push(Row::Columns(String::from("Service charge"), self.charges.service_charge().to_major_units_string()));
+        }
+        rows.push(Row::DoubleRule);
+        rows.push(Row::Columns(format!("TOTAL {}", self.total_price.currency()), self.total_price.to_major_units_string()));
+        if included {
+            // Taxes included in the menu prices are only shown, they are already in the total
+            rows.extend(self.charges.tax_lines().iter().map(|tax_line| {
+                Row::Columns(format!("incl. {} {}%", tax_line.get_name(), tax_line.rate()), tax_line.amount().to_major_units_string())
+            }));
+        }
+        rows.push(Row::Rule);
+
+        for payment in self.payments.iter() {
+            let mut method = payment.method().to_string();
+            if let Some(reference) = payment.reference() {
+                method = format!("{} {}", method, reference);
+            }
+            if let Some(check) = payment.check() {
+                method = format!("{} ({})", method, check);
+            }
+            rows.push(Row::Columns(method, payment.amount().to_major_units_string()));
+            rows.push(Row::Note(format_time(payment.time())));
+        }
+        rows.push(Row::Columns(String::from("Paid"), self.amount_paid.to_major_units_string()));
+        rows.push(Row::Columns(String::from("Balance due"), self.balance_due.to_major_units_string()));
+        rows.push(Row::Rule);
+
+        if let Some(closed_time) = self.closed_time {
+            rows.push(Row::Columns(String::from("Paid in full"), format_time(closed_time)));
+        }
+        rows.push(Row::Columns(String::from("Printed"), format_time(self.printed_time)));
+        rows.push(Row::Centered(String::from("Thank you!")));
+        rows
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+// Columns the text takes on the paper, ex. two for every CJK character
+fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+// Breaks the text into lines of at most width columns at spaces, and words longer than a line wherever they reach its end
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while display_width(&word) > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let (head, tail) = split_at_width(&word, width);
+            lines.push(head.to_string());
+            word = tail.to_string();
+        }
+        if line.is_empty() {
+            line = word;
+        } else if display_width(&line) + 1 + display_width(&word) <= width {
+            line = format!("{} {}", line, word);
+        } else {
+            lines.push(std::mem::replace(&mut line, word));
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// Splits the word after as many characters as fit in width columns, at least one so wrapping always moves on
+fn split_at_width(word: &str, width: usize) -> (&str, &str) {
+    let mut taken = 0;
+    for (index, c) in word.char_indices() {
+        taken += UnicodeWidthChar::width(c).unwrap_or(0);
+        if taken > width && index > 0 {
+            return word.split_at(index);
+        }
+    }
+    (word, "")
+}
+
+fn center(line: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(line)) / 2;
+    format!("{}{}", " ".repeat(padding), line).trim_end().to_string()
+}
+
+// Puts the label on the left and the value right-aligned on the last line of the label
+fn columns(label: &str, value: &str, width: usize) -> Vec<String> {
+    let value_width = display_width(value);
+    if value_width + 2 > width {
+        let mut lines = wrap(label, width);
+        lines.push(format!("{}{}", " ".repeat(width.saturating_sub(value_width)), value));
+        return lines;
+    }
+    let mut lines = wrap(label, width - value_width - 1);
+    let last = lines.pop().unwrap_or_default();
+    let padding = width - display_width(&last) - value_width;
+    lines.push(format!("{}{}{}", last, " ".repeat(padding), value).trim_end().to_string());
+    lines
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod receipt_test {
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use unicode_width::UnicodeWidthStr;
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::payment::{Payment, PaymentMethod};
+    use crate::models::price::Price;
+    use crate::models::promotion::AppliedPromotion;
+    use crate::models::receipt::{Receipt, ReceiptWidth, Restaurant};
+    use crate::models::tax::{ServiceCharge, Tax, TaxPolicy, TaxPricing};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    fn opened_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    fn restaurant() -> Restaurant {
+        Restaurant {
+            name: String::from("Rusty Spoon"),
+            address: Some(String::from("1 Main Street, Springfield")),
+            phone: Some(String::from("555-0100")),
+        }
+    }
+
+    fn meal_item(name: &str, price: i64, position: i64) -> MealItem {
+        let menu_item = MenuItem::new(String::from(name), usd(price));
+        MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), opened_time() + TimeDelta::seconds(position))
+    }
+
+    // Burger and fries, with a removed soda, a dollar off, a 10% sales tax and a 10% service charge for 6 guests
+    fn priced_order() -> (Order, Vec<MealItem>) {
+        let meal_items = vec![meal_item("Burger", 1000, 0), meal_item("Fries", 400, 1), meal_item("Soda", 250, 2)];
+        let mut order = Order::new(2, Currency::Usd, meal_items.clone(), opened_time()).unwrap().with_guests(Some(6));
+        order.remove_meal_items(vec![meal_items[2].id()], opened_time());
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "10".parse().unwrap(), categories: None }],
+            service_charge: Some(ServiceCharge { rate: "10".parse().unwrap(), above_guests: 5 }),
+            ..TaxPolicy::default()
+        };
+        let dollar_off = AppliedPromotion::new(Uuid::new_v4(), String::from("dollar off"), usd(100), vec![]);
+        assert!(order.apply_pricing(vec![dollar_off], tax_policy, opened_time()));
+        (order, meal_items)
+    }
+
+    #[test]
+    fn test_receipt_width() {
+        assert_eq!(Some(ReceiptWidth::Narrow), ReceiptWidth::from_columns(32));
+        assert_eq!(Some(ReceiptWidth::Wide), ReceiptWidth::from_columns(42));
+        assert_eq!(None, ReceiptWidth::from_columns(80));
+        assert_eq!(ReceiptWidth::Wide, ReceiptWidth::default());
+    }
+
+    #[test]
+    fn test_new_receipt() {
+        let (order, meal_items) = priced_order();
+        let receipt = Receipt::new(&order, &restaurant(), opened_time());
+
+        // The removed soda is not on the receipt
+        let names: Vec<_> = receipt.meal_items().iter().map(|meal_item| (meal_item.meal_item_id, meal_item.name.clone())).collect();
+        assert_eq!(vec![(meal_items[0].id(), String::from("Burger")), (meal_items[1].id(), String::from("Fries"))], names);
+        // 1300 after the dollar off, plus 130 of tax and 130 of service charge
+        assert_eq!((usd(1400), usd(100), usd(1560)), (receipt.subtotal(), receipt.discount(), receipt.total_price()));
+        assert_eq!(usd(1560), receipt.balance_due());
+    }
+
+    #[test]
+    fn test_receipt_to_wide_text() {
+        let (mut order, _) = priced_order();
+        order.add_payment(Payment::new(PaymentMethod::Card, Some(String::from("VISA-4242")), usd(1560), opened_time() + TimeDelta::minutes(45))).unwrap();
+        let receipt = Receipt::new(&order, &restaurant(), opened_time() + TimeDelta::minutes(46));
+
+        let order_id = order.id().to_string();
+        let expected = [
+            "               Rusty Spoon",
+            "        1 Main Street, Springfield",
+            "               Tel 555-0100",
+            "==========================================",
+            "Table 2                           Guests 6",
+            &format!("Order                             {}", &order_id[..8]),
+            "Opened                2024-05-01 12:00 UTC",
+            "------------------------------------------",
+            "Burger                               10.00",
+            "Fries                                 4.00",
+            "------------------------------------------",
+            "Subtotal                             14.00",
+            "dollar off                           -1.00",
+            "sales tax 10%                         1.30",
+            "Service charge                        1.30",
+            "==========================================",
+            "TOTAL USD                            15.60",
+            "------------------------------------------",
+            "card VISA-4242                       15.60",
+            "  2024-05-01 12:45 UTC",
+            "Paid                                 15.60",
+            "Balance due                           0.00",
+            "------------------------------------------",
+            "Paid in full          2024-05-01 12:45 UTC",
+            "Printed               2024-05-01 12:46 UTC",
+            "                Thank you!",
+        ].join("\n") + "\n";
+        assert_eq!(expected, receipt.to_text(ReceiptWidth::Wide));
+    }
+
+    #[test]
+    fn test_receipt_to_narrow_text() {
+        let long_name = "Slow-roasted pork belly with apple cider glaze";
+        let meal_items = vec![meal_item(long_name, 2450, 0), meal_item("Soup", 600, 1)];
+        let mut order = Order::new(7, Currency::Usd, meal_items, opened_time()).unwrap();
+        // Inclusive taxes are only shown under the total
+        let tax_policy = TaxPolicy {
+            pricing: TaxPricing::Inclusive,
+            taxes: vec![Tax { name: String::from("VAT"), rate: "20".parse().unwrap(), categories: None }],
+            ..TaxPolicy::default()
+        };
+        assert!(order.apply_pricing(vec![], tax_policy, opened_time()));
+        let text = Receipt::new(&order, &restaurant(), opened_time()).to_text(ReceiptWidth::Narrow);
+
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines.iter().all(|line| line.chars().count() <= 32), "{}", text);
+        // A long name wraps at spaces, leaving room for its price on its last line
+        let name_at = lines.iter().position(|line| line.starts_with("Slow-roasted")).unwrap();
+        assert_eq!(vec!["Slow-roasted pork belly", "with apple cider glaze     24.50"], lines[name_at..name_at + 2].to_vec());
+        let total_at = lines.iter().position(|line| line.starts_with("TOTAL")).unwrap();
+        assert_eq!(vec!["TOTAL USD                  30.50", "incl. VAT 20%               5.08"], lines[total_at..total_at + 2].to_vec());
+        assert!(!text.contains("Guests"));
+    }
+
+    #[test]
+    fn test_receipt_to_text_wraps_long_words() {
+        let meal_items = vec![meal_item("Supercalifragilisticexpialidocious-sundae", 999, 0)];
+        let order = Order::new(1, Currency::Usd, meal_items, opened_time()).unwrap();
+        let text = Receipt::new(&order, &restaurant(), opened_time()).to_text(ReceiptWidth::Narrow);
+
+        assert!(text.lines().all(|line| line.chars().count() <= 32), "{}", text);
+        assert!(text.contains("Supercalifragilisticexpiali\ndocious-sundae              9.99\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_new_receipt_lists_meal_items_ordered_together_by_id() {
+        // Given the meal items are ordered in one request, they share their creation time
+        let meal_items = vec![meal_item("Soda", 250, 0), meal_item("Soda", 250, 0), meal_item("Fries", 400, 0), meal_item("Burger", 1000, 0)];
+        let order = Order::new(1, Currency::Usd, meal_items.clone(), opened_time()).unwrap();
+
+        let receipt = Receipt::new(&order, &restaurant(), opened_time());
+
+        let mut expected: Vec<_> = meal_items.iter().map(|meal_item| meal_item.id()).collect();
+        expected.sort();
+        assert_eq!(expected, receipt.meal_items().iter().map(|meal_item| meal_item.meal_item_id).collect::<Vec<_>>());
+        assert_eq!(receipt.to_text(ReceiptWidth::Wide), Receipt::new(&order, &restaurant(), opened_time()).to_text(ReceiptWidth::Wide));
+    }
+
+    #[test]
+    fn test_receipt_to_text_prints_quantities() {
+        // Sodas ordered apart are still printed together, where the first one is listed
+        let meal_items = vec![meal_item("Soda", 250, 0), meal_item("Burger", 1000, 1), meal_item("Soda", 250, 2), meal_item("Soda", 300, 3)];
+        let order = Order::new(1, Currency::Usd, meal_items, opened_time()).unwrap();
+        let receipt = Receipt::new(&order, &restaurant(), opened_time());
+
+        // Every meal item is still listed on its own, only the printed lines are grouped
+        assert_eq!(4, receipt.meal_items().len());
+        let text = receipt.to_text(ReceiptWidth::Wide);
+        assert!(text.contains(&[
+            "2 x Soda                              5.00",
+            "Burger                               10.00",
+            "Soda                                  3.00",
+        ].join("\n")), "{}", text);
+        assert!(text.contains("Subtotal                             18.00\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_receipt_to_text_wraps_double_width_names() {
+        // Every CJK character takes two columns on the paper
+        let meal_items = vec![meal_item("紅燒牛肉麵特大碗加辣加蛋 招牌", 1850, 0), meal_item("珍珠奶茶", 550, 1)];
+        let order = Order::new(1, Currency::Usd, meal_items, opened_time()).unwrap();
+        let restaurant = Restaurant { name: String::from("老張牛肉麵館"), address: None, phone: None };
+        let text = Receipt::new(&order, &restaurant, opened_time()).to_text(ReceiptWidth::Narrow);
+
+        assert!(text.lines().all(|line| UnicodeWidthStr::width(line) <= 32), "{}", text);
+        assert!(text.starts_with("          老張牛肉麵館\n"), "{}", text);
+        assert!(text.contains("紅燒牛肉麵特大碗加辣加蛋\n招牌                       18.50\n"), "{}", text);
+        assert!(text.contains("珍珠奶茶                    5.50\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_receipt_to_html() {
+        let meal_item = meal_item("Fish & <Chips>", 1200, 0);
+        let mut order = Order::new(3, Currency::Usd, vec![meal_item.clone()], opened_time()).unwrap();
+        order.update_meal_item_status(meal_item.id(), MealItemStatus::Preparing, opened_time());
+        let restaurant = Restaurant { name: String::from("Bob's"), address: None, phone: None };
+        let html = Receipt::new(&order, &restaurant, opened_time()).to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<strong>Bob&#39;s</strong>"));
+        assert!(html.contains("<tr><td>Fish &amp; &lt;Chips&gt;</td><td class=\"amount\">12.00</td></tr>"));
+        assert!(html.contains("<tr><td>TOTAL USD</td><td class=\"amount\">12.00</td></tr>"));
+    }
+}
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::price::{Price, PriceError};
+
+// Most guests a bill can be split evenly among
+pub const MAX_GUESTS: u32 = 100;
+
+// Meal items put on a named check, ex. the name of the guest paying for them
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedCheck {
+    pub name: String,
+    pub meal_item_ids: Vec<Uuid>,
+}
+
+// How the bill of an order is split into checks paid on their own
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BillSplit {
+    // Every meal item charged goes on exactly one of the checks
+    ByMealItem { checks: Vec<NamedCheck> },
+    // The total price is shared evenly, the checks are named guest-1 to guest-N
+    Evenly { guests: u32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CheckStatus {
+    Unpaid,
+    PartiallyPaid,
+    Paid,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SplitError {
+    NoChecks,
+    EmptyCheckName,
+    DuplicateCheckName(String),
+    InvalidGuests(u32),
+    // The meal item doesn't belong to the order, or is removed or voided so it is not charged
+    UnknownMealItem(Uuid),
+    DuplicateMealItem(Uuid),
+    MissingMealItem(Uuid),
+    OrderClosed,
+    // Payments made before the bill was split can't be told apart between the checks
+    PaidBeforeSplit,
+    // A check already paid in part has to stay in the split
+    PaidCheckMissing(String),
+    // A check already paid in part can't be charged less than what it paid
+    CheckOverpaid(String),
+    Price(PriceError),
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SplitError::NoChecks => write!(f, "the bill has to be split into at least one check"),
+            SplitError::EmptyCheckName => write!(f, "every check has to be named"),
+            SplitError::DuplicateCheckName(name) => write!(f, "check '{}' is given more than once", name),
+            SplitError::InvalidGuests(guests) => write!(f, "the bill can't be split among {} guests, only 1 to {}", guests, MAX_GUESTS),
+            SplitError::UnknownMealItem(meal_item_id) => write!(f, "meal item {} is not charged on this order", meal_item_id),
+            SplitError::DuplicateMealItem(meal_item_id) => write!(f, "meal item {} is put on more than one check", meal_item_id),
+            SplitError::MissingMealItem(meal_item_id) => write!(f, "meal item {} is not put on any check", meal_item_id),
+            SplitError::OrderClosed => write!(f, "order is already paid in full"),
+            SplitError::PaidBeforeSplit => write!(f, "the bill was paid in part before it was split"),
+            SplitError::PaidCheckMissing(name) => write!(f, "check '{}' is paid in part and has to stay in the split", name),
+            SplitError::CheckOverpaid(name) => write!(f, "check '{}' would be charged less than it paid", name),
+            SplitError::Price(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for SplitError {}
+
+impl From<PriceError> for SplitError {
+    fn from(err: PriceError) -> Self {
+        SplitError::Price(err)
+    }
+}
+
+// One of the checks the bill of an order is split into, along with what was paid on it
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubCheck {
+    name: String,
+    // Empty when the bill is split evenly, as every check then shares every meal item
+    meal_item_ids: Vec<Uuid>,
+    subtotal: Price,
+    // Savings of promotions on the meal items of the check, and its share of those on the whole order
+    discount: Price,
+    // Taxes added on top of the menu prices, none when the menu prices include them
+    tax: Price,
+    service_charge: Price,
+    amount_paid: Price,
+}
+
+impl SubCheck {
+    pub fn new(name: String, meal_item_ids: Vec<Uuid>, subtotal: Price, discount: Price, tax: Price, service_charge: Price, amount_paid: Price) -> Self {
+        SubCheck {
+            name,
+            meal_item_ids,
+            subtotal,
+            discount,
+            tax,
+            service_charge,
+            amount_paid,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn meal_item_ids(&self) -> Vec<Uuid> {
+        self.meal_item_ids.clone()
+    }
+
+    pub fn subtotal(&self) -> Price {
+        self.subtotal
+    }
+
+    pub fn discount(&self) -> Price {
+        self.discount
+    }
+
+    pub fn tax(&self) -> Price {
+        self.tax
+    }
+
+    pub fn service_charge(&self) -> Price {
+        self.service_charge
+    }
+
+    pub fn total(&self) -> Price {
+        self.subtotal.checked_sub(self.discount)
+            .and_then(|total| total.checked_add(self.tax))
+            .and_then(|total| total.checked_add(self.service_charge))
+            .expect("check total fits in the order total")
+    }
+
+    pub fn amount_paid(&self) -> Price {
+        self.amount_paid
+    }
+
+    pub fn balance_due(&self) -> Price {
+        self.total().checked_sub(self.amount_paid).expect("payments never add up to more than the check total")
+    }
+
+    pub fn status(&self) -> CheckStatus {
+        if self.balance_due().minor_units() == 0 {
+            CheckStatus::Paid
+        } else if self.amount_paid.minor_units() > 0 {
+            CheckStatus::PartiallyPaid
+        } else {
+            CheckStatus::Unpaid
+        }
+    }
+}
+
+// Name of the check of the guest at the position, counting from 1
+pub fn guest_check_name(position: u32) -> String {
+    format!("guest-{}", position)
+}
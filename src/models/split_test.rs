@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod split_test {
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::price::Price;
+    use crate::models::split::{BillSplit, CheckStatus, NamedCheck, SplitError, SubCheck};
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    #[test]
+    fn test_sub_check_status() {
+        let unpaid = SubCheck::new(String::from("ann"), vec![], usd(1000), usd(0), usd(80), usd(0), usd(0));
+        assert_eq!((usd(1080), usd(1080), CheckStatus::Unpaid), (unpaid.total(), unpaid.balance_due(), unpaid.status()));
+
+        let partially_paid = SubCheck::new(String::from("ann"), vec![], usd(1000), usd(0), usd(80), usd(0), usd(500));
+        assert_eq!((usd(580), CheckStatus::PartiallyPaid), (partially_paid.balance_due(), partially_paid.status()));
+
+        let paid = SubCheck::new(String::from("ann"), vec![], usd(1100), usd(100), usd(60), usd(20), usd(1080));
+        assert_eq!(CheckStatus::Paid, paid.status());
+    }
+
+    #[test]
+    fn test_serialize_bill_split() {
+        let meal_item_id = Uuid::new_v4();
+        let split = BillSplit::ByMealItem { checks: vec![NamedCheck { name: String::from("ann"), meal_item_ids: vec![meal_item_id] }] };
+        let json = serde_json::to_string(&split).unwrap();
+        assert_eq!(format!("{{\"type\":\"by_meal_item\",\"checks\":[{{\"name\":\"ann\",\"meal_item_ids\":[\"{}\"]}}]}}", meal_item_id), json);
+        assert_eq!(split, serde_json::from_str(&json).unwrap());
+
+        assert_eq!("{\"type\":\"evenly\",\"guests\":3}", serde_json::to_string(&BillSplit::Evenly { guests: 3 }).unwrap());
+    }
+
+    #[test]
+    fn test_display_split_error() {
+        assert_eq!("the bill can't be split among 0 guests, only 1 to 100", SplitError::InvalidGuests(0).to_string());
+        assert_eq!("check 'ann' would be charged less than it paid", SplitError::CheckOverpaid(String::from("ann")).to_string());
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// Part of the kitchen a menu item is cooked at, each with its own chefs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Station {
+    #[default]
+    Grill,
+    Fryer,
+    Salad,
+    Bar,
+}
+
+impl Station {
+    pub const ALL: [Station; 4] = [Station::Grill, Station::Fryer, Station::Salad, Station::Bar];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Station::Grill => "grill",
+            Station::Fryer => "fryer",
+            Station::Salad => "salad",
+            Station::Bar => "bar",
+        }
+    }
+}
+
+impl fmt::Display for Station {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Station {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Station::ALL.into_iter()
+            .find(|station| station.name() == s)
+            .ok_or_else(|| format!("unknown station '{}'", s))
+    }
+}
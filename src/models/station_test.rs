@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod station_test {
+    use crate::models::station::Station;
+
+    #[test]
+    fn test_parse_and_display_station() {
+        for station in Station::ALL {
+            assert_eq!(Ok(station), station.to_string().parse());
+        }
+        assert_eq!(Ok(Station::Fryer), "fryer".parse());
+        assert!("Fryer".parse::<Station>().is_err());
+        assert_eq!("\"salad\"", serde_json::to_string(&Station::Salad).unwrap());
+        assert_eq!(Station::Grill, Station::default());
+    }
+}
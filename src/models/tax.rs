@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::models::currency::Currency;
+use crate::models::price::{Price, PriceError, RoundingMode};
+
+// 100% in thousandths of a percent
+const HUNDRED_PERCENT: u32 = 100_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaxError {
+    InvalidRate(String),
+    EmptyName,
+    DuplicateName(String),
+    EmptyCategory(String),
+}
+
+impl fmt::Display for TaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaxError::InvalidRate(rate) => write!(f, "'{}' is not a percentage between 0 and 100 with at most 3 decimals", rate),
+            TaxError::EmptyName => write!(f, "every tax has to be named"),
+            TaxError::DuplicateName(name) => write!(f, "tax '{}' is given more than once", name),
+            TaxError::EmptyCategory(name) => write!(f, "tax '{}' lists a category without a name", name),
+        }
+    }
+}
+
+impl Error for TaxError {}
+
+// A percentage between 0% and 100% with at most 3 decimals, ex. "8.875", kept in thousandths of a percent so arithmetic is exact
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rate(u32);
+
+impl Rate {
+    pub fn thousandths_of_percent(&self) -> u32 {
+        self.0
+    }
+
+    // The rate of the price, rounded to the minor unit with the given mode
+    pub fn of(&self, price: Price, rounding_mode: RoundingMode) -> Result<Price, PriceError> {
+        price.mul_ratio(self.0 as u64, HUNDRED_PERCENT as u64, rounding_mode)
+    }
+}
+
+impl FromStr for Rate {
+    type Err = TaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TaxError::InvalidRate(s.to_string());
+        let (whole, fraction) = s.trim().split_once('.').unwrap_or((s.trim(), ""));
+        if whole.is_empty() || fraction.len() > 3
+            || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let whole: u32 = whole.parse().map_err(|_| invalid())?;
+        let fraction: u32 = format!("{:0<3}", fraction).parse().map_err(|_| invalid())?;
+        match whole.checked_mul(1000).and_then(|whole| whole.checked_add(fraction)) {
+            Some(rate) if rate <= HUNDRED_PERCENT => Ok(Rate(rate)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for Rate {
+    type Error = TaxError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Rate> for String {
+    fn from(rate: Rate) -> Self {
+        rate.to_string()
+    }
+}
+
+// Formats the rate without trailing zeros, ex. "8.875", "10.5" or "20"
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (whole, fraction) = (self.0 / 1000, self.0 % 1000);
+        if fraction == 0 {
+            write!(f, "{}", whole)
+        } else {
+            write!(f, "{}.{}", whole, format!("{:03}", fraction).trim_end_matches('0'))
+        }
+    }
+}
+
+// Whether menu prices already include the taxes, or the taxes are added on top of them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxPricing {
+    #[default]
+    Exclusive,
+    Inclusive,
+}
+
+// A tax charged on meal items of the listed categories, or on every meal item if no category is listed.
+// A meal item can be charged several taxes, ex. a state and a city sales tax.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tax {
+    pub name: String,
+    pub rate: Rate,
+    #[serde(default)]
+    pub categories: Option<Vec<String>>,
+}
+
+impl Tax {
+    fn applies_to(&self, category: Option<&str>) -> bool {
+        match &self.categories {
+            Some(categories) => category.is_some_and(|category| categories.iter().any(|taxed| taxed == category)),
+            None => true,
+        }
+    }
+}
+
+// Charged on what the meal items cost before tax once promotions are taken off, for parties of more than above_guests guests
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ServiceCharge {
+    pub rate: Rate,
+    pub above_guests: u32,
+}
+
+// Taxes and service charge of the restaurant, by default none. Every order keeps the policy it was last priced with,
+// so the totals of orders paid under a previous policy never change.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaxPolicy {
+    #[serde(default)]
+    pub pricing: TaxPricing,
+    #[serde(default)]
+    pub taxes: Vec<Tax>,
+    #[serde(default)]
+    pub service_charge: Option<ServiceCharge>,
+    // How tax lines and the service charge are rounded to the minor unit, half up by default
+    #[serde(default)]
+    pub rounding: RoundingMode,
+}
+
+// Tax charged on the meal items it applies to; with inclusive pricing, taxable is what they cost before the tax
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxLine {
+    name: String,
+    rate: Rate,
+    taxable: Price,
+    amount: Price,
+}
+
+impl TaxLine {
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn rate(&self) -> Rate {
+        self.rate
+    }
+
+    pub fn taxable(&self) -> Price {
+        self.taxable
+    }
+
+    pub fn amount(&self) -> Price {
+        self.amount
+    }
+}
+
+// Taxes and service charge of an order, along with the share of each meal item charged, in the order they were given
+#[derive(Clone, Debug, PartialEq)]
+pub struct Charges {
+    pricing: TaxPricing,
+    tax_lines: Vec<TaxLine>,
+    service_charge: Price,
+    meal_item_taxes_added: Vec<Price>,
+    meal_item_service_charges: Vec<Price>,
+}
+
+impl Charges {
+    pub fn pricing(&self) -> TaxPricing {
+        self.pricing
+    }
+
+    pub fn tax_lines(&self) -> Vec<TaxLine> {
+        self.tax_lines.clone()
+    }
+
+    pub fn service_charge(&self) -> Price {
+        self.service_charge
+    }
+
+    // Taxes added on top of the menu prices, none when the menu prices include them
+    pub fn tax_added(&self) -> Price {
+        self.meal_item_taxes_added.iter()
+            .fold(Price::zero(self.service_charge.currency()), |tax, meal_item_tax| tax.checked_add(*meal_item_tax)
+                .expect("taxes added fit in the order total"))
+    }
+
+    pub fn meal_item_taxes_added(&self) -> Vec<Price> {
+        self.meal_item_taxes_added.clone()
+    }
+
+    pub fn meal_item_service_charges(&self) -> Vec<Price> {
+        self.meal_item_service_charges.clone()
+    }
+}
+
+impl TaxPolicy {
+    pub fn validate(&self) -> Result<(), TaxError> {
+        let mut names: Vec<&str> = Vec::new();
+        for tax in self.taxes.iter() {
+            let name = tax.name.trim();
+            if name.is_empty() {
+                return Err(TaxError::EmptyName);
+            }
+            if names.contains(&name) {
+                return Err(TaxError::DuplicateName(name.to_string()));
+            }
+            if tax.categories.iter().flatten().any(|category| category.trim().is_empty()) {
+                return Err(TaxError::EmptyCategory(name.to_string()));
+            }
+            names.push(name);
+        }
+        Ok(())
+    }
+
+    // Works out the taxes and service charge of meal items given by category and price once promotions are taken off.
+    // Every tax line is rounded to the minor unit once, on the sum of the meal items it applies to. With inclusive pricing,
+    // the tax in each group of meal items charged the same taxes is worked out on their sum, then shared among the taxes by rate.
+    // The service charge is rounded the same way. Shares of meal items are split in proportion to their prices, so they always add up.
+    pub fn charge(&self, meal_items: &[(Option<String>, Price)], guests: Option<u32>, currency: Currency) -> Result<Charges, PriceError> {
+        let taxes_of: Vec<Vec<usize>> = meal_items.iter()
+            .map(|(category, _)| (0..self.taxes.len()).filter(|index| self.taxes[*index].applies_to(category.as_deref())).collect())
+            .collect();
+        let taxed_prices = |index: usize| -> Vec<i64> {
+            meal_items.iter().zip(taxes_of.iter())
+                .map(|((_, price), taxes)| if taxes.contains(&index) { price.minor_units() } else { 0 })
+                .collect()
+        };
+
+        let mut tax_lines = Vec::new();
+        let mut meal_item_taxes_added = vec![Price::zero(currency); meal_items.len()];
+        let mut tax_included = Price::zero(currency);
+        match self.pricing {
+            TaxPricing::Exclusive => {
+                for (index, tax) in self.taxes.iter().enumerate() {
+                    let weights = taxed_prices(index);
+                    let taxable = Price::from_minor_units(weights.iter().sum(), currency)?;
+                    let amount = tax.rate.of(taxable, self.rounding)?;
+                    if amount.minor_units() > 0 {
+                        for (meal_item_tax, share) in meal_item_taxes_added.iter_mut().zip(amount.allocate(&weights)?) {
+                            *meal_item_tax = meal_item_tax.checked_add(share)?;
+                        }
+                    }
+                    tax_lines.push(TaxLine { name: tax.name.clone(), rate: tax.rate, taxable, amount });
+                }
+            }
+            TaxPricing::Inclusive => {
+                let mut groups: BTreeMap<Vec<usize>, Price> = BTreeMap::new();
+                for ((_, price), taxes) in meal_items.iter().zip(taxes_of.iter()) {
+                    let gross = groups.entry(taxes.clone()).or_insert(Price::zero(currency));
+                    *gross = gross.checked_add(*price)?;
+                }
+                let mut lines: Vec<(Price, Price)> = vec![(Price::zero(currency), Price::zero(currency)); self.taxes.len()];
+                for (taxes, gross) in groups.into_iter().filter(|(taxes, _)| !taxes.is_empty()) {
+                    let rates: Vec<i64> = taxes.iter().map(|index| self.taxes[*index].rate.0 as i64).collect();
+                    let total_rate: u64 = rates.iter().sum::<i64>() as u64;
+                    let tax = gross.mul_ratio(total_rate, HUNDRED_PERCENT as u64 + total_rate, self.rounding)?;
+                    let net = gross.checked_sub(tax)?;
+                    let shares = if tax.minor_units() > 0 { tax.allocate(&rates)? } else { vec![Price::zero(currency); taxes.len()] };
+                    for (index, share) in taxes.into_iter().zip(shares) {
+                        lines[index] = (lines[index].0.checked_add(net)?, lines[index].1.checked_add(share)?);
+                        tax_included = tax_included.checked_add(share)?;
+                    }
+                }
+                for (tax, (taxable, amount)) in self.taxes.iter().zip(lines) {
+                    tax_lines.push(TaxLine { name: tax.name.clone(), rate: tax.rate, taxable, amount });
+                }
+            }
+        }
+
+        let prices: Vec<i64> = meal_items.iter().map(|(_, price)| price.minor_units()).collect();
+        let service_charge = match self.service_charge {
+            Some(service_charge) if guests.is_some_and(|guests| guests > service_charge.above_guests) => {
+                let charged = Price::from_minor_units(prices.iter().sum(), currency)?;
+                service_charge.rate.of(charged.checked_sub(tax_included)?, self.rounding)?
+            }
+            _ => Price::zero(currency),
+        };
+        let meal_item_service_charges = if service_charge.minor_units() > 0 {
+            service_charge.allocate(&prices)?
+        } else {
+            vec![Price::zero(currency); meal_items.len()]
+        };
+
+        Ok(Charges {
+            pricing: self.pricing,
+            tax_lines,
+            service_charge,
+            meal_item_taxes_added,
+            meal_item_service_charges,
+        })
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tax_test {
+    use crate::models::currency::Currency;
+    use crate::models::price::{Price, RoundingMode};
+    use crate::models::tax::{Rate, ServiceCharge, Tax, TaxError, TaxPolicy, TaxPricing};
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    fn rate(rate: &str) -> Rate {
+        rate.parse().unwrap()
+    }
+
+    fn tax(name: &str, rate_given: &str, categories: Option<&[&str]>) -> Tax {
+        Tax {
+            name: String::from(name),
+            rate: rate(rate_given),
+            categories: categories.map(|categories| categories.iter().map(|category| category.to_string()).collect()),
+        }
+    }
+
+    fn meal_item(category: Option<&str>, price: i64) -> (Option<String>, Price) {
+        (category.map(String::from), usd(price))
+    }
+
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(8875, rate("8.875").thousandths_of_percent());
+        assert_eq!(10500, rate(" 10.5 ").thousandths_of_percent());
+        assert_eq!(100000, rate("100").thousandths_of_percent());
+        assert_eq!(0, rate("0").thousandths_of_percent());
+        for invalid in ["", ".5", "8.8755", "-1", "100.001", "1e2", "ten"] {
+            assert_eq!(Err(TaxError::InvalidRate(invalid.to_string())), invalid.parse::<Rate>(), "{}", invalid);
+        }
+
+        assert_eq!("8.875", rate("8.875").to_string());
+        assert_eq!("10.5", rate("10.500").to_string());
+        assert_eq!("20", rate("20.0").to_string());
+    }
+
+    #[test]
+    fn test_deserialize_tax_policy() {
+        let json = r#"{
+            "pricing": "inclusive",
+            "taxes": [{"name": "VAT", "rate": "20"}, {"name": "alcohol duty", "rate": "2.5", "categories": ["drinks"]}],
+            "service_charge": {"rate": "12.5", "above_guests": 5},
+            "rounding": "half_even"
+        }"#;
+        let tax_policy: TaxPolicy = serde_json::from_str(json).unwrap();
+        assert_eq!(TaxPolicy {
+            pricing: TaxPricing::Inclusive,
+            taxes: vec![tax("VAT", "20", None), tax("alcohol duty", "2.5", Some(&["drinks"]))],
+            service_charge: Some(ServiceCharge { rate: rate("12.5"), above_guests: 5 }),
+            rounding: RoundingMode::HalfEven,
+        }, tax_policy);
+        assert_eq!(tax_policy, serde_json::from_str(&serde_json::to_string(&tax_policy).unwrap()).unwrap());
+
+        assert_eq!(TaxPolicy::default(), serde_json::from_str("{}").unwrap());
+        assert!(serde_json::from_str::<TaxPolicy>(r#"{"taxes": [{"name": "VAT", "rate": "101"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_validate_tax_policy() {
+        let policy = |taxes| TaxPolicy { taxes, ..TaxPolicy::default() };
+        assert_eq!(Ok(()), policy(vec![tax("state", "4", None), tax("city", "4.875", None)]).validate());
+        assert_eq!(Err(TaxError::EmptyName), policy(vec![tax(" ", "4", None)]).validate());
+        assert_eq!(Err(TaxError::DuplicateName(String::from("state"))), policy(vec![tax("state", "4", None), tax("state", "1", None)]).validate());
+        assert_eq!(Err(TaxError::EmptyCategory(String::from("state"))), policy(vec![tax("state", "4", Some(&["mains", ""]))]).validate());
+    }
+
+    #[test]
+    fn test_charge_exclusive_taxes() {
+        let tax_policy = TaxPolicy {
+            pricing: TaxPricing::Exclusive,
+            taxes: vec![tax("sales tax", "8.875", None), tax("drinks tax", "5", Some(&["drinks"]))],
+            service_charge: None,
+            rounding: RoundingMode::HalfUp,
+        };
+        let meal_items = [meal_item(Some("mains"), 1000), meal_item(Some("drinks"), 350), meal_item(None, 199)];
+        let charges = tax_policy.charge(&meal_items, None, Currency::Usd).unwrap();
+
+        // 8.875% of 1549 is 137.47, 5% of 350 is 17.5, each rounded half up once on the whole line
+        let lines: Vec<_> = charges.tax_lines().iter().map(|line| (line.get_name(), line.taxable(), line.amount())).collect();
+        assert_eq!(vec![(String::from("sales tax"), usd(1549), usd(137)), (String::from("drinks tax"), usd(350), usd(18))], lines);
+        assert_eq!(usd(155), charges.tax_added());
+        // Every line is shared among its meal items in proportion to their prices
+        assert_eq!(vec![usd(88), usd(49), usd(18)], charges.meal_item_taxes_added());
+        assert_eq!(usd(0), charges.service_charge());
+    }
+
+    #[test]
+    fn test_charge_inclusive_taxes() {
+        let tax_policy = TaxPolicy {
+            pricing: TaxPricing::Inclusive,
+            taxes: vec![tax("VAT", "20", None), tax("alcohol duty", "5", Some(&["drinks"]))],
+            service_charge: None,
+            rounding: RoundingMode::HalfUp,
+        };
+        let meal_items = [meal_item(Some("mains"), 1200), meal_item(Some("drinks"), 500), meal_item(Some("drinks"), 500)];
+        let charges = tax_policy.charge(&meal_items, None, Currency::Usd).unwrap();
+
+        // Mains are 1200 with 20% in it, so 1000 before tax. Drinks are 1000 with 25% in it, so 800 before tax,
+        // and the 200 of tax in them is shared 20 to 5 between the VAT and the duty.
+        let lines: Vec<_> = charges.tax_lines().iter().map(|line| (line.taxable(), line.amount())).collect();
+        assert_eq!(vec![(usd(1800), usd(360)), (usd(800), usd(40))], lines);
+        // The menu prices already include the taxes
+        assert_eq!(usd(0), charges.tax_added());
+    }
+
+    #[test]
+    fn test_charge_service_charge() {
+        let service_charge = |pricing| TaxPolicy {
+            pricing,
+            taxes: vec![tax("VAT", "10", None)],
+            service_charge: Some(ServiceCharge { rate: rate("12.5"), above_guests: 5 }),
+            rounding: RoundingMode::HalfUp,
+        };
+        let meal_items = [meal_item(None, 1100), meal_item(None, 2200)];
+
+        // Only parties of more than 5 guests are charged
+        for guests in [None, Some(2), Some(5)] {
+            assert_eq!(usd(0), service_charge(TaxPricing::Exclusive).charge(&meal_items, guests, Currency::Usd).unwrap().service_charge());
+        }
+        let charges = service_charge(TaxPricing::Exclusive).charge(&meal_items, Some(6), Currency::Usd).unwrap();
+        // 12.5% of 3300 is 412.5, rounded half up
+        assert_eq!(usd(413), charges.service_charge());
+        assert_eq!(vec![usd(138), usd(275)], charges.meal_item_service_charges());
+
+        // With inclusive pricing, the service charge is on what the meal items cost before tax, 3000
+        let charges = service_charge(TaxPricing::Inclusive).charge(&meal_items, Some(6), Currency::Usd).unwrap();
+        assert_eq!(usd(375), charges.service_charge());
+    }
+
+    #[test]
+    fn test_charge_rounding() {
+        let tax_policy = |pricing, rounding| TaxPolicy {
+            pricing,
+            taxes: vec![tax("sales tax", "12.5", None)],
+            service_charge: Some(ServiceCharge { rate: rate("12.5"), above_guests: 5 }),
+            rounding,
+        };
+        let meal_items = [meal_item(None, 1100), meal_item(None, 2200)];
+
+        // 12.5% of 3300 is 412.5, so the tax and the service charge are a tie
+        let charged = |rounding| {
+            let charges = tax_policy(TaxPricing::Exclusive, rounding).charge(&meal_items, Some(6), Currency::Usd).unwrap();
+            (charges.tax_added(), charges.service_charge())
+        };
+        assert_eq!((usd(412), usd(412)), charged(RoundingMode::Down));
+        assert_eq!((usd(413), usd(413)), charged(RoundingMode::Up));
+        assert_eq!((usd(413), usd(413)), charged(RoundingMode::HalfUp));
+        assert_eq!((usd(412), usd(412)), charged(RoundingMode::HalfEven));
+
+        // With inclusive pricing, the tax in 1.00 is 1/9 of it, 11.11..., which is rounded too
+        let meal_items = [meal_item(None, 100)];
+        let tax_included = |rounding| tax_policy(TaxPricing::Inclusive, rounding).charge(&meal_items, None, Currency::Usd).unwrap().tax_lines()[0].amount();
+        assert_eq!(usd(11), tax_included(RoundingMode::HalfUp));
+        assert_eq!(usd(12), tax_included(RoundingMode::Up));
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use uuid::Uuid;
+use crate::libraries::interrupt::Interrupt;
+use crate::models::station::Station;
+
+// A meal item being prepared: the worker preparing it, the station it is prepared at and when it should be ready
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Assignment {
+    pub worker_id: usize,
+    pub station: Station,
+    pub ready_time: DateTime<Utc>,
+}
+
+// Keeps which worker is preparing each meal item, so the kitchen can see who is cooking what.
+// Meal items are only tracked while they are being prepared.
+pub struct KitchenRepo {
+    assignments: DashMap<Uuid, Assignment>,
+    // Interrupt of the job preparing each meal item, to stop it cooking at once
+    job_interrupts: DashMap<Uuid, Arc<Interrupt>>,
+}
+
+impl KitchenRepo {
+    pub fn new() -> Self {
+        KitchenRepo {
+            assignments: DashMap::new(),
+            job_interrupts: DashMap::new(),
+        }
+    }
+
+    pub fn assign(&self, meal_item_id: Uuid, assignment: Assignment) {
+        self.assignments.insert(meal_item_id, assignment);
+    }
+
+    pub fn set_job_interrupt(&self, meal_item_id: Uuid, job_interrupt: Arc<Interrupt>) {
+        self.job_interrupts.insert(meal_item_id, job_interrupt);
+    }
+
+    // Wakes up the job preparing the meal item, ex. once a manager voids it; does nothing if no job is preparing it
+    pub fn interrupt(&self, meal_item_id: Uuid) {
+        if let Some(job_interrupt) = self.job_interrupts.get(&meal_item_id) {
+            job_interrupt.interrupt();
+        }
+    }
+
+    pub fn release(&self, meal_item_id: Uuid) {
+        self.assignments.remove(&meal_item_id);
+        self.job_interrupts.remove(&meal_item_id);
+    }
+
+    pub fn get_worker_id(&self, meal_item_id: Uuid) -> Option<usize> {
+        self.assignments.get(&meal_item_id).map(|assignment| assignment.worker_id)
+    }
+
+    // Meal items being prepared at the station, with the time each one should be ready; there are never more than workers
+    pub fn get_preparing(&self, station: Station) -> Vec<(Uuid, DateTime<Utc>)> {
+        self.assignments.iter()
+            .filter(|entry| entry.station == station)
+            .map(|entry| (*entry.key(), entry.ready_time))
+            .collect()
+    }
+
+    // Whether no worker is preparing any meal item
+    pub fn is_idle(&self) -> bool {
+        self.assignments.is_empty()
+    }
+}
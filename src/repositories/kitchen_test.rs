@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod kitchen_test {
+    use std::sync::Arc;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::libraries::interrupt::Interrupt;
+    use crate::models::station::Station;
+    use crate::repositories::kitchen::{Assignment, KitchenRepo};
+
+    #[test]
+    fn test_assign_and_release() {
+        let repo = KitchenRepo::new();
+        let meal_item_id = Uuid::new_v4();
+
+        assert_eq!(None, repo.get_worker_id(meal_item_id));
+
+        let ready_time = Utc::now();
+        repo.assign(meal_item_id, Assignment { worker_id: 1, station: Station::Grill, ready_time });
+        assert_eq!(Some(1), repo.get_worker_id(meal_item_id));
+        assert_eq!(vec![(meal_item_id, ready_time)], repo.get_preparing(Station::Grill));
+        assert!(repo.get_preparing(Station::Bar).is_empty());
+
+        repo.release(meal_item_id);
+        assert_eq!(None, repo.get_worker_id(meal_item_id));
+        assert!(repo.get_preparing(Station::Grill).is_empty());
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let repo = KitchenRepo::new();
+        let meal_item_id = Uuid::new_v4();
+        let job_interrupt = Arc::new(Interrupt::new());
+
+        // Given no job is preparing the meal item, there is nothing to interrupt
+        repo.interrupt(meal_item_id);
+
+        repo.set_job_interrupt(meal_item_id, job_interrupt.clone());
+        repo.interrupt(meal_item_id);
+        assert!(job_interrupt.is_interrupted());
+
+        // Given the meal item is released, its job is no longer kept
+        repo.release(meal_item_id);
+        assert_eq!(1, Arc::strong_count(&job_interrupt));
+    }
+}
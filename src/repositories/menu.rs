@@ -1,7 +1,11 @@
 use std::sync::{Arc, Mutex};
 use dashmap::DashMap;
 use uuid::Uuid;
-use crate::models::menu::Menu;
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::Price;
+use crate::models::station::Station;
 
 pub struct MenuRepo {
     pub menus: Arc<DashMap<Uuid, Arc<Mutex<Menu>>>>,
@@ -20,9 +24,70 @@ impl MenuRepo {
         })
     }
 
+    pub fn get_currency(&self, id: Uuid) -> Option<Currency> {
+        self.menus.get(&id).map(|menu_arc| menu_arc.lock().unwrap().get_currency())
+    }
+
+    pub fn get_all(&self) -> Vec<Arc<Mutex<Menu>>> {
+        self.menus.iter().map(|entry| entry.value().clone()).collect()
+    }
+
+    // Returns (menu items found for the given ids, ids which are unknown or retired)
+    // Ids can repeat, in which case the menu item is returned once per occurrence.
+    pub fn resolve_menu_items(&self, menu_item_ids: &[Uuid]) -> (Vec<MenuItem>, Vec<Uuid>) {
+        let mut menu_items = Vec::with_capacity(menu_item_ids.len());
+        let mut invalid_menu_item_ids = Vec::new();
+        for menu_item_id in menu_item_ids.iter() {
+            let menu_item = self.menus.iter()
+                .find_map(|entry| entry.value().lock().unwrap().get_menu_item(*menu_item_id));
+
+            match menu_item {
+                Some(menu_item) if !menu_item.is_retired() => menu_items.push(menu_item),
+                _ => {
+                    if !invalid_menu_item_ids.contains(menu_item_id) {
+                        invalid_menu_item_ids.push(*menu_item_id);
+                    }
+                }
+            }
+        }
+        (menu_items, invalid_menu_item_ids)
+    }
+
     pub fn add(&self, menu: Menu) {
         let menu_id = menu.id();
         let menu_arc = Arc::new(Mutex::new(menu));
         self.menus.insert(menu_id, menu_arc);
     }
-}
\ No newline at end of file
+
+    pub fn add_menu_items(&self, menu_id: Uuid, menu_items: Vec<MenuItem>) -> bool {
+        if let Some(menu_arc) = self.menus.get(&menu_id) {
+            let mut menu = menu_arc.lock().unwrap();
+            menu.add_menu_items(menu_items)
+        } else {
+            false
+        }
+    }
+
+    // Returns (updated, menu existed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_menu_item(&self, menu_id: Uuid, menu_item_id: Uuid, name: Option<String>, price: Option<Price>,
+                            cooking_time_profile: Option<CookingTimeProfile>, station: Option<Station>,
+                            category: Option<String>) -> (bool, bool) {
+        if let Some(menu_arc) = self.menus.get(&menu_id) {
+            let mut menu = menu_arc.lock().unwrap();
+            (menu.update_menu_item(menu_item_id, name, price, cooking_time_profile, station, category), true)
+        } else {
+            (false, false)
+        }
+    }
+
+    // Returns (retired, menu existed)
+    pub fn retire_menu_item(&self, menu_id: Uuid, menu_item_id: Uuid) -> (bool, bool) {
+        if let Some(menu_arc) = self.menus.get(&menu_id) {
+            let mut menu = menu_arc.lock().unwrap();
+            (menu.retire_menu_item(menu_item_id), true)
+        } else {
+            (false, false)
+        }
+    }
+}
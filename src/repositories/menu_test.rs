@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod menu_test {
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::menu::{Menu, MenuItem};
+    use crate::models::price::Price;
+    use crate::repositories::menu::MenuRepo;
+
+    #[test]
+    fn test_add() {
+        let repo = MenuRepo::new();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+        let menu_id = menu.id();
+
+        repo.add(menu);
+
+        if let Some(menu_arc) = repo.get(menu_id) {
+            let fetched_menu = menu_arc.lock().unwrap();
+            assert_eq!("lunch", fetched_menu.get_name());
+            assert_eq!(vec![menu_item], fetched_menu.get_menu_items());
+        } else {
+            panic!("menu not found");
+        }
+    }
+
+    #[test]
+    fn test_get_all() {
+        let repo = MenuRepo::new();
+        assert!(repo.get_all().is_empty());
+
+        repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![]));
+        repo.add(Menu::new(String::from("dinner"), Currency::Usd, vec![]));
+
+        assert_eq!(2, repo.get_all().len());
+    }
+
+    #[test]
+    fn test_add_menu_items() {
+        let repo = MenuRepo::new();
+
+        let existed = repo.add_menu_items(Uuid::new_v4(), vec![]);
+        assert!(!existed);
+
+        let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![]);
+        let menu_id = menu.id();
+        repo.add(menu);
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let existed = repo.add_menu_items(menu_id, vec![menu_item.clone()]);
+        assert!(existed);
+
+        let menu_arc = repo.get(menu_id).unwrap();
+        assert_eq!(vec![menu_item], menu_arc.lock().unwrap().get_menu_items());
+    }
+
+    #[test]
+    fn test_update_menu_item() {
+        let repo = MenuRepo::new();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+        let menu_id = menu.id();
+        repo.add(menu);
+
+        let (updated, existed) = repo.update_menu_item(Uuid::new_v4(), menu_item.id(), None, None, None, None, None);
+        assert!(!updated);
+        assert!(!existed);
+
+        let (updated, existed) = repo.update_menu_item(menu_id, menu_item.id(), Some(String::from("large fries")), None, None, None, None);
+        assert!(updated);
+        assert!(existed);
+
+        let menu_arc = repo.get(menu_id).unwrap();
+        let fetched_menu_item = menu_arc.lock().unwrap().get_menu_item(menu_item.id()).unwrap();
+        assert_eq!("large fries", fetched_menu_item.get_name());
+        assert_eq!("345", fetched_menu_item.price().to_string());
+    }
+
+    #[test]
+    fn test_retire_menu_item() {
+        let repo = MenuRepo::new();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+        let menu_id = menu.id();
+        repo.add(menu);
+
+        let (retired, existed) = repo.retire_menu_item(menu_id, Uuid::new_v4());
+        assert!(!retired);
+        assert!(existed);
+
+        let (retired, existed) = repo.retire_menu_item(menu_id, menu_item.id());
+        assert!(retired);
+        assert!(existed);
+
+        let menu_arc = repo.get(menu_id).unwrap();
+        assert!(menu_arc.lock().unwrap().get_menu_item(menu_item.id()).unwrap().is_retired());
+    }
+
+    #[test]
+    fn test_resolve_menu_items() {
+        let repo = MenuRepo::new();
+
+        let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone(), burger.clone()]);
+        let menu_id = menu.id();
+        repo.add(menu);
+        repo.retire_menu_item(menu_id, burger.id());
+
+        let (menu_items, invalid_menu_item_ids) = repo.resolve_menu_items(&[fries.id(), fries.id()]);
+        assert_eq!(vec![fries.clone(), fries.clone()], menu_items);
+        assert!(invalid_menu_item_ids.is_empty());
+
+        let unknown_menu_item_id = Uuid::new_v4();
+        let (_, invalid_menu_item_ids) = repo.resolve_menu_items(&[fries.id(), burger.id(), unknown_menu_item_id, burger.id()]);
+        assert_eq!(vec![burger.id(), unknown_menu_item_id], invalid_menu_item_ids);
+    }
+}
@@ -1,3 +1,15 @@
 pub mod order;
+pub mod order_sqlite;
+pub mod order_log;
+pub mod order_record;
 pub mod menu;
-mod order_test;
\ No newline at end of file
+pub mod kitchen;
+pub mod promotion;
+pub mod order_event;
+mod order_test;
+mod menu_test;
+mod order_sqlite_test;
+mod order_log_test;
+mod kitchen_test;
+mod promotion_test;
+mod order_event_test;
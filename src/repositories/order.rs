@@ -1,91 +1,454 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use uuid::Uuid;
 use crate::models::meal::{MealItem, MealItemStatus};
-use crate::models::order::{Order, OrderStatus};
+use crate::models::order::Order;
+use crate::models::order_event::OrderState;
+use crate::models::payment::{Payment, PaymentError};
+use crate::models::price::PriceError;
+use crate::models::promotion::AppliedPromotion;
+use crate::models::tax::TaxPolicy;
+use crate::models::split::{BillSplit, SplitError};
+use crate::repositories::order_event::OrderEventRepo;
+use crate::repositories::order_log::{OrderLog, OrderLogEntry, OrderLogOptions};
+use crate::repositories::order_record::{AppliedPromotionRecord, MealItemRecord, OrderRecord, PaymentRecord};
 
+// Storage of orders, so handlers are not tied to a specific database vendor.
+// Orders and meal items are returned for reading only; every change has to go through the repository, along with the time it was made.
+pub trait OrderRepository: Send + Sync {
+    // Adds the order as the current order of its table, unless the table already has an open order.
+    // Returns whether it was added.
+    fn add(&self, order: Order) -> Result<bool, OrderStoreError>;
+    fn get_order(&self, order_id: Uuid) -> Result<Option<Order>, OrderStoreError>;
+    // Returns the current order of the table, which is the last one added
+    fn get_order_by_table_id(&self, id: u32) -> Result<Option<Order>, OrderStoreError>;
+    // Returns every order of the table, oldest first
+    fn get_orders_by_table_id(&self, table_id: u32) -> Result<Vec<Order>, OrderStoreError>;
+    // Returns every order which still has meal items received or being prepared, in no particular order
+    fn get_active_orders(&self) -> Result<Vec<Order>, OrderStoreError>;
+    fn get_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid) -> Result<Option<MealItem>, OrderStoreError>;
+    // Returns whether the meal item existed
+    fn update_order_meal_item_status(&self, order_id: Uuid, meal_item_id: Uuid, meal_item_status: MealItemStatus, now: DateTime<Utc>) -> Result<bool, OrderStoreError>;
+    // Returns whether the meal items were added, which they can't be to a closed or canceled order, and whether the order existed,
+    // or the error if the meal items can't be priced into the order
+    fn add_order_meal_items(&self, order_id: Uuid, meal_items: Vec<MealItem>, now: DateTime<Utc>) -> Result<Result<(bool, bool), PriceError>, OrderStoreError>;
+    // Returns the meal items which could not be removed and whether the order existed
+    fn remove_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError>;
+    // Returns whether the order was removed and whether it existed
+    fn remove_order(&self, order_id: Uuid, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError>;
+    // Returns the meal item as it was voided, none if it wasn't, which only those being prepared can be, and whether it existed
+    fn void_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid, reason: String, now: DateTime<Utc>) -> Result<(Option<MealItem>, bool), OrderStoreError>;
+    // Returns the meal items which could not be served and whether the order existed
+    fn serve_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError>;
+    // Returns every order with non-removed meal items in the status, oldest first
+    fn get_orders_with_meal_items_in(&self, status: MealItemStatus) -> Result<Vec<Order>, OrderStoreError>;
+    // Returns the order with the payment added, none if it doesn't exist, or why the payment was refused
+    fn add_order_payment(&self, order_id: Uuid, payment: Payment) -> Result<Result<Option<Order>, PaymentError>, OrderStoreError>;
+    // Returns the order with its bill split, none if it doesn't exist, or why the bill can't be split that way
+    fn split_order_bill(&self, order_id: Uuid, split: BillSplit, now: DateTime<Utc>) -> Result<Result<Option<Order>, SplitError>, OrderStoreError>;
+    // Takes the promotions worked out on the order off it and charges it the taxes of the policy, with the order locked throughout,
+    // so the promotions always match the meal items it has when priced.
+    // Returns whether the promotions taken off the order or its tax policy changed, and whether it existed
+    fn set_order_pricing(&self, order_id: Uuid, promotions: Promotions, tax_policy: TaxPolicy, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError>;
+    // Makes every change made so far durable, ex. before the process stops
+    fn flush(&self) -> Result<(), OrderStoreError>;
+}
+
+// Works out the promotions taken off the order, ex. with promotion::apply_promotions
+pub type Promotions<'a> = &'a dyn Fn(&Order) -> Vec<AppliedPromotion>;
+
+// Voids the meal item of the order, see Order::void_meal_item, along with the meal item as it was voided
+pub(crate) fn void_meal_item(order: &mut Order, meal_item_id: Uuid, reason: String, now: DateTime<Utc>) -> Option<Option<MealItem>> {
+    let voided = order.void_meal_item(meal_item_id, reason, now)?;
+    Some(order.get_meal_item(meal_item_id).filter(|_| voided).map(|meal_item_arc| meal_item_arc.lock().unwrap().clone()))
+}
+
+// The orders couldn't be read or stored, ex. the database or the log failed; what went wrong is only worth logging
+#[derive(Debug)]
+pub struct OrderStoreError(pub String);
+
+impl fmt::Display for OrderStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to read or store orders: {}", self.0)
+    }
+}
+
+impl std::error::Error for OrderStoreError {}
+
+impl From<io::Error> for OrderStoreError {
+    fn from(err: io::Error) -> Self {
+        OrderStoreError(err.to_string())
+    }
+}
+
+// Appends a change to the log of the repository, if it has one, given the order as the change left it
+type Append<'a> = &'a mut dyn FnMut(&Order) -> Result<(), OrderStoreError>;
+
+// In-memory order store. Without a log, orders are lost when the process stops;
+// with one, every change is appended to it and orders are rebuilt from it on startup.
 pub struct OrderRepo {
-    pub orders: Arc<DashMap<u32, Arc<Mutex<Order>>>>,
+    pub orders: Arc<DashMap<Uuid, Arc<Mutex<Order>>>>,
+    // Ids of the orders of each table, oldest first; the last one is the current order of the table
+    table_orders: DashMap<u32, Vec<Uuid>>,
+    log: Option<Mutex<OrderLog>>,
+    // Where the changes of orders are published, if anybody follows them
+    order_event_repo: Option<Arc<OrderEventRepo>>,
 }
 
 impl OrderRepo {
-    // In practice, it's common to pass in the DB client to avoid being tied to a specific database vendor.
-    // The logic is simplified here.
     pub fn new() -> Self {
         OrderRepo {
-            orders: Arc::new(DashMap::new())
+            orders: Arc::new(DashMap::new()),
+            table_orders: DashMap::new(),
+            log: None,
+            order_event_repo: None,
         }
     }
 
-    pub fn add(&self, order: Order) {
-        let table_id = order.get_table_id();
-        let order_arc = Arc::new(Mutex::new(order));
-        self.orders.insert(table_id, order_arc);
+    // Changes replayed from the log on startup are not published again
+    pub fn with_events(mut self, order_event_repo: Arc<OrderEventRepo>) -> Self {
+        self.order_event_repo = Some(order_event_repo);
+        self
     }
 
-    pub fn get_order_by_table_id(&self, id: u32) -> Option<Arc<Mutex<Order>>> {
-        self.orders.get(&id).map(|order_arc| order_arc.clone())
+    // Rebuilds the orders from the snapshot and log kept in the directory, then compacts them into a new snapshot
+    pub fn with_log(dir: &Path, options: OrderLogOptions) -> io::Result<Self> {
+        let (mut log, snapshot, entries) = OrderLog::open(dir, options)?;
+        let mut repo = OrderRepo::new();
+        for record in snapshot {
+            let order = record.into_order().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            repo.apply_add(order);
+        }
+        for entry in entries {
+            repo.replay(entry).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+        log.snapshot(repo.records())?;
+        repo.log = Some(Mutex::new(log));
+        Ok(repo)
     }
 
-    pub fn get_order_meal_item(&self, table_id: u32, meal_item_id: Uuid) -> Option<Arc<Mutex<MealItem>>> {
-        if let Some(order_arc) = self.orders.get(&table_id) {
-            let order = order_arc.lock().unwrap();
-            return order.get_meal_item(meal_item_id);
+    fn replay(&self, entry: OrderLogEntry) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // Replayed changes are in the log already
+        let append = &mut |_: &Order| Ok(());
+        match entry {
+            OrderLogEntry::Add { order } => self.apply_add(order.into_order()?),
+            OrderLogEntry::AddMealItems { order_id, meal_items, time } => {
+                let meal_items = meal_items.into_iter()
+                    .map(MealItemRecord::into_meal_item)
+                    .collect::<Result<Vec<MealItem>, PriceError>>()?;
+                self.apply_add_order_meal_items(order_id, meal_items, time, append)??;
+            }
+            OrderLogEntry::RemoveMealItems { order_id, meal_item_ids, time } => {
+                self.apply_remove_order_meal_items(order_id, meal_item_ids, time, append)?;
+            }
+            OrderLogEntry::UpdateMealItemStatus { order_id, meal_item_id, status, time } => {
+                self.apply_update_order_meal_item_status(order_id, meal_item_id, status, time, append)?;
+            }
+            OrderLogEntry::RemoveOrder { order_id, time } => {
+                self.apply_remove_order(order_id, time, append)?;
+            }
+            OrderLogEntry::VoidMealItem { order_id, meal_item_id, reason, time } => {
+                self.apply_void_order_meal_item(order_id, meal_item_id, reason, time, append)?;
+            }
+            OrderLogEntry::ServeMealItems { order_id, meal_item_ids, time } => {
+                self.apply_serve_order_meal_items(order_id, meal_item_ids, time, append)?;
+            }
+            OrderLogEntry::AddPayment { order_id, payment } => {
+                if let Some(order_arc) = self.get_order_arc(order_id) {
+                    let currency = order_arc.lock().unwrap().get_currency();
+                    let _ = self.apply_add_order_payment(order_id, payment.into_payment(currency)?, append)?;
+                }
+            }
+            OrderLogEntry::SplitBill { order_id, split, time } => {
+                let _ = self.apply_split_order_bill(order_id, split, time, append)?;
+            }
+            OrderLogEntry::PriceOrder { order_id, promotions, tax_policy, time } => {
+                if let Some(order_arc) = self.get_order_arc(order_id) {
+                    let currency = order_arc.lock().unwrap().get_currency();
+                    let promotions = promotions.into_iter()
+                        .map(|promotion| promotion.into_applied_promotion(currency))
+                        .collect::<Result<Vec<AppliedPromotion>, PriceError>>()?;
+                    self.apply_set_order_pricing(order_id, &|_| promotions.clone(), tax_policy, time, append)?;
+                }
+            }
         }
-        None
+        Ok(())
     }
 
-    pub fn update_order_meal_item_status(&self, table_id: u32, meal_item_id: Uuid, meal_item_status: MealItemStatus) -> bool {
-        if let Some(order_arc) = self.orders.get(&table_id) {
-            let order = order_arc.lock().unwrap();
+    // Orders of each table are listed oldest first, so adding them back in turn rebuilds the current orders
+    fn records(&self) -> Vec<OrderRecord> {
+        self.table_orders.iter()
+            .flat_map(|entry| entry.value().clone())
+            .filter_map(|order_id| self.orders.get(&order_id).map(|order_arc| OrderRecord::from(&*order_arc.lock().unwrap())))
+            .collect()
+    }
+
+    fn get_order_arc(&self, order_id: Uuid) -> Option<Arc<Mutex<Order>>> {
+        self.orders.get(&order_id).map(|order_arc| order_arc.clone())
+    }
 
-            if let Some(meal_item_arc) = order.get_meal_item(meal_item_id) {
-                let mut meal_item = meal_item_arc.lock().unwrap();
-                meal_item.update_state(meal_item_status);
-                return true;
+    fn get_current_order(&self, table_id: u32) -> Option<Arc<Mutex<Order>>> {
+        let order_id = *self.table_orders.get(&table_id)?.last()?;
+        self.get_order_arc(order_id)
+    }
+
+    // Makes the change with the log locked throughout, so the log keeps the order the changes were made in.
+    // The change calls append once it took effect and before keeping it, so a change the log refused is never kept.
+    // A failed snapshot is tried again on the next change, the log still holds every change meanwhile.
+    fn write<T>(&self, entry: OrderLogEntry, change: impl FnOnce(Append) -> Result<T, OrderStoreError>) -> Result<T, OrderStoreError> {
+        self.write_with(|_| entry, change)
+    }
+
+    // Same as write for a change whose entry is only known from the order it left, ex. the promotions worked out on it
+    fn write_with<T>(&self, entry: impl FnOnce(&Order) -> OrderLogEntry, change: impl FnOnce(Append) -> Result<T, OrderStoreError>) -> Result<T, OrderStoreError> {
+        let mut log = self.log.as_ref().map(|log| log.lock().unwrap());
+        let mut entry = Some(entry);
+        let result = change(&mut |order| match (log.as_mut(), entry.take()) {
+            (Some(log), Some(entry)) => Ok(log.append(entry(order))?),
+            _ => Ok(()),
+        })?;
+        if let Some(log) = log.as_mut().filter(|log| entry.is_none() && log.is_snapshot_due()) {
+            if let Err(err) = log.snapshot(self.records()) {
+                eprintln!("Failed to snapshot orders: {}", err);
             }
-            false
-        } else {
-            false
         }
+        Ok(result)
     }
 
-    pub fn add_order_meal_items(&self, table_id: u32, meal_items: Vec<MealItem>) -> bool {
-        if let Some(order_arc) = self.orders.get(&table_id) {
-            let mut order = order_arc.lock().unwrap();
-            order.add_meal_items(meal_items);
-            true
-        } else {
-            false
+    // Makes the change on a copy of the order with it locked. If the change took effect, it is appended to the log,
+    // then the copy takes the place of the order and what the change did is published. Returns None if the order doesn't exist.
+    fn update_order<T>(&self, order_id: Uuid, now: DateTime<Utc>, change: impl FnOnce(&mut Order) -> T,
+                       took_effect: impl FnOnce(&T) -> bool, append: Append) -> Result<Option<T>, OrderStoreError> {
+        let order_arc = match self.get_order_arc(order_id) {
+            Some(order_arc) => order_arc,
+            None => return Ok(None),
+        };
+        let mut order = order_arc.lock().unwrap();
+        let mut changed_order = order.deep_clone();
+        let result = change(&mut changed_order);
+        if took_effect(&result) {
+            append(&changed_order)?;
+            if let Some(order_event_repo) = &self.order_event_repo {
+                order_event_repo.publish_between(&OrderState::of(&order), &OrderState::of(&changed_order), now);
+            }
+            *order = changed_order;
         }
+        Ok(Some(result))
+    }
+
+    // Orders read back were accepted when they were added, so they are added whatever the other orders of the table
+    fn apply_add(&self, order: Order) {
+        let mut order_ids = self.table_orders.entry(order.get_table_id()).or_default();
+        self.insert(order, &mut order_ids);
+    }
+
+    // The orders of the table are held from checking its current order until the new one is added,
+    // so two orders can't both be added to a free table
+    fn apply_add_to_free_table(&self, order: Order, append: Append) -> Result<bool, OrderStoreError> {
+        let mut order_ids = self.table_orders.entry(order.get_table_id()).or_default();
+        let is_taken = order_ids.last()
+            .and_then(|order_id| self.get_order_arc(*order_id))
+            .is_some_and(|order_arc| order_arc.lock().unwrap().is_open());
+        if is_taken {
+            return Ok(false);
+        }
+        append(&order)?;
+        self.insert(order, &mut order_ids);
+        Ok(true)
+    }
+
+    fn insert(&self, order: Order, order_ids: &mut Vec<Uuid>) {
+        // Published before the order can be changed, so its other events follow
+        if let Some(order_event_repo) = &self.order_event_repo {
+            order_event_repo.publish_added(&order);
+        }
+        order_ids.push(order.id());
+        self.orders.insert(order.id(), Arc::new(Mutex::new(order)));
+    }
+
+    fn apply_update_order_meal_item_status(&self, order_id: Uuid, meal_item_id: Uuid, meal_item_status: MealItemStatus, now: DateTime<Utc>, append: Append) -> Result<bool, OrderStoreError> {
+        let existed = self.update_order(order_id, now, |order| order.update_meal_item_status(meal_item_id, meal_item_status, now), |existed| *existed, append)?;
+        Ok(existed.unwrap_or(false))
+    }
+
+    fn apply_add_order_meal_items(&self, order_id: Uuid, meal_items: Vec<MealItem>, now: DateTime<Utc>, append: Append) -> Result<Result<(bool, bool), PriceError>, OrderStoreError> {
+        let result = self.update_order(order_id, now, |order| order.add_meal_items(meal_items, now), |result| matches!(result, Ok(true)), append)?;
+        Ok(match result {
+            Some(result) => result.map(|added| (added, true)),
+            None => Ok((false, false)),
+        })
     }
 
-    pub fn remove_order_meal_items(&self, table_id: u32, meal_item_ids: Vec<Uuid>) -> (Vec<Uuid>, bool) {
-        if let Some(order_arc) = self.orders.get(&table_id) {
-            let mut order = order_arc.lock().unwrap();
-            (order.remove_meal_items(meal_item_ids), true)
-        } else {
-            (vec![], false)
+    fn apply_remove_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>, append: Append) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        let non_removable_items = self.update_order(order_id, now, |order| order.remove_meal_items(meal_item_ids, now), |_| true, append)?;
+        Ok(match non_removable_items {
+            Some(non_removable_items) => (non_removable_items, true),
+            None => (vec![], false),
+        })
+    }
+
+    fn apply_remove_order(&self, order_id: Uuid, now: DateTime<Utc>, append: Append) -> Result<(bool, bool), OrderStoreError> {
+        let order_arc = match self.get_order_arc(order_id) {
+            Some(order_arc) => order_arc,
+            None => return Ok((false, false)),
+        };
+        let mut order = order_arc.lock().unwrap();
+        let mut removed_order = order.deep_clone();
+        if !removed_order.remove(now) {
+            return Ok((false, true));
+        }
+        append(&removed_order)?;
+        if let Some(order_event_repo) = &self.order_event_repo {
+            order_event_repo.publish_removed(&removed_order, now);
         }
+        *order = removed_order;
+        Ok((true, true))
+    }
+
+    fn apply_void_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid, reason: String, now: DateTime<Utc>, append: Append) -> Result<(Option<MealItem>, bool), OrderStoreError> {
+        let voided = self.update_order(order_id, now, |order| void_meal_item(order, meal_item_id, reason, now), |voided| matches!(voided, Some(Some(_))), append)?;
+        Ok(match voided.flatten() {
+            Some(voided) => (voided, true),
+            None => (None, false),
+        })
+    }
+
+    fn apply_add_order_payment(&self, order_id: Uuid, payment: Payment, append: Append) -> Result<Result<Option<Order>, PaymentError>, OrderStoreError> {
+        let result = self.update_order(order_id, payment.time(), |order| order.add_payment(payment).map(|_| order.clone()), |result| result.is_ok(), append)?;
+        Ok(result.transpose())
+    }
+
+    fn apply_split_order_bill(&self, order_id: Uuid, split: BillSplit, now: DateTime<Utc>, append: Append) -> Result<Result<Option<Order>, SplitError>, OrderStoreError> {
+        let result = self.update_order(order_id, now, |order| order.split_bill(split, now).map(|_| order.clone()), |result| result.is_ok(), append)?;
+        Ok(result.transpose())
+    }
+
+    fn apply_set_order_pricing(&self, order_id: Uuid, promotions: Promotions, tax_policy: TaxPolicy, now: DateTime<Utc>, append: Append) -> Result<(bool, bool), OrderStoreError> {
+        let changed = self.update_order(order_id, now, |order| order.apply_pricing(promotions(order), tax_policy, now), |changed| *changed, append)?;
+        Ok(match changed {
+            Some(changed) => (changed, true),
+            None => (false, false),
+        })
+    }
+
+    fn apply_serve_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>, append: Append) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        let non_servable_items = self.update_order(order_id, now, |order| order.serve_meal_items(meal_item_ids, now), |_| true, append)?;
+        Ok(match non_servable_items {
+            Some(non_servable_items) => (non_servable_items, true),
+            None => (vec![], false),
+        })
+    }
+}
+
+impl OrderRepository for OrderRepo {
+    fn add(&self, order: Order) -> Result<bool, OrderStoreError> {
+        let entry = OrderLogEntry::Add { order: OrderRecord::from(&order) };
+        self.write(entry, |append| self.apply_add_to_free_table(order, append))
+    }
+
+    fn get_order(&self, order_id: Uuid) -> Result<Option<Order>, OrderStoreError> {
+        Ok(self.get_order_arc(order_id).map(|order_arc| order_arc.lock().unwrap().clone()))
+    }
+
+    fn get_order_by_table_id(&self, id: u32) -> Result<Option<Order>, OrderStoreError> {
+        Ok(self.get_current_order(id).map(|order_arc| order_arc.lock().unwrap().clone()))
+    }
+
+    fn get_orders_by_table_id(&self, table_id: u32) -> Result<Vec<Order>, OrderStoreError> {
+        let order_ids = self.table_orders.get(&table_id).map(|order_ids| order_ids.clone()).unwrap_or_default();
+        Ok(order_ids.iter()
+            .filter_map(|order_id| self.get_order_arc(*order_id))
+            .map(|order_arc| order_arc.lock().unwrap().clone())
+            .collect())
+    }
+
+    fn get_active_orders(&self) -> Result<Vec<Order>, OrderStoreError> {
+        Ok(self.orders.iter()
+            .map(|entry| entry.value().lock().unwrap().clone())
+            .filter(|order| order.is_active())
+            .collect())
     }
 
-    pub fn remove_order(&self, table_id: u32) -> (bool, bool) {
-        if let Some(order_arc) = self.orders.get(&table_id) {
+    fn get_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid) -> Result<Option<MealItem>, OrderStoreError> {
+        if let Some(order_arc) = self.get_order_arc(order_id) {
             let order = order_arc.lock().unwrap();
+            return Ok(order.get_meal_item(meal_item_id).map(|meal_item_arc| meal_item_arc.lock().unwrap().clone()));
+        }
+        Ok(None)
+    }
 
-            match order.get_order_status() {
-                OrderStatus::Preparing | OrderStatus::Completed => { return (false, true); }
-                _ => {}
-            }
+    fn update_order_meal_item_status(&self, order_id: Uuid, meal_item_id: Uuid, meal_item_status: MealItemStatus, now: DateTime<Utc>) -> Result<bool, OrderStoreError> {
+        let entry = OrderLogEntry::UpdateMealItemStatus { order_id, meal_item_id, status: meal_item_status, time: now };
+        self.write(entry, |append| self.apply_update_order_meal_item_status(order_id, meal_item_id, meal_item_status, now, append))
+    }
 
-            for meal_item_arc in order.get_meal_items().iter() {
-                let mut meal_item = meal_item_arc.lock().unwrap();
-                meal_item.remove();
-            }
+    fn add_order_meal_items(&self, order_id: Uuid, meal_items: Vec<MealItem>, now: DateTime<Utc>) -> Result<Result<(bool, bool), PriceError>, OrderStoreError> {
+        let entry = OrderLogEntry::AddMealItems { order_id, meal_items: meal_items.iter().map(MealItemRecord::from).collect(), time: now };
+        self.write(entry, |append| self.apply_add_order_meal_items(order_id, meal_items, now, append))
+    }
+
+    fn remove_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        let entry = OrderLogEntry::RemoveMealItems { order_id, meal_item_ids: meal_item_ids.clone(), time: now };
+        self.write(entry, |append| self.apply_remove_order_meal_items(order_id, meal_item_ids, now, append))
+    }
+
+    fn remove_order(&self, order_id: Uuid, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError> {
+        self.write(OrderLogEntry::RemoveOrder { order_id, time: now }, |append| self.apply_remove_order(order_id, now, append))
+    }
+
+    fn void_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid, reason: String, now: DateTime<Utc>) -> Result<(Option<MealItem>, bool), OrderStoreError> {
+        let entry = OrderLogEntry::VoidMealItem { order_id, meal_item_id, reason: reason.clone(), time: now };
+        self.write(entry, |append| self.apply_void_order_meal_item(order_id, meal_item_id, reason, now, append))
+    }
 
-            (true, true)
-        } else {
-            (false, false)
+    fn serve_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        let entry = OrderLogEntry::ServeMealItems { order_id, meal_item_ids: meal_item_ids.clone(), time: now };
+        self.write(entry, |append| self.apply_serve_order_meal_items(order_id, meal_item_ids, now, append))
+    }
+
+    fn get_orders_with_meal_items_in(&self, status: MealItemStatus) -> Result<Vec<Order>, OrderStoreError> {
+        let mut orders: Vec<Order> = self.orders.iter()
+            .map(|entry| entry.value().lock().unwrap().clone())
+            .filter(|order| order.get_meal_items().iter().any(|meal_item_arc| {
+                let meal_item = meal_item_arc.lock().unwrap();
+                !meal_item.is_removed() && meal_item.get_status() == status
+            }))
+            .collect();
+        orders.sort_by_key(|order| order.creation_time());
+        Ok(orders)
+    }
+
+    fn add_order_payment(&self, order_id: Uuid, payment: Payment) -> Result<Result<Option<Order>, PaymentError>, OrderStoreError> {
+        let entry = OrderLogEntry::AddPayment { order_id, payment: PaymentRecord::from(&payment) };
+        self.write(entry, |append| self.apply_add_order_payment(order_id, payment, append))
+    }
+
+    fn split_order_bill(&self, order_id: Uuid, split: BillSplit, now: DateTime<Utc>) -> Result<Result<Option<Order>, SplitError>, OrderStoreError> {
+        let entry = OrderLogEntry::SplitBill { order_id, split: split.clone(), time: now };
+        self.write(entry, |append| self.apply_split_order_bill(order_id, split, now, append))
+    }
+
+    fn set_order_pricing(&self, order_id: Uuid, promotions: Promotions, tax_policy: TaxPolicy, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError> {
+        // The promotions are logged as they were worked out, so replaying doesn't depend on those running then
+        let entry = |order: &Order| OrderLogEntry::PriceOrder {
+            order_id,
+            promotions: order.get_promotions().iter().map(AppliedPromotionRecord::from).collect(),
+            tax_policy: order.get_tax_policy(),
+            time: now,
+        };
+        self.write_with(entry, |append| self.apply_set_order_pricing(order_id, promotions, tax_policy, now, append))
+    }
+
+    // Compacts the log into a snapshot, so the next startup has nothing to replay
+    fn flush(&self) -> Result<(), OrderStoreError> {
+        if let Some(log) = &self.log {
+            log.lock().unwrap().snapshot(self.records())?;
         }
+        Ok(())
     }
-}
\ No newline at end of file
+}
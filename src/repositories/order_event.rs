@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use crate::models::order::Order;
+use crate::models::order_event::{EventId, OrderChange, OrderEvent, OrderState};
+
+// Most recent events kept for clients resuming after the last one they got
+pub const EVENT_HISTORY: usize = 1000;
+
+struct EventLog {
+    next_event_number: u64,
+    events: VecDeque<OrderEvent>,
+    // None once closed
+    sender: Option<broadcast::Sender<OrderEvent>>,
+}
+
+// What a client following the changes of orders gets on subscribing
+pub struct Subscription {
+    // Whether events were published after the last one the client got which are no longer kept, ex. from before a restart,
+    // so it can't catch up from the events alone and must reload the orders
+    pub missed_events: bool,
+    // Events kept after the last one the client got
+    pub events: Vec<OrderEvent>,
+    // Events published from now on
+    pub receiver: broadcast::Receiver<OrderEvent>,
+}
+
+// Keeps the most recent changes of orders in memory and hands them to the clients following them.
+// Events are numbered from 1 every time the server starts, under an epoch drawn at random.
+pub struct OrderEventRepo {
+    epoch: u32,
+    log: Mutex<EventLog>,
+    capacity: usize,
+}
+
+impl OrderEventRepo {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        OrderEventRepo {
+            epoch: rand::random(),
+            log: Mutex::new(EventLog {
+                next_event_number: 1,
+                events: VecDeque::with_capacity(capacity),
+                sender: Some(sender),
+            }),
+            capacity,
+        }
+    }
+
+    pub fn publish(&self, order_id: Uuid, table_id: u32, changes: Vec<OrderChange>, now: DateTime<Utc>) {
+        let mut log = self.log.lock().unwrap();
+        for change in changes {
+            let event_id = EventId { epoch: self.epoch, number: log.next_event_number };
+            let event = OrderEvent::new(event_id, order_id, table_id, change, now);
+            log.next_event_number += 1;
+            if log.events.len() == self.capacity {
+                log.events.pop_front();
+            }
+            log.events.push_back(event.clone());
+            if let Some(sender) = &log.sender {
+                // Nobody may be following, which is fine
+                let _ = sender.send(event);
+            }
+        }
+    }
+
+    // Order repositories publish the changes they make with the order locked, so the events of an order are published
+    // in the order its changes were made
+    pub fn publish_added(&self, order: &Order) {
+        let state = OrderState::of(order);
+        self.publish(order.id(), order.get_table_id(), vec![OrderChange::added(&state)], order.creation_time());
+    }
+
+    // What a change did is told from the states of the order before and after it
+    pub fn publish_between(&self, before: &OrderState, after: &OrderState, now: DateTime<Utc>) {
+        self.publish(after.order_id(), after.table_id(), OrderChange::between(before, after), now);
+    }
+
+    // The meal items of a removed order are removed along with it, which the order removed event stands for
+    pub fn publish_removed(&self, order: &Order, now: DateTime<Utc>) {
+        self.publish(order.id(), order.get_table_id(), vec![OrderChange::OrderRemoved], now);
+    }
+
+    // Returns the events kept after the last one the client got, along with a receiver of those published from now on,
+    // so none is repeated, and whether some were missed. A client without an id only gets those published from now on.
+    // An id of another run of the server, ex. from before a restart, or one it never gave gets none either, but is told
+    // it missed some, as the client reloads the orders then. Returns none once closed.
+    pub fn subscribe(&self, last_event_id: Option<EventId>) -> Option<Subscription> {
+        let log = self.log.lock().unwrap();
+        let receiver = log.sender.as_ref()?.subscribe();
+        let oldest_event_number = log.events.front().map_or(log.next_event_number, |event| event.id().number);
+        let latest_event_number = log.next_event_number - 1;
+        let (missed_events, last_event_number) = match last_event_id {
+            Some(EventId { epoch, number }) if epoch == self.epoch && number < log.next_event_number => (number + 1 < oldest_event_number, number),
+            Some(_) => (true, latest_event_number),
+            None => (false, latest_event_number),
+        };
+        let events = log.events.iter().filter(|event| event.id().number > last_event_number).cloned().collect();
+        Some(Subscription { missed_events, events, receiver })
+    }
+
+    // Every event kept, for tests to check what was published
+    #[cfg(test)]
+    pub fn kept_events(&self) -> Vec<OrderEvent> {
+        self.log.lock().unwrap().events.iter().cloned().collect()
+    }
+
+    // Ends every subscription, so the server can stop while clients are still following orders
+    pub fn close(&self) {
+        self.log.lock().unwrap().sender = None;
+    }
+}
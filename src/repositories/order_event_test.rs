@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod order_event_test {
+    use std::sync::Arc;
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::{Order, OrderStatus};
+    use crate::models::order_event::{EventId, OrderChange, OrderEvent};
+    use crate::models::payment::{Payment, PaymentMethod};
+    use crate::models::price::Price;
+    use crate::models::split::BillSplit;
+    use crate::models::tax::{Tax, TaxPolicy};
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::repositories::order_event::{OrderEventRepo, Subscription};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn opened_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    fn meal_item(name: &str) -> MealItem {
+        let menu_item = MenuItem::new(String::from(name), Price::from_minor_units(500, Currency::Usd).unwrap());
+        MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), opened_time())
+    }
+
+    fn changes(events: &[OrderEvent]) -> Vec<OrderChange> {
+        events.iter().map(|event| event.change()).collect()
+    }
+
+    fn numbers(events: &[OrderEvent]) -> Vec<u64> {
+        events.iter().map(|event| event.id().number).collect()
+    }
+
+    // Publishes 5 events, of which the 3 most recent are kept, and returns the epoch they were published in
+    fn publish_past_history(repo: &OrderEventRepo) -> u32 {
+        for _ in 0..5 {
+            repo.publish(Uuid::new_v4(), 1, vec![OrderChange::OrderRemoved], opened_time());
+        }
+        repo.kept_events()[0].id().epoch
+    }
+
+    #[test]
+    fn test_publish_and_subscribe() {
+        let repo = OrderEventRepo::new(10);
+        let order_id = Uuid::new_v4();
+        repo.publish(order_id, 1, vec![OrderChange::OrderRemoved], opened_time());
+
+        // Given the client has no id, it gets none of the events published before it subscribed
+        let Subscription { missed_events, events, mut receiver } = repo.subscribe(None).unwrap();
+        repo.publish(order_id, 1, vec![OrderChange::OrderStatusChanged { from: OrderStatus::Received, to: OrderStatus::Canceled }], opened_time());
+
+        assert!(!missed_events);
+        assert!(events.is_empty());
+        let event = receiver.try_recv().unwrap();
+        assert_eq!((2, order_id, 1), (event.id().number, event.order_id(), event.table_id()));
+        assert_eq!(repo.kept_events()[0].id().epoch, event.id().epoch);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_subscribe_after_last_event_id() {
+        let repo = OrderEventRepo::new(3);
+        let epoch = publish_past_history(&repo);
+
+        let ids = |last_event_id| {
+            let subscription = repo.subscribe(last_event_id).unwrap();
+            (subscription.missed_events, numbers(&subscription.events))
+        };
+        // Only the 3 most recent events are kept, and a client without an id gets none of them
+        assert_eq!((false, vec![]), ids(None));
+        assert_eq!((false, vec![3, 4, 5]), ids(Some(EventId { epoch, number: 2 })));
+        assert_eq!((false, vec![5]), ids(Some(EventId { epoch, number: 4 })));
+        assert_eq!((false, vec![]), ids(Some(EventId { epoch, number: 5 })));
+        // Ids the server never gave are told to reload instead
+        assert_eq!((true, vec![]), ids(Some(EventId { epoch, number: 42 })));
+    }
+
+    #[test]
+    fn test_subscribe_after_restart() {
+        let repo = OrderEventRepo::new(3);
+        let epoch = publish_past_history(&repo);
+
+        // The number of an event of the last run may well be given again in this one
+        let subscription = repo.subscribe(Some(EventId { epoch: epoch.wrapping_add(1), number: 4 })).unwrap();
+
+        assert!(subscription.missed_events);
+        assert!(subscription.events.is_empty());
+    }
+
+    #[test]
+    fn test_event_id() {
+        let event_id = EventId { epoch: 3054127789, number: 42 };
+
+        assert_eq!("3054127789-42", event_id.to_string());
+        assert_eq!(Ok(event_id), "3054127789-42".parse::<EventId>());
+        assert!("42".parse::<EventId>().is_err());
+        assert!("abc-42".parse::<EventId>().is_err());
+    }
+
+    #[test]
+    fn test_subscribe_past_history() {
+        let repo = OrderEventRepo::new(3);
+        let epoch = publish_past_history(&repo);
+
+        // Event 2 is no longer kept
+        let subscription = repo.subscribe(Some(EventId { epoch, number: 1 })).unwrap();
+
+        assert!(subscription.missed_events);
+        assert_eq!(vec![3, 4, 5], numbers(&subscription.events));
+    }
+
+    #[test]
+    fn test_close() {
+        let repo = OrderEventRepo::new(10);
+        let mut receiver = repo.subscribe(None).unwrap().receiver;
+
+        repo.close();
+
+        assert!(repo.subscribe(None).is_none());
+        assert!(matches!(receiver.try_recv(), Err(tokio::sync::broadcast::error::TryRecvError::Closed)));
+    }
+
+    #[test]
+    fn test_order_repo_publishes_changes() {
+        let order_event_repo = Arc::new(OrderEventRepo::new(100));
+        let repo = OrderRepo::new().with_events(order_event_repo.clone());
+        let fries = meal_item("fries");
+        let burger = meal_item("burger");
+        let order = Order::new(1, Currency::Usd, vec![fries.clone()], opened_time()).unwrap();
+        let now = opened_time() + TimeDelta::minutes(1);
+
+        repo.add(order.clone()).unwrap();
+        repo.add_order_meal_items(order.id(), vec![burger.clone()], now).unwrap().unwrap();
+        assert!(repo.update_order_meal_item_status(order.id(), fries.id(), MealItemStatus::Preparing, now).unwrap());
+        // Nothing changed, so nothing is published
+        assert!(repo.update_order_meal_item_status(order.id(), fries.id(), MealItemStatus::Preparing, now).unwrap());
+        repo.remove_order_meal_items(order.id(), vec![burger.id()], now).unwrap();
+
+        let events = order_event_repo.kept_events();
+        let expected = vec![
+            OrderChange::OrderAdded { meal_item_ids: vec![fries.id()], status: OrderStatus::Received },
+            OrderChange::MealItemsAdded { meal_item_ids: vec![burger.id()] },
+            OrderChange::MealItemStatusChanged { meal_item_id: fries.id(), from: MealItemStatus::Received, to: MealItemStatus::Preparing },
+            OrderChange::OrderStatusChanged { from: OrderStatus::Received, to: OrderStatus::Preparing },
+            OrderChange::MealItemsRemoved { meal_item_ids: vec![burger.id()] },
+        ];
+        assert_eq!(expected, changes(&events));
+        assert!(events.iter().all(|event| (event.order_id(), event.table_id()) == (order.id(), 1)));
+        assert_eq!(now, events.last().unwrap().time());
+    }
+
+    #[test]
+    fn test_order_repo_publishes_removed_order() {
+        let order_event_repo = Arc::new(OrderEventRepo::new(100));
+        let repo = OrderRepo::new().with_events(order_event_repo.clone());
+        let order = Order::new(2, Currency::Usd, vec![meal_item("fries")], opened_time()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        assert_eq!((true, true), repo.remove_order(order.id(), opened_time()).unwrap());
+        assert_eq!((false, false), repo.remove_order(Uuid::new_v4(), opened_time()).unwrap());
+
+        let events = order_event_repo.kept_events();
+        assert_eq!(vec![OrderChange::OrderRemoved], changes(&events[1..]));
+        assert_eq!(2, events[1].table_id());
+    }
+
+    #[test]
+    fn test_order_repo_publishes_bill_changes() {
+        let order_event_repo = Arc::new(OrderEventRepo::new(100));
+        let repo = OrderRepo::new().with_events(order_event_repo.clone());
+        let order = Order::new(2, Currency::Usd, vec![meal_item("fries")], opened_time()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "10".parse().unwrap(), categories: None }],
+            ..TaxPolicy::default()
+        };
+        assert_eq!((true, true), repo.set_order_pricing(order.id(), &|_| vec![], tax_policy, opened_time()).unwrap());
+        repo.split_order_bill(order.id(), BillSplit::Evenly { guests: 2 }, opened_time()).unwrap().unwrap();
+        let payments = [275, 275].map(|minor_units| {
+            Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(minor_units, Currency::Usd).unwrap(), opened_time())
+        });
+        for (payment, check) in payments.iter().zip(["guest-1", "guest-2"]) {
+            repo.add_order_payment(order.id(), payment.clone().with_check(Some(String::from(check)))).unwrap().unwrap();
+        }
+
+        let events = order_event_repo.kept_events();
+        let expected = vec![
+            OrderChange::OrderPriced,
+            OrderChange::BillSplit,
+            OrderChange::PaymentAdded { payment_id: payments[0].id() },
+            OrderChange::PaymentAdded { payment_id: payments[1].id() },
+            OrderChange::OrderClosed,
+        ];
+        assert_eq!(expected, changes(&events[1..]));
+    }
+}
@@ -0,0 +1,195 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::meal::MealItemStatus;
+use crate::models::split::BillSplit;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::order_record::{AppliedPromotionRecord, MealItemRecord, OrderRecord, PaymentRecord};
+
+const LOG_FILE_NAME: &str = "orders.log";
+const SNAPSHOT_FILE_NAME: &str = "orders.snapshot";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FsyncPolicy {
+    // Every entry is synced before the change is answered, so a crash loses nothing
+    Always,
+    // Entries are synced once the interval has passed since the last sync, so a crash loses at most the interval
+    Interval(Duration),
+    // Syncing is left to the operating system
+    Never,
+}
+
+impl FromStr for FsyncPolicy {
+    type Err = String;
+
+    // Accepts "always", "never", or the interval in milliseconds, ex. "1000"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(FsyncPolicy::Always),
+            "never" => Ok(FsyncPolicy::Never),
+            _ => s.parse::<u64>()
+                .map(|millis| FsyncPolicy::Interval(Duration::from_millis(millis)))
+                .map_err(|_| format!("invalid fsync policy '{}'", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct OrderLogOptions {
+    pub fsync_policy: FsyncPolicy,
+    // Number of entries appended before the log is compacted into a snapshot
+    pub snapshot_every: usize,
+}
+
+impl Default for OrderLogOptions {
+    fn default() -> Self {
+        OrderLogOptions {
+            fsync_policy: FsyncPolicy::Always,
+            snapshot_every: 1000,
+        }
+    }
+}
+
+// Every change made to the orders, in the order it was applied, along with the time it was made so replaying it keeps the same update times.
+// Entries logged before the time was recorded are replayed at the time they are read.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum OrderLogEntry {
+    Add { order: OrderRecord },
+    AddMealItems { order_id: Uuid, meal_items: Vec<MealItemRecord>, #[serde(default = "Utc::now")] time: DateTime<Utc> },
+    RemoveMealItems { order_id: Uuid, meal_item_ids: Vec<Uuid>, #[serde(default = "Utc::now")] time: DateTime<Utc> },
+    UpdateMealItemStatus { order_id: Uuid, meal_item_id: Uuid, status: MealItemStatus, #[serde(default = "Utc::now")] time: DateTime<Utc> },
+    RemoveOrder { order_id: Uuid, #[serde(default = "Utc::now")] time: DateTime<Utc> },
+    VoidMealItem { order_id: Uuid, meal_item_id: Uuid, reason: String, time: DateTime<Utc> },
+    ServeMealItems { order_id: Uuid, meal_item_ids: Vec<Uuid>, time: DateTime<Utc> },
+    AddPayment { order_id: Uuid, payment: PaymentRecord },
+    SplitBill { order_id: Uuid, split: BillSplit, time: DateTime<Utc> },
+    PriceOrder { order_id: Uuid, promotions: Vec<AppliedPromotionRecord>, #[serde(default)] tax_policy: TaxPolicy, time: DateTime<Utc> },
+}
+
+#[derive(Serialize, Deserialize)]
+struct LogLine {
+    seq: u64,
+    entry: OrderLogEntry,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Snapshot {
+    // Sequence number of the last entry the snapshot includes, entries up to it are skipped on replay
+    last_seq: u64,
+    orders: Vec<OrderRecord>,
+}
+
+// Write-ahead log of order changes, one JSON entry per line, compacted into a snapshot of every order.
+// Orders are rebuilt by loading the snapshot and replaying the entries logged after it.
+pub struct OrderLog {
+    snapshot_path: PathBuf,
+    file: File,
+    options: OrderLogOptions,
+    seq: u64,
+    entries_since_snapshot: usize,
+    last_sync: Instant,
+}
+
+impl OrderLog {
+    // Returns the log along with the orders of the last snapshot and the entries to replay on top of them
+    pub fn open(dir: &Path, options: OrderLogOptions) -> io::Result<(Self, Vec<OrderRecord>, Vec<OrderLogEntry>)> {
+        fs::create_dir_all(dir)?;
+        let log_path = dir.join(LOG_FILE_NAME);
+        let snapshot_path = dir.join(SNAPSHOT_FILE_NAME);
+
+        let snapshot: Snapshot = match File::open(&snapshot_path) {
+            Ok(file) => serde_json::from_reader(BufReader::new(file)).map_err(invalid_data)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Snapshot::default(),
+            Err(err) => return Err(err),
+        };
+
+        let mut seq = snapshot.last_seq;
+        let mut entries = Vec::new();
+        if let Ok(file) = File::open(&log_path) {
+            for line in BufReader::new(file).lines() {
+                // A line cut short by a crash ends the log, the change it held was never answered
+                let log_line: LogLine = match serde_json::from_str(&line?) {
+                    Ok(log_line) => log_line,
+                    Err(err) => {
+                        eprintln!("Ignoring the rest of the order log after an unreadable entry: {}", err);
+                        break;
+                    }
+                };
+                if log_line.seq > seq {
+                    seq = log_line.seq;
+                    entries.push(log_line.entry);
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&log_path)?;
+        let log = OrderLog {
+            snapshot_path,
+            file,
+            options,
+            seq,
+            entries_since_snapshot: entries.len(),
+            last_sync: Instant::now(),
+        };
+        Ok((log, snapshot.orders, entries))
+    }
+
+    pub fn append(&mut self, entry: OrderLogEntry) -> io::Result<()> {
+        let log_line = LogLine {
+            seq: self.seq + 1,
+            entry,
+        };
+        let mut line = serde_json::to_vec(&log_line).map_err(invalid_data)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.seq += 1;
+        self.entries_since_snapshot += 1;
+
+        match self.options.fsync_policy {
+            FsyncPolicy::Always => self.sync()?,
+            FsyncPolicy::Interval(interval) if self.last_sync.elapsed() >= interval => self.sync()?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn is_snapshot_due(&self) -> bool {
+        self.entries_since_snapshot >= self.options.snapshot_every
+    }
+
+    // Replaces the snapshot with the given orders, which have to include every entry appended so far, and empties the log
+    pub fn snapshot(&mut self, orders: Vec<OrderRecord>) -> io::Result<()> {
+        let snapshot = Snapshot {
+            last_seq: self.seq,
+            orders,
+        };
+
+        // The snapshot is swapped in by renaming, so a crash leaves either the previous or the new one
+        let tmp_path = self.snapshot_path.with_extension("tmp");
+        let mut tmp_file = File::create(&tmp_path)?;
+        serde_json::to_writer(&mut tmp_file, &snapshot).map_err(invalid_data)?;
+        tmp_file.sync_all()?;
+        fs::rename(&tmp_path, &self.snapshot_path)?;
+
+        self.file.set_len(0)?;
+        self.sync()?;
+        self.entries_since_snapshot = 0;
+        Ok(())
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod order_log_test {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::models::meal::MealItemStatus;
+    use crate::repositories::order_log::{FsyncPolicy, OrderLog, OrderLogEntry, OrderLogOptions};
+
+    // Log directory which is deleted once the test is done
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            TempDir(std::env::temp_dir().join(format!("order-log-{}", Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_fsync_policy() {
+        assert_eq!(Ok(FsyncPolicy::Always), "always".parse());
+        assert_eq!(Ok(FsyncPolicy::Never), "never".parse());
+        assert_eq!(Ok(FsyncPolicy::Interval(Duration::from_millis(500))), "500".parse());
+        assert!("sometimes".parse::<FsyncPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_append_and_reopen() {
+        let dir = TempDir::new();
+        let entries = vec![
+            OrderLogEntry::RemoveOrder { order_id: Uuid::new_v4(), time: Utc::now() },
+            OrderLogEntry::UpdateMealItemStatus { order_id: Uuid::new_v4(), meal_item_id: Uuid::new_v4(), status: MealItemStatus::Preparing, time: Utc::now() },
+        ];
+
+        let (mut log, snapshot, replayed) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        assert!(snapshot.is_empty());
+        assert!(replayed.is_empty());
+        for entry in entries.iter() {
+            log.append(entry.clone()).unwrap();
+        }
+        drop(log);
+
+        let (_, _, replayed) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        assert_eq!(entries, replayed);
+    }
+
+    #[test]
+    fn test_snapshot_empties_log() {
+        let dir = TempDir::new();
+        let options = OrderLogOptions { fsync_policy: FsyncPolicy::Never, snapshot_every: 2 };
+
+        let (mut log, _, _) = OrderLog::open(&dir.0, options).unwrap();
+        log.append(OrderLogEntry::RemoveOrder { order_id: Uuid::new_v4(), time: Utc::now() }).unwrap();
+        assert!(!log.is_snapshot_due());
+        log.append(OrderLogEntry::RemoveOrder { order_id: Uuid::new_v4(), time: Utc::now() }).unwrap();
+        assert!(log.is_snapshot_due());
+
+        log.snapshot(vec![]).unwrap();
+        assert!(!log.is_snapshot_due());
+        let entry = OrderLogEntry::RemoveOrder { order_id: Uuid::new_v4(), time: Utc::now() };
+        log.append(entry.clone()).unwrap();
+        drop(log);
+
+        let (_, _, replayed) = OrderLog::open(&dir.0, options).unwrap();
+        assert_eq!(vec![entry], replayed);
+    }
+
+    #[test]
+    fn test_torn_entry_ends_log() {
+        let dir = TempDir::new();
+
+        let entry = OrderLogEntry::RemoveOrder { order_id: Uuid::new_v4(), time: Utc::now() };
+        let (mut log, _, _) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        log.append(entry.clone()).unwrap();
+        drop(log);
+
+        // Given the process crashed while appending, the half written entry should be ignored
+        let mut file = OpenOptions::new().append(true).open(dir.0.join("orders.log")).unwrap();
+        file.write_all(b"{\"seq\":2,\"entry\":{\"type\":\"Remo").unwrap();
+
+        let (_, _, replayed) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        assert_eq!(vec![entry], replayed);
+    }
+
+    #[test]
+    fn test_entries_in_snapshot_are_skipped() {
+        let dir = TempDir::new();
+
+        let order_id = Uuid::new_v4();
+        let (mut log, _, _) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        log.append(OrderLogEntry::RemoveOrder { order_id, time: Utc::now() }).unwrap();
+        log.snapshot(vec![]).unwrap();
+        drop(log);
+
+        // Given the process crashed after saving the snapshot but before emptying the log, the entry should not be replayed twice
+        let mut file = OpenOptions::new().append(true).open(dir.0.join("orders.log")).unwrap();
+        writeln!(file, "{{\"seq\":1,\"entry\":{{\"type\":\"RemoveOrder\",\"order_id\":\"{}\"}}}}", order_id).unwrap();
+
+        let (_, _, replayed) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        assert!(replayed.is_empty());
+    }
+
+    #[test]
+    fn test_entry_logged_without_time() {
+        let dir = TempDir::new();
+        fs::create_dir_all(&dir.0).unwrap();
+
+        // Given the entry was logged before its time was recorded, it should be replayed at the time it is read
+        let order_id = Uuid::new_v4();
+        let mut file = OpenOptions::new().create(true).append(true).open(dir.0.join("orders.log")).unwrap();
+        writeln!(file, "{{\"seq\":1,\"entry\":{{\"type\":\"RemoveOrder\",\"order_id\":\"{}\"}}}}", order_id).unwrap();
+
+        let before = Utc::now();
+        let (_, _, replayed) = OrderLog::open(&dir.0, OrderLogOptions::default()).unwrap();
+        match replayed.as_slice() {
+            [OrderLogEntry::RemoveOrder { order_id: replayed_order_id, time }] => {
+                assert_eq!(order_id, *replayed_order_id);
+                assert!(*time >= before);
+            }
+            _ => panic!("unexpected entries {:?}", replayed),
+        }
+    }
+}
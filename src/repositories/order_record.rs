@@ -0,0 +1,203 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::order::{Order, OrderPriority};
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::price::{Price, PriceError};
+use crate::models::promotion::AppliedPromotion;
+use crate::models::split::BillSplit;
+use crate::models::station::Station;
+use crate::models::tax::TaxPolicy;
+
+// Stored form of an order, kept apart from the domain model so what is written to disk only changes on purpose.
+// Fields added since the first release default when missing, so orders and log entries written before them still read,
+// ex. as normal priority, unpaid, undiscounted and charged no tax, with their meal items cooked at the grill.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderRecord {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    #[serde(default)]
+    pub priority: OrderPriority,
+    #[serde(default)]
+    pub guests: Option<u32>,
+    pub currency: Currency,
+    pub meal_items: Vec<MealItemRecord>,
+    #[serde(default)]
+    pub payments: Vec<PaymentRecord>,
+    #[serde(default)]
+    pub promotions: Vec<AppliedPromotionRecord>,
+    #[serde(default)]
+    pub tax_policy: TaxPolicy,
+    #[serde(default)]
+    pub split: Option<BillSplit>,
+    #[serde(default)]
+    pub closed_time: Option<DateTime<Utc>>,
+    pub creation_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MealItemRecord {
+    pub meal_item_id: Uuid,
+    pub menu_item_id: Uuid,
+    pub name: String,
+    pub price: i64,
+    pub currency: Currency,
+    pub cooking_time_in_min: u32,
+    #[serde(default)]
+    pub station: Station,
+    #[serde(default)]
+    pub category: Option<String>,
+    pub is_removed: bool,
+    pub status: MealItemStatus,
+    #[serde(default)]
+    pub void_reason: Option<String>,
+    #[serde(default)]
+    pub ready_time: Option<DateTime<Utc>>,
+    pub creation_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
+}
+
+impl From<&Order> for OrderRecord {
+    fn from(order: &Order) -> Self {
+        OrderRecord {
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            priority: order.get_priority(),
+            guests: order.get_guests(),
+            currency: order.get_currency(),
+            meal_items: order.get_meal_items().iter()
+                .map(|meal_item_arc| MealItemRecord::from(&*meal_item_arc.lock().unwrap()))
+                .collect(),
+            payments: order.get_payments().iter().map(PaymentRecord::from).collect(),
+            promotions: order.get_promotions().iter().map(AppliedPromotionRecord::from).collect(),
+            tax_policy: order.get_tax_policy(),
+            split: order.get_split(),
+            closed_time: order.closed_time(),
+            creation_time: order.creation_time(),
+            update_time: order.update_time(),
+        }
+    }
+}
+
+impl OrderRecord {
+    pub fn into_order(self) -> Result<Order, PriceError> {
+        let meal_items = self.meal_items.into_iter()
+            .map(MealItemRecord::into_meal_item)
+            .collect::<Result<Vec<MealItem>, PriceError>>()?;
+        let payments = self.payments.into_iter()
+            .map(|payment| payment.into_payment(self.currency))
+            .collect::<Result<Vec<Payment>, PriceError>>()?;
+        let promotions = self.promotions.into_iter()
+            .map(|promotion| promotion.into_applied_promotion(self.currency))
+            .collect::<Result<Vec<AppliedPromotion>, PriceError>>()?;
+        Ok(Order::restore(self.order_id, self.table_id, self.priority, self.currency, meal_items, self.creation_time, self.update_time)?
+            .with_guests(self.guests)
+            .with_payments(payments, self.closed_time)
+            .with_promotions(promotions)
+            .with_tax_policy(self.tax_policy)
+            .with_split(self.split))
+    }
+}
+
+impl From<&MealItem> for MealItemRecord {
+    fn from(meal_item: &MealItem) -> Self {
+        let menu_item = meal_item.get_menu_item();
+        MealItemRecord {
+            meal_item_id: meal_item.id(),
+            menu_item_id: menu_item.id(),
+            name: menu_item.get_name(),
+            price: menu_item.price().minor_units(),
+            currency: menu_item.price().currency(),
+            cooking_time_in_min: meal_item.cooking_time_in_min(),
+            station: meal_item.station(),
+            category: meal_item.category(),
+            is_removed: meal_item.is_removed(),
+            status: meal_item.get_status(),
+            void_reason: meal_item.void_reason(),
+            ready_time: meal_item.ready_time(),
+            creation_time: meal_item.creation_time(),
+            update_time: meal_item.update_time(),
+        }
+    }
+}
+
+impl MealItemRecord {
+    pub fn into_meal_item(self) -> Result<MealItem, PriceError> {
+        let price = Price::from_minor_units(self.price, self.currency)?;
+        let menu_item = MenuItem::restore(self.menu_item_id, self.name, price)
+            .with_station(self.station)
+            .with_category(self.category);
+        Ok(MealItem::restore(self.meal_item_id, menu_item, self.cooking_time_in_min, self.is_removed,
+                             self.status, self.creation_time, self.update_time)
+            .with_void_reason(self.void_reason)
+            .with_ready_time(self.ready_time))
+    }
+}
+
+// Stored form of a payment, its amount is in the currency of its order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PaymentRecord {
+    pub payment_id: Uuid,
+    pub method: PaymentMethod,
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub check: Option<String>,
+    pub amount: i64,
+    pub time: DateTime<Utc>,
+}
+
+impl From<&Payment> for PaymentRecord {
+    fn from(payment: &Payment) -> Self {
+        PaymentRecord {
+            payment_id: payment.id(),
+            method: payment.method(),
+            reference: payment.reference(),
+            check: payment.check(),
+            amount: payment.amount().minor_units(),
+            time: payment.time(),
+        }
+    }
+}
+
+impl PaymentRecord {
+    pub fn into_payment(self, currency: Currency) -> Result<Payment, PriceError> {
+        let amount = Price::from_minor_units(self.amount, currency)?;
+        Ok(Payment::restore(self.payment_id, self.method, self.reference, amount, self.time).with_check(self.check))
+    }
+}
+
+// Stored form of a promotion taken off an order, its amounts are in the currency of its order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AppliedPromotionRecord {
+    pub promotion_id: Uuid,
+    pub name: String,
+    pub saved: i64,
+    pub meal_item_savings: Vec<(Uuid, i64)>,
+}
+
+impl From<&AppliedPromotion> for AppliedPromotionRecord {
+    fn from(promotion: &AppliedPromotion) -> Self {
+        AppliedPromotionRecord {
+            promotion_id: promotion.promotion_id(),
+            name: promotion.get_name(),
+            saved: promotion.saved().minor_units(),
+            meal_item_savings: promotion.meal_item_savings().into_iter()
+                .map(|(meal_item_id, saving)| (meal_item_id, saving.minor_units()))
+                .collect(),
+        }
+    }
+}
+
+impl AppliedPromotionRecord {
+    pub fn into_applied_promotion(self, currency: Currency) -> Result<AppliedPromotion, PriceError> {
+        let saved = Price::from_minor_units(self.saved, currency)?;
+        let meal_item_savings = self.meal_item_savings.into_iter()
+            .map(|(meal_item_id, saving)| Ok((meal_item_id, Price::from_minor_units(saving, currency)?)))
+            .collect::<Result<Vec<(Uuid, Price)>, PriceError>>()?;
+        Ok(AppliedPromotion::new(self.promotion_id, self.name, saved, meal_item_savings))
+    }
+}
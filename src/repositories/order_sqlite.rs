@@ -0,0 +1,459 @@
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use rusqlite::types::Type;
+use uuid::Uuid;
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::order::{Order, OrderPriority};
+use crate::models::order_event::OrderState;
+use crate::models::payment::{Payment, PaymentError, PaymentMethod};
+use crate::models::price::{Price, PriceError};
+use crate::models::promotion::AppliedPromotion;
+use crate::models::split::{BillSplit, SplitError};
+use crate::models::station::Station;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::order::{void_meal_item, OrderRepository, OrderStoreError, Promotions};
+use crate::repositories::order_event::OrderEventRepo;
+use crate::repositories::order_record::AppliedPromotionRecord;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS orders (
+        order_id BLOB PRIMARY KEY,
+        table_id INTEGER NOT NULL,
+        priority TEXT NOT NULL DEFAULT 'Normal',
+        guests INTEGER,
+        currency TEXT NOT NULL,
+        bill_split TEXT,
+        promotions TEXT,
+        tax_policy TEXT,
+        closed_time TEXT,
+        creation_time TEXT NOT NULL,
+        update_time TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS orders_table_id ON orders (table_id);
+    CREATE TABLE IF NOT EXISTS meal_items (
+        meal_item_id BLOB PRIMARY KEY,
+        order_id BLOB NOT NULL REFERENCES orders (order_id),
+        menu_item_id BLOB NOT NULL,
+        name TEXT NOT NULL,
+        price INTEGER NOT NULL,
+        cooking_time_in_min INTEGER NOT NULL,
+        station TEXT NOT NULL DEFAULT 'grill',
+        category TEXT,
+        void_reason TEXT,
+        ready_time TEXT,
+        is_removed INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        creation_time TEXT NOT NULL,
+        update_time TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS meal_items_order_id ON meal_items (order_id);
+    CREATE TABLE IF NOT EXISTS payments (
+        payment_id BLOB PRIMARY KEY,
+        order_id BLOB NOT NULL REFERENCES orders (order_id),
+        method TEXT NOT NULL,
+        reference TEXT,
+        check_name TEXT,
+        amount INTEGER NOT NULL,
+        time TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS payments_order_id ON payments (order_id);
+";
+
+// Order store backed by a local SQLite file, so orders survive a restart.
+// Every change loads the order, applies it through the domain model and writes it back within one transaction.
+pub struct SqliteOrderRepo {
+    conn: Mutex<Connection>,
+    // Where the changes of orders are published, if anybody follows them
+    order_event_repo: Option<Arc<OrderEventRepo>>,
+}
+
+impl SqliteOrderRepo {
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        add_column_if_missing(&conn, "orders", "priority", "TEXT NOT NULL DEFAULT 'Normal'")?;
+        add_column_if_missing(&conn, "orders", "closed_time", "TEXT")?;
+        add_column_if_missing(&conn, "orders", "bill_split", "TEXT")?;
+        add_column_if_missing(&conn, "orders", "promotions", "TEXT")?;
+        add_column_if_missing(&conn, "orders", "guests", "INTEGER")?;
+        add_column_if_missing(&conn, "orders", "tax_policy", "TEXT")?;
+        add_column_if_missing(&conn, "payments", "check_name", "TEXT")?;
+        add_column_if_missing(&conn, "meal_items", "station", "TEXT NOT NULL DEFAULT 'grill'")?;
+        add_column_if_missing(&conn, "meal_items", "void_reason", "TEXT")?;
+        add_column_if_missing(&conn, "meal_items", "ready_time", "TEXT")?;
+        add_column_if_missing(&conn, "meal_items", "category", "TEXT")?;
+        // Meal items completed before pickups were tracked wait on the pass, so they are found by their status
+        conn.execute("UPDATE meal_items SET status = 'ReadyForPickup' WHERE status = 'Completed'", [])?;
+        Ok(SqliteOrderRepo {
+            conn: Mutex::new(conn),
+            order_event_repo: None,
+        })
+    }
+
+    pub fn with_events(mut self, order_event_repo: Arc<OrderEventRepo>) -> Self {
+        self.order_event_repo = Some(order_event_repo);
+        self
+    }
+
+    fn transaction<T>(&self, f: impl FnOnce(&Transaction) -> rusqlite::Result<T>) -> Result<T, OrderStoreError> {
+        let mut conn = self.conn.lock().unwrap();
+        in_transaction(&mut conn, f)
+    }
+
+    // Applies the change to the order and saves it, returns None if the order doesn't exist.
+    // What it did is published once committed, before the next change can be made.
+    fn update_order<T>(&self, order_id: Uuid, now: DateTime<Utc>, f: impl FnOnce(&mut Order) -> T) -> Result<Option<T>, OrderStoreError> {
+        let mut conn = self.conn.lock().unwrap();
+        let (result, states) = in_transaction(&mut conn, |tx| {
+            match load_order(tx, order_id)? {
+                Some(mut order) => {
+                    let before = self.order_event_repo.as_ref().map(|_| OrderState::of(&order));
+                    let result = f(&mut order);
+                    save_order(tx, &order)?;
+                    Ok((Some(result), before.map(|before| (before, OrderState::of(&order)))))
+                }
+                None => Ok((None, None)),
+            }
+        })?;
+        if let (Some(order_event_repo), Some((before, after))) = (&self.order_event_repo, states) {
+            order_event_repo.publish_between(&before, &after, now);
+        }
+        Ok(result)
+    }
+}
+
+impl OrderRepository for SqliteOrderRepo {
+    fn add(&self, order: Order) -> Result<bool, OrderStoreError> {
+        let mut conn = self.conn.lock().unwrap();
+        let added = in_transaction(&mut conn, |tx| {
+            let current_order = match current_order_id(tx, order.get_table_id())? {
+                Some(order_id) => load_order(tx, order_id)?,
+                None => None,
+            };
+            if current_order.is_some_and(|current_order| current_order.is_open()) {
+                return Ok(false);
+            }
+            save_order(tx, &order)?;
+            Ok(true)
+        })?;
+        if let Some(order_event_repo) = self.order_event_repo.as_ref().filter(|_| added) {
+            order_event_repo.publish_added(&order);
+        }
+        Ok(added)
+    }
+
+    fn get_order(&self, order_id: Uuid) -> Result<Option<Order>, OrderStoreError> {
+        self.transaction(|tx| load_order(tx, order_id))
+    }
+
+    fn get_order_by_table_id(&self, id: u32) -> Result<Option<Order>, OrderStoreError> {
+        self.transaction(|tx| {
+            match current_order_id(tx, id)? {
+                Some(order_id) => load_order(tx, order_id),
+                None => Ok(None),
+            }
+        })
+    }
+
+    fn get_orders_by_table_id(&self, table_id: u32) -> Result<Vec<Order>, OrderStoreError> {
+        self.transaction(|tx| {
+            let order_ids = tx.prepare("SELECT order_id FROM orders WHERE table_id = ?1 ORDER BY rowid")?
+                .query_map(params![table_id], |row| row.get::<_, Uuid>(0))?
+                .collect::<rusqlite::Result<Vec<Uuid>>>()?;
+            let mut orders = Vec::new();
+            for order_id in order_ids {
+                orders.extend(load_order(tx, order_id)?);
+            }
+            Ok(orders)
+        })
+    }
+
+    fn get_active_orders(&self) -> Result<Vec<Order>, OrderStoreError> {
+        self.transaction(|tx| {
+            let order_ids = tx.prepare(
+                "SELECT DISTINCT order_id FROM meal_items WHERE is_removed = 0 AND status IN ('Received', 'Preparing')",
+            )?
+                .query_map([], |row| row.get::<_, Uuid>(0))?
+                .collect::<rusqlite::Result<Vec<Uuid>>>()?;
+            let mut orders = Vec::new();
+            for order_id in order_ids {
+                orders.extend(load_order(tx, order_id)?);
+            }
+            Ok(orders)
+        })
+    }
+
+    fn get_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid) -> Result<Option<MealItem>, OrderStoreError> {
+        Ok(self.get_order(order_id)?
+            .and_then(|order| order.get_meal_item(meal_item_id))
+            .map(|meal_item_arc| meal_item_arc.lock().unwrap().clone()))
+    }
+
+    fn update_order_meal_item_status(&self, order_id: Uuid, meal_item_id: Uuid, meal_item_status: MealItemStatus, now: DateTime<Utc>) -> Result<bool, OrderStoreError> {
+        Ok(self.update_order(order_id, now, |order| order.update_meal_item_status(meal_item_id, meal_item_status, now))?
+            .unwrap_or(false))
+    }
+
+    fn add_order_meal_items(&self, order_id: Uuid, meal_items: Vec<MealItem>, now: DateTime<Utc>) -> Result<Result<(bool, bool), PriceError>, OrderStoreError> {
+        Ok(match self.update_order(order_id, now, |order| order.add_meal_items(meal_items, now))? {
+            Some(result) => result.map(|added| (added, true)),
+            None => Ok((false, false)),
+        })
+    }
+
+    fn remove_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        Ok(match self.update_order(order_id, now, |order| order.remove_meal_items(meal_item_ids, now))? {
+            Some(non_removable_items) => (non_removable_items, true),
+            None => (vec![], false),
+        })
+    }
+
+    fn remove_order(&self, order_id: Uuid, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError> {
+        let mut conn = self.conn.lock().unwrap();
+        let order = in_transaction(&mut conn, |tx| {
+            match load_order(tx, order_id)? {
+                Some(mut order) => {
+                    let removed = order.remove(now);
+                    save_order(tx, &order)?;
+                    Ok(Some((order, removed)))
+                }
+                None => Ok(None),
+            }
+        })?;
+        Ok(match order {
+            Some((order, removed)) => {
+                if let Some(order_event_repo) = self.order_event_repo.as_ref().filter(|_| removed) {
+                    order_event_repo.publish_removed(&order, now);
+                }
+                (removed, true)
+            }
+            None => (false, false),
+        })
+    }
+
+    fn void_order_meal_item(&self, order_id: Uuid, meal_item_id: Uuid, reason: String, now: DateTime<Utc>) -> Result<(Option<MealItem>, bool), OrderStoreError> {
+        Ok(match self.update_order(order_id, now, |order| void_meal_item(order, meal_item_id, reason, now))?.flatten() {
+            Some(voided) => (voided, true),
+            None => (None, false),
+        })
+    }
+
+    fn serve_order_meal_items(&self, order_id: Uuid, meal_item_ids: Vec<Uuid>, now: DateTime<Utc>) -> Result<(Vec<Uuid>, bool), OrderStoreError> {
+        Ok(match self.update_order(order_id, now, |order| order.serve_meal_items(meal_item_ids, now))? {
+            Some(non_servable_items) => (non_servable_items, true),
+            None => (vec![], false),
+        })
+    }
+
+    fn get_orders_with_meal_items_in(&self, status: MealItemStatus) -> Result<Vec<Order>, OrderStoreError> {
+        self.transaction(|tx| {
+            let order_ids = tx.prepare(
+                "SELECT order_id FROM orders WHERE order_id IN (SELECT order_id FROM meal_items WHERE is_removed = 0 AND status = ?1)
+                 ORDER BY creation_time",
+            )?
+                .query_map(params![status.to_string()], |row| row.get::<_, Uuid>(0))?
+                .collect::<rusqlite::Result<Vec<Uuid>>>()?;
+            let mut orders = Vec::new();
+            for order_id in order_ids {
+                orders.extend(load_order(tx, order_id)?);
+            }
+            Ok(orders)
+        })
+    }
+
+    fn add_order_payment(&self, order_id: Uuid, payment: Payment) -> Result<Result<Option<Order>, PaymentError>, OrderStoreError> {
+        Ok(self.update_order(order_id, payment.time(), |order| order.add_payment(payment).map(|_| order.clone()))?.transpose())
+    }
+
+    fn split_order_bill(&self, order_id: Uuid, split: BillSplit, now: DateTime<Utc>) -> Result<Result<Option<Order>, SplitError>, OrderStoreError> {
+        Ok(self.update_order(order_id, now, |order| order.split_bill(split, now).map(|_| order.clone()))?.transpose())
+    }
+
+    fn set_order_pricing(&self, order_id: Uuid, promotions: Promotions, tax_policy: TaxPolicy, now: DateTime<Utc>) -> Result<(bool, bool), OrderStoreError> {
+        Ok(match self.update_order(order_id, now, |order| order.apply_pricing(promotions(order), tax_policy, now))? {
+            Some(changed) => (changed, true),
+            None => (false, false),
+        })
+    }
+
+    // Every change is committed as it is made
+    fn flush(&self) -> Result<(), OrderStoreError> {
+        Ok(())
+    }
+}
+
+impl From<rusqlite::Error> for OrderStoreError {
+    fn from(err: rusqlite::Error) -> Self {
+        OrderStoreError(err.to_string())
+    }
+}
+
+// The transaction is rolled back when dropped without being committed, ex. when the change fails
+fn in_transaction<T>(conn: &mut Connection, f: impl FnOnce(&Transaction) -> rusqlite::Result<T>) -> Result<T, OrderStoreError> {
+    let tx = conn.transaction()?;
+    let result = f(&tx)?;
+    tx.commit()?;
+    Ok(result)
+}
+
+// Orders are never deleted, the current order of a table is the last one added
+fn current_order_id(conn: &Connection, table_id: u32) -> rusqlite::Result<Option<Uuid>> {
+    conn.query_row(
+        "SELECT order_id FROM orders WHERE table_id = ?1 ORDER BY rowid DESC LIMIT 1",
+        params![table_id],
+        |row| row.get(0),
+    ).optional()
+}
+
+fn load_order(conn: &Connection, order_id: Uuid) -> rusqlite::Result<Option<Order>> {
+    let order_row = conn.query_row(
+        "SELECT table_id, currency, creation_time, update_time, priority, closed_time, bill_split, promotions, guests, tax_policy
+         FROM orders WHERE order_id = ?1",
+        params![order_id],
+        |row| Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?, row.get(2)?, row.get(3)?, row.get::<_, String>(4)?, row.get(5)?,
+                  row.get::<_, Option<String>>(6)?, row.get::<_, Option<String>>(7)?, row.get::<_, Option<u32>>(8)?, row.get::<_, Option<String>>(9)?)),
+    ).optional()?;
+
+    let (table_id, currency, creation_time, update_time, priority, closed_time, split, promotions, guests, tax_policy) = match order_row {
+        Some(order_row) => order_row,
+        None => return Ok(None),
+    };
+    let currency = Currency::from_str(&currency).map_err(|err| invalid_column(1, Type::Text, err.into()))?;
+    let priority = OrderPriority::from_str(&priority).map_err(|err| invalid_column(4, Type::Text, err.into()))?;
+    let split = split.map(|split| serde_json::from_str::<BillSplit>(&split))
+        .transpose()
+        .map_err(|err| invalid_column(6, Type::Text, Box::new(err)))?;
+    // Promotions are stored as JSON, as they are only ever read back along with their order
+    let promotions = promotions.map(|promotions| serde_json::from_str::<Vec<AppliedPromotionRecord>>(&promotions))
+        .transpose()
+        .map_err(|err| invalid_column(7, Type::Text, Box::new(err)))?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|promotion| promotion.into_applied_promotion(currency))
+        .collect::<Result<Vec<AppliedPromotion>, PriceError>>()
+        .map_err(|err| invalid_column(7, Type::Text, Box::new(err)))?;
+    let tax_policy = tax_policy.map(|tax_policy| serde_json::from_str::<TaxPolicy>(&tax_policy))
+        .transpose()
+        .map_err(|err| invalid_column(9, Type::Text, Box::new(err)))?
+        .unwrap_or_default();
+
+    let mut stmt = conn.prepare(
+        "SELECT meal_item_id, menu_item_id, name, price, cooking_time_in_min, is_removed, status, creation_time, update_time, station, void_reason, ready_time, category
+         FROM meal_items WHERE order_id = ?1",
+    )?;
+    let meal_items = stmt.query_map(params![order_id], |row| {
+        let price = Price::from_minor_units(row.get(3)?, currency)
+            .map_err(|err| invalid_column(3, Type::Integer, Box::new(err)))?;
+        let status = MealItemStatus::from_str(&row.get::<_, String>(6)?)
+            .map_err(|err| invalid_column(6, Type::Text, err.into()))?;
+        let station = Station::from_str(&row.get::<_, String>(9)?)
+            .map_err(|err| invalid_column(9, Type::Text, err.into()))?;
+        let menu_item = MenuItem::restore(row.get(1)?, row.get(2)?, price)
+            .with_station(station)
+            .with_category(row.get(12)?);
+        Ok(MealItem::restore(row.get(0)?, menu_item, row.get(4)?, row.get(5)?, status, row.get(7)?, row.get(8)?)
+            .with_void_reason(row.get(10)?)
+            .with_ready_time(row.get(11)?))
+    })?.collect::<rusqlite::Result<Vec<MealItem>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT payment_id, method, reference, amount, time, check_name FROM payments WHERE order_id = ?1 ORDER BY rowid",
+    )?;
+    let payments = stmt.query_map(params![order_id], |row| {
+        let method = PaymentMethod::from_str(&row.get::<_, String>(1)?)
+            .map_err(|err| invalid_column(1, Type::Text, err.into()))?;
+        let amount = Price::from_minor_units(row.get(3)?, currency)
+            .map_err(|err| invalid_column(3, Type::Integer, Box::new(err)))?;
+        Ok(Payment::restore(row.get(0)?, method, row.get(2)?, amount, row.get(4)?).with_check(row.get(5)?))
+    })?.collect::<rusqlite::Result<Vec<Payment>>>()?;
+
+    let order = Order::restore(order_id, table_id, priority, currency, meal_items, creation_time, update_time)
+        .map_err(|err| invalid_column(3, Type::Integer, Box::new(err)))?
+        .with_guests(guests)
+        .with_payments(payments, closed_time)
+        .with_promotions(promotions)
+        .with_tax_policy(tax_policy)
+        .with_split(split);
+    Ok(Some(order))
+}
+
+fn save_order(conn: &Connection, order: &Order) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO orders (order_id, table_id, currency, creation_time, update_time, priority, closed_time, bill_split, promotions, guests, tax_policy)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+         ON CONFLICT (order_id) DO UPDATE SET update_time = excluded.update_time, closed_time = excluded.closed_time, bill_split = excluded.bill_split,
+                                              promotions = excluded.promotions, tax_policy = excluded.tax_policy",
+        params![order.id(), order.get_table_id(), order.get_currency().code(), order.creation_time(), order.update_time(),
+                order.get_priority().to_string(), order.closed_time(),
+                order.get_split().map(|split| serde_json::to_string(&split).expect("bill split is serializable")),
+                serde_json::to_string(&order.get_promotions().iter().map(AppliedPromotionRecord::from).collect::<Vec<_>>())
+                    .expect("promotions are serializable"),
+                order.get_guests(),
+                serde_json::to_string(&order.get_tax_policy()).expect("tax policy is serializable")],
+    )?;
+
+    // Payments are never changed once made
+    let mut stmt = conn.prepare(
+        "INSERT INTO payments (payment_id, order_id, method, reference, amount, time, check_name) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT (payment_id) DO NOTHING",
+    )?;
+    for payment in order.get_payments() {
+        stmt.execute(params![
+            payment.id(),
+            order.id(),
+            payment.method().to_string(),
+            payment.reference(),
+            payment.amount().minor_units(),
+            payment.time(),
+            payment.check(),
+        ])?;
+    }
+
+    let mut stmt = conn.prepare(
+        "INSERT INTO meal_items (meal_item_id, order_id, menu_item_id, name, price, cooking_time_in_min, is_removed, status, creation_time, update_time, station, void_reason, ready_time, category)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+         ON CONFLICT (meal_item_id) DO UPDATE SET is_removed = excluded.is_removed, status = excluded.status, update_time = excluded.update_time,
+         void_reason = excluded.void_reason, ready_time = excluded.ready_time",
+    )?;
+    for meal_item_arc in order.get_meal_items().iter() {
+        let meal_item = meal_item_arc.lock().unwrap();
+        let menu_item = meal_item.get_menu_item();
+        stmt.execute(params![
+            meal_item.id(),
+            order.id(),
+            menu_item.id(),
+            menu_item.get_name(),
+            menu_item.price().minor_units(),
+            meal_item.cooking_time_in_min(),
+            meal_item.is_removed(),
+            meal_item.get_status().to_string(),
+            meal_item.creation_time(),
+            meal_item.update_time(),
+            meal_item.station().to_string(),
+            meal_item.void_reason(),
+            meal_item.ready_time(),
+            menu_item.category(),
+        ])?;
+    }
+    Ok(())
+}
+
+// Columns added after the first release are added to databases created before them
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> rusqlite::Result<()> {
+    let exists = conn.prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1", table))?
+        .exists(params![column])?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
+    Ok(())
+}
+
+fn invalid_column(column: usize, column_type: Type, err: Box<dyn Error + Send + Sync>) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(column, column_type, err)
+}
@@ -0,0 +1,466 @@
+#[cfg(test)]
+mod order_sqlite_test {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::price::Price;
+    use crate::models::order::{Order, OrderPriority, OrderStatus};
+    use crate::models::order_event::OrderChange;
+    use crate::models::payment::{Payment, PaymentError, PaymentMethod};
+    use crate::models::promotion::AppliedPromotion;
+    use crate::models::split::{BillSplit, CheckStatus, NamedCheck, SplitError};
+    use crate::models::station::Station;
+    use crate::models::tax::{ServiceCharge, Tax, TaxPolicy};
+    use crate::repositories::order::OrderRepository;
+    use crate::repositories::order_event::OrderEventRepo;
+    use crate::repositories::order_sqlite::SqliteOrderRepo;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    // Database file which is deleted once the test is done
+    struct TempDb(PathBuf);
+
+    impl TempDb {
+        fn new() -> Self {
+            TempDb(std::env::temp_dir().join(format!("orders-{}.db", Uuid::new_v4())))
+        }
+
+        fn open(&self) -> SqliteOrderRepo {
+            SqliteOrderRepo::open(&self.0).unwrap()
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn create_meal_item(name: &str, minor_units: i64) -> MealItem {
+        MealItem::create(MenuItem::new(String::from(name), Price::from_minor_units(minor_units, Currency::Usd).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now())
+    }
+
+    #[test]
+    fn test_add() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let meal_item = create_meal_item("fries", 345);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+
+        repo.add(order.clone()).unwrap();
+
+        let fetched_order = repo.get_order_by_table_id(1).unwrap().expect("order not found");
+        assert_eq!(order.id(), fetched_order.id());
+        assert_eq!(order.get_total_price(), fetched_order.get_total_price());
+        assert_eq!(order.creation_time(), fetched_order.creation_time());
+        assert_eq!(meal_item, fetched_order.get_meal_item(meal_item.id()).unwrap().lock().unwrap().clone());
+        assert!(repo.get_order_by_table_id(2).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_add_keeps_previous_orders_of_table() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let first_meal_item = create_meal_item("fries", 345);
+        let mut first_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        first_order.add_meal_items(vec![first_meal_item.clone()], Utc::now()).unwrap();
+        repo.add(first_order.clone()).unwrap();
+        repo.remove_order(first_order.id(), Utc::now()).unwrap();
+
+        let second_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        assert!(repo.add(second_order.clone()).unwrap());
+        repo.add(Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+
+        // The table now refers to the second order, but the first one is kept in its history
+        assert_eq!(second_order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
+        assert!(repo.get_order_meal_item(second_order.id(), first_meal_item.id()).unwrap().is_none());
+
+        let order_ids: Vec<Uuid> = repo.get_orders_by_table_id(1).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![first_order.id(), second_order.id()], order_ids);
+        assert!(repo.get_orders_by_table_id(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_to_table_with_open_order() {
+        let db = TempDb::new();
+        let repo = db.open();
+        let first_order = Order::new(1, Currency::Usd, vec![create_meal_item("fries", 345)], Utc::now()).unwrap();
+        let second_order = Order::new(1, Currency::Usd, vec![create_meal_item("burger", 895)], Utc::now()).unwrap();
+
+        assert!(repo.add(first_order.clone()).unwrap());
+        assert!(!repo.add(second_order.clone()).unwrap());
+
+        assert_eq!(first_order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
+        assert!(repo.get_order(second_order.id()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failing_database() {
+        let db = TempDb::new();
+        let repo = db.open();
+        let order = Order::new(1, Currency::Usd, vec![create_meal_item("fries", 345)], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        // Given the meal items can't be read or written anymore, ex. the database was damaged
+        rusqlite::Connection::open(&db.0).unwrap().execute_batch("DROP TABLE meal_items").unwrap();
+
+        assert!(repo.get_order(order.id()).is_err());
+        assert!(repo.add(Order::new(2, Currency::Usd, vec![create_meal_item("tea", 123)], Utc::now()).unwrap()).is_err());
+        assert!(repo.remove_order(order.id(), Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_orders_survive_reopen() {
+        let db = TempDb::new();
+
+        let meal_item = create_meal_item("fries", 345);
+        let fryer_menu_item = MenuItem::new(String::from("onion rings"), Price::from_minor_units(789, Currency::Usd).unwrap()).with_station(Station::Fryer);
+        let fryer_meal_item = MealItem::create(fryer_menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap().with_priority(OrderPriority::Rush);
+        order.add_meal_items(vec![meal_item.clone(), fryer_meal_item.clone()], Utc::now()).unwrap();
+
+        let repo = db.open();
+        repo.add(order.clone()).unwrap();
+        repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+        drop(repo);
+
+        let repo = db.open();
+        let fetched_order = repo.get_order_by_table_id(1).unwrap().expect("order not found");
+        assert_eq!(order.id(), fetched_order.id());
+        assert_eq!(OrderPriority::Rush, fetched_order.get_priority());
+        assert_eq!(Price::from_minor_units(1134, Currency::Usd).unwrap(), fetched_order.get_total_price());
+        assert_eq!(2, fetched_order.get_meal_items().len());
+        assert_eq!(MealItemStatus::Preparing, repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().get_status());
+        assert_eq!(Station::Grill, repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().station());
+        assert_eq!(Station::Fryer, repo.get_order_meal_item(order.id(), fryer_meal_item.id()).unwrap().unwrap().station());
+    }
+
+    #[test]
+    fn test_completed_meal_items_migrated_to_ready_for_pickup() {
+        let db = TempDb::new();
+        let repo = db.open();
+        let meal_item = create_meal_item("fries", 345);
+        let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+        drop(repo);
+
+        // Given the meal item was stored as completed before pickups were tracked
+        rusqlite::Connection::open(&db.0).unwrap().execute_batch("UPDATE meal_items SET status = 'Completed'").unwrap();
+
+        let repo = db.open();
+        assert_eq!(MealItemStatus::ReadyForPickup, repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().get_status());
+        let order_ids: Vec<Uuid> = repo.get_orders_with_meal_items_in(MealItemStatus::ReadyForPickup).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_update_order_meal_item_status() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let meal_item = create_meal_item("fries", 345);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        let order_id = order.id();
+        repo.add(order).unwrap();
+
+        assert_eq!(MealItemStatus::Received, repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().get_status());
+
+        assert!(repo.update_order_meal_item_status(order_id, meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap());
+        assert_eq!(MealItemStatus::ReadyForPickup, repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().get_status());
+
+        assert!(!repo.update_order_meal_item_status(order_id, Uuid::new_v4(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap());
+        assert!(!repo.update_order_meal_item_status(Uuid::new_v4(), meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap());
+    }
+
+    #[test]
+    fn test_add_order_meal_items() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        assert_eq!((false, false), repo.add_order_meal_items(Uuid::new_v4(), vec![], Utc::now()).unwrap().unwrap());
+
+        let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let order_id = order.id();
+        repo.add(order).unwrap();
+
+        let meal_item = create_meal_item("burger", 789);
+        assert_eq!((true, true), repo.add_order_meal_items(order_id, vec![meal_item.clone()], Utc::now()).unwrap().unwrap());
+
+        let order = repo.get_order_by_table_id(1).unwrap().unwrap();
+        assert_eq!(Price::from_minor_units(789, Currency::Usd).unwrap(), order.get_total_price());
+        assert_eq!(meal_item, repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap());
+
+        // Meal items priced in another currency are rejected and nothing is saved
+        let other_meal_item = MealItem::create(MenuItem::new(String::from("sushi"), Price::from_minor_units(500, Currency::Jpy).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now());
+        assert!(repo.add_order_meal_items(order_id, vec![other_meal_item.clone()], Utc::now()).unwrap().is_err());
+        assert!(repo.get_order_meal_item(order_id, other_meal_item.id()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_remove_order_meal_items() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let received_meal_item = create_meal_item("fries", 345);
+        let mut preparing_meal_item = create_meal_item("burger", 789);
+        preparing_meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![received_meal_item.clone(), preparing_meal_item.clone()], Utc::now()).unwrap();
+        let order_id = order.id();
+        repo.add(order).unwrap();
+
+        let (non_removable_items, existed) = repo.remove_order_meal_items(order_id, vec![received_meal_item.id(), preparing_meal_item.id()], Utc::now()).unwrap();
+        assert!(existed);
+        assert_eq!(vec![preparing_meal_item.id()], non_removable_items);
+
+        assert!(repo.get_order_meal_item(order_id, received_meal_item.id()).unwrap().unwrap().is_removed());
+        assert!(!repo.get_order_meal_item(order_id, preparing_meal_item.id()).unwrap().unwrap().is_removed());
+        assert_eq!(Price::from_minor_units(789, Currency::Usd).unwrap(), repo.get_order_by_table_id(1).unwrap().unwrap().get_total_price());
+
+        let (_, existed) = repo.remove_order_meal_items(Uuid::new_v4(), vec![received_meal_item.id()], Utc::now()).unwrap();
+        assert!(!existed);
+    }
+
+    #[test]
+    fn test_remove_order() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let meal_item = create_meal_item("fries", 345);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        let order_id = order.id();
+        repo.add(order).unwrap();
+
+        let (removed, existed) = repo.remove_order(order_id, Utc::now()).unwrap();
+        assert!(removed);
+        assert!(existed);
+        assert!(repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().is_removed());
+
+        let (removed, existed) = repo.remove_order(Uuid::new_v4(), Utc::now()).unwrap();
+        assert!(!removed);
+        assert!(!existed);
+    }
+
+    #[test]
+    fn test_remove_order_failed() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        // Given Order in Preparing status, when attempting cancellation, order should not be canceled.
+        let mut meal_item = create_meal_item("fries", 345);
+        meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        let order_id = order.id();
+        repo.add(order).unwrap();
+
+        let (removed, existed) = repo.remove_order(order_id, Utc::now()).unwrap();
+        assert!(!removed);
+        assert!(existed);
+        assert!(!repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().is_removed());
+    }
+
+    #[test]
+    fn test_get_active_orders() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let meal_item = create_meal_item("fries", 345);
+        let mut active_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        active_order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(active_order.clone()).unwrap();
+        repo.update_order_meal_item_status(active_order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+
+        let meal_item = create_meal_item("fries", 345);
+        let mut completed_order = Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap();
+        completed_order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(completed_order.clone()).unwrap();
+        repo.update_order_meal_item_status(completed_order.id(), meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+
+        let mut canceled_order = Order::new(3, Currency::Usd, vec![], Utc::now()).unwrap();
+        canceled_order.add_meal_items(vec![create_meal_item("fries", 345)], Utc::now()).unwrap();
+        repo.add(canceled_order.clone()).unwrap();
+        repo.remove_order(canceled_order.id(), Utc::now()).unwrap();
+
+        let order_ids: Vec<Uuid> = repo.get_active_orders().unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![active_order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_void_order_meal_item() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let voided_meal_item = create_meal_item("steak", 2500);
+        let meal_item = create_meal_item("fries", 345);
+        let order = Order::new(1, Currency::Usd, vec![voided_meal_item.clone(), meal_item.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+        repo.add(Order::new(2, Currency::Usd, vec![create_meal_item("salad", 500)], Utc::now()).unwrap()).unwrap();
+        repo.update_order_meal_item_status(order.id(), voided_meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+
+        assert_eq!((None, true), repo.void_order_meal_item(order.id(), meal_item.id(), String::from("guest left"), Utc::now()).unwrap());
+        // The meal item comes back voided, as it was stored
+        let (voided, existed) = repo.void_order_meal_item(order.id(), voided_meal_item.id(), String::from("guest left"), Utc::now()).unwrap();
+        assert!(existed);
+        assert_eq!(voided, repo.get_order_meal_item(order.id(), voided_meal_item.id()).unwrap());
+        assert_eq!(Some(MealItemStatus::Voided), voided.map(|voided| voided.get_status()));
+        assert_eq!((None, false), repo.void_order_meal_item(Uuid::new_v4(), meal_item.id(), String::from("guest left"), Utc::now()).unwrap());
+        drop(repo);
+
+        let repo = db.open();
+        let fetched_meal_item = repo.get_order_meal_item(order.id(), voided_meal_item.id()).unwrap().unwrap();
+        assert_eq!(MealItemStatus::Voided, fetched_meal_item.get_status());
+        assert_eq!(Some(String::from("guest left")), fetched_meal_item.void_reason());
+        assert_eq!(Price::from_minor_units(345, Currency::Usd).unwrap(), repo.get_order(order.id()).unwrap().unwrap().get_total_price());
+        let order_ids: Vec<Uuid> = repo.get_orders_with_meal_items_in(MealItemStatus::Voided).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_serve_order_meal_items() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let served_meal_item = create_meal_item("fries", 345);
+        let meal_item = create_meal_item("steak", 2500);
+        let order = Order::new(1, Currency::Usd, vec![served_meal_item.clone(), meal_item.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+        let ready_time = Utc::now();
+        repo.update_order_meal_item_status(order.id(), served_meal_item.id(), MealItemStatus::ReadyForPickup, ready_time).unwrap();
+        repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::ReadyForPickup, ready_time).unwrap();
+
+        assert_eq!((vec![], true), repo.serve_order_meal_items(order.id(), vec![served_meal_item.id()], Utc::now()).unwrap());
+        assert_eq!((vec![], false), repo.serve_order_meal_items(Uuid::new_v4(), vec![meal_item.id()], Utc::now()).unwrap());
+        drop(repo);
+
+        let repo = db.open();
+        let fetched_meal_item = repo.get_order_meal_item(order.id(), served_meal_item.id()).unwrap().unwrap();
+        assert_eq!(MealItemStatus::Served, fetched_meal_item.get_status());
+        assert_eq!(Some(ready_time), fetched_meal_item.ready_time());
+        assert_eq!(OrderStatus::Completed, repo.get_order(order.id()).unwrap().unwrap().get_order_status());
+        let order_ids: Vec<Uuid> = repo.get_orders_with_meal_items_in(MealItemStatus::ReadyForPickup).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_add_order_payment() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let order = Order::new(1, Currency::Usd, vec![create_meal_item("steak", 2500)], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        let cash = Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(1000, Currency::Usd).unwrap(), Utc::now());
+        let card = Payment::new(PaymentMethod::Card, Some(String::from("VISA-0042")), Price::from_minor_units(1500, Currency::Usd).unwrap(), Utc::now());
+        // The order comes back with the payment added, as it was stored
+        let balance_due = |result: Result<Option<Order>, PaymentError>| result.map(|order| order.map(|order| order.get_balance_due().minor_units()));
+        assert_eq!(Ok(Some(1500)), balance_due(repo.add_order_payment(order.id(), cash.clone()).unwrap()));
+        assert_eq!(Ok(None), balance_due(repo.add_order_payment(Uuid::new_v4(), card.clone()).unwrap()));
+        assert_eq!(Err(PaymentError::ExceedsBalance(Price::from_minor_units(1500, Currency::Usd).unwrap())),
+                   balance_due(repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(1501, Currency::Usd).unwrap(), Utc::now())).unwrap()));
+        assert_eq!(Ok(Some(0)), balance_due(repo.add_order_payment(order.id(), card.clone()).unwrap()));
+        drop(repo);
+
+        let repo = db.open();
+        let paid_order = repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(vec![cash, card.clone()], paid_order.get_payments());
+        assert_eq!(Some(card.time()), paid_order.closed_time());
+        assert!(!paid_order.is_open());
+    }
+
+    #[test]
+    fn test_split_order_bill() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let fries = create_meal_item("fries", 345);
+        let burger = create_meal_item("burger", 789);
+        let order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        let split = BillSplit::ByMealItem {
+            checks: vec![
+                NamedCheck { name: String::from("ann"), meal_item_ids: vec![fries.id()] },
+                NamedCheck { name: String::from("bob"), meal_item_ids: vec![burger.id()] },
+            ],
+        };
+        assert!(matches!(repo.split_order_bill(Uuid::new_v4(), split.clone(), Utc::now()).unwrap(), Ok(None)));
+        assert_eq!(Err(SplitError::InvalidGuests(0)), repo.split_order_bill(order.id(), BillSplit::Evenly { guests: 0 }, Utc::now()).unwrap().map(|_| ()));
+        // The order comes back split, as it was stored
+        let split_order = repo.split_order_bill(order.id(), split.clone(), Utc::now()).unwrap().unwrap().unwrap();
+        assert_eq!(Some(split.clone()), split_order.get_split());
+        let payment = Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(345, Currency::Usd).unwrap(), Utc::now())
+            .with_check(Some(String::from("ann")));
+        assert!(repo.add_order_payment(order.id(), payment.clone()).unwrap().unwrap().is_some());
+        drop(repo);
+
+        let repo = db.open();
+        let split_order = repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(Some(split), split_order.get_split());
+        assert_eq!(vec![payment], split_order.get_payments());
+        let statuses: Vec<_> = split_order.get_sub_checks().iter().map(|sub_check| (sub_check.name(), sub_check.status())).collect();
+        assert_eq!(vec![(String::from("ann"), CheckStatus::Paid), (String::from("bob"), CheckStatus::Unpaid)], statuses);
+    }
+
+    #[test]
+    fn test_set_order_pricing() {
+        let db = TempDb::new();
+        let repo = db.open();
+
+        let fries = create_meal_item("fries", 345);
+        let order = Order::new(1, Currency::Usd, vec![fries.clone()], Utc::now()).unwrap().with_guests(Some(6));
+        repo.add(order.clone()).unwrap();
+
+        let saving = Price::from_minor_units(45, Currency::Usd).unwrap();
+        let promotions = vec![
+            AppliedPromotion::new(Uuid::new_v4(), String::from("fries off"), saving, vec![(fries.id(), saving)]),
+            AppliedPromotion::new(Uuid::new_v4(), String::from("order off"), saving, vec![]),
+        ];
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "10".parse().unwrap(), categories: None }],
+            service_charge: Some(ServiceCharge { rate: "10".parse().unwrap(), above_guests: 5 }),
+            ..TaxPolicy::default()
+        };
+        assert_eq!((false, false), repo.set_order_pricing(Uuid::new_v4(), &|_| promotions.clone(), tax_policy.clone(), Utc::now()).unwrap());
+        assert_eq!((true, true), repo.set_order_pricing(order.id(), &|_| promotions.clone(), tax_policy.clone(), Utc::now()).unwrap());
+        drop(repo);
+
+        let repo = db.open();
+        let priced_order = repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(promotions, priced_order.get_promotions());
+        assert_eq!((tax_policy.clone(), Some(6)), (priced_order.get_tax_policy(), priced_order.get_guests()));
+        // 255 once promotions are taken off, plus 26 of tax and 26 of service charge
+        assert_eq!(Price::from_minor_units(307, Currency::Usd).unwrap(), priced_order.get_total_price());
+    }
+
+    #[test]
+    fn test_publishes_changes() {
+        let db = TempDb::new();
+        let order_event_repo = Arc::new(OrderEventRepo::new(100));
+        let repo = db.open().with_events(order_event_repo.clone());
+        let fries = create_meal_item("fries", 345);
+        let order = Order::new(1, Currency::Usd, vec![fries.clone()], Utc::now()).unwrap();
+
+        repo.add(order.clone()).unwrap();
+        assert!(repo.update_order_meal_item_status(order.id(), fries.id(), MealItemStatus::Preparing, Utc::now()).unwrap());
+        // Nothing changed, so nothing is published
+        assert!(repo.update_order_meal_item_status(order.id(), fries.id(), MealItemStatus::Preparing, Utc::now()).unwrap());
+
+        let changes: Vec<OrderChange> = order_event_repo.kept_events().iter().map(|event| event.change()).collect();
+        let expected = vec![
+            OrderChange::OrderAdded { meal_item_ids: vec![fries.id()], status: OrderStatus::Received },
+            OrderChange::MealItemStatusChanged { meal_item_id: fries.id(), from: MealItemStatus::Received, to: MealItemStatus::Preparing },
+            OrderChange::OrderStatusChanged { from: OrderStatus::Received, to: OrderStatus::Preparing },
+        ];
+        assert_eq!(expected, changes);
+    }
+}
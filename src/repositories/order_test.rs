@@ -1,23 +1,50 @@
 #[cfg(test)]
 mod order_test {
+    use std::fs;
+    use std::path::PathBuf;
+    use chrono::{TimeDelta, Utc};
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
     use crate::models::meal::{MealItem, MealItemStatus};
     use crate::models::menu::MenuItem;
-    use crate::models::order::Order;
-    use crate::repositories::order::OrderRepo;
+    use crate::models::price::Price;
+    use crate::models::order::{Order, OrderPriority, OrderStatus};
+    use crate::models::payment::{Payment, PaymentError, PaymentMethod};
+    use crate::models::promotion::AppliedPromotion;
+    use crate::models::split::{BillSplit, CheckStatus, NamedCheck, SplitError};
+    use crate::models::station::Station;
+    use crate::models::tax::{ServiceCharge, Tax, TaxPolicy};
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::repositories::order_log::{FsyncPolicy, OrderLogOptions};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    // Log directory which is deleted once the test is done
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            TempDir(std::env::temp_dir().join(format!("order-repo-{}", Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
 
     #[test]
     fn test_add() {
         let repo = OrderRepo::new();
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("34.5"));
-        let order = Order::new(1, vec![menu_item]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
 
-        repo.add(order.clone());
+        repo.add(order.clone()).unwrap();
 
-        if let Some(order_arc) = repo.get_order_by_table_id(1) {
-            let fetched_order = order_arc.lock().unwrap();
-            let fetched_meal_item = fetched_order.get_meal_items().get(0).unwrap().lock().unwrap().clone();
-            let meal_item = order.get_meal_items().get(0).unwrap().lock().unwrap().clone();
+        if let Some(fetched_order) = repo.get_order_by_table_id(1).unwrap() {
+            let fetched_meal_item = fetched_order.get_meal_items().first().unwrap().lock().unwrap().clone();
+            let meal_item = order.get_meal_items().first().unwrap().lock().unwrap().clone();
             assert_eq!(order.get_table_id(), fetched_order.get_table_id());
             assert_eq!(order.get_total_price(), fetched_order.get_total_price());
             assert_eq!(meal_item, fetched_meal_item);
@@ -30,28 +57,62 @@ mod order_test {
     fn test_get_order_by_table_id() {
         let repo = OrderRepo::new();
 
-        assert!(repo.get_order_by_table_id(1).is_none());
-        assert!(repo.get_order_by_table_id(2).is_none());
+        assert!(repo.get_order_by_table_id(1).unwrap().is_none());
+        assert!(repo.get_order_by_table_id(2).unwrap().is_none());
+
+        repo.add(Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+
+        assert!(repo.get_order_by_table_id(1).unwrap().is_some());
+        assert!(repo.get_order_by_table_id(2).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_orders_by_table_id() {
+        let repo = OrderRepo::new();
+
+        let first_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let second_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        repo.add(first_order.clone()).unwrap();
+        repo.add(Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+        repo.add(second_order.clone()).unwrap();
 
-        repo.add(Order::new(1, vec![]));
+        // The table now refers to the second order, but the first one is kept in its history
+        assert_eq!(second_order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
 
-        assert!(repo.get_order_by_table_id(1).is_some());
-        assert!(repo.get_order_by_table_id(2).is_none());
+        let order_ids: Vec<Uuid> = repo.get_orders_by_table_id(1).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![first_order.id(), second_order.id()], order_ids);
+        assert!(repo.get_orders_by_table_id(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_to_table_with_open_order() {
+        let repo = OrderRepo::new();
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = || MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+        let first_order = Order::new(1, Currency::Usd, vec![meal_item()], Utc::now()).unwrap();
+        let second_order = Order::new(1, Currency::Usd, vec![meal_item()], Utc::now()).unwrap();
+
+        assert!(repo.add(first_order.clone()).unwrap());
+        assert!(!repo.add(second_order.clone()).unwrap());
+
+        assert_eq!(first_order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
+        assert!(repo.get_order(second_order.id()).unwrap().is_none());
+        assert_eq!(1, repo.get_orders_by_table_id(1).unwrap().len());
     }
 
     #[test]
     fn test_get_order_meal_item() {
         let repo = OrderRepo::new();
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
-        repo.add(order.clone());
+        let order_id = order.id();
+        repo.add(order.clone()).unwrap();
 
-        if let Some(meal_item_arc) = repo.get_order_meal_item(1, meal_item.id()) {
-            let fetched_meal_item = meal_item_arc.lock().unwrap().clone();
+        if let Some(fetched_meal_item) = repo.get_order_meal_item(order_id, meal_item.id()).unwrap() {
             assert_eq!(meal_item, fetched_meal_item);
         } else {
             panic!("meal item not found")
@@ -62,26 +123,25 @@ mod order_test {
     fn test_update_order_meal_item_status() {
         let repo = OrderRepo::new();
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
-        repo.add(order);
+        let order_id = order.id();
+        repo.add(order).unwrap();
 
-        if let Some(meal_item_arc) = repo.get_order_meal_item(1, meal_item.id()) {
-            let meal_item_arc = meal_item_arc.lock().unwrap().clone();
-            assert_eq!(MealItemStatus::Received, meal_item_arc.get_status());
+        if let Some(fetched_meal_item) = repo.get_order_meal_item(order_id, meal_item.id()).unwrap() {
+            assert_eq!(MealItemStatus::Received, fetched_meal_item.get_status());
         } else {
             panic!("meal item not found")
         }
 
-        let existed = repo.update_order_meal_item_status(1, meal_item.id(), MealItemStatus::Preparing);
+        let existed = repo.update_order_meal_item_status(order_id, meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
         assert!(existed);
 
-        if let Some(meal_item_arc) = repo.get_order_meal_item(1, meal_item.id()) {
-            let meal_item_arc = meal_item_arc.lock().unwrap().clone();
-            assert_eq!(MealItemStatus::Preparing, meal_item_arc.get_status());
+        if let Some(fetched_meal_item) = repo.get_order_meal_item(order_id, meal_item.id()).unwrap() {
+            assert_eq!(MealItemStatus::Preparing, fetched_meal_item.get_status());
         } else {
             panic!("meal item not found")
         }
@@ -92,31 +152,29 @@ mod order_test {
         let repo = OrderRepo::new();
         let mut expected_meal_items = Vec::new();
 
-        let existed = repo.add_order_meal_items(1, vec![]);
-        assert!(!existed);
+        assert_eq!((false, false), repo.add_order_meal_items(Uuid::new_v4(), vec![], Utc::now()).unwrap().unwrap());
 
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        let mut order = Order::new(1, vec![]);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
         expected_meal_items.push(meal_item);
 
-        repo.add(order);
+        let order_id = order.id();
+        repo.add(order).unwrap();
 
-        let menu_item = MenuItem::new(String::from("burger"), String::from("789"));
-        let meal_item = MealItem::create(menu_item);
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
         expected_meal_items.push(meal_item.clone());
 
-        let existed = repo.add_order_meal_items(1, vec![meal_item.clone()]);
-        assert!(existed);
+        assert_eq!((true, true), repo.add_order_meal_items(order_id, vec![meal_item.clone()], Utc::now()).unwrap().unwrap());
 
-        if let Some(order_arc) = repo.get_order_by_table_id(1) {
-            let order = order_arc.lock().unwrap().clone();
+        if let Some(order) = repo.get_order_by_table_id(1).unwrap() {
             let mut actual_meal_items = order.get_meal_items();
             assert_eq!(expected_meal_items.len(), actual_meal_items.len());
 
-            expected_meal_items.sort_by(|a, b| a.id().cmp(&b.id()));
-            actual_meal_items.sort_by(|a, b| a.lock().unwrap().id().cmp(&b.lock().unwrap().id()));
+            expected_meal_items.sort_by_key(|a| a.id());
+            actual_meal_items.sort_by_key(|a| a.lock().unwrap().id());
 
             for (expected, actual) in expected_meal_items.iter().zip(actual_meal_items.iter()) {
                 assert_eq!(expected.clone(), actual.lock().unwrap().clone());
@@ -130,18 +188,18 @@ mod order_test {
     fn test_remove_order_meal_items() {
         let repo = OrderRepo::new();
 
-        let mut order = Order::new(1, vec![]);
-        let menu_item_one = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item_one = MealItem::create(menu_item_one);
-        let menu_item_two = MenuItem::new(String::from("burger"), String::from("789"));
-        let meal_item_two = MealItem::create(menu_item_two);
-        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone()]);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let menu_item_one = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item_one = MealItem::create(menu_item_one, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item_two = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let meal_item_two = MealItem::create(menu_item_two, &MockCookingTimeEstimator::new(10), Utc::now());
+        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone()], Utc::now()).unwrap();
         let meal_item_ids = vec![meal_item_one.id(), meal_item_two.id()];
 
-        repo.add(order);
+        let order_id = order.id();
+        repo.add(order).unwrap();
 
-        if let Some(order_arc) = repo.get_order_by_table_id(1) {
-            let order = order_arc.lock().unwrap().clone();
+        if let Some(order) = repo.get_order_by_table_id(1).unwrap() {
             for meal_item_arc in order.get_meal_items().iter() {
                 assert!(!meal_item_arc.lock().unwrap().is_removed());
             }
@@ -149,10 +207,9 @@ mod order_test {
             panic!("order  not found")
         }
 
-        repo.remove_order_meal_items(1, meal_item_ids);
+        repo.remove_order_meal_items(order_id, meal_item_ids, Utc::now()).unwrap();
 
-        if let Some(order_arc) = repo.get_order_by_table_id(1) {
-            let order = order_arc.lock().unwrap().clone();
+        if let Some(order) = repo.get_order_by_table_id(1).unwrap() {
             for meal_item_arc in order.get_meal_items().iter() {
                 assert!(meal_item_arc.lock().unwrap().is_removed());
             }
@@ -166,14 +223,15 @@ mod order_test {
         let repo = OrderRepo::new();
 
         // Given Order in Received status, when attempting cancellation, order should be canceled.
-        let mut order = Order::new(1, vec![]);
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let meal_item = MealItem::create(menu_item);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
-        repo.add(order);
+        let order_id = order.id();
+        repo.add(order).unwrap();
 
-        let (removed, existed) = repo.remove_order(1);
+        let (removed, existed) = repo.remove_order(order_id, Utc::now()).unwrap();
         assert!(removed);
         assert!(existed);
     }
@@ -183,16 +241,308 @@ mod order_test {
         let repo = OrderRepo::new();
 
         // Given Order in Preparing status, when attempting cancellation, order should not be canceled.
-        let mut order = Order::new(1, vec![]);
-        let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-        let mut meal_item = MealItem::create(menu_item);
-        meal_item.update_state(MealItemStatus::Preparing);
-        order.add_meal_items(vec![meal_item.clone()]);
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
 
-        repo.add(order);
+        let order_id = order.id();
+        repo.add(order).unwrap();
 
-        let (removed, existed) = repo.remove_order(1);
+        let (removed, existed) = repo.remove_order(order_id, Utc::now()).unwrap();
         assert!(!removed);
         assert!(existed);
     }
+
+    #[test]
+    fn test_orders_rebuilt_from_log() {
+        let dir = TempDir::new();
+
+        let menu_item_one = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item_one = MealItem::create(menu_item_one, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item_two = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let meal_item_two = MealItem::create(menu_item_two, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item_three = MenuItem::new(String::from("salad"), Price::from_minor_units(500, Currency::Usd).unwrap()).with_station(Station::Salad);
+        let meal_item_three = MealItem::create(menu_item_three, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item_one.clone(), meal_item_two.clone()], Utc::now()).unwrap();
+
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let order_id = order.id();
+        repo.add(order.clone()).unwrap();
+        let other_order = Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap();
+        repo.add(other_order.clone()).unwrap();
+        let preparing_time = Utc::now() - TimeDelta::minutes(5);
+        repo.update_order_meal_item_status(order_id, meal_item_one.id(), MealItemStatus::Preparing, preparing_time).unwrap();
+        repo.add_order_meal_items(order_id, vec![meal_item_three.clone()], Utc::now()).unwrap().unwrap();
+        let removal_time = Utc::now() - TimeDelta::minutes(1);
+        repo.remove_order_meal_items(order_id, vec![meal_item_two.id()], removal_time).unwrap();
+        repo.remove_order(other_order.id(), Utc::now()).unwrap();
+        drop(repo);
+
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let fetched_order = repo.get_order_by_table_id(1).unwrap().expect("order not found");
+        assert_eq!(order.id(), fetched_order.id());
+        assert_eq!(Price::from_minor_units(845, Currency::Usd).unwrap(), fetched_order.get_total_price());
+        assert_eq!(MealItemStatus::Preparing, repo.get_order_meal_item(order_id, meal_item_one.id()).unwrap().unwrap().get_status());
+        assert!(repo.get_order_meal_item(order_id, meal_item_two.id()).unwrap().unwrap().is_removed());
+        assert_eq!(meal_item_three, repo.get_order_meal_item(order_id, meal_item_three.id()).unwrap().unwrap());
+        assert_eq!(Station::Salad, repo.get_order_meal_item(order_id, meal_item_three.id()).unwrap().unwrap().station());
+        assert!(!repo.get_order_by_table_id(2).unwrap().unwrap().is_active());
+        // Replayed changes keep the time they were made at
+        assert_eq!(preparing_time, repo.get_order_meal_item(order_id, meal_item_one.id()).unwrap().unwrap().update_time());
+        assert_eq!(removal_time, fetched_order.update_time());
+    }
+
+    #[test]
+    fn test_order_history_rebuilt_from_snapshot() {
+        let dir = TempDir::new();
+
+        let first_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        let second_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap().with_priority(OrderPriority::Rush);
+
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        repo.add(first_order.clone()).unwrap();
+        repo.add(second_order.clone()).unwrap();
+        drop(repo);
+
+        // Reopening compacts the log into a snapshot, so the second reopen rebuilds the history from the snapshot
+        drop(OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap());
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        assert_eq!(second_order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
+        assert_eq!(OrderPriority::Rush, repo.get_order_by_table_id(1).unwrap().unwrap().get_priority());
+        let order_ids: Vec<Uuid> = repo.get_orders_by_table_id(1).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![first_order.id(), second_order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_orders_rebuilt_from_snapshot() {
+        let dir = TempDir::new();
+        let options = OrderLogOptions { fsync_policy: FsyncPolicy::Never, snapshot_every: 2 };
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+
+        // Given the second change triggers a snapshot, the third one is only in the log
+        let repo = OrderRepo::with_log(&dir.0, options).unwrap();
+        let order_id = order.id();
+        repo.add(order.clone()).unwrap();
+        repo.update_order_meal_item_status(order_id, meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+        repo.update_order_meal_item_status(order_id, meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+        drop(repo);
+
+        let repo = OrderRepo::with_log(&dir.0, options).unwrap();
+        assert_eq!(order.id(), repo.get_order_by_table_id(1).unwrap().unwrap().id());
+        assert_eq!(MealItemStatus::ReadyForPickup, repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().get_status());
+    }
+
+    #[test]
+    fn test_get_active_orders() {
+        let repo = OrderRepo::new();
+
+        let meal_item = MealItem::create(MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut active_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        active_order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(active_order.clone()).unwrap();
+        repo.update_order_meal_item_status(active_order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+
+        let meal_item = MealItem::create(MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now());
+        let mut completed_order = Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap();
+        completed_order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(completed_order.clone()).unwrap();
+        repo.update_order_meal_item_status(completed_order.id(), meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+
+        let mut canceled_order = Order::new(3, Currency::Usd, vec![], Utc::now()).unwrap();
+        canceled_order.add_meal_items(vec![MealItem::create(MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+        repo.add(canceled_order.clone()).unwrap();
+        repo.remove_order(canceled_order.id(), Utc::now()).unwrap();
+
+        let order_ids: Vec<Uuid> = repo.get_active_orders().unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![active_order.id()], order_ids);
+    }
+
+    #[test]
+    fn test_void_order_meal_item() {
+        let dir = TempDir::new();
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+        repo.add(Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+
+        assert_eq!((None, true), repo.void_order_meal_item(order.id(), meal_item.id(), String::from("guest left"), Utc::now()).unwrap());
+        assert!(repo.get_orders_with_meal_items_in(MealItemStatus::Voided).unwrap().is_empty());
+        repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+        let void_time = Utc::now();
+        // The meal item comes back voided, as it was stored
+        let (voided, existed) = repo.void_order_meal_item(order.id(), meal_item.id(), String::from("guest left"), void_time).unwrap();
+        assert!(existed);
+        assert_eq!(voided, repo.get_order_meal_item(order.id(), meal_item.id()).unwrap());
+        assert_eq!(Some(MealItemStatus::Voided), voided.map(|voided| voided.get_status()));
+        assert_eq!((None, false), repo.void_order_meal_item(order.id(), Uuid::new_v4(), String::from("guest left"), Utc::now()).unwrap());
+        assert_eq!((None, false), repo.void_order_meal_item(Uuid::new_v4(), meal_item.id(), String::from("guest left"), Utc::now()).unwrap());
+        drop(repo);
+
+        // The void is replayed from the log along with its reason
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let voided_meal_item = repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap();
+        assert_eq!(MealItemStatus::Voided, voided_meal_item.get_status());
+        assert_eq!(Some(String::from("guest left")), voided_meal_item.void_reason());
+        assert_eq!(void_time, voided_meal_item.update_time());
+        let order_ids: Vec<Uuid> = repo.get_orders_with_meal_items_in(MealItemStatus::Voided).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![order.id()], order_ids);
+        assert!(repo.get_active_orders().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_serve_order_meal_items() {
+        let dir = TempDir::new();
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        assert_eq!((vec![meal_item.id()], true), repo.serve_order_meal_items(order.id(), vec![meal_item.id()], Utc::now()).unwrap());
+        let ready_time = Utc::now();
+        repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::ReadyForPickup, ready_time).unwrap();
+        let order_ids: Vec<Uuid> = repo.get_orders_with_meal_items_in(MealItemStatus::ReadyForPickup).unwrap().iter().map(|order| order.id()).collect();
+        assert_eq!(vec![order.id()], order_ids);
+        assert_eq!((vec![], true), repo.serve_order_meal_items(order.id(), vec![meal_item.id()], Utc::now()).unwrap());
+        assert_eq!((vec![], false), repo.serve_order_meal_items(Uuid::new_v4(), vec![meal_item.id()], Utc::now()).unwrap());
+        drop(repo);
+
+        // Serving is replayed from the log, and the meal item keeps the time it reached the pass
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let served_meal_item = repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap();
+        assert_eq!(MealItemStatus::Served, served_meal_item.get_status());
+        assert_eq!(Some(ready_time), served_meal_item.ready_time());
+        assert_eq!(OrderStatus::Served, repo.get_order(order.id()).unwrap().unwrap().get_order_status());
+        assert!(repo.get_orders_with_meal_items_in(MealItemStatus::ReadyForPickup).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_order_payment() {
+        let dir = TempDir::new();
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![meal_item], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        let cash = Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(1000, Currency::Usd).unwrap(), Utc::now());
+        let card = Payment::new(PaymentMethod::Card, Some(String::from("VISA-0042")), Price::from_minor_units(1500, Currency::Usd).unwrap(), Utc::now());
+        // The order comes back with the payment added, as it was stored
+        let balance_due = |result: Result<Option<Order>, PaymentError>| result.map(|order| order.map(|order| order.get_balance_due().minor_units()));
+        assert_eq!(Ok(Some(1500)), balance_due(repo.add_order_payment(order.id(), cash.clone()).unwrap()));
+        assert_eq!(Ok(None), balance_due(repo.add_order_payment(Uuid::new_v4(), card.clone()).unwrap()));
+        assert_eq!(Ok(Some(0)), balance_due(repo.add_order_payment(order.id(), card.clone()).unwrap()));
+        assert_eq!(Err(PaymentError::OrderClosed), balance_due(repo.add_order_payment(order.id(), cash.clone()).unwrap()));
+        drop(repo);
+
+        // Payments are replayed from the log, and the order stays closed
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let paid_order = repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(vec![cash, card.clone()], paid_order.get_payments());
+        assert_eq!(Some(card.time()), paid_order.closed_time());
+    }
+
+    #[test]
+    fn test_split_order_bill() {
+        let dir = TempDir::new();
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+
+        let split = BillSplit::ByMealItem {
+            checks: vec![
+                NamedCheck { name: String::from("ann"), meal_item_ids: vec![fries.id()] },
+                NamedCheck { name: String::from("bob"), meal_item_ids: vec![burger.id()] },
+            ],
+        };
+        assert!(matches!(repo.split_order_bill(Uuid::new_v4(), split.clone(), Utc::now()).unwrap(), Ok(None)));
+        assert_eq!(Err(SplitError::InvalidGuests(0)), repo.split_order_bill(order.id(), BillSplit::Evenly { guests: 0 }, Utc::now()).unwrap().map(|_| ()));
+        // The order comes back split, as it was stored
+        let split_order = repo.split_order_bill(order.id(), split.clone(), Utc::now()).unwrap().unwrap().unwrap();
+        assert_eq!(Some(split.clone()), split_order.get_split());
+        let payment = Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(345, Currency::Usd).unwrap(), Utc::now())
+            .with_check(Some(String::from("ann")));
+        assert!(repo.add_order_payment(order.id(), payment.clone()).unwrap().unwrap().is_some());
+        drop(repo);
+
+        // The split and the check of every payment are replayed from the log
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+        let split_order = repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(Some(split), split_order.get_split());
+        assert_eq!(vec![payment], split_order.get_payments());
+        let statuses: Vec<_> = split_order.get_sub_checks().iter().map(|sub_check| (sub_check.name(), sub_check.status())).collect();
+        assert_eq!(vec![(String::from("ann"), CheckStatus::Paid), (String::from("bob"), CheckStatus::Unpaid)], statuses);
+    }
+
+    #[test]
+    fn test_set_order_pricing_works_out_promotions_on_order_as_stored() {
+        let repo = OrderRepo::new();
+        let usd = |minor_units| Price::from_minor_units(minor_units, Currency::Usd).unwrap();
+        let fries = MealItem::create(MenuItem::new(String::from("fries"), usd(345)), &MockCookingTimeEstimator::new(10), Utc::now());
+        let burger = MealItem::create(MenuItem::new(String::from("burger"), usd(789)), &MockCookingTimeEstimator::new(15), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![fries], Utc::now()).unwrap();
+        repo.add(order.clone()).unwrap();
+        repo.add_order_meal_items(order.id(), vec![burger], Utc::now()).unwrap().unwrap();
+
+        // Given 100 off every meal item, the burger added after the order was read is discounted too
+        let per_meal_item = |order: &Order| {
+            let saving = usd(100 * order.get_meal_items().len() as i64);
+            vec![AppliedPromotion::new(Uuid::new_v4(), String::from("100 off each"), saving, vec![])]
+        };
+        assert_eq!((true, true), repo.set_order_pricing(order.id(), &per_meal_item, TaxPolicy::default(), Utc::now()).unwrap());
+        assert_eq!(usd(934), repo.get_order(order.id()).unwrap().unwrap().get_total_price());
+    }
+
+    #[test]
+    fn test_set_order_pricing() {
+        let dir = TempDir::new();
+        let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![fries.clone()], Utc::now()).unwrap().with_guests(Some(6));
+        repo.add(order.clone()).unwrap();
+
+        let saving = Price::from_minor_units(45, Currency::Usd).unwrap();
+        let promotions = vec![
+            AppliedPromotion::new(Uuid::new_v4(), String::from("fries off"), saving, vec![(fries.id(), saving)]),
+            AppliedPromotion::new(Uuid::new_v4(), String::from("order off"), saving, vec![]),
+        ];
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "10".parse().unwrap(), categories: None }],
+            service_charge: Some(ServiceCharge { rate: "10".parse().unwrap(), above_guests: 5 }),
+            ..TaxPolicy::default()
+        };
+        assert_eq!((false, false), repo.set_order_pricing(Uuid::new_v4(), &|_| promotions.clone(), tax_policy.clone(), Utc::now()).unwrap());
+        assert_eq!((true, true), repo.set_order_pricing(order.id(), &|_| promotions.clone(), tax_policy.clone(), Utc::now()).unwrap());
+        assert_eq!((false, true), repo.set_order_pricing(order.id(), &|_| promotions.clone(), tax_policy.clone(), Utc::now()).unwrap());
+        drop(repo);
+
+        // The promotions are replayed from the log, then read back from the snapshot it was compacted into
+        for _ in 0..2 {
+            let repo = OrderRepo::with_log(&dir.0, OrderLogOptions::default()).unwrap();
+            let priced_order = repo.get_order(order.id()).unwrap().unwrap();
+            assert_eq!(promotions, priced_order.get_promotions());
+            assert_eq!((tax_policy.clone(), Some(6)), (priced_order.get_tax_policy(), priced_order.get_guests()));
+            // 255 once promotions are taken off, plus 26 of tax and 26 of service charge
+            assert_eq!(Price::from_minor_units(307, Currency::Usd).unwrap(), priced_order.get_total_price());
+        }
+    }
 }
@@ -0,0 +1,33 @@
+use std::sync::Mutex;
+use uuid::Uuid;
+use crate::models::promotion::Promotion;
+
+// Promotions running in the restaurant, kept in the order they are tried when pricing an order
+pub struct PromotionRepo {
+    promotions: Mutex<Vec<Promotion>>,
+}
+
+impl PromotionRepo {
+    pub fn new() -> Self {
+        PromotionRepo {
+            promotions: Mutex::new(Vec::new()),
+        }
+    }
+
+    // The promotion is tried after every promotion added before it
+    pub fn add(&self, promotion: Promotion) {
+        self.promotions.lock().unwrap().push(promotion);
+    }
+
+    pub fn get_all(&self) -> Vec<Promotion> {
+        self.promotions.lock().unwrap().clone()
+    }
+
+    // Returns whether the promotion existed
+    pub fn remove(&self, promotion_id: Uuid) -> bool {
+        let mut promotions = self.promotions.lock().unwrap();
+        let count = promotions.len();
+        promotions.retain(|promotion| promotion.id() != promotion_id);
+        promotions.len() < count
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod promotion_test {
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+    use crate::repositories::promotion::PromotionRepo;
+
+    fn promotion(name: &str) -> Promotion {
+        Promotion::new(String::from(name), Currency::Usd, Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None).unwrap()
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let repo = PromotionRepo::new();
+        let first = promotion("first");
+        let second = promotion("second");
+
+        repo.add(first.clone());
+        repo.add(second.clone());
+        assert_eq!(vec![first.clone(), second.clone()], repo.get_all());
+
+        assert!(repo.remove(first.id()));
+        assert!(!repo.remove(first.id()));
+        assert!(!repo.remove(Uuid::new_v4()));
+        assert_eq!(vec![second], repo.get_all());
+    }
+}
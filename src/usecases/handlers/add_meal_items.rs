@@ -1,26 +1,32 @@
 use std::sync::{Arc};
-use std::thread::{sleep};
-use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
-use crate::libraries::thread_pool::{ThreadPoolDyn};
-use crate::models::meal::{MealItem, MealItemStatus};
-use crate::models::menu::MenuItem;
-use crate::repositories::order::OrderRepo;
+use crate::usecases::models::error::{ErrResp, UnavailableMenuItemsResp, MESSAGE_MENU_ITEMS_UNAVAILABLE, MESSAGE_ORDER_NOT_OPEN};
+use crate::libraries::clock::Clock;
+use crate::models::cooking_time::CookingTimeEstimator;
+use crate::models::meal::MealItem;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::order::OrderRepository;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::handlers::add_order::{order_price_err_resp, to_menu_item_ids, MenuItemReq};
+use crate::usecases::jobs::cook_meal_item::{cook_meal_item, cooking_job_info};
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::price_order::price_order;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::usecases::models::order_key::OrderKey;
 use crate::usecases::models::order_resp::OrderResp;
 
 #[derive(Deserialize)]
-pub struct MenuItemReq {
-    pub menu_item_id: Uuid,
-    pub name: String,
-    pub price: String,
+pub struct AddMealItemsReq {
+    pub table_id: u32,
+    pub menu_items: Vec<MenuItemReq>,
 }
 
 #[derive(Deserialize)]
-pub struct AddMealItemsReq {
-    pub table_id: u32,
+pub struct AddOrderMealItemsReq {
     pub menu_items: Vec<MenuItemReq>,
 }
 
@@ -30,73 +36,112 @@ pub struct AddMealItemsResp {
 }
 
 pub struct AddMealItemsHandler {
-    order_repo: Arc<OrderRepo>,
-    thread_pool: Arc<dyn ThreadPoolDyn>,
+    order_repo: Arc<dyn OrderRepository>,
+    menu_repo: Arc<MenuRepo>,
+    promotion_repo: Arc<PromotionRepo>,
+    tax_policy: Arc<TaxPolicy>,
+    cooking_time_estimator: Arc<dyn CookingTimeEstimator>,
+    kitchen_repo: Arc<KitchenRepo>,
+    kitchen: Arc<Kitchen>,
+    ready_time_estimator: Arc<ReadyTimeEstimator>,
+    clock: Arc<dyn Clock>,
 }
 
 impl AddMealItemsHandler {
-    pub fn new(order_repo: Arc<OrderRepo>, thread_pool: Arc<dyn ThreadPoolDyn>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(order_repo: Arc<dyn OrderRepository>, menu_repo: Arc<MenuRepo>, promotion_repo: Arc<PromotionRepo>, tax_policy: Arc<TaxPolicy>, cooking_time_estimator: Arc<dyn CookingTimeEstimator>, kitchen_repo: Arc<KitchenRepo>, kitchen: Arc<Kitchen>, ready_time_estimator: Arc<ReadyTimeEstimator>, clock: Arc<dyn Clock>) -> Self {
         AddMealItemsHandler {
             order_repo,
-            thread_pool,
+            menu_repo,
+            promotion_repo,
+            tax_policy,
+            cooking_time_estimator,
+            kitchen_repo,
+            kitchen,
+            ready_time_estimator,
+            clock,
         }
     }
 
     pub fn handle(&self, req: AddMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
-        let mut meal_items = Vec::with_capacity(req.menu_items.len());
-        for menu_item_req in req.menu_items {
-            let menu_item = MenuItem::create(
-                menu_item_req.menu_item_id,
-                menu_item_req.name,
-                menu_item_req.price,
-            );
+        self.handle_order(OrderKey::Table(req.table_id), req.menu_items)
+    }
 
-            meal_items.push(MealItem::create(menu_item));
-        }
+    pub fn handle_by_id(&self, order_id: Uuid, req: AddOrderMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id), req.menu_items)
+    }
 
-        let existed = self.order_repo.add_order_meal_items(req.table_id, meal_items.clone());
-        if !existed {
-            let resp = ErrResp {
-                error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+    fn handle_order(&self, key: OrderKey, menu_item_reqs: Vec<MenuItemReq>) -> Result<impl warp::Reply, warp::Rejection> {
+        let menu_item_ids = match to_menu_item_ids(&menu_item_reqs) {
+            Ok(menu_item_ids) => menu_item_ids,
+            Err(resp) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+        let (menu_items, unavailable_menu_item_ids) = self.menu_repo.resolve_menu_items(&menu_item_ids);
+        if !unavailable_menu_item_ids.is_empty() {
+            let resp = UnavailableMenuItemsResp {
+                unavailable_menu_item_ids,
+                error_message: MESSAGE_MENU_ITEMS_UNAVAILABLE.to_string(),
             };
             return Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
-                StatusCode::NOT_FOUND,
+                StatusCode::BAD_REQUEST,
             ));
         }
 
-        for meal_item in meal_items.iter() {
-            let meal_item_id = meal_item.id();
-            let table_id = req.table_id;
-            let order_repo_arc = Arc::clone(&self.order_repo);
-
-            self.thread_pool.execute(Box::new(move || {
-                if let Some(meal_item_arc) = order_repo_arc.get_order_meal_item(table_id, meal_item_id) {
-                    let meal_item = meal_item_arc.lock().unwrap();
-                    // If item is removed, continue without further processing
-                    if meal_item.is_removed() { return; }
-
-                    let cooking_time_in_min = meal_item.cooking_time_in_min();
-                    drop(meal_item);
-
-                    println!("start preparing {}", meal_item_id);
+        let now = self.clock.now();
+        let meal_items: Vec<MealItem> = menu_items.into_iter()
+            .map(|menu_item| MealItem::create(menu_item, self.cooking_time_estimator.as_ref(), now))
+            .collect();
 
-                    // Update status as Preparing to prevent meal item being canceled
-                    let existed = order_repo_arc.update_order_meal_item_status(table_id, meal_item_id, MealItemStatus::Preparing);
-                    if !existed { return; }
-
-                    // Simulates cooking time by putting the thread to sleep, blocking it from accepting new meals until the current meal is prepared.
-                    sleep(Duration::from_secs(cooking_time_in_min as u64));
+        // The order is checked while adding the meal items, so none is added once it was paid in full or canceled
+        let added = match key.get_order(&*self.order_repo)? {
+            Some(order) => self.order_repo.add_order_meal_items(order.id(), meal_items.clone(), now)?
+                .map(|(added, existed)| existed.then_some((added, order))),
+            None => Ok(None),
+        };
+        let order = match added {
+            Ok(Some((true, order))) => order,
+            Ok(Some((false, _))) => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_OPEN.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::CONFLICT,
+                ));
+            }
+            Ok(None) => {
+                let resp = ErrResp {
+                    error_message: key.order_not_found_message().to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+            Err(err) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&order_price_err_resp(err)),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
 
-                    order_repo_arc.update_order_meal_item_status(table_id, meal_item_id, MealItemStatus::Completed);
-                    println!("completed {}", meal_item_id);
-                }
-            }))
+        // The meal items are stored by now, so they are cooked even if the order can't be priced again
+        for meal_item in meal_items.iter() {
+            let job = cook_meal_item(self.order_repo.clone(), self.kitchen_repo.clone(), self.clock.clone(), order.id(), meal_item.id());
+            self.kitchen.execute(meal_item.station(), job, cooking_job_info(&order, meal_item));
         }
+        price_order(&*self.order_repo, &self.promotion_repo, &self.tax_policy, order.id(), now)?;
 
-        if let Some(order) = self.order_repo.get_order_by_table_id(req.table_id) {
+        if let Some(order) = self.order_repo.get_order(order.id())? {
             let resp = AddMealItemsResp {
-                data: OrderResp::new(order.lock().unwrap().clone(), false),
+                data: OrderResp::new(order, false, &self.ready_time_estimator.estimate()),
             };
             return Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
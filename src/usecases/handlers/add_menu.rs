@@ -0,0 +1,103 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_COOKING_TIME_PROFILE, MESSAGE_INVALID_PRICE};
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::{Price, PriceError};
+use crate::models::station::Station;
+use crate::repositories::menu::MenuRepo;
+use crate::usecases::models::menu_resp::MenuResp;
+
+#[derive(Deserialize)]
+pub struct MenuItemReq {
+    pub name: String,
+    pub price: String,
+    // Cooking time of every meal item ordered from the menu item, between 5 and 15 minutes if not given
+    #[serde(default)]
+    pub cooking_time_profile: CookingTimeProfile,
+    // Station cooking every meal item ordered from the menu item, the grill if not given
+    #[serde(default)]
+    pub station: Station,
+    // Ex. drinks or mains, which promotions can target
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+pub fn to_menu_items(menu_item_reqs: Vec<MenuItemReq>, currency: Currency) -> Result<Vec<MenuItem>, ErrResp> {
+    menu_item_reqs.into_iter()
+        .map(|menu_item_req| {
+            let price = Price::parse(&menu_item_req.price, currency).map_err(invalid_price_resp)?;
+            menu_item_req.cooking_time_profile.validate().map_err(invalid_cooking_time_profile_resp)?;
+            Ok(MenuItem::new(menu_item_req.name, price)
+                .with_cooking_time_profile(menu_item_req.cooking_time_profile)
+                .with_station(menu_item_req.station)
+                .with_category(to_category(menu_item_req.category)))
+        })
+        .collect()
+}
+
+// A blank category is no category
+pub fn to_category(category: Option<String>) -> Option<String> {
+    category.map(|category| category.trim().to_string()).filter(|category| !category.is_empty())
+}
+
+pub fn invalid_price_resp(err: PriceError) -> ErrResp {
+    ErrResp {
+        error_message: format!("{}: {}", MESSAGE_INVALID_PRICE, err),
+    }
+}
+
+pub fn invalid_cooking_time_profile_resp(err: String) -> ErrResp {
+    ErrResp {
+        error_message: format!("{}: {}", MESSAGE_INVALID_COOKING_TIME_PROFILE, err),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AddMenuReq {
+    pub name: String,
+    pub currency: Currency,
+    pub menu_items: Vec<MenuItemReq>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddMenuResp {
+    pub data: MenuResp,
+}
+
+pub struct AddMenuHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl AddMenuHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        AddMenuHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self, req: AddMenuReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let menu_items = match to_menu_items(req.menu_items, req.currency) {
+            Ok(menu_items) => menu_items,
+            Err(resp) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let menu = Menu::new(req.name, req.currency, menu_items);
+        let resp = AddMenuResp {
+            data: MenuResp::new(&menu),
+        };
+        self.menu_repo.add(menu);
+
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::CREATED,
+        ))
+    }
+}
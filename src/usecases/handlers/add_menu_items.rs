@@ -0,0 +1,84 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_NOT_FOUND};
+use crate::repositories::menu::MenuRepo;
+use crate::usecases::handlers::add_menu::{to_menu_items, MenuItemReq};
+use crate::usecases::models::menu_resp::MenuResp;
+
+#[derive(Deserialize)]
+pub struct AddMenuItemsReq {
+    pub menu_items: Vec<MenuItemReq>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddMenuItemsResp {
+    pub data: MenuResp,
+}
+
+pub struct AddMenuItemsHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl AddMenuItemsHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        AddMenuItemsHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self, menu_id: Uuid, req: AddMenuItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let currency = match self.menu_repo.get_currency(menu_id) {
+            Some(currency) => currency,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+
+        let menu_items = match to_menu_items(req.menu_items, currency) {
+            Ok(menu_items) => menu_items,
+            Err(resp) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let existed = self.menu_repo.add_menu_items(menu_id, menu_items);
+        if !existed {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        if let Some(menu_arc) = self.menu_repo.get(menu_id) {
+            let resp = AddMenuItemsResp {
+                data: MenuResp::new(&menu_arc.lock().unwrap()),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::OK,
+            ));
+        }
+
+        let resp = ErrResp {
+            error_message: StatusCode::INTERNAL_SERVER_ERROR.to_string()
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    }
+}
@@ -1,28 +1,77 @@
 use std::sync::{Arc};
-use std::thread::{sleep};
-use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_ADD_CONFLICT};
-use crate::libraries::thread_pool::{ThreadPoolDyn};
-use crate::models::meal::{MealItemStatus};
-use crate::models::menu::MenuItem;
-use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::usecases::models::error::{ErrResp, UnavailableMenuItemsResp, MESSAGE_MENU_ITEMS_UNAVAILABLE, MESSAGE_ORDER_ADD_CONFLICT, MESSAGE_ORDER_CURRENCY_MISMATCH, MESSAGE_GUESTS_INVALID, MESSAGE_ORDER_EMPTY, MESSAGE_ORDER_PRICE_INVALID, MESSAGE_QUANTITY_INVALID, MESSAGE_TOO_MANY_MEAL_ITEMS};
+use crate::libraries::clock::Clock;
+use crate::models::cooking_time::CookingTimeEstimator;
+use crate::models::meal::MealItem;
+use crate::models::order::{Order, OrderPriority};
+use crate::models::price::PriceError;
+use crate::models::promotion::apply_promotions;
+use crate::models::split::MAX_GUESTS;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::order::OrderRepository;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::jobs::cook_meal_item::{cook_meal_item, cooking_job_info};
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
 use crate::usecases::models::order_resp::OrderResp;
 
+// Every meal item is a job for the kitchen, so a request can't order more than a table would
+pub const MAX_QUANTITY: u32 = 99;
+pub const MAX_MEAL_ITEMS: u32 = 200;
+
 #[derive(Deserialize)]
 pub struct MenuItemReq {
     pub menu_item_id: Uuid,
-    pub name: String,
-    pub price: String,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+// Name and price are never taken from the client, only the ids are, and they are resolved against the menus
+pub fn to_menu_item_ids(menu_item_reqs: &[MenuItemReq]) -> Result<Vec<Uuid>, ErrResp> {
+    if menu_item_reqs.iter().any(|menu_item_req| menu_item_req.quantity == 0 || menu_item_req.quantity > MAX_QUANTITY) {
+        return Err(ErrResp {
+            error_message: MESSAGE_QUANTITY_INVALID.to_string(),
+        });
+    }
+    if menu_item_reqs.iter().map(|menu_item_req| u64::from(menu_item_req.quantity)).sum::<u64>() > u64::from(MAX_MEAL_ITEMS) {
+        return Err(ErrResp {
+            error_message: MESSAGE_TOO_MANY_MEAL_ITEMS.to_string(),
+        });
+    }
+    Ok(menu_item_reqs.iter()
+        .flat_map(|menu_item_req| std::iter::repeat_n(menu_item_req.menu_item_id, menu_item_req.quantity as usize))
+        .collect())
+}
+
+pub fn order_price_err_resp(err: PriceError) -> ErrResp {
+    let message = match err {
+        PriceError::CurrencyMismatch(_, _) => MESSAGE_ORDER_CURRENCY_MISMATCH,
+        _ => MESSAGE_ORDER_PRICE_INVALID,
+    };
+    ErrResp {
+        error_message: format!("{}: {}", message, err),
+    }
 }
 
 #[derive(Deserialize)]
 pub struct AddOrderReq {
     pub table_id: u32,
     pub menu_items: Vec<MenuItemReq>,
+    // Meal items of rush and VIP orders are cooked ahead of those of normal orders
+    #[serde(default)]
+    pub priority: OrderPriority,
+    // Parties of more guests than the tax policy allows are charged its service charge
+    #[serde(default)]
+    pub guests: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -31,74 +80,114 @@ pub struct AddOrderResp {
 }
 
 pub struct AddOrderHandler {
-    order_repo: Arc<OrderRepo>,
-    thread_pool: Arc<dyn ThreadPoolDyn>,
+    order_repo: Arc<dyn OrderRepository>,
+    menu_repo: Arc<MenuRepo>,
+    promotion_repo: Arc<PromotionRepo>,
+    tax_policy: Arc<TaxPolicy>,
+    cooking_time_estimator: Arc<dyn CookingTimeEstimator>,
+    kitchen_repo: Arc<KitchenRepo>,
+    kitchen: Arc<Kitchen>,
+    ready_time_estimator: Arc<ReadyTimeEstimator>,
+    clock: Arc<dyn Clock>,
 }
 
 impl AddOrderHandler {
-    pub fn new(order_repo: Arc<OrderRepo>, thread_pool: Arc<dyn ThreadPoolDyn>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(order_repo: Arc<dyn OrderRepository>, menu_repo: Arc<MenuRepo>, promotion_repo: Arc<PromotionRepo>, tax_policy: Arc<TaxPolicy>, cooking_time_estimator: Arc<dyn CookingTimeEstimator>, kitchen_repo: Arc<KitchenRepo>, kitchen: Arc<Kitchen>, ready_time_estimator: Arc<ReadyTimeEstimator>, clock: Arc<dyn Clock>) -> Self {
         AddOrderHandler {
             order_repo,
-            thread_pool,
+            menu_repo,
+            promotion_repo,
+            tax_policy,
+            cooking_time_estimator,
+            kitchen_repo,
+            kitchen,
+            ready_time_estimator,
+            clock,
         }
     }
 
     pub fn handle(&self, req: AddOrderReq) -> Result<impl warp::Reply, warp::Rejection> {
-        if let Some(order_arc) = self.order_repo.get_order_by_table_id(req.table_id) {
-            if order_arc.lock().unwrap().is_active() {
+        if req.guests.is_some_and(|guests| guests == 0 || guests > MAX_GUESTS) {
+            let resp = ErrResp {
+                error_message: MESSAGE_GUESTS_INVALID.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let menu_item_ids = match to_menu_item_ids(&req.menu_items) {
+            Ok(menu_item_ids) => menu_item_ids,
+            Err(resp) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let (menu_items, unavailable_menu_item_ids) = self.menu_repo.resolve_menu_items(&menu_item_ids);
+        if !unavailable_menu_item_ids.is_empty() {
+            let resp = UnavailableMenuItemsResp {
+                unavailable_menu_item_ids,
+                error_message: MESSAGE_MENU_ITEMS_UNAVAILABLE.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        // The order takes the currency of its first item; Order refuses any item in another currency
+        let currency = match menu_items.first() {
+            Some(menu_item) => menu_item.price().currency(),
+            None => {
                 let resp = ErrResp {
-                    error_message: MESSAGE_ORDER_ADD_CONFLICT.to_string()
+                    error_message: MESSAGE_ORDER_EMPTY.to_string(),
                 };
                 return Ok(warp::reply::with_status(
                     warp::reply::json(&resp),
-                    StatusCode::CONFLICT,
+                    StatusCode::BAD_REQUEST,
                 ));
             }
-        }
-        let mut menu_items = Vec::with_capacity(req.menu_items.len());
-        for menu_item_req in req.menu_items {
-            let menu_item = MenuItem::create(
-                menu_item_req.menu_item_id,
-                menu_item_req.name,
-                menu_item_req.price,
-            );
-            menu_items.push(menu_item);
-        }
+        };
 
-        let order = Order::new(req.table_id, menu_items.clone());
-        self.order_repo.add(order.clone());
+        let now = self.clock.now();
+        let meal_items = menu_items.into_iter()
+            .map(|menu_item| MealItem::create(menu_item, self.cooking_time_estimator.as_ref(), now))
+            .collect();
+        let mut order = match Order::new(req.table_id, currency, meal_items, now) {
+            Ok(order) => order.with_priority(req.priority).with_guests(req.guests),
+            Err(err) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&order_price_err_resp(err)),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+        // Priced before it is stored, so the order is added along with its promotions and taxes or not at all
+        let promotions = apply_promotions(&self.promotion_repo.get_all(), &order);
+        order.apply_pricing(promotions, self.tax_policy.as_ref().clone(), now);
+        if !self.order_repo.add(order.clone())? {
+            let resp = ErrResp {
+                error_message: MESSAGE_ORDER_ADD_CONFLICT.to_string()
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::CONFLICT,
+            ));
+        }
         for meal_item_arc in order.get_meal_items() {
             let meal_item = meal_item_arc.lock().unwrap();
-            let meal_item_id = meal_item.id();
-            let table_id = req.table_id;
-            let order_repo_arc = Arc::clone(&self.order_repo);
-
-            self.thread_pool.execute(Box::new(move || {
-                if let Some(meal_item_arc) = order_repo_arc.get_order_meal_item(table_id, meal_item_id) {
-                    let meal_item = meal_item_arc.lock().unwrap();
-                    if meal_item.is_removed() { return; }
-
-                    let cooking_time_in_min = meal_item.cooking_time_in_min();
-                    drop(meal_item);
-
-                    println!("start preparing {}", meal_item_id);
-
-                    let existed = order_repo_arc.update_order_meal_item_status(table_id, meal_item_id, MealItemStatus::Preparing);
-                    if !existed { return; }
-
-                    // Simulates cooking time by putting the thread to sleep, blocking it from accepting new meals until the current meal is prepared.
-                    sleep(Duration::from_secs(cooking_time_in_min as u64));
-
-                    order_repo_arc.update_order_meal_item_status(table_id, meal_item_id, MealItemStatus::Completed);
-
-                    println!("completed {}", meal_item_id);
-                }
-            }))
+            let job = cook_meal_item(self.order_repo.clone(), self.kitchen_repo.clone(), self.clock.clone(), order.id(), meal_item.id());
+            self.kitchen.execute(meal_item.station(), job, cooking_job_info(&order, &meal_item));
         }
 
-        if let Some(order) = self.order_repo.get_order_by_table_id(req.table_id) {
+        if let Some(order) = self.order_repo.get_order(order.id())? {
             let resp = AddOrderResp {
-                data: OrderResp::new(order.lock().unwrap().clone(), false),
+                data: OrderResp::new(order, false, &self.ready_time_estimator.estimate()),
             };
             return Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
@@ -0,0 +1,118 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::libraries::clock::Clock;
+use crate::models::payment::{Payment, PaymentError, PaymentMethod};
+use crate::models::price::Price;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::bill_resp::BillResp;
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_PAYMENT_AMOUNT, MESSAGE_ORDER_CLOSED, MESSAGE_ORDER_NOT_FOUND,
+                                     MESSAGE_PAYMENT_REFERENCE_MISSING, MESSAGE_PAYMENT_REFUSED};
+
+#[derive(Deserialize)]
+pub struct AddPaymentReq {
+    pub method: PaymentMethod,
+    // In minor units of the currency of the order, ex. "5095" for 50.95 USD
+    pub amount: String,
+    // Required for card payments
+    #[serde(default)]
+    pub reference: Option<String>,
+    // Check paid, required once the bill is split
+    #[serde(default)]
+    pub check: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddPaymentResp {
+    pub data: BillResp,
+}
+
+pub struct AddPaymentHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl AddPaymentHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, clock: Arc<dyn Clock>) -> Self {
+        AddPaymentHandler {
+            order_repo,
+            clock,
+        }
+    }
+
+    // Records part or all of the bill of the current order of the table as paid, and answers the bill as it stands after it
+    pub fn handle(&self, table_id: u32, req: AddPaymentReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let order = match self.order_repo.get_order_by_table_id(table_id)? {
+            Some(order) => order,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+
+        let reference = req.reference.filter(|reference| !reference.trim().is_empty());
+        if req.method == PaymentMethod::Card && reference.is_none() {
+            let resp = ErrResp {
+                error_message: MESSAGE_PAYMENT_REFERENCE_MISSING.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let amount = Price::parse(&req.amount, order.get_currency())
+            .map_err(|err| err.to_string())
+            .and_then(|amount| if amount.minor_units() > 0 || order.get_balance_due().minor_units() == 0 { Ok(amount) } else { Err(PaymentError::ZeroAmount.to_string()) });
+        let amount = match amount {
+            Ok(amount) => amount,
+            Err(err) => {
+                let resp = ErrResp {
+                    error_message: format!("{}: {}", MESSAGE_INVALID_PAYMENT_AMOUNT, err),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let payment = Payment::new(req.method, reference, amount, self.clock.now())
+            .with_check(req.check.map(|check| check.trim().to_string()));
+        let order = match self.order_repo.add_order_payment(order.id(), payment)? {
+            Ok(Some(order)) => order,
+            Ok(None) => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+            Err(err) => {
+                let error_message = match err {
+                    PaymentError::OrderClosed => MESSAGE_ORDER_CLOSED.to_string(),
+                    err => format!("{}: {}", MESSAGE_PAYMENT_REFUSED, err),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&ErrResp { error_message }),
+                    StatusCode::CONFLICT,
+                ));
+            }
+        };
+
+        let resp = AddPaymentResp {
+            data: BillResp::new(&order),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::CREATED,
+        ))
+    }
+}
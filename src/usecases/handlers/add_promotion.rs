@@ -0,0 +1,106 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_PROMOTION};
+use crate::models::currency::Currency;
+use crate::models::price::Price;
+use crate::models::promotion::{Promotion, PromotionError, PromotionTarget, Reward};
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::handlers::add_menu::invalid_price_resp;
+use crate::usecases::models::promotion_resp::PromotionResp;
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromotionTargetReq {
+    MenuItem { menu_item_id: Uuid },
+    Category { category: String },
+    Order,
+}
+
+// Amounts are whole numbers of minor units in the currency of the promotion, like menu item prices
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RewardReq {
+    PercentOff { target: PromotionTargetReq, percent: u32 },
+    AmountOff { target: PromotionTargetReq, amount: String },
+    BuyXGetY { target: PromotionTargetReq, buy: u32, get: u32 },
+    ComboPrice { menu_item_ids: Vec<Uuid>, price: String },
+}
+
+// Also the format of the promotions loaded from PROMOTIONS_FILE on startup
+#[derive(Deserialize)]
+pub struct AddPromotionReq {
+    pub name: String,
+    pub currency: Currency,
+    // Orders spending less than this before discounts don't get the promotion
+    #[serde(default)]
+    pub min_spend: Option<String>,
+    pub reward: RewardReq,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddPromotionResp {
+    pub data: PromotionResp,
+}
+
+fn to_target(target_req: PromotionTargetReq) -> PromotionTarget {
+    match target_req {
+        PromotionTargetReq::MenuItem { menu_item_id } => PromotionTarget::MenuItem(menu_item_id),
+        PromotionTargetReq::Category { category } => PromotionTarget::Category(category.trim().to_string()),
+        PromotionTargetReq::Order => PromotionTarget::Order,
+    }
+}
+
+pub fn to_promotion(req: AddPromotionReq) -> Result<Promotion, ErrResp> {
+    let parse = |amount: &str| Price::parse(amount, req.currency).map_err(invalid_price_resp);
+    let reward = match req.reward {
+        RewardReq::PercentOff { target, percent } => Reward::PercentOff { target: to_target(target), percent },
+        RewardReq::AmountOff { target, amount } => Reward::AmountOff { target: to_target(target), amount: parse(&amount)? },
+        RewardReq::BuyXGetY { target, buy, get } => Reward::BuyXGetY { target: to_target(target), buy, get },
+        RewardReq::ComboPrice { menu_item_ids, price } => Reward::ComboPrice { menu_item_ids, price: parse(&price)? },
+    };
+    let min_spend = req.min_spend.as_deref().map(parse).transpose()?;
+    Promotion::new(req.name, req.currency, reward, min_spend).map_err(invalid_promotion_resp)
+}
+
+fn invalid_promotion_resp(err: PromotionError) -> ErrResp {
+    ErrResp {
+        error_message: format!("{}: {}", MESSAGE_INVALID_PROMOTION, err),
+    }
+}
+
+pub struct AddPromotionHandler {
+    promotion_repo: Arc<PromotionRepo>,
+}
+
+impl AddPromotionHandler {
+    pub fn new(promotion_repo: Arc<PromotionRepo>) -> Self {
+        AddPromotionHandler {
+            promotion_repo,
+        }
+    }
+
+    // Orders get the promotion the next time they are priced, ex. when meal items are added or at checkout
+    pub fn handle(&self, req: AddPromotionReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let promotion = match to_promotion(req) {
+            Ok(promotion) => promotion,
+            Err(resp) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let resp = AddPromotionResp {
+            data: PromotionResp::new(&promotion),
+        };
+        self.promotion_repo.add(promotion);
+
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::CREATED,
+        ))
+    }
+}
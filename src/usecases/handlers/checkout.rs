@@ -0,0 +1,62 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::models::tax::TaxPolicy;
+use crate::libraries::clock::Clock;
+use crate::repositories::order::OrderRepository;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::jobs::price_order::price_order;
+use crate::usecases::models::bill_resp::BillResp;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CheckoutResp {
+    pub data: BillResp,
+}
+
+pub struct CheckoutHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    promotion_repo: Arc<PromotionRepo>,
+    tax_policy: Arc<TaxPolicy>,
+    clock: Arc<dyn Clock>,
+}
+
+impl CheckoutHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, promotion_repo: Arc<PromotionRepo>, tax_policy: Arc<TaxPolicy>, clock: Arc<dyn Clock>) -> Self {
+        CheckoutHandler {
+            order_repo,
+            promotion_repo,
+            tax_policy,
+            clock,
+        }
+    }
+
+    // Produces the bill of the current order of the table, which can be asked for again at any time, ex. after a partial payment.
+    // The order is priced with the promotions running now, unless it is already paid in full.
+    pub fn handle(&self, table_id: u32) -> Result<impl warp::Reply, warp::Rejection> {
+        match self.order_repo.get_order_by_table_id(table_id)? {
+            Some(order) => {
+                let order = match price_order(&*self.order_repo, &self.promotion_repo, &self.tax_policy, order.id(), self.clock.now())? {
+                    true => self.order_repo.get_order(order.id())?.unwrap_or(order),
+                    false => order,
+                };
+                let resp = CheckoutResp {
+                    data: BillResp::new(&order),
+                };
+                Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::OK,
+                ))
+            }
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::repositories::menu::MenuRepo;
+use crate::usecases::models::menu_resp::MenuResp;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListMenusResp {
+    pub data: Vec<MenuResp>,
+}
+
+pub struct ListMenusHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl ListMenusHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        ListMenusHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self) -> Result<impl warp::Reply, warp::Rejection> {
+        let resp = ListMenusResp {
+            data: self.menu_repo.get_all().iter()
+                .map(|menu_arc| MenuResp::new(&menu_arc.lock().unwrap()))
+                .collect(),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::models::promotion_resp::PromotionResp;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListPromotionsResp {
+    pub data: Vec<PromotionResp>,
+}
+
+pub struct ListPromotionsHandler {
+    promotion_repo: Arc<PromotionRepo>,
+}
+
+impl ListPromotionsHandler {
+    pub fn new(promotion_repo: Arc<PromotionRepo>) -> Self {
+        ListPromotionsHandler {
+            promotion_repo,
+        }
+    }
+
+    // Promotions are listed in the order they are tried when pricing an order
+    pub fn handle(&self) -> Result<impl warp::Reply, warp::Rejection> {
+        let resp = ListPromotionsResp {
+            data: self.promotion_repo.get_all().iter().map(PromotionResp::new).collect(),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
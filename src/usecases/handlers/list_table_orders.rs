@@ -0,0 +1,39 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::usecases::models::order_resp::OrderResp;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListTableOrdersResp {
+    pub data: Vec<OrderResp>,
+}
+
+pub struct ListTableOrdersHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    ready_time_estimator: Arc<ReadyTimeEstimator>,
+}
+
+impl ListTableOrdersHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, ready_time_estimator: Arc<ReadyTimeEstimator>) -> Self {
+        ListTableOrdersHandler {
+            order_repo,
+            ready_time_estimator,
+        }
+    }
+
+    // Lists every order the table has had, oldest first, including the completed and canceled ones
+    pub fn handle(&self, table_id: u32, include_removed_items: bool) -> Result<impl warp::Reply, warp::Rejection> {
+        let ready_times = self.ready_time_estimator.estimate();
+        let resp = ListTableOrdersResp {
+            data: self.order_repo.get_orders_by_table_id(table_id)?.into_iter()
+                .map(|order| OrderResp::new(order, include_removed_items, &ready_times))
+                .collect(),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+use warp::{Filter, Rejection, Reply};
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MANAGER_ONLY};
+
+// Header carrying the PIN of the manager, ex. X-Manager-Pin: 2468
+pub const MANAGER_PIN_HEADER: &str = "x-manager-pin";
+
+#[derive(Debug)]
+pub struct NotManager;
+
+impl warp::reject::Reject for NotManager {}
+
+// Lets requests through only if they carry the manager PIN, then rejects them, see recover_not_manager.
+// Without a PIN configured, nobody is a manager.
+pub fn manager_only(pin: Option<Arc<String>>) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>(MANAGER_PIN_HEADER)
+        .and_then(move |given_pin: Option<String>| {
+            let pin = pin.clone();
+            async move {
+                match (pin, given_pin) {
+                    (Some(pin), Some(given_pin)) if *pin == given_pin => Ok(()),
+                    _ => Err(warp::reject::custom(NotManager)),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+// Answers the requests rejected for lacking the manager PIN with 403, any other rejection is left to warp
+pub async fn recover_not_manager(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<NotManager>().is_some() {
+        let resp = ErrResp {
+            error_message: MESSAGE_MANAGER_ONLY.to_string(),
+        };
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::FORBIDDEN,
+        ));
+    }
+    Err(rejection)
+}
@@ -4,6 +4,28 @@ pub mod query_meal_item;
 pub mod add_meal_items;
 pub mod remove_meal_items;
 pub mod remove_order;
+pub mod add_menu;
+pub mod query_menu;
+pub mod list_menus;
+pub mod add_menu_items;
+pub mod update_menu_item;
+pub mod retire_menu_item;
+pub mod list_table_orders;
+pub mod query_kitchen_queue;
+pub mod shutdown;
+pub mod manager;
+pub mod void_meal_item;
+pub mod query_waste_report;
+pub mod serve_meal_items;
+pub mod query_pass;
+pub mod checkout;
+pub mod add_payment;
+pub mod split_bill;
+pub mod add_promotion;
+pub mod list_promotions;
+pub mod remove_promotion;
+pub mod query_receipt;
+pub mod stream_order_events;
+pub mod store;
 #[cfg(test)]
 mod tests;
-
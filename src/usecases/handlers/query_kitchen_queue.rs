@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::{Arc};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::libraries::clock::Clock;
+use crate::models::meal::MealItemStatus;
+use crate::models::station::Station;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct KitchenQueueItemResp {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub priority: String,
+    pub meal_item_id: Uuid,
+    pub name: String,
+    pub station: Station,
+    pub status: String,
+    pub cooking_time_in_min: u32,
+    // Seconds since the meal item was ordered
+    pub age_in_sec: i64,
+    // Worker of the station preparing the meal item, none while it is waiting in the queue
+    pub worker_id: Option<usize>,
+    // None if no worker will pick the meal item up
+    pub estimated_ready_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct StationQueueResp {
+    pub station: Station,
+    pub chef_count: usize,
+    pub preparing_count: usize,
+    pub waiting_count: usize,
+    // Time the station is done with every meal item it has, none if it has no chefs for those waiting
+    pub estimated_clear_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct KitchenQueueResp {
+    pub data: Vec<KitchenQueueItemResp>,
+    pub stations: Vec<StationQueueResp>,
+}
+
+// Station, then meal items being prepared, queued, or not queued, then the position in the station's queue,
+// then the time they started or were ordered
+type QueueSortKey = (usize, u8, usize, DateTime<Utc>);
+
+pub struct QueryKitchenQueueHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    kitchen_repo: Arc<KitchenRepo>,
+    kitchen: Arc<Kitchen>,
+    ready_time_estimator: Arc<ReadyTimeEstimator>,
+    clock: Arc<dyn Clock>,
+}
+
+impl QueryKitchenQueueHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, kitchen_repo: Arc<KitchenRepo>, kitchen: Arc<Kitchen>, ready_time_estimator: Arc<ReadyTimeEstimator>, clock: Arc<dyn Clock>) -> Self {
+        QueryKitchenQueueHandler {
+            order_repo,
+            kitchen_repo,
+            kitchen,
+            ready_time_estimator,
+            clock,
+        }
+    }
+
+    // Lists every meal item across all orders which is received or being prepared, station by station, or only those of
+    // the given station, in the order it will be cooked: meal items being prepared first, by the time they were started,
+    // then the waiting ones, in the order the station's chefs would pick them. Received meal items which are not queued,
+    // ex. those restored after a restart, come last by the time they were ordered.
+    pub fn handle(&self, station: Option<Station>) -> Result<impl warp::Reply, warp::Rejection> {
+        let now = self.clock.now();
+        let stations: Vec<Station> = match station {
+            Some(station) => vec![station],
+            None => Station::ALL.to_vec(),
+        };
+        let station_indexes: HashMap<Station, usize> = stations.iter().enumerate()
+            .map(|(station_index, station)| (*station, station_index))
+            .collect();
+        // Position of every queued meal item in the queue of its station, looked up once per meal item
+        let queue_positions: Vec<HashMap<Uuid, usize>> = stations.iter()
+            .map(|station| self.kitchen.pool(*station).queued_jobs().iter().enumerate().map(|(position, info)| (info.id, position)).collect())
+            .collect();
+        let ready_times = self.ready_time_estimator.estimate();
+        let mut queue: Vec<(QueueSortKey, KitchenQueueItemResp)> = Vec::new();
+        for order in self.order_repo.get_active_orders()? {
+            for meal_item_arc in order.get_meal_items() {
+                let meal_item = meal_item_arc.lock().unwrap();
+                if meal_item.is_removed() {
+                    continue;
+                }
+                let station_index = match station_indexes.get(&meal_item.station()) {
+                    Some(station_index) => *station_index,
+                    None => continue,
+                };
+
+                let sort_key = match meal_item.get_status() {
+                    MealItemStatus::Preparing => (station_index, 0, 0, meal_item.update_time()),
+                    MealItemStatus::Received => match queue_positions[station_index].get(&meal_item.id()) {
+                        Some(position) => (station_index, 1, *position, meal_item.creation_time()),
+                        None => (station_index, 2, 0, meal_item.creation_time()),
+                    },
+                    MealItemStatus::ReadyForPickup | MealItemStatus::Served | MealItemStatus::Voided => continue,
+                };
+                let item_resp = KitchenQueueItemResp {
+                    order_id: order.id(),
+                    table_id: order.get_table_id(),
+                    priority: order.get_priority().to_string(),
+                    meal_item_id: meal_item.id(),
+                    name: meal_item.get_name(),
+                    station: meal_item.station(),
+                    status: meal_item.get_status().to_string(),
+                    cooking_time_in_min: meal_item.cooking_time_in_min(),
+                    age_in_sec: (now - meal_item.creation_time()).num_seconds(),
+                    worker_id: self.kitchen_repo.get_worker_id(meal_item.id()),
+                    estimated_ready_time: ready_times.meal_item(&meal_item),
+                };
+                queue.push((sort_key, item_resp));
+            }
+        }
+        queue.sort_by_key(|(sort_key, _)| *sort_key);
+
+        let preparing = MealItemStatus::Preparing.to_string();
+        let station_resps = stations.iter()
+            .map(|station| {
+                let (preparing_items, waiting_items): (Vec<_>, Vec<_>) = queue.iter()
+                    .filter(|(_, item_resp)| item_resp.station == *station)
+                    .partition(|(_, item_resp)| item_resp.status == preparing);
+                StationQueueResp {
+                    station: *station,
+                    chef_count: self.kitchen.pool(*station).worker_count(),
+                    preparing_count: preparing_items.len(),
+                    waiting_count: waiting_items.len(),
+                    estimated_clear_time: ready_times.station(*station),
+                }
+            })
+            .collect();
+        let resp = KitchenQueueResp {
+            data: queue.into_iter().map(|(_, item_resp)| item_resp).collect(),
+            stations: station_resps,
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
@@ -2,9 +2,10 @@ use std::sync::{Arc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ITEM_NOT_FOUND};
+use crate::usecases::models::error::ErrResp;
 use crate::models::meal::{MealItem};
-use crate::repositories::order::OrderRepo;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::order_key::OrderKey;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MealItemResp {
@@ -14,6 +15,7 @@ pub struct MealItemResp {
     cooking_time_in_min: u32,
     is_removed: bool,
     status: String,
+    void_reason: Option<String>,
 }
 
 impl MealItemResp {
@@ -25,6 +27,7 @@ impl MealItemResp {
             cooking_time_in_min: item.cooking_time_in_min(),
             is_removed: item.is_removed(),
             status: item.get_status().to_string(),
+            void_reason: item.void_reason(),
         }
     }
 }
@@ -35,19 +38,30 @@ pub struct QueryMealItemResp {
 }
 
 pub struct QueryMealItemHandler {
-    order_repo: Arc<OrderRepo>,
+    order_repo: Arc<dyn OrderRepository>,
 }
 
 impl QueryMealItemHandler {
-    pub fn new(order_repo: Arc<OrderRepo>) -> Self {
+    pub fn new(order_repo: Arc<dyn OrderRepository>) -> Self {
         QueryMealItemHandler {
             order_repo,
         }
     }
 
     pub fn handle(&self, table_id: u32, meal_item_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
-        if let Some(item_arc) = self.order_repo.get_order_meal_item(table_id, meal_item_id) {
-            let item = item_arc.lock().unwrap().clone();
+        self.handle_order(OrderKey::Table(table_id), meal_item_id)
+    }
+
+    pub fn handle_by_id(&self, order_id: Uuid, meal_item_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id), meal_item_id)
+    }
+
+    fn handle_order(&self, key: OrderKey, meal_item_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        let item = match key.get_order(&*self.order_repo)? {
+            Some(order) => self.order_repo.get_order_meal_item(order.id(), meal_item_id)?,
+            None => None,
+        };
+        if let Some(item) = item {
             let resp = QueryMealItemResp {
                 data: MealItemResp::new(item),
             };
@@ -57,7 +71,7 @@ impl QueryMealItemHandler {
             ))
         } else {
             let resp = ErrResp {
-                error_message: MESSAGE_ITEM_NOT_FOUND.to_string(),
+                error_message: key.meal_item_not_found_message().to_string(),
             };
             Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
@@ -0,0 +1,45 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_NOT_FOUND};
+use crate::repositories::menu::MenuRepo;
+use crate::usecases::models::menu_resp::MenuResp;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryMenuResp {
+    pub data: MenuResp,
+}
+
+pub struct QueryMenuHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl QueryMenuHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        QueryMenuHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self, menu_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        if let Some(menu_arc) = self.menu_repo.get(menu_id) {
+            let menu = menu_arc.lock().unwrap();
+            let resp = QueryMenuResp {
+                data: MenuResp::new(&menu),
+            };
+            Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::OK,
+            ))
+        } else {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+            };
+            Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ))
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use std::sync::{Arc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
-use crate::repositories::order::OrderRepo;
+use crate::usecases::models::error::ErrResp;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::usecases::models::order_key::OrderKey;
 use crate::usecases::models::order_resp::OrderResp;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -11,21 +14,30 @@ pub struct QueryOrderResp {
 }
 
 pub struct QueryOrderHandler {
-    order_repo: Arc<OrderRepo>,
+    order_repo: Arc<dyn OrderRepository>,
+    ready_time_estimator: Arc<ReadyTimeEstimator>,
 }
 
 impl QueryOrderHandler {
-    pub fn new(order_repo: Arc<OrderRepo>) -> Self {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, ready_time_estimator: Arc<ReadyTimeEstimator>) -> Self {
         QueryOrderHandler {
             order_repo,
+            ready_time_estimator,
         }
     }
 
     pub fn handle(&self, table_id: u32, include_removed_items: bool) -> Result<impl warp::Reply, warp::Rejection> {
-        if let Some(order_arc) = self.order_repo.get_order_by_table_id(table_id) {
-            let order = order_arc.lock().unwrap().clone();
+        self.handle_order(OrderKey::Table(table_id), include_removed_items)
+    }
+
+    pub fn handle_by_id(&self, order_id: Uuid, include_removed_items: bool) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id), include_removed_items)
+    }
+
+    fn handle_order(&self, key: OrderKey, include_removed_items: bool) -> Result<impl warp::Reply, warp::Rejection> {
+        if let Some(order) = key.get_order(&*self.order_repo)? {
             let resp = QueryOrderResp {
-                data: OrderResp::new(order, include_removed_items),
+                data: OrderResp::new(order, include_removed_items, &self.ready_time_estimator.estimate()),
             };
             Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
@@ -33,7 +45,7 @@ impl QueryOrderHandler {
             ))
         } else {
             let resp = ErrResp {
-                error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                error_message: key.order_not_found_message().to_string(),
             };
             Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
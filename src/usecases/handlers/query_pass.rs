@@ -0,0 +1,73 @@
+use std::sync::{Arc};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::libraries::clock::Clock;
+use crate::models::meal::MealItemStatus;
+use crate::models::station::Station;
+use crate::repositories::order::OrderRepository;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PassItemResp {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub meal_item_id: Uuid,
+    pub name: String,
+    pub station: Station,
+    pub ready_time: DateTime<Utc>,
+    // Seconds the meal item has been waiting on the pass for a waiter
+    pub waiting_in_sec: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PassResp {
+    pub data: Vec<PassItemResp>,
+}
+
+pub struct QueryPassHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl QueryPassHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, clock: Arc<dyn Clock>) -> Self {
+        QueryPassHandler {
+            order_repo,
+            clock,
+        }
+    }
+
+    // Lists every meal item cooked but not served yet, the one waiting the longest first
+    pub fn handle(&self) -> Result<impl warp::Reply, warp::Rejection> {
+        let now = self.clock.now();
+        let mut pass_items = Vec::new();
+        for order in self.order_repo.get_orders_with_meal_items_in(MealItemStatus::ReadyForPickup)? {
+            for meal_item_arc in order.get_meal_items() {
+                let meal_item = meal_item_arc.lock().unwrap();
+                if meal_item.get_status() != MealItemStatus::ReadyForPickup {
+                    continue;
+                }
+                let ready_time = match meal_item.ready_time() {
+                    Some(ready_time) => ready_time,
+                    None => continue,
+                };
+                pass_items.push(PassItemResp {
+                    order_id: order.id(),
+                    table_id: order.get_table_id(),
+                    meal_item_id: meal_item.id(),
+                    name: meal_item.get_name(),
+                    station: meal_item.station(),
+                    ready_time,
+                    waiting_in_sec: (now - ready_time).num_seconds(),
+                });
+            }
+        }
+        pass_items.sort_by_key(|item| item.ready_time);
+
+        Ok(warp::reply::with_status(
+            warp::reply::json(&PassResp { data: pass_items }),
+            StatusCode::OK,
+        ))
+    }
+}
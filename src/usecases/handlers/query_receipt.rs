@@ -0,0 +1,121 @@
+use std::str::FromStr;
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use warp::reply::{Reply, Response};
+use crate::libraries::clock::Clock;
+use crate::models::receipt::{Receipt, ReceiptWidth, Restaurant};
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND, MESSAGE_RECEIPT_FORMAT_INVALID, MESSAGE_RECEIPT_WIDTH_INVALID};
+use crate::usecases::models::receipt_resp::ReceiptResp;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceiptFormat {
+    // Plain text for thermal printers
+    Text,
+    Html,
+    #[default]
+    Json,
+}
+
+impl ReceiptFormat {
+    pub const ALL: [ReceiptFormat; 3] = [ReceiptFormat::Text, ReceiptFormat::Html, ReceiptFormat::Json];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReceiptFormat::Text => "text",
+            ReceiptFormat::Html => "html",
+            ReceiptFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ReceiptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReceiptFormat::ALL.into_iter()
+            .find(|format| format.name() == s)
+            .ok_or_else(|| format!("unknown receipt format '{}'", s))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryReceiptResp {
+    pub data: ReceiptResp,
+}
+
+pub struct QueryReceiptHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    restaurant: Arc<Restaurant>,
+    clock: Arc<dyn Clock>,
+}
+
+impl QueryReceiptHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, restaurant: Arc<Restaurant>, clock: Arc<dyn Clock>) -> Self {
+        QueryReceiptHandler {
+            order_repo,
+            restaurant,
+            clock,
+        }
+    }
+
+    // Prints the receipt of the current order of the table as it was last priced, so it never changes the order.
+    // Receipts are JSON unless text or html is asked for, and text receipts are as wide as the paper, 42 columns unless 32 are asked for.
+    // Both are taken as given in the query, so what the route couldn't parse is refused here rather than left to other routes.
+    pub fn handle(&self, table_id: u32, format: Option<String>, width: Option<String>) -> Result<Response, warp::Rejection> {
+        let format = match format.as_deref().map(ReceiptFormat::from_str).transpose() {
+            Ok(format) => format.unwrap_or_default(),
+            Err(_) => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_RECEIPT_FORMAT_INVALID.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ).into_response());
+            }
+        };
+        let width = match width {
+            Some(columns) => match columns.trim().parse().ok().and_then(ReceiptWidth::from_columns) {
+                Some(width) => width,
+                None => {
+                    let resp = ErrResp {
+                        error_message: MESSAGE_RECEIPT_WIDTH_INVALID.to_string(),
+                    };
+                    return Ok(warp::reply::with_status(
+                        warp::reply::json(&resp),
+                        StatusCode::BAD_REQUEST,
+                    ).into_response());
+                }
+            },
+            None => ReceiptWidth::default(),
+        };
+
+        let order = match self.order_repo.get_order_by_table_id(table_id)? {
+            Some(order) => order,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ).into_response());
+            }
+        };
+
+        let receipt = Receipt::new(&order, &self.restaurant, self.clock.now());
+        let reply = match format {
+            ReceiptFormat::Text => warp::reply::with_header(receipt.to_text(width), "content-type", "text/plain; charset=utf-8").into_response(),
+            ReceiptFormat::Html => warp::reply::html(receipt.to_html()).into_response(),
+            ReceiptFormat::Json => {
+                let resp = QueryReceiptResp {
+                    data: ReceiptResp::new(&receipt),
+                };
+                warp::reply::json(&resp).into_response()
+            }
+        };
+        Ok(warp::reply::with_status(reply, StatusCode::OK).into_response())
+    }
+}
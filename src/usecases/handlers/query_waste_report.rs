@@ -0,0 +1,73 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::models::meal::MealItemStatus;
+use crate::models::price::Price;
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::error::{ErrResp, MESSAGE_WASTE_TOTAL_INVALID};
+use crate::usecases::models::price_resp::PriceResp;
+use crate::usecases::models::waste_resp::WastedMealItemResp;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WasteReportResp {
+    pub data: Vec<WastedMealItemResp>,
+    // One total per currency the wasted meal items were priced in
+    pub total_wasted_costs: Vec<PriceResp>,
+}
+
+pub struct QueryWasteReportHandler {
+    order_repo: Arc<dyn OrderRepository>,
+}
+
+impl QueryWasteReportHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>) -> Self {
+        QueryWasteReportHandler {
+            order_repo,
+        }
+    }
+
+    // Lists every meal item voided so far, by the time it was voided, along with the cost wasted
+    pub fn handle(&self) -> Result<impl warp::Reply, warp::Rejection> {
+        let mut wasted_meal_items = Vec::new();
+        let mut totals: Vec<Price> = Vec::new();
+        for order in self.order_repo.get_orders_with_meal_items_in(MealItemStatus::Voided)? {
+            for meal_item_arc in order.get_meal_items() {
+                let meal_item = meal_item_arc.lock().unwrap();
+                let wasted_cost = match meal_item.wasted_cost() {
+                    Some(wasted_cost) => wasted_cost,
+                    None => continue,
+                };
+                let total = match totals.iter_mut().find(|total| total.currency() == wasted_cost.currency()) {
+                    Some(total) => total,
+                    None => {
+                        totals.push(Price::zero(wasted_cost.currency()));
+                        totals.last_mut().unwrap()
+                    }
+                };
+                *total = match total.checked_add(wasted_cost) {
+                    Ok(sum) => sum,
+                    Err(_) => {
+                        let resp = ErrResp {
+                            error_message: MESSAGE_WASTE_TOTAL_INVALID.to_string(),
+                        };
+                        return Ok(warp::reply::with_status(
+                            warp::reply::json(&resp),
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                        ));
+                    }
+                };
+                wasted_meal_items.extend(WastedMealItemResp::new(&order, &meal_item));
+            }
+        }
+        wasted_meal_items.sort_by_key(|meal_item| meal_item.voided_time);
+
+        let resp = WasteReportResp {
+            data: wasted_meal_items,
+            total_wasted_costs: totals.into_iter().map(PriceResp::new).collect(),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
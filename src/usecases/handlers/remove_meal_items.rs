@@ -3,8 +3,13 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use warp::http::StatusCode;
 use warp::reply::json;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_REMOVED, MESSAGE_ORDER_NOT_FOUND};
-use crate::repositories::order::OrderRepo;
+use crate::models::tax::TaxPolicy;
+use crate::libraries::clock::Clock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_REMOVED};
+use crate::repositories::order::OrderRepository;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::jobs::price_order::price_order;
+use crate::usecases::models::order_key::OrderKey;
 
 #[derive(Deserialize)]
 pub struct RemoveMealItemsReq {
@@ -12,6 +17,11 @@ pub struct RemoveMealItemsReq {
     pub meal_item_ids: Vec<Uuid>,
 }
 
+#[derive(Deserialize)]
+pub struct RemoveOrderMealItemsReq {
+    pub meal_item_ids: Vec<Uuid>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct RemoveMealItemsResp {
     pub non_removable_meal_item_ids: Vec<Uuid>,
@@ -19,21 +29,43 @@ pub struct RemoveMealItemsResp {
 }
 
 pub struct RemoveMealItemsHandler {
-    order_repo: Arc<OrderRepo>,
+    order_repo: Arc<dyn OrderRepository>,
+    promotion_repo: Arc<PromotionRepo>,
+    tax_policy: Arc<TaxPolicy>,
+    clock: Arc<dyn Clock>,
 }
 
 impl RemoveMealItemsHandler {
-    pub fn new(order_repo: Arc<OrderRepo>) -> Self {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, promotion_repo: Arc<PromotionRepo>, tax_policy: Arc<TaxPolicy>, clock: Arc<dyn Clock>) -> Self {
         RemoveMealItemsHandler {
             order_repo,
+            promotion_repo,
+            tax_policy,
+            clock,
         }
     }
 
     pub fn handle(&self, req: RemoveMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
-        let (ids, existed) = self.order_repo.remove_order_meal_items(req.table_id, req.meal_item_ids);
+        self.handle_order(OrderKey::Table(req.table_id), req.meal_item_ids)
+    }
+
+    pub fn handle_by_id(&self, order_id: Uuid, req: RemoveOrderMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id), req.meal_item_ids)
+    }
+
+    fn handle_order(&self, key: OrderKey, meal_item_ids: Vec<Uuid>) -> Result<impl warp::Reply, warp::Rejection> {
+        let now = self.clock.now();
+        let (ids, existed) = match key.get_order(&*self.order_repo)? {
+            Some(order) => {
+                let removed = self.order_repo.remove_order_meal_items(order.id(), meal_item_ids, now)?;
+                price_order(&*self.order_repo, &self.promotion_repo, &self.tax_policy, order.id(), now)?;
+                removed
+            }
+            None => (vec![], false),
+        };
         if !existed {
             let resp = ErrResp {
-                error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                error_message: key.order_not_found_message().to_string(),
             };
             return Ok(warp::reply::with_status(
                 json(&resp),
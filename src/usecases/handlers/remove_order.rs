@@ -1,24 +1,40 @@
 use std::sync::{Arc};
+use uuid::Uuid;
 use warp::http::{StatusCode};
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND, MESSAGE_ORDER_REMOVAL_CONFLICT};
-use crate::repositories::order::OrderRepo;
+use crate::libraries::clock::Clock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_REMOVAL_CONFLICT};
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::order_key::OrderKey;
 
 pub struct RemoveOrderHandler {
-    order_repo: Arc<OrderRepo>,
+    order_repo: Arc<dyn OrderRepository>,
+    clock: Arc<dyn Clock>,
 }
 
 impl RemoveOrderHandler {
-    pub fn new(order_repo: Arc<OrderRepo>) -> Self {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, clock: Arc<dyn Clock>) -> Self {
         RemoveOrderHandler {
             order_repo,
+            clock,
         }
     }
 
     pub fn handle(&self, table_id: u32) -> Result<impl warp::Reply, warp::Rejection> {
-        let (result, existed) = self.order_repo.remove_order(table_id);
+        self.handle_order(OrderKey::Table(table_id))
+    }
+
+    pub fn handle_by_id(&self, order_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id))
+    }
+
+    fn handle_order(&self, key: OrderKey) -> Result<impl warp::Reply, warp::Rejection> {
+        let (result, existed) = match key.get_order(&*self.order_repo)? {
+            Some(order) => self.order_repo.remove_order(order.id(), self.clock.now())?,
+            None => (false, false),
+        };
         if !existed {
             let resp = ErrResp {
-                error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                error_message: key.order_not_found_message().to_string(),
             };
             return Ok(warp::reply::with_status(
                 warp::reply::json(&resp),
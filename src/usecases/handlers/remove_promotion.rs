@@ -0,0 +1,35 @@
+use std::sync::{Arc};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_PROMOTION_NOT_FOUND};
+use crate::repositories::promotion::PromotionRepo;
+
+pub struct RemovePromotionHandler {
+    promotion_repo: Arc<PromotionRepo>,
+}
+
+impl RemovePromotionHandler {
+    pub fn new(promotion_repo: Arc<PromotionRepo>) -> Self {
+        RemovePromotionHandler {
+            promotion_repo,
+        }
+    }
+
+    // Orders keep the promotion until they are priced again
+    pub fn handle(&self, promotion_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        if self.promotion_repo.remove(promotion_id) {
+            Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({})),
+                StatusCode::NO_CONTENT,
+            ))
+        } else {
+            let resp = ErrResp {
+                error_message: MESSAGE_PROMOTION_NOT_FOUND.to_string(),
+            };
+            Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ))
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::{Arc};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_ITEM_NOT_FOUND, MESSAGE_MENU_NOT_FOUND};
+use crate::repositories::menu::MenuRepo;
+
+pub struct RetireMenuItemHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl RetireMenuItemHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        RetireMenuItemHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self, menu_id: Uuid, menu_item_id: Uuid) -> Result<impl warp::Reply, warp::Rejection> {
+        let (retired, existed) = self.menu_repo.retire_menu_item(menu_id, menu_item_id);
+        if !existed {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        if retired {
+            Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({})),
+                StatusCode::NO_CONTENT,
+            ))
+        } else {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_ITEM_NOT_FOUND.to_string(),
+            };
+            Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ))
+        }
+    }
+}
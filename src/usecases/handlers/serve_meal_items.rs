@@ -0,0 +1,81 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use warp::reply::json;
+use crate::libraries::clock::Clock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_SERVED};
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::order_key::OrderKey;
+
+#[derive(Deserialize)]
+pub struct ServeMealItemsReq {
+    pub table_id: u32,
+    pub meal_item_ids: Vec<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct ServeOrderMealItemsReq {
+    pub meal_item_ids: Vec<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ServeMealItemsResp {
+    pub non_servable_meal_item_ids: Vec<Uuid>,
+    pub message: String,
+}
+
+// Waiters mark the meal items they took from the pass to the table as served
+pub struct ServeMealItemsHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ServeMealItemsHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, clock: Arc<dyn Clock>) -> Self {
+        ServeMealItemsHandler {
+            order_repo,
+            clock,
+        }
+    }
+
+    pub fn handle(&self, req: ServeMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Table(req.table_id), req.meal_item_ids)
+    }
+
+    pub fn handle_by_id(&self, order_id: Uuid, req: ServeOrderMealItemsReq) -> Result<impl warp::Reply, warp::Rejection> {
+        self.handle_order(OrderKey::Id(order_id), req.meal_item_ids)
+    }
+
+    fn handle_order(&self, key: OrderKey, meal_item_ids: Vec<Uuid>) -> Result<impl warp::Reply, warp::Rejection> {
+        let (ids, existed) = match key.get_order(&*self.order_repo)? {
+            Some(order) => self.order_repo.serve_order_meal_items(order.id(), meal_item_ids, self.clock.now())?,
+            None => (vec![], false),
+        };
+        if !existed {
+            let resp = ErrResp {
+                error_message: key.order_not_found_message().to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        if ids.is_empty() {
+            Ok(warp::reply::with_status(
+                json(&serde_json::json!({})),
+                StatusCode::NO_CONTENT,
+            ))
+        } else {
+            let error_resp = ServeMealItemsResp {
+                non_servable_meal_item_ids: ids,
+                message: MESSAGE_ITEMS_PARTIALLY_SERVED.to_string(),
+            };
+            Ok(warp::reply::with_status(
+                json(&error_resp),
+                StatusCode::OK,
+            ))
+        }
+    }
+}
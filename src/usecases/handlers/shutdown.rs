@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use warp::{Filter, Rejection, Reply};
+use warp::http::StatusCode;
+use crate::libraries::shutdown::Shutdown;
+use crate::usecases::models::error::{ErrResp, MESSAGE_SHUTTING_DOWN};
+
+#[derive(Debug)]
+pub struct ShuttingDown;
+
+impl warp::reject::Reject for ShuttingDown {}
+
+// Lets changes through until the server starts shutting down, then rejects them, see recover_shutting_down
+pub fn accepting_changes(shutdown: Arc<Shutdown>) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::any()
+        .and_then(move || {
+            let shutdown = shutdown.clone();
+            async move {
+                if shutdown.is_draining() {
+                    Err(warp::reject::custom(ShuttingDown))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .untuple_one()
+}
+
+// Answers the changes rejected while shutting down with 503, any other rejection is left to warp
+pub async fn recover_shutting_down(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<ShuttingDown>().is_some() {
+        let resp = ErrResp {
+            error_message: MESSAGE_SHUTTING_DOWN.to_string(),
+        };
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::SERVICE_UNAVAILABLE,
+        ));
+    }
+    Err(rejection)
+}
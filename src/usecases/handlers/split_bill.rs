@@ -0,0 +1,101 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use warp::http::StatusCode;
+use crate::libraries::clock::Clock;
+use crate::models::split::{BillSplit, NamedCheck, SplitError};
+use crate::repositories::order::OrderRepository;
+use crate::usecases::models::bill_resp::BillResp;
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_SPLIT, MESSAGE_ORDER_CLOSED, MESSAGE_ORDER_NOT_FOUND,
+                                     MESSAGE_SPLIT_MODE_INVALID, MESSAGE_SPLIT_REFUSED};
+
+// Either checks, to put every meal item on one of them, or guests, to share the total evenly among them
+#[derive(Deserialize)]
+pub struct SplitBillReq {
+    #[serde(default)]
+    pub checks: Option<Vec<NamedCheck>>,
+    #[serde(default)]
+    pub guests: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SplitBillResp {
+    pub data: BillResp,
+}
+
+pub struct SplitBillHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SplitBillHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, clock: Arc<dyn Clock>) -> Self {
+        SplitBillHandler {
+            order_repo,
+            clock,
+        }
+    }
+
+    // Splits the bill of the current order of the table into checks, and answers the bill along with them
+    pub fn handle(&self, table_id: u32, req: SplitBillReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let order = match self.order_repo.get_order_by_table_id(table_id)? {
+            Some(order) => order,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+
+        let split = match (req.checks, req.guests) {
+            (Some(checks), None) => BillSplit::ByMealItem { checks },
+            (None, Some(guests)) => BillSplit::Evenly { guests },
+            _ => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_SPLIT_MODE_INVALID.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        let order = match self.order_repo.split_order_bill(order.id(), split, self.clock.now())? {
+            Ok(Some(order)) => order,
+            Ok(None) => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+            Err(err) => {
+                let (error_message, status) = match err {
+                    SplitError::OrderClosed => (MESSAGE_ORDER_CLOSED.to_string(), StatusCode::CONFLICT),
+                    SplitError::PaidBeforeSplit | SplitError::PaidCheckMissing(_) | SplitError::CheckOverpaid(_) => {
+                        (format!("{}: {}", MESSAGE_SPLIT_REFUSED, err), StatusCode::CONFLICT)
+                    }
+                    err => (format!("{}: {}", MESSAGE_INVALID_SPLIT, err), StatusCode::BAD_REQUEST),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&ErrResp { error_message }),
+                    status,
+                ));
+            }
+        };
+
+        let resp = SplitBillResp {
+            data: BillResp::new(&order),
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
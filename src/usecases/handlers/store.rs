@@ -0,0 +1,22 @@
+use warp::{Rejection, Reply};
+use warp::http::StatusCode;
+use crate::repositories::order::OrderStoreError;
+use crate::usecases::models::error::{ErrResp, MESSAGE_STORE_FAILED};
+
+// Lets handlers give up on the request with ? once the orders can't be read or stored, see recover_store_failed
+impl warp::reject::Reject for OrderStoreError {}
+
+// Answers the requests given up as the orders couldn't be read or stored with 500, any other rejection is left to warp
+pub async fn recover_store_failed(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if let Some(err) = rejection.find::<OrderStoreError>() {
+        eprintln!("{}", err);
+        let resp = ErrResp {
+            error_message: MESSAGE_STORE_FAILED.to_string(),
+        };
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ));
+    }
+    Err(rejection)
+}
@@ -0,0 +1,93 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use futures_util::{future, stream, Stream, StreamExt};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use warp::http::StatusCode;
+use warp::reply::{Reply, Response};
+use warp::sse::Event;
+use crate::models::order_event::{EventId, OrderEvent};
+use crate::repositories::order_event::{OrderEventRepo, Subscription};
+use crate::usecases::models::error::{ErrResp, MESSAGE_EVENTS_CLOSED};
+use crate::usecases::models::order_event_resp::OrderEventResp;
+
+// Sent when events the client didn't get are no longer kept, so it reloads the orders instead of missing changes
+const RESET_EVENT: &str = "reset";
+
+// What a client following the changes of orders is sent
+enum Streamed {
+    Event(OrderEvent),
+    Reset,
+}
+
+pub struct StreamOrderEventsHandler {
+    order_event_repo: Arc<OrderEventRepo>,
+}
+
+impl StreamOrderEventsHandler {
+    pub fn new(order_event_repo: Arc<OrderEventRepo>) -> Self {
+        StreamOrderEventsHandler {
+            order_event_repo,
+        }
+    }
+
+    // Streams the changes of the orders of the table, or of every order without one, as server-sent events.
+    // New clients only get the events published from now on, while those reconnecting with the id of the last event
+    // they got first get the events kept since; one they can't parse is taken as none, as browsers send back whatever id
+    // they were last given. Those who missed events no longer kept, or fell too far behind while following, are sent
+    // a reset event telling them to reload the orders.
+    pub fn handle(&self, table_id: Option<u32>, last_event_id: Option<String>) -> Result<Response, warp::Rejection> {
+        let last_event_id = last_event_id.and_then(|last_event_id| last_event_id.trim().parse::<EventId>().ok());
+        let subscription = match self.order_event_repo.subscribe(last_event_id) {
+            Some(subscription) => subscription,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_EVENTS_CLOSED.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::SERVICE_UNAVAILABLE,
+                ).into_response());
+            }
+        };
+
+        let events = order_events(subscription)
+            .filter(move |streamed| future::ready(match streamed {
+                Streamed::Event(event) => table_id.is_none_or(|table_id| event.table_id() == table_id),
+                Streamed::Reset => true,
+            }))
+            .map(|streamed| to_sse_event(&streamed));
+        Ok(warp::sse::reply(warp::sse::keep_alive().stream(events)).into_response())
+    }
+}
+
+// The events kept followed by those published from now on, until the repository is closed, with a reset wherever
+// some were missed. A client too slow to keep up skips the events it fell behind on.
+fn order_events(subscription: Subscription) -> impl Stream<Item = Streamed> {
+    let Subscription { missed_events, events, receiver } = subscription;
+    let reset = missed_events.then_some(Streamed::Reset);
+    let published = stream::unfold(receiver, |mut receiver: broadcast::Receiver<OrderEvent>| async move {
+        match receiver.recv().await {
+            Ok(event) => Some((Streamed::Event(event), receiver)),
+            Err(RecvError::Lagged(_)) => Some((Streamed::Reset, receiver)),
+            Err(RecvError::Closed) => None,
+        }
+    });
+    stream::iter(reset).chain(stream::iter(events).map(Streamed::Event)).chain(published)
+}
+
+// A reset has no id, so a client reconnecting after one still resumes from the last event it got
+fn to_sse_event(streamed: &Streamed) -> Result<Event, Infallible> {
+    match streamed {
+        Streamed::Event(event) => {
+            let resp = OrderEventResp::new(event);
+            Ok(Event::default()
+                .id(event.id().to_string())
+                .event(event.change().name())
+                .data(serde_json::to_string(&resp).unwrap_or_default()))
+        }
+        Streamed::Reset => Ok(Event::default()
+            .event(RESET_EVENT)
+            .data(serde_json::json!({ "type": RESET_EVENT }).to_string())),
+    }
+}
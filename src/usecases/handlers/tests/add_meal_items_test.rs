@@ -1,36 +1,58 @@
 use std::sync::Arc;
+use chrono::Utc;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::handlers::add_meal_items::{AddMealItemsHandler, AddMealItemsReq, AddMealItemsResp, MenuItemReq};
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::add_meal_items::{AddMealItemsHandler, AddMealItemsReq, AddMealItemsResp, AddOrderMealItemsReq};
+use crate::usecases::handlers::add_order::{MenuItemReq, MAX_QUANTITY};
+use crate::usecases::models::error::{ErrResp, UnavailableMenuItemsResp, MESSAGE_MENU_ITEMS_UNAVAILABLE, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_NOT_OPEN, MESSAGE_ORDER_NOT_FOUND, MESSAGE_QUANTITY_INVALID};
 use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+use crate::models::currency::Currency;
+use crate::models::meal::MealItem;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::Price;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
 use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::tax::TaxPolicy;
 
 #[tokio::test]
 async fn test_add_meal_items_handler_handle_success() {
     let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
     let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
 
-    let handler = AddMealItemsHandler::new(order_repo.clone(), thread_pool.clone());
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
 
-    let order = Order::new(1, vec![]);
-    order_repo.add(order);
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone(), burger.clone()]));
+
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let req = AddMealItemsReq {
         table_id: 1,
         menu_items: vec![
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("fries"),
-                price: String::from("345"),
+                menu_item_id: fries.id(),
+                quantity: 1,
             },
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("burger"),
-                price: String::from("789"),
+                menu_item_id: burger.id(),
+                quantity: 1,
             },
         ],
     };
@@ -44,13 +66,13 @@ async fn test_add_meal_items_handler_handle_success() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: AddMealItemsResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: AddMealItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     thread_pool.wait();
     assert_eq!(status, StatusCode::OK);
     assert_eq!(2, thread_pool.get_count());
     // can be improved here
-    assert_eq!("1134", actual_body.data.total_price);
+    assert_eq!("1134", actual_body.data.total_price.amount);
     assert_eq!("Received", actual_body.data.status);
     assert_eq!(2, actual_body.data.meal_items.len());
 }
@@ -58,22 +80,28 @@ async fn test_add_meal_items_handler_handle_success() {
 #[tokio::test]
 async fn test_add_meal_items_handler_handle_not_found() {
     let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
     let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
 
-    let handler = AddMealItemsHandler::new(order_repo.clone(), thread_pool.clone());
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone(), burger.clone()]));
 
     let req = AddMealItemsReq {
         table_id: 1,
         menu_items: vec![
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("fries"),
-                price: String::from("345"),
+                menu_item_id: fries.id(),
+                quantity: 1,
             },
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("burger"),
-                price: String::from("789"),
+                menu_item_id: burger.id(),
+                quantity: 1,
             },
         ],
     };
@@ -85,7 +113,7 @@ async fn test_add_meal_items_handler_handle_not_found() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_NOT_FOUND.to_string() };
 
@@ -94,3 +122,287 @@ async fn test_add_meal_items_handler_handle_not_found() {
     assert_eq!(expected_body, actual_body);
     assert_eq!(0, thread_pool.get_count());
 }
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_unavailable_menu_items() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
+
+    let unknown_menu_item_id = Uuid::new_v4();
+    let req = AddMealItemsReq {
+        table_id: 1,
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: unknown_menu_item_id,
+                quantity: 2,
+            },
+        ],
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: UnavailableMenuItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body = UnavailableMenuItemsResp {
+        unavailable_menu_item_ids: vec![unknown_menu_item_id],
+        error_message: MESSAGE_MENU_ITEMS_UNAVAILABLE.to_string(),
+    };
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(expected_body, actual_body);
+    assert_eq!(0, thread_pool.get_count());
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_by_id_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+
+    let req = AddOrderMealItemsReq {
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 2,
+            },
+        ],
+    };
+
+    let response = handler.handle_by_id(order_id, req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: AddMealItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(2, thread_pool.get_count());
+    assert_eq!(order_id, actual_body.data.order_id);
+    assert_eq!("690", actual_body.data.total_price.amount);
+    assert_eq!(2, actual_body.data.meal_items.len());
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+
+    let req = AddOrderMealItemsReq {
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 1,
+            },
+        ],
+    };
+
+    let response = handler.handle_by_id(Uuid::new_v4(), req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string() };
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+    assert_eq!(0, thread_pool.get_count());
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_closed_order() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+
+    // Given the order is paid in full, it is closed
+    let meal_item = MealItem::create(fries.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![meal_item], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, fries.price(), Utc::now())).unwrap().unwrap();
+
+    let req = AddMealItemsReq {
+        table_id: 1,
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 1,
+            },
+        ],
+    };
+
+    let response = handler.handle(req).unwrap().into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(MESSAGE_ORDER_NOT_OPEN, actual_body.error_message);
+    assert_eq!(0, thread_pool.get_count());
+    assert_eq!(1, order_repo.get_order(order.id()).unwrap().unwrap().get_meal_items().len());
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_by_id_order_not_open() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+
+    // Given the order is canceled and the table has moved on to another order
+    let meal_item = MealItem::create(fries.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+    let canceled_order = Order::new(1, Currency::Usd, vec![meal_item], Utc::now()).unwrap();
+    order_repo.add(canceled_order.clone()).unwrap();
+    order_repo.remove_order(canceled_order.id(), Utc::now()).unwrap();
+    let meal_item = MealItem::create(fries.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+    let current_order = Order::new(1, Currency::Usd, vec![meal_item], Utc::now()).unwrap();
+    assert!(order_repo.add(current_order.clone()).unwrap());
+
+    let req = AddOrderMealItemsReq {
+        menu_items: vec![MenuItemReq { menu_item_id: fries.id(), quantity: 1 }],
+    };
+    let response = handler.handle_by_id(canceled_order.id(), req).unwrap().into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(StatusCode::CONFLICT, status);
+    assert_eq!(MESSAGE_ORDER_NOT_OPEN, actual_body.error_message);
+    assert_eq!(0, thread_pool.get_count());
+    assert_eq!(1, order_repo.get_order(canceled_order.id()).unwrap().unwrap().get_meal_items().len());
+    assert!(!order_repo.get_order(canceled_order.id()).unwrap().unwrap().is_open());
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_prices_order() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), promotion_repo.clone(), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let soda = MenuItem::new(String::from("soda"), Price::from_minor_units(250, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("drinks"), Currency::Usd, vec![soda.clone()]));
+    let promotion = Promotion::new(String::from("soda 1+1"), Currency::Usd,
+                                   Reward::BuyXGetY { target: PromotionTarget::MenuItem(soda.id()), buy: 1, get: 1 }, None).unwrap();
+    promotion_repo.add(promotion.clone());
+
+    let order = Order::new(1, Currency::Usd, vec![MealItem::create(soda.clone(), &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
+
+    // Given the order has one soda, adding a second one makes it free
+    let req = AddMealItemsReq {
+        table_id: 1,
+        menu_items: vec![MenuItemReq { menu_item_id: soda.id(), quantity: 1 }],
+    };
+    let response = handler.handle(req).unwrap().into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddMealItemsResp = serde_json::from_slice(&body).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!("500", actual_body.data.subtotal.amount);
+    assert_eq!("250", actual_body.data.total_price.amount);
+    assert_eq!(1, actual_body.data.promotions.len());
+    assert_eq!((promotion.id(), "250"), (actual_body.data.promotions[0].promotion_id, actual_body.data.promotions[0].saved.amount.as_str()));
+}
+
+#[tokio::test]
+async fn test_add_meal_items_handler_handle_quantity() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddMealItemsHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+
+    for quantity in [0, MAX_QUANTITY + 1] {
+        let req = AddMealItemsReq { table_id: 1, menu_items: vec![MenuItemReq { menu_item_id: fries.id(), quantity }] };
+        let response = handler.handle(req).unwrap().into_response();
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+        assert_eq!(MESSAGE_QUANTITY_INVALID, actual_body.error_message);
+    }
+
+    let req = AddOrderMealItemsReq { menu_items: vec![MenuItemReq { menu_item_id: fries.id(), quantity: MAX_QUANTITY }] };
+    assert_eq!(StatusCode::OK, handler.handle_by_id(order.id(), req).unwrap().into_response().status());
+    thread_pool.wait();
+    assert_eq!(MAX_QUANTITY as usize, thread_pool.get_count());
+}
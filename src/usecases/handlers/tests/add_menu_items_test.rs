@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_NOT_FOUND};
+use crate::usecases::handlers::add_menu::MenuItemReq;
+use crate::usecases::handlers::add_menu_items::{AddMenuItemsHandler, AddMenuItemsReq, AddMenuItemsResp};
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::station::Station;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::Price;
+use crate::repositories::menu::MenuRepo;
+
+#[tokio::test]
+async fn test_add_menu_items_handler_handle_success() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = AddMenuItemsHandler::new(menu_repo.clone());
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item]);
+    let menu_id = menu.id();
+    menu_repo.add(menu);
+
+    let req = AddMenuItemsReq {
+        menu_items: vec![
+            MenuItemReq {
+                name: String::from("burger"),
+                price: String::from("789"),
+                cooking_time_profile: CookingTimeProfile::default(),
+                station: Station::default(),
+                category: None,
+            },
+        ],
+    };
+
+    let response = handler.handle(menu_id, req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: AddMenuItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(2, actual_body.data.menu_items.len());
+}
+
+#[tokio::test]
+async fn test_add_menu_items_handler_handle_not_found() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = AddMenuItemsHandler::new(menu_repo.clone());
+
+    let req = AddMenuItemsReq {
+        menu_items: vec![],
+    };
+
+    let response = handler.handle(Uuid::new_v4(), req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_MENU_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
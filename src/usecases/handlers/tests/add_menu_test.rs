@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::add_menu::{AddMenuHandler, AddMenuReq, AddMenuResp, MenuItemReq};
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_COOKING_TIME_PROFILE, MESSAGE_INVALID_PRICE};
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::station::Station;
+use crate::models::currency::Currency;
+use crate::repositories::menu::MenuRepo;
+
+#[tokio::test]
+async fn test_add_menu_handler_handle_success() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = AddMenuHandler::new(menu_repo.clone());
+
+    let req = AddMenuReq {
+        name: String::from("lunch"),
+        currency: Currency::Usd,
+        menu_items: vec![
+            MenuItemReq {
+                name: String::from("fries"),
+                price: String::from("345"),
+                cooking_time_profile: CookingTimeProfile::default(),
+                station: Station::default(),
+                category: None,
+            },
+            MenuItemReq {
+                name: String::from("burger"),
+                price: String::from("789"),
+                cooking_time_profile: CookingTimeProfile::Triangular { min_minutes: 8, most_likely_minutes: 12, max_minutes: 20 },
+                station: Station::Fryer,
+                category: Some(String::from(" mains ")),
+            },
+        ],
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: AddMenuResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!("lunch", actual_body.data.name);
+    assert_eq!(2, actual_body.data.menu_items.len());
+    assert_eq!(CookingTimeProfile::Triangular { min_minutes: 8, most_likely_minutes: 12, max_minutes: 20 }, actual_body.data.menu_items[1].cooking_time_profile);
+    assert_eq!(Station::Grill, actual_body.data.menu_items[0].station);
+    assert_eq!(Station::Fryer, actual_body.data.menu_items[1].station);
+    assert_eq!(None, actual_body.data.menu_items[0].category);
+    assert_eq!(Some(String::from("mains")), actual_body.data.menu_items[1].category);
+    assert!(menu_repo.get(actual_body.data.menu_id).is_some());
+}
+
+#[tokio::test]
+async fn test_add_menu_handler_handle_invalid_price() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = AddMenuHandler::new(menu_repo.clone());
+
+    let req = AddMenuReq {
+        name: String::from("lunch"),
+        currency: Currency::Usd,
+        menu_items: vec![
+            MenuItemReq {
+                name: String::from("fries"),
+                price: String::from("abc"),
+                cooking_time_profile: CookingTimeProfile::default(),
+                station: Station::default(),
+                category: None,
+            },
+        ],
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(actual_body.error_message.starts_with(MESSAGE_INVALID_PRICE));
+    assert!(menu_repo.get_all().is_empty());
+}
+
+#[tokio::test]
+async fn test_add_menu_handler_handle_invalid_cooking_time_profile() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = AddMenuHandler::new(menu_repo.clone());
+
+    let req = AddMenuReq {
+        name: String::from("lunch"),
+        currency: Currency::Usd,
+        menu_items: vec![
+            MenuItemReq {
+                name: String::from("fries"),
+                price: String::from("345"),
+                cooking_time_profile: CookingTimeProfile::Range { min_minutes: 15, max_minutes: 5 },
+                station: Station::default(),
+                category: None,
+            },
+        ],
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(actual_body.error_message.starts_with(MESSAGE_INVALID_COOKING_TIME_PROFILE));
+    assert!(menu_repo.get_all().is_empty());
+}
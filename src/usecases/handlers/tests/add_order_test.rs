@@ -1,33 +1,59 @@
 use std::sync::Arc;
+use chrono::Utc;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use uuid::Uuid;
 use warp::http::StatusCode;
-use crate::usecases::handlers::add_order::{AddOrderHandler, AddOrderReq, AddOrderResp, MenuItemReq};
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::add_order::{AddOrderHandler, AddOrderReq, AddOrderResp, MenuItemReq, MAX_MEAL_ITEMS, MAX_QUANTITY};
+use crate::usecases::models::error::{ErrResp, UnavailableMenuItemsResp, MESSAGE_GUESTS_INVALID, MESSAGE_MENU_ITEMS_UNAVAILABLE, MESSAGE_ORDER_ADD_CONFLICT, MESSAGE_ORDER_CURRENCY_MISMATCH, MESSAGE_QUANTITY_INVALID, MESSAGE_TOO_MANY_MEAL_ITEMS};
 use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
-use crate::repositories::order::OrderRepo;
+use crate::libraries::thread_pool::ThreadPoolDyn;
+use crate::models::currency::Currency;
+use crate::models::meal::MealItemStatus;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::order::OrderPriority;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::price::Price;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::tax::{ServiceCharge, Tax, TaxPolicy};
 
 #[tokio::test]
 async fn test_add_order_handler_handle_success() {
     let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
     let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
 
-    let handler = AddOrderHandler::new(order_repo.clone(), thread_pool.clone());
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone(), burger.clone()]));
 
     let req = AddOrderReq {
         table_id: 1,
         menu_items: vec![
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("fries"),
-                price: String::from("345"),
+                menu_item_id: fries.id(),
+                quantity: 2,
             },
             MenuItemReq {
-                menu_item_id: Uuid::new_v4(),
-                name: String::from("burger"),
-                price: String::from("789"),
+                menu_item_id: burger.id(),
+                quantity: 1,
             },
         ],
+        priority: OrderPriority::Normal,
+        guests: None,
     };
 
     let response = handler.handle(req).unwrap();
@@ -37,13 +63,365 @@ async fn test_add_order_handler_handle_success() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: AddOrderResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: AddOrderResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     thread_pool.wait();
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(2, thread_pool.get_count());
+    assert_eq!(3, thread_pool.get_count());
     // can be improved here
-    assert_eq!("1134", actual_body.data.total_price);
+    assert_eq!("1479", actual_body.data.total_price.amount);
+    assert_eq!(Currency::Usd, actual_body.data.total_price.currency);
     assert_eq!("Received", actual_body.data.status);
-    assert_eq!(2, actual_body.data.meal_items.len());
+    assert_eq!(3, actual_body.data.meal_items.len());
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_unavailable_menu_items() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone(), burger.clone()]);
+    let menu_id = menu.id();
+    menu_repo.add(menu);
+    menu_repo.retire_menu_item(menu_id, burger.id());
+
+    let unknown_menu_item_id = Uuid::new_v4();
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 1,
+            },
+            MenuItemReq {
+                menu_item_id: burger.id(),
+                quantity: 1,
+            },
+            MenuItemReq {
+                menu_item_id: unknown_menu_item_id,
+                quantity: 1,
+            },
+        ],
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: UnavailableMenuItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body = UnavailableMenuItemsResp {
+        unavailable_menu_item_ids: vec![burger.id(), unknown_menu_item_id],
+        error_message: MESSAGE_MENU_ITEMS_UNAVAILABLE.to_string(),
+    };
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(expected_body, actual_body);
+    assert_eq!(0, thread_pool.get_count());
+    assert!(order_repo.get_order_by_table_id(1).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_currency_mismatch() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let ramen = MenuItem::new(String::from("ramen"), Price::from_minor_units(980, Currency::Jpy).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+    menu_repo.add(Menu::new(String::from("ranchi"), Currency::Jpy, vec![ramen.clone()]));
+
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 1,
+            },
+            MenuItemReq {
+                menu_item_id: ramen.id(),
+                quantity: 1,
+            },
+        ],
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(actual_body.error_message.starts_with(MESSAGE_ORDER_CURRENCY_MISMATCH));
+    assert_eq!(0, thread_pool.get_count());
+    assert!(order_repo.get_order_by_table_id(1).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_vip_order() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![
+            MenuItemReq {
+                menu_item_id: fries.id(),
+                quantity: 2,
+            },
+        ],
+        priority: OrderPriority::Vip,
+        guests: None,
+    };
+
+    let response = handler.handle(req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: AddOrderResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    thread_pool.wait();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!("Vip", actual_body.data.priority);
+    assert_eq!(OrderPriority::Vip, order_repo.get_order_by_table_id(1).unwrap().unwrap().get_priority());
+    assert!(actual_body.data.estimated_ready_time.unwrap() > actual_body.data.creation_time);
+    assert_eq!(20, actual_body.data.remaining_cooking_time_upper_bound_in_min);
+
+    // Meal items of the order are queued ahead of normal ones, and age from the time the order was placed
+    let queued_jobs = thread_pool.queued_jobs();
+    assert_eq!(2, queued_jobs.len());
+    for info in queued_jobs.iter() {
+        assert_eq!(2, info.priority);
+        assert_eq!(actual_body.data.creation_time, info.requested_at);
+    }
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_table_busy_until_paid() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+    let req = || AddOrderReq {
+        table_id: 1,
+        menu_items: vec![MenuItemReq { menu_item_id: fries.id(), quantity: 1 }],
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+
+    assert_eq!(StatusCode::OK, handler.handle(req()).unwrap().into_response().status());
+    let order = order_repo.get_order_by_table_id(1).unwrap().unwrap();
+    for meal_item_arc in order.get_meal_items() {
+        let meal_item_id = meal_item_arc.lock().unwrap().id();
+        order_repo.update_order_meal_item_status(order.id(), meal_item_id, MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+        order_repo.serve_order_meal_items(order.id(), vec![meal_item_id], Utc::now()).unwrap();
+    }
+
+    // Given the order is served but not paid, the table is still busy
+    let response = handler.handle(req()).unwrap().into_response();
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_ORDER_ADD_CONFLICT, actual_body.error_message);
+
+    order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Card, Some(String::from("VISA-0042")), fries.price(), Utc::now())).unwrap().unwrap();
+    assert_eq!(StatusCode::OK, handler.handle(req()).unwrap().into_response().status());
+    assert_ne!(order.id(), order_repo.get_order_by_table_id(1).unwrap().unwrap().id());
+    thread_pool.wait();
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_charges_taxes() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let tax_policy = TaxPolicy {
+        taxes: vec![Tax { name: String::from("sales tax"), rate: "8.875".parse().unwrap(), categories: Some(vec![String::from("mains")]) }],
+        service_charge: Some(ServiceCharge { rate: "18".parse().unwrap(), above_guests: 5 }),
+        ..TaxPolicy::default()
+    };
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(tax_policy), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(1000, Currency::Usd).unwrap()).with_category(Some(String::from("mains")));
+    let soda = MenuItem::new(String::from("soda"), Price::from_minor_units(250, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![burger.clone(), soda.clone()]));
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![MenuItemReq { menu_item_id: burger.id(), quantity: 1 }, MenuItemReq { menu_item_id: soda.id(), quantity: 1 }],
+        priority: OrderPriority::Normal,
+        guests: Some(6),
+    };
+
+    let response = handler.handle(req).unwrap().into_response();
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddOrderResp = serde_json::from_slice(&body).expect("failed to parse");
+    thread_pool.wait();
+
+    let data = actual_body.data;
+    assert_eq!(Some(6), data.guests);
+    assert_eq!("1250", data.subtotal.amount);
+    assert_eq!("0", data.discount.amount);
+    // 8.875% of the burger is 88.75, rounded half up; the soda is not a main
+    assert_eq!(1, data.taxes.len());
+    assert_eq!(("sales tax", "8.875", "1000", "89", false),
+               (data.taxes[0].name.as_str(), data.taxes[0].rate.as_str(), data.taxes[0].taxable_amount.amount.as_str(), data.taxes[0].amount.amount.as_str(), data.taxes[0].is_included));
+    // 18% of 1250 for a party of 6
+    assert_eq!("225", data.service_charge.amount);
+    assert_eq!("1564", data.total_price.amount);
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_stores_order_priced() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), promotion_repo.clone(), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let burger = MenuItem::new(String::from("burger"), Price::from_minor_units(1000, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![burger.clone()]));
+    let promotion = Promotion::new(String::from("15% off"), Currency::Usd,
+                                   Reward::PercentOff { target: PromotionTarget::Order, percent: 15 }, None).unwrap();
+    promotion_repo.add(promotion.clone());
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![MenuItemReq { menu_item_id: burger.id(), quantity: 1 }],
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+
+    assert_eq!(StatusCode::OK, handler.handle(req).unwrap().into_response().status());
+    thread_pool.wait();
+
+    // The order is added with the promotion already taken off
+    let order = order_repo.get_order_by_table_id(1).unwrap().unwrap();
+    assert_eq!(1, order.get_promotions().len());
+    assert_eq!(Price::from_minor_units(850, Currency::Usd).unwrap(), order.get_total_price());
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_invalid_guests() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+    let req = AddOrderReq {
+        table_id: 1,
+        menu_items: vec![MenuItemReq { menu_item_id: fries.id(), quantity: 1 }],
+        priority: OrderPriority::Normal,
+        guests: Some(0),
+    };
+
+    let response = handler.handle(req).unwrap().into_response();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_GUESTS_INVALID, actual_body.error_message);
+    assert!(order_repo.get_order_by_table_id(1).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_add_order_handler_handle_quantity() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(MockCookingTimeEstimator::new(10)), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![fries.clone()]));
+    let req = |table_id: u32, quantities: &[u32]| AddOrderReq {
+        table_id,
+        menu_items: quantities.iter().map(|quantity| MenuItemReq { menu_item_id: fries.id(), quantity: *quantity }).collect(),
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+
+    for (quantities, message) in [
+        (vec![0], MESSAGE_QUANTITY_INVALID),
+        (vec![1, MAX_QUANTITY + 1], MESSAGE_QUANTITY_INVALID),
+        (vec![u32::MAX], MESSAGE_QUANTITY_INVALID),
+        (vec![MAX_QUANTITY, MAX_QUANTITY, MAX_MEAL_ITEMS - 2 * MAX_QUANTITY + 1], MESSAGE_TOO_MANY_MEAL_ITEMS),
+    ] {
+        let response = handler.handle(req(1, &quantities)).unwrap().into_response();
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+        assert_eq!(message, actual_body.error_message);
+    }
+    assert!(order_repo.get_order_by_table_id(1).unwrap().is_none());
+
+    let response = handler.handle(req(1, &[MAX_QUANTITY])).unwrap().into_response();
+    assert_eq!(StatusCode::OK, response.status());
+    let response = handler.handle(req(2, &[MAX_QUANTITY, MAX_QUANTITY, MAX_MEAL_ITEMS - 2 * MAX_QUANTITY])).unwrap().into_response();
+    assert_eq!(StatusCode::OK, response.status());
+
+    thread_pool.wait();
+    assert_eq!((MAX_QUANTITY + MAX_MEAL_ITEMS) as usize, thread_pool.get_count());
+    assert_eq!(MAX_MEAL_ITEMS as usize, order_repo.get_order_by_table_id(2).unwrap().unwrap().get_meal_items().len());
 }
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::add_payment::{AddPaymentHandler, AddPaymentReq, AddPaymentResp};
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_PAYMENT_AMOUNT, MESSAGE_ORDER_CLOSED, MESSAGE_ORDER_NOT_FOUND,
+                                     MESSAGE_PAYMENT_REFERENCE_MISSING, MESSAGE_PAYMENT_REFUSED};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::payment::PaymentMethod;
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn add_order(order_repo: &OrderRepo) -> Order {
+    let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![meal_item], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order
+}
+
+fn payment_req(method: PaymentMethod, amount: &str, reference: Option<&str>) -> AddPaymentReq {
+    AddPaymentReq {
+        method,
+        amount: String::from(amount),
+        reference: reference.map(String::from),
+        check: None,
+    }
+}
+
+async fn err_body(response: warp::reply::Response) -> ErrResp {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).expect("failed to parse")
+}
+
+#[tokio::test]
+async fn test_add_payment_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = AddPaymentHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let order = add_order(&order_repo);
+
+    let response = handler.handle(1, payment_req(PaymentMethod::Cash, "1000", None)).unwrap().into_response();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddPaymentResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!("1500", actual_body.data.balance_due.amount);
+    assert!(!actual_body.data.is_closed);
+
+    // The rest is paid by card, which closes the order
+    let response = handler.handle(1, payment_req(PaymentMethod::Card, "1500", Some("VISA-0042"))).unwrap().into_response();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddPaymentResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!("0", actual_body.data.balance_due.amount);
+    assert!(actual_body.data.is_closed);
+    assert_eq!(Some(String::from("VISA-0042")), actual_body.data.payments[1].reference);
+    assert!(order_repo.get_order(order.id()).unwrap().unwrap().is_closed());
+
+    let response = handler.handle(1, payment_req(PaymentMethod::Cash, "1", None)).unwrap().into_response();
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert_eq!(MESSAGE_ORDER_CLOSED, err_body(response).await.error_message);
+}
+
+#[tokio::test]
+async fn test_add_payment_handler_handle_invalid() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = AddPaymentHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let order = add_order(&order_repo);
+
+    let response = handler.handle(1, payment_req(PaymentMethod::Card, "1000", Some(" "))).unwrap().into_response();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(MESSAGE_PAYMENT_REFERENCE_MISSING, err_body(response).await.error_message);
+
+    for amount in ["0", "-5", "ten"] {
+        let response = handler.handle(1, payment_req(PaymentMethod::Cash, amount, None)).unwrap().into_response();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        assert!(err_body(response).await.error_message.starts_with(MESSAGE_INVALID_PAYMENT_AMOUNT));
+    }
+
+    let response = handler.handle(1, payment_req(PaymentMethod::Other, "2501", Some("voucher"))).unwrap().into_response();
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert_eq!(format!("{}: payment is more than the balance due of 2500 USD", MESSAGE_PAYMENT_REFUSED), err_body(response).await.error_message);
+    assert!(order_repo.get_order(order.id()).unwrap().unwrap().get_payments().is_empty());
+}
+
+#[tokio::test]
+async fn test_add_payment_handler_handle_nothing_due() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = AddPaymentHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let order = add_order(&order_repo);
+    let meal_item_id = order.get_meal_items()[0].lock().unwrap().id();
+    order_repo.update_order_meal_item_status(order.id(), meal_item_id, MealItemStatus::Preparing, Utc::now()).unwrap();
+    order_repo.void_order_meal_item(order.id(), meal_item_id, String::from("guest left"), Utc::now()).unwrap();
+
+    // Given the only meal item is voided, a payment of zero settles the order
+    let response = handler.handle(1, payment_req(PaymentMethod::Other, "0", Some("comped"))).unwrap().into_response();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddPaymentResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!("0", actual_body.data.balance_due.amount);
+    assert!(actual_body.data.is_closed);
+    assert!(order_repo.get_order(order.id()).unwrap().unwrap().is_closed());
+}
+
+#[tokio::test]
+async fn test_add_payment_handler_handle_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = AddPaymentHandler::new(order_repo.clone(), Arc::new(SystemClock));
+
+    let response = handler.handle(1, payment_req(PaymentMethod::Cash, "1000", None)).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(MESSAGE_ORDER_NOT_FOUND, err_body(response).await.error_message);
+}
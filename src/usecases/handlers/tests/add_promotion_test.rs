@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::add_promotion::{AddPromotionHandler, AddPromotionReq, AddPromotionResp};
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_PRICE, MESSAGE_INVALID_PROMOTION};
+use crate::usecases::models::promotion_resp::{PromotionTargetResp, RewardResp};
+use crate::models::currency::Currency;
+use crate::repositories::promotion::PromotionRepo;
+
+fn promotion_req(req: serde_json::Value) -> AddPromotionReq {
+    serde_json::from_value(req).expect("failed to parse")
+}
+
+#[tokio::test]
+async fn test_add_promotion_handler_handle_success() {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = AddPromotionHandler::new(promotion_repo.clone());
+    let menu_item_id = Uuid::new_v4();
+
+    let req = promotion_req(serde_json::json!({
+        "name": "burger 1 off",
+        "currency": "USD",
+        "min_spend": "2000",
+        "reward": { "type": "amount_off", "target": { "type": "menu_item", "menu_item_id": menu_item_id }, "amount": "100" },
+    }));
+    let response = handler.handle(req).unwrap().into_response();
+
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddPromotionResp = serde_json::from_slice(&body).expect("failed to parse");
+    let promotion = actual_body.data;
+    assert_eq!(("burger 1 off", Currency::Usd), (promotion.name.as_str(), promotion.currency));
+    assert_eq!(Some(String::from("2000")), promotion.min_spend.map(|min_spend| min_spend.amount));
+    match promotion.reward {
+        RewardResp::AmountOff { target, amount } => {
+            assert_eq!(PromotionTargetResp::MenuItem { menu_item_id }, target);
+            assert_eq!("1.00", amount.formatted_amount);
+        }
+        reward => panic!("unexpected reward {:?}", reward),
+    }
+    let promotions = promotion_repo.get_all();
+    assert_eq!(vec![promotion.promotion_id], promotions.iter().map(|promotion| promotion.id()).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_add_promotion_handler_handle_invalid() {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = AddPromotionHandler::new(promotion_repo.clone());
+
+    let req = promotion_req(serde_json::json!({
+        "name": "drinks 0+1",
+        "currency": "USD",
+        "reward": { "type": "buy_x_get_y", "target": { "type": "category", "category": "drinks" }, "buy": 0, "get": 1 },
+    }));
+    let response = handler.handle(req).unwrap().into_response();
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(format!("{}: buy and get have to be at least 1", MESSAGE_INVALID_PROMOTION), actual_body.error_message);
+
+    let req = promotion_req(serde_json::json!({
+        "name": "combo",
+        "currency": "USD",
+        "reward": { "type": "combo_price", "menu_item_ids": [Uuid::new_v4(), Uuid::new_v4()], "price": "9.99" },
+    }));
+    let response = handler.handle(req).unwrap().into_response();
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert!(actual_body.error_message.starts_with(MESSAGE_INVALID_PRICE));
+    assert!(promotion_repo.get_all().is_empty());
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use chrono::{TimeDelta, Utc};
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::checkout::{CheckoutHandler, CheckoutResp};
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::price::Price;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+use crate::models::order::Order;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::tax::TaxPolicy;
+
+#[tokio::test]
+async fn test_checkout_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = CheckoutHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
+
+    let now = Utc::now();
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), now);
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), now + TimeDelta::minutes(1));
+    let menu_item = MenuItem::new(String::from("salad"), Price::from_minor_units(500, Currency::Usd).unwrap());
+    let salad = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), now + TimeDelta::minutes(2));
+    let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+    let steak = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), now + TimeDelta::minutes(3));
+    let order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone(), salad.clone(), steak.clone()], now).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    // Given the salad is removed and the steak voided, neither is charged
+    order_repo.remove_order_meal_items(order.id(), vec![salad.id()], Utc::now()).unwrap();
+    order_repo.update_order_meal_item_status(order.id(), steak.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+    order_repo.void_order_meal_item(order.id(), steak.id(), String::from("burnt"), Utc::now()).unwrap();
+    order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(500, Currency::Usd).unwrap(), Utc::now())).unwrap().unwrap();
+
+    let response = handler.handle(1).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: CheckoutResp = serde_json::from_slice(&body).expect("failed to parse");
+    let bill = actual_body.data;
+    let meal_item_ids: Vec<_> = bill.meal_items.iter().map(|meal_item| meal_item.meal_item_id).collect();
+    assert_eq!(vec![fries.id(), burger.id()], meal_item_ids);
+    assert_eq!(("1134", "500", "634"), (bill.total_price.amount.as_str(), bill.amount_paid.amount.as_str(), bill.balance_due.amount.as_str()));
+    assert_eq!(1, bill.payments.len());
+    assert_eq!(PaymentMethod::Cash, bill.payments[0].method);
+    assert!(!bill.is_closed);
+    assert_eq!(None, bill.closed_time);
+}
+
+#[tokio::test]
+async fn test_checkout_handler_handle_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = CheckoutHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
+
+    let response = handler.handle(1).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_ORDER_NOT_FOUND, actual_body.error_message);
+}
+
+#[tokio::test]
+async fn test_checkout_handler_handle_prices_order() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = CheckoutHandler::new(order_repo.clone(), promotion_repo.clone(), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
+
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(1000, Currency::Usd).unwrap());
+    let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![burger], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    // Given the promotion starts after the order was placed, the order gets it at checkout
+    let promotion = Promotion::new(String::from("15% off"), Currency::Usd,
+                                   Reward::PercentOff { target: PromotionTarget::Order, percent: 15 }, None).unwrap();
+    promotion_repo.add(promotion.clone());
+
+    let response = handler.handle(1).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: CheckoutResp = serde_json::from_slice(&body).expect("failed to parse");
+    let bill = actual_body.data;
+    assert_eq!(("1000", "850"), (bill.subtotal.amount.as_str(), bill.total_price.amount.as_str()));
+    assert_eq!(1, bill.promotions.len());
+    assert_eq!((promotion.id(), "150"), (bill.promotions[0].promotion_id, bill.promotions[0].saved.amount.as_str()));
+    assert_eq!(Price::from_minor_units(850, Currency::Usd).unwrap(), order_repo.get_order(order.id()).unwrap().unwrap().get_balance_due());
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::list_menus::{ListMenusHandler, ListMenusResp};
+use crate::models::currency::Currency;
+use crate::models::menu::Menu;
+use crate::repositories::menu::MenuRepo;
+
+#[tokio::test]
+async fn test_list_menus_handler_handle_success() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = ListMenusHandler::new(menu_repo.clone());
+
+    menu_repo.add(Menu::new(String::from("lunch"), Currency::Usd, vec![]));
+    menu_repo.add(Menu::new(String::from("dinner"), Currency::Usd, vec![]));
+
+    let response = handler.handle().unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ListMenusResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(2, actual_body.data.len());
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::list_promotions::{ListPromotionsHandler, ListPromotionsResp};
+use crate::usecases::models::promotion_resp::{PromotionTargetResp, RewardResp};
+use crate::models::currency::Currency;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+use crate::repositories::promotion::PromotionRepo;
+
+#[tokio::test]
+async fn test_list_promotions_handler_handle_success() {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = ListPromotionsHandler::new(promotion_repo.clone());
+
+    let order_off = Promotion::new(String::from("10% off"), Currency::Usd,
+                                   Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None).unwrap();
+    let drinks = Promotion::new(String::from("drinks 2+1"), Currency::Usd,
+                                Reward::BuyXGetY { target: PromotionTarget::Category(String::from("drinks")), buy: 2, get: 1 }, None).unwrap();
+    promotion_repo.add(order_off.clone());
+    promotion_repo.add(drinks.clone());
+
+    let response = handler.handle().unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ListPromotionsResp = serde_json::from_slice(&body).expect("failed to parse");
+    // Promotions are listed in the order they are tried
+    assert_eq!(vec![order_off.id(), drinks.id()], actual_body.data.iter().map(|promotion| promotion.promotion_id).collect::<Vec<_>>());
+    assert_eq!(RewardResp::PercentOff { target: PromotionTargetResp::Order, percent: 10 }, actual_body.data[0].reward);
+    assert_eq!(RewardResp::BuyXGetY { target: PromotionTargetResp::Category { category: String::from("drinks") }, buy: 2, get: 1 },
+               actual_body.data[1].reward);
+}
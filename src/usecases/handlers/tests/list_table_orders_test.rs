@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::list_table_orders::{ListTableOrdersHandler, ListTableOrdersResp};
+use crate::models::currency::Currency;
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::meal::MealItem;
+
+#[tokio::test]
+async fn test_list_table_orders_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = ListTableOrdersHandler::new(order_repo.clone(), ready_time_estimator);
+
+    // Given the first order of the table was canceled, it should still be listed before the current one
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+    order_repo.remove_order(order_id, Utc::now()).unwrap();
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    order_repo.add(Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap()).unwrap();
+    let menu_item = MenuItem::new(String::from("salad"), Price::from_minor_units(500, Currency::Usd).unwrap());
+    order_repo.add(Order::new(2, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap()).unwrap();
+
+    let response = handler.handle(1, true).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ListTableOrdersResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(2, actual_body.data.len());
+    assert_eq!("Canceled", actual_body.data[0].status);
+    assert_eq!("789", actual_body.data[1].total_price.amount);
+    assert_eq!("Received", actual_body.data[1].status);
+}
+
+#[tokio::test]
+async fn test_list_table_orders_handler_handle_empty() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = ListTableOrdersHandler::new(order_repo.clone(), ready_time_estimator);
+
+    let response = handler.handle(1, false).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ListTableOrdersResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(actual_body.data.is_empty());
+}
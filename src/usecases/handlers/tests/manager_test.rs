@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use warp::Filter;
+use warp::http::StatusCode;
+use crate::usecases::handlers::manager::{manager_only, recover_not_manager, MANAGER_PIN_HEADER};
+use crate::usecases::models::error::{ErrResp, MESSAGE_MANAGER_ONLY};
+
+fn routes(pin: Option<Arc<String>>) -> impl Filter<Extract = (impl warp::Reply,), Error = std::convert::Infallible> + Clone {
+    let void = warp::post()
+        .and(warp::path("void"))
+        .and(warp::path::end())
+        .and(manager_only(pin))
+        .map(|| "voided");
+    let read = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .map(|| "orders");
+    void.or(read).recover(recover_not_manager).recover(|_| async { Ok::<_, std::convert::Infallible>(StatusCode::NOT_FOUND) })
+}
+
+#[tokio::test]
+async fn test_manager_only_with_pin() {
+    let routes = routes(Some(Arc::new(String::from("2468"))));
+
+    let response = warp::test::request().method("POST").path("/void").header(MANAGER_PIN_HEADER, "2468").reply(&routes).await;
+
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn test_manager_only_refuses_others() {
+    let routes = routes(Some(Arc::new(String::from("2468"))));
+
+    for request in [warp::test::request(), warp::test::request().header(MANAGER_PIN_HEADER, "1234")] {
+        let response = request.method("POST").path("/void").reply(&routes).await;
+
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        let body: ErrResp = serde_json::from_slice(response.body()).expect("failed to parse");
+        assert_eq!(MESSAGE_MANAGER_ONLY, body.error_message);
+    }
+
+    // Other routes don't need the PIN
+    let response = warp::test::request().method("GET").path("/orders").reply(&routes).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn test_manager_only_without_pin_configured() {
+    let routes = routes(None);
+
+    let response = warp::test::request().method("POST").path("/void").header(MANAGER_PIN_HEADER, "").reply(&routes).await;
+
+    assert_eq!(StatusCode::FORBIDDEN, response.status());
+}
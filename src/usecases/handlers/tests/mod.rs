@@ -3,4 +3,28 @@ mod add_order_test;
 mod query_order_test;
 mod query_meal_item_test;
 mod remove_order_test;
-mod remove_meal_items_test;
\ No newline at end of file
+mod remove_meal_items_test;
+mod add_menu_test;
+mod query_menu_test;
+mod list_menus_test;
+mod add_menu_items_test;
+mod update_menu_item_test;
+mod retire_menu_item_test;
+mod list_table_orders_test;
+mod query_kitchen_queue_test;
+mod service_shift_test;
+mod shutdown_test;
+mod manager_test;
+mod void_meal_item_test;
+mod query_waste_report_test;
+mod serve_meal_items_test;
+mod query_pass_test;
+mod checkout_test;
+mod add_payment_test;
+mod split_bill_test;
+mod add_promotion_test;
+mod list_promotions_test;
+mod remove_promotion_test;
+mod query_receipt_test;
+mod stream_order_events_test;
+mod store_test;
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::libraries::job::JobStep;
+use crate::usecases::handlers::query_kitchen_queue::{KitchenQueueResp, QueryKitchenQueueHandler};
+use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+use crate::libraries::thread_pool::ThreadPoolDyn;
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::station::Station;
+use crate::models::order::{Order, OrderPriority};
+use crate::repositories::kitchen::{Assignment, KitchenRepo};
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::usecases::jobs::cook_meal_item::cooking_job_info;
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+#[tokio::test]
+async fn test_query_kitchen_queue_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+
+    let thread_pool = Arc::new(MockThreadPool::new());
+
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = QueryKitchenQueueHandler::new(order_repo.clone(), kitchen_repo.clone(), kitchen, ready_time_estimator, Arc::new(SystemClock));
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let received_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let preparing_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("salad"), Price::from_minor_units(500, Currency::Usd).unwrap());
+    let removed_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![received_meal_item.clone(), preparing_meal_item.clone(), removed_meal_item.clone()], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+    order_repo.update_order_meal_item_status(order_id, preparing_meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+    order_repo.remove_order_meal_items(order_id, vec![removed_meal_item.id()], Utc::now()).unwrap();
+    kitchen_repo.assign(preparing_meal_item.id(), Assignment { worker_id: 3, station: preparing_meal_item.station(), ready_time: Utc::now() });
+
+    // Given the order of table 2 is completed, none of its meal items should be listed
+    let menu_item = MenuItem::new(String::from("tea"), Price::from_minor_units(123, Currency::Usd).unwrap());
+    let completed_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![completed_meal_item.clone()], Utc::now()).unwrap();
+    let completed_order_id = order.id();
+    order_repo.add(order).unwrap();
+    order_repo.update_order_meal_item_status(completed_order_id, completed_meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+
+    let response = handler.handle(None).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: KitchenQueueResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(2, actual_body.data.len());
+
+    assert_eq!(preparing_meal_item.id(), actual_body.data[0].meal_item_id);
+    assert_eq!("burger", actual_body.data[0].name);
+    assert_eq!("Preparing", actual_body.data[0].status);
+    assert_eq!(1, actual_body.data[0].table_id);
+    assert_eq!(Some(3), actual_body.data[0].worker_id);
+
+    assert_eq!(received_meal_item.id(), actual_body.data[1].meal_item_id);
+    assert_eq!("Received", actual_body.data[1].status);
+    assert_eq!(order_id, actual_body.data[1].order_id);
+    assert_eq!(None, actual_body.data[1].worker_id);
+}
+
+#[tokio::test]
+async fn test_query_kitchen_queue_handler_handle_empty() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = QueryKitchenQueueHandler::new(order_repo.clone(), kitchen_repo, kitchen, ready_time_estimator, Arc::new(SystemClock));
+
+    let response = handler.handle(None).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: KitchenQueueResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(actual_body.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_query_kitchen_queue_handler_handle_follows_pool_queue() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let thread_pool = Arc::new(MockThreadPool::new());
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = QueryKitchenQueueHandler::new(order_repo.clone(), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let normal_meal_item = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut normal_order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    normal_order.add_meal_items(vec![normal_meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(normal_order.clone()).unwrap();
+
+    let vip_meal_item = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut vip_order = Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap().with_priority(OrderPriority::Vip);
+    vip_order.add_meal_items(vec![vip_meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(vip_order.clone()).unwrap();
+
+    // Given the pool would cook the VIP meal item first, and the third one isn't queued at all, ex. restored after a restart
+    let unqueued_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    order_repo.add_order_meal_items(normal_order.id(), vec![unqueued_meal_item.clone()], Utc::now()).unwrap().unwrap();
+    thread_pool.execute(Box::new(|| JobStep::Done), cooking_job_info(&vip_order, &vip_meal_item));
+    thread_pool.execute(Box::new(|| JobStep::Done), cooking_job_info(&normal_order, &normal_meal_item));
+
+    let response = handler.handle(None).unwrap();
+
+    let response = response.into_response();
+
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: KitchenQueueResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let meal_item_ids: Vec<_> = actual_body.data.iter().map(|item| item.meal_item_id).collect();
+    assert_eq!(vec![vip_meal_item.id(), normal_meal_item.id(), unqueued_meal_item.id()], meal_item_ids);
+    assert_eq!("Vip", actual_body.data[0].priority);
+    // Given two workers, both queued meal items start right away while the one not queued is never ready
+    assert!(actual_body.data[0].estimated_ready_time.is_some());
+    assert!(actual_body.data[1].estimated_ready_time.is_some());
+    assert_eq!(None, actual_body.data[2].estimated_ready_time);
+    thread_pool.wait();
+}
+
+#[tokio::test]
+async fn test_query_kitchen_queue_handler_handle_per_station() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let fryer_pool = Arc::new(MockThreadPool::with_worker_count(2));
+    let bar_pool = Arc::new(MockThreadPool::with_worker_count(0));
+    let kitchen = Arc::new(Kitchen::new(|station| match station {
+        Station::Bar => bar_pool.clone(),
+        _ => fryer_pool.clone(),
+    }));
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock)));
+    let handler = QueryKitchenQueueHandler::new(order_repo.clone(), kitchen_repo, kitchen.clone(), ready_time_estimator, Arc::new(SystemClock));
+
+    let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()).with_station(Station::Fryer);
+    let beer = MenuItem::new(String::from("beer"), Price::from_minor_units(600, Currency::Usd).unwrap()).with_station(Station::Bar);
+    let fries_meal_item = MealItem::create(fries, &MockCookingTimeEstimator::new(5), Utc::now());
+    let beer_meal_item = MealItem::create(beer, &MockCookingTimeEstimator::new(1), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![beer_meal_item.clone(), fries_meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    for meal_item in [&beer_meal_item, &fries_meal_item] {
+        kitchen.execute(meal_item.station(), Box::new(|| JobStep::Done), cooking_job_info(&order, meal_item));
+    }
+
+    let response = handler.handle(None).unwrap().into_response();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: KitchenQueueResp = serde_json::from_slice(&body).expect("failed to parse");
+
+    // Given meal items are listed station by station, the fries come before the beer
+    let meal_item_ids: Vec<_> = actual_body.data.iter().map(|item| item.meal_item_id).collect();
+    assert_eq!(vec![fries_meal_item.id(), beer_meal_item.id()], meal_item_ids);
+    assert_eq!(Station::ALL.to_vec(), actual_body.stations.iter().map(|station| station.station).collect::<Vec<_>>());
+    let fryer = &actual_body.stations[1];
+    assert_eq!((2, 0, 1), (fryer.chef_count, fryer.preparing_count, fryer.waiting_count));
+    assert!(fryer.estimated_clear_time.is_some());
+    // Given the bar has no chefs, the beer is never ready
+    let bar = &actual_body.stations[3];
+    assert_eq!((0, 0, 1), (bar.chef_count, bar.preparing_count, bar.waiting_count));
+    assert_eq!(None, bar.estimated_clear_time);
+    assert_eq!(None, actual_body.data[1].estimated_ready_time);
+    assert!(actual_body.stations[0].estimated_clear_time.is_some());
+
+    let response = handler.handle(Some(Station::Fryer)).unwrap().into_response();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: KitchenQueueResp = serde_json::from_slice(&body).expect("failed to parse");
+
+    assert_eq!(vec![fries_meal_item.id()], actual_body.data.iter().map(|item| item.meal_item_id).collect::<Vec<_>>());
+    assert_eq!(1, actual_body.stations.len());
+    assert_eq!(Station::Fryer, actual_body.stations[0].station);
+    fryer_pool.wait();
+    bar_pool.wait();
+}
@@ -1,14 +1,18 @@
 use std::sync::Arc;
+use chrono::Utc;
 use uuid::Uuid;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ITEM_NOT_FOUND};
+use crate::usecases::models::error::{ErrResp, MESSAGE_ITEM_NOT_FOUND, MESSAGE_ORDER_ITEM_NOT_FOUND};
 use crate::usecases::handlers::query_meal_item::{MealItemResp, QueryMealItemHandler, QueryMealItemResp};
+use crate::models::currency::Currency;
 use crate::models::meal::MealItem;
 use crate::models::menu::MenuItem;
+use crate::models::price::Price;
 use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
 
 #[tokio::test]
 async fn test_query_meal_item_handler_handle_success() {
@@ -16,13 +20,13 @@ async fn test_query_meal_item_handler_handle_success() {
 
     let handler = QueryMealItemHandler::new(order_repo.clone());
 
-    let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-    let meal_item_fries = MealItem::create(menu_item);
-    let menu_item = MenuItem::new(String::from("burger"), String::from("789"));
-    let meal_item_burger = MealItem::create(menu_item);
-    let mut order = Order::new(1, vec![]);
-    order.add_meal_items(vec![meal_item_fries.clone(), meal_item_burger.clone()]);
-    order_repo.add(order);
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item_fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let meal_item_burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item_fries.clone(), meal_item_burger.clone()], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let response = handler.handle(1, meal_item_burger.id()).unwrap();
 
@@ -31,7 +35,7 @@ async fn test_query_meal_item_handler_handle_success() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: QueryMealItemResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: QueryMealItemResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
     let expected_body = QueryMealItemResp { data: MealItemResp::new(meal_item_burger) };
 
     assert_eq!(status, StatusCode::OK);
@@ -51,10 +55,63 @@ async fn test_query_meal_item_handler_handle_not_found() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ITEM_NOT_FOUND.to_string() };
 
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert_eq!(expected_body, actual_body);
 }
+
+#[tokio::test]
+async fn test_query_meal_item_handler_handle_by_id_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = QueryMealItemHandler::new(order_repo.clone());
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item_fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item_fries.clone()], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+    order_repo.add(Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+
+    let response = handler.handle_by_id(order_id, meal_item_fries.id()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: QueryMealItemResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+    let expected_body = QueryMealItemResp { data: MealItemResp::new(meal_item_fries) };
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(expected_body, actual_body);
+}
+
+#[tokio::test]
+async fn test_query_meal_item_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = QueryMealItemHandler::new(order_repo.clone());
+
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+
+    let response = handler.handle_by_id(order_id, Uuid::new_v4()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_ITEM_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
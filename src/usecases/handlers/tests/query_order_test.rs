@@ -1,29 +1,41 @@
 use std::sync::Arc;
+use chrono::Utc;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND};
+use uuid::Uuid;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_NOT_FOUND};
 use crate::usecases::handlers::query_order::{QueryOrderHandler, QueryOrderResp};
+use crate::models::currency::Currency;
 use crate::models::meal::MealItem;
 use crate::models::menu::MenuItem;
+use crate::models::price::Price;
 use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
 
 #[tokio::test]
 async fn test_query_order_handler_handle_success() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = QueryOrderHandler::new(order_repo.clone());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = QueryOrderHandler::new(order_repo.clone(), ready_time_estimator);
 
-    let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-    let meal_item = MealItem::create(menu_item);
-    let mut order = Order::new(1, vec![]);
-    order.add_meal_items(vec![meal_item]);
-    let menu_item = MenuItem::new(String::from("burger"), String::from("789"));
-    let meal_item = MealItem::create(menu_item);
-    order.add_meal_items(vec![meal_item.clone()]);
-    order_repo.add(order);
-    order_repo.remove_order_meal_items(1, vec![meal_item.id()]);
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item], Utc::now()).unwrap();
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+    order_repo.remove_order_meal_items(order_id, vec![meal_item.id()], Utc::now()).unwrap();
 
     let response = handler.handle(1, false).unwrap();
 
@@ -32,9 +44,9 @@ async fn test_query_order_handler_handle_success() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: QueryOrderResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: QueryOrderResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
     assert_eq!(status, StatusCode::OK);
-    assert_eq!("345", actual_body.data.total_price);
+    assert_eq!("345", actual_body.data.total_price.amount);
     assert_eq!("Received", actual_body.data.status);
     assert_eq!(1, actual_body.data.meal_items.len());
 }
@@ -43,7 +55,8 @@ async fn test_query_order_handler_handle_success() {
 async fn test_query_order_handler_handle_not_found() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = QueryOrderHandler::new(order_repo.clone());
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = QueryOrderHandler::new(order_repo.clone(), ready_time_estimator);
 
     let response = handler.handle(1, false).unwrap();
 
@@ -52,10 +65,61 @@ async fn test_query_order_handler_handle_not_found() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_NOT_FOUND.to_string() };
 
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert_eq!(expected_body, actual_body);
 }
+
+#[tokio::test]
+async fn test_query_order_handler_handle_by_id_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = QueryOrderHandler::new(order_repo.clone(), ready_time_estimator);
+
+    // Given the table has a newer order, the previous one should still be found by its id
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.add(Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap()).unwrap();
+
+    let response = handler.handle_by_id(order.id(), false).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: QueryOrderResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(order.id(), actual_body.data.order_id);
+    assert_eq!(1, actual_body.data.table_id);
+    assert_eq!(order.creation_time(), actual_body.data.creation_time);
+    assert_eq!(order.update_time(), actual_body.data.update_time);
+    assert_eq!("345", actual_body.data.total_price.amount);
+}
+
+#[tokio::test]
+async fn test_query_order_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), Arc::new(Kitchen::new(|_| Arc::new(MockThreadPool::new()))), Arc::new(SystemClock)));
+    let handler = QueryOrderHandler::new(order_repo.clone(), ready_time_estimator);
+
+    let response = handler.handle_by_id(Uuid::new_v4(), false).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
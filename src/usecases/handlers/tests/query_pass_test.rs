@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::VirtualClock;
+use crate::usecases::handlers::query_pass::{PassResp, QueryPassHandler};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn at(minute: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap() + TimeDelta::minutes(minute)
+}
+
+fn add_meal_item(order_repo: &OrderRepo, table_id: u32, name: &str, status: MealItemStatus, minute: i64) -> MealItem {
+    let menu_item = MenuItem::new(String::from(name), Price::from_minor_units(500, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(5), at(0));
+    let order = Order::new(table_id, Currency::Usd, vec![meal_item.clone()], at(0)).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::ReadyForPickup, at(minute)).unwrap();
+    if status == MealItemStatus::Served {
+        order_repo.serve_order_meal_items(order.id(), vec![meal_item.id()], at(minute + 1)).unwrap();
+    }
+    meal_item
+}
+
+#[tokio::test]
+async fn test_query_pass_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let clock = Arc::new(VirtualClock::new(at(20)));
+    let handler = QueryPassHandler::new(order_repo.clone(), clock.clone());
+
+    let fresh_meal_item = add_meal_item(&order_repo, 1, "steak", MealItemStatus::ReadyForPickup, 18);
+    let dying_meal_item = add_meal_item(&order_repo, 2, "fries", MealItemStatus::ReadyForPickup, 8);
+    // Given the salad is served, it is no longer on the pass
+    add_meal_item(&order_repo, 3, "salad", MealItemStatus::Served, 5);
+
+    let response = handler.handle().unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: PassResp = serde_json::from_slice(&body).expect("failed to parse");
+    let pass_items: Vec<_> = actual_body.data.iter()
+        .map(|item| (item.meal_item_id, item.table_id, item.ready_time, item.waiting_in_sec))
+        .collect();
+    assert_eq!(vec![
+        (dying_meal_item.id(), 2, at(8), 12 * 60),
+        (fresh_meal_item.id(), 1, at(18), 2 * 60),
+    ], pass_items);
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use chrono::{TimeDelta, TimeZone, Utc};
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::VirtualClock;
+use crate::usecases::handlers::query_receipt::{QueryReceiptHandler, QueryReceiptResp};
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND, MESSAGE_RECEIPT_FORMAT_INVALID, MESSAGE_RECEIPT_WIDTH_INVALID};
+use crate::models::currency::Currency;
+use crate::models::meal::MealItem;
+use crate::models::menu::MenuItem;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::models::receipt::Restaurant;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn restaurant() -> Arc<Restaurant> {
+    Arc::new(Restaurant { name: String::from("Rusty Spoon"), address: None, phone: Some(String::from("555-0100")) })
+}
+
+// Table 1 ordered fries and a burger, removed the burger and paid 2.00 of the fries
+fn setup() -> (Arc<OrderRepo>, Order) {
+    let order_repo = Arc::new(OrderRepo::new());
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), now);
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), now + TimeDelta::minutes(1));
+    let order = Order::new(1, Currency::Usd, vec![fries, burger.clone()], now).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.remove_order_meal_items(order.id(), vec![burger.id()], now).unwrap();
+    order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, Price::from_minor_units(200, Currency::Usd).unwrap(), now)).unwrap().unwrap();
+    (order_repo, order)
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_json() {
+    let (order_repo, order) = setup();
+    let printed_time = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(printed_time)));
+
+    let response = handler.handle(1, None, None).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: QueryReceiptResp = serde_json::from_slice(&body).expect("failed to parse");
+    let receipt = actual_body.data;
+    assert_eq!(("Rusty Spoon", order.id()), (receipt.restaurant.name.as_str(), receipt.order_id));
+    let names: Vec<_> = receipt.meal_items.iter().map(|meal_item| meal_item.name.as_str()).collect();
+    assert_eq!(vec!["fries"], names);
+    assert_eq!(("345", "200", "145"), (receipt.total_price.amount.as_str(), receipt.amount_paid.amount.as_str(), receipt.balance_due.amount.as_str()));
+    assert_eq!(1, receipt.payments.len());
+    assert_eq!(printed_time, receipt.printed_time);
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_text() {
+    let (order_repo, _) = setup();
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(Utc::now())));
+
+    for (width, columns) in [(None, 42), (Some(String::from("32")), 32)] {
+        let response = handler.handle(1, Some(String::from("text")), width.clone()).unwrap().into_response();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("text/plain; charset=utf-8", response.headers()["content-type"]);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.lines().all(|line| line.chars().count() <= columns), "{}", text);
+        assert!(text.contains("Tel 555-0100"));
+        assert!(text.lines().any(|line| line.starts_with("fries") && line.ends_with("3.45")), "{}", text);
+        assert!(!text.contains("burger"));
+        assert!(text.lines().any(|line| line.starts_with("Balance due") && line.ends_with("1.45")), "{}", text);
+    }
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_html() {
+    let (order_repo, _) = setup();
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(Utc::now())));
+
+    let response = handler.handle(1, Some(String::from("html")), None).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("text/html; charset=utf-8", response.headers()["content-type"]);
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let html = String::from_utf8(body.to_vec()).unwrap();
+    assert!(html.contains("<tr><td>fries</td><td class=\"amount\">3.45</td></tr>"));
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_invalid_width() {
+    let (order_repo, _) = setup();
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(Utc::now())));
+
+    for width in ["80", "wide"] {
+        let response = handler.handle(1, Some(String::from("text")), Some(String::from(width))).unwrap().into_response();
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+        assert_eq!(MESSAGE_RECEIPT_WIDTH_INVALID, actual_body.error_message);
+    }
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_invalid_format() {
+    let (order_repo, _) = setup();
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(Utc::now())));
+
+    let response = handler.handle(1, Some(String::from("pdf")), None).unwrap().into_response();
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_RECEIPT_FORMAT_INVALID, actual_body.error_message);
+}
+
+#[tokio::test]
+async fn test_query_receipt_handler_handle_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = QueryReceiptHandler::new(order_repo.clone(), restaurant(), Arc::new(VirtualClock::new(Utc::now())));
+
+    let response = handler.handle(1, None, None).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_ORDER_NOT_FOUND, actual_body.error_message);
+}
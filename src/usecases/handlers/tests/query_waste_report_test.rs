@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use chrono::{TimeDelta, Utc};
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::query_waste_report::{QueryWasteReportHandler, WasteReportResp};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn add_voided_order(order_repo: &OrderRepo, table_id: u32, currency: Currency, minor_units: i64, reason: &str, minutes_ago: i64) -> MealItem {
+    let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(minor_units, currency).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), Utc::now());
+    let order = Order::new(table_id, currency, vec![meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+    order_repo.void_order_meal_item(order.id(), meal_item.id(), String::from(reason), Utc::now() - TimeDelta::minutes(minutes_ago)).unwrap();
+    meal_item
+}
+
+#[tokio::test]
+async fn test_query_waste_report_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = QueryWasteReportHandler::new(order_repo.clone());
+
+    let late_meal_item = add_voided_order(&order_repo, 1, Currency::Usd, 2500, "guest left", 1);
+    let early_meal_item = add_voided_order(&order_repo, 2, Currency::Usd, 1800, "dropped", 10);
+    let eur_meal_item = add_voided_order(&order_repo, 3, Currency::Eur, 900, "burnt", 5);
+    // Given the fries are served, they are not wasted
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    order_repo.add(Order::new(4, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(5), Utc::now())], Utc::now()).unwrap()).unwrap();
+
+    let response = handler.handle().unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: WasteReportResp = serde_json::from_slice(&body).expect("failed to parse");
+    let meal_item_ids: Vec<_> = actual_body.data.iter().map(|meal_item| meal_item.meal_item_id).collect();
+    assert_eq!(vec![early_meal_item.id(), eur_meal_item.id(), late_meal_item.id()], meal_item_ids);
+    assert_eq!("dropped", actual_body.data[0].reason);
+    let mut totals: Vec<_> = actual_body.total_wasted_costs.iter().map(|total| (total.currency, total.amount.clone())).collect();
+    totals.sort_by_key(|(currency, _)| currency.code());
+    assert_eq!(vec![(Currency::Eur, String::from("900")), (Currency::Usd, String::from("4300"))], totals);
+}
+
+#[tokio::test]
+async fn test_query_waste_report_handler_handle_empty() {
+    let handler = QueryWasteReportHandler::new(Arc::new(OrderRepo::new()));
+
+    let response = handler.handle().unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: WasteReportResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert!(actual_body.data.is_empty());
+    assert!(actual_body.total_wasted_costs.is_empty());
+}
@@ -1,25 +1,33 @@
 use std::sync::Arc;
+use chrono::Utc;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_REMOVED, MESSAGE_ORDER_NOT_FOUND};
-use crate::usecases::handlers::remove_meal_items::{RemoveMealItemsHandler, RemoveMealItemsReq, RemoveMealItemsResp};
+use uuid::Uuid;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_REMOVED, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_NOT_FOUND};
+use crate::usecases::handlers::remove_meal_items::{RemoveMealItemsHandler, RemoveMealItemsReq, RemoveMealItemsResp, RemoveOrderMealItemsReq};
+use crate::models::currency::Currency;
 use crate::models::meal::{MealItem, MealItemStatus};
 use crate::models::menu::MenuItem;
+use crate::models::price::Price;
 use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::tax::TaxPolicy;
 
 #[tokio::test]
 async fn test_remove_meal_items_handler_handle_success() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveMealItemsHandler::new(order_repo.clone());
+    let handler = RemoveMealItemsHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
 
-    let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-    let meal_item = MealItem::create(menu_item);
-    let mut order = Order::new(1, vec![]);
-    order.add_meal_items(vec![meal_item.clone()]);
-    order_repo.add(order);
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let request = RemoveMealItemsReq {
         table_id: 1,
@@ -37,7 +45,7 @@ async fn test_remove_meal_items_handler_handle_success() {
 async fn test_remove_meal_items_handler_handle_not_found() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveMealItemsHandler::new(order_repo.clone());
+    let handler = RemoveMealItemsHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
 
     let request = RemoveMealItemsReq {
         table_id: 1,
@@ -50,7 +58,7 @@ async fn test_remove_meal_items_handler_handle_not_found() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_NOT_FOUND.to_string() };
 
@@ -62,16 +70,16 @@ async fn test_remove_meal_items_handler_handle_not_found() {
 async fn test_remove_meal_items_handler_handle_partial_success() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveMealItemsHandler::new(order_repo.clone());
+    let handler = RemoveMealItemsHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
 
-    let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-    let meal_item_fries = MealItem::create(menu_item);
-    let menu_item = MenuItem::new(String::from("burger"), String::from("789"));
-    let mut meal_item_burger = MealItem::create(menu_item);
-    meal_item_burger.update_state(MealItemStatus::Preparing);
-    let mut order = Order::new(1, vec![]);
-    order.add_meal_items(vec![meal_item_fries.clone(), meal_item_burger.clone()]);
-    order_repo.add(order);
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item_fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let mut meal_item_burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    meal_item_burger.update_state(MealItemStatus::Preparing, Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item_fries.clone(), meal_item_burger.clone()], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let request = RemoveMealItemsReq {
         table_id: 1,
@@ -85,7 +93,7 @@ async fn test_remove_meal_items_handler_handle_partial_success() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: RemoveMealItemsResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: RemoveMealItemsResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body = RemoveMealItemsResp {
         non_removable_meal_item_ids: vec![meal_item_burger.id()],
@@ -95,3 +103,52 @@ async fn test_remove_meal_items_handler_handle_partial_success() {
     assert_eq!(status, StatusCode::OK);
     assert_eq!(expected_body, actual_body);
 }
+
+#[tokio::test]
+async fn test_remove_meal_items_handler_handle_by_id_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = RemoveMealItemsHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+    let order_id = order.id();
+    order_repo.add(order).unwrap();
+
+    let request = RemoveOrderMealItemsReq {
+        meal_item_ids: vec![meal_item.id()],
+    };
+    let response = handler.handle_by_id(order_id, request).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(order_repo.get_order_meal_item(order_id, meal_item.id()).unwrap().unwrap().is_removed());
+}
+
+#[tokio::test]
+async fn test_remove_meal_items_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = RemoveMealItemsHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(SystemClock));
+
+    let request = RemoveOrderMealItemsReq {
+        meal_item_ids: vec![],
+    };
+    let response = handler.handle_by_id(Uuid::new_v4(), request).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
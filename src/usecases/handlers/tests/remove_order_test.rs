@@ -1,22 +1,28 @@
 use std::sync::Arc;
+use chrono::Utc;
 use warp::reply::Reply;
 use warp::hyper::body::to_bytes;
 use warp::http::StatusCode;
-use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_NOT_FOUND, MESSAGE_ORDER_REMOVAL_CONFLICT};
+use uuid::Uuid;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_NOT_FOUND, MESSAGE_ORDER_REMOVAL_CONFLICT};
 use crate::usecases::handlers::remove_order::RemoveOrderHandler;
+use crate::models::currency::Currency;
 use crate::models::meal::{MealItem, MealItemStatus};
 use crate::models::menu::MenuItem;
+use crate::models::price::Price;
 use crate::models::order::Order;
-use crate::repositories::order::OrderRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
 
 #[tokio::test]
 async fn test_remove_order_handler_handle_success() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveOrderHandler::new(order_repo.clone());
+    let handler = RemoveOrderHandler::new(order_repo.clone(), Arc::new(SystemClock));
 
-    let order = Order::new(1, vec![]);
-    order_repo.add(order);
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let response = handler.handle(1).unwrap();
 
@@ -30,7 +36,7 @@ async fn test_remove_order_handler_handle_success() {
 async fn test_remove_order_handler_handle_not_found() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveOrderHandler::new(order_repo.clone());
+    let handler = RemoveOrderHandler::new(order_repo.clone(), Arc::new(SystemClock));
 
     let response = handler.handle(1).unwrap();
 
@@ -39,7 +45,7 @@ async fn test_remove_order_handler_handle_not_found() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_NOT_FOUND.to_string() };
 
@@ -51,14 +57,14 @@ async fn test_remove_order_handler_handle_not_found() {
 async fn test_remove_order_handler_handle_conflict() {
     let order_repo = Arc::new(OrderRepo::new());
 
-    let handler = RemoveOrderHandler::new(order_repo.clone());
+    let handler = RemoveOrderHandler::new(order_repo.clone(), Arc::new(SystemClock));
 
-    let menu_item = MenuItem::new(String::from("fries"), String::from("345"));
-    let mut meal_item = MealItem::create(menu_item);
-    meal_item.update_state(MealItemStatus::Preparing);
-    let mut order = Order::new(1, vec![]);
-    order.add_meal_items(vec![meal_item]);
-    order_repo.add(order);
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let mut meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    meal_item.update_state(MealItemStatus::Preparing, Utc::now());
+    let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order.add_meal_items(vec![meal_item], Utc::now()).unwrap();
+    order_repo.add(order).unwrap();
 
     let response = handler.handle(1).unwrap();
 
@@ -67,10 +73,55 @@ async fn test_remove_order_handler_handle_conflict() {
     let status = response.status();
     let body = to_bytes(response.into_body()).await.unwrap();
     let body_bytes = body.to_vec();
-    let actual_body: ErrResp = serde_json::from_slice(&*body_bytes).expect("failed to parse");
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
 
     let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_REMOVAL_CONFLICT.to_string() };
 
     assert_eq!(status, StatusCode::CONFLICT);
     assert_eq!(expected_body, actual_body);
 }
+
+#[tokio::test]
+async fn test_remove_order_handler_handle_by_id_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = RemoveOrderHandler::new(order_repo.clone(), Arc::new(SystemClock));
+
+    // Given the table has a newer order, removing the previous, canceled one by its id should leave the newer one untouched
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.remove_order(order.id(), Utc::now()).unwrap();
+    let current_order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    order_repo.add(current_order.clone()).unwrap();
+
+    let response = handler.handle_by_id(order.id()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert!(!order_repo.get_order(order.id()).unwrap().unwrap().is_active());
+    assert!(order_repo.get_order(current_order.id()).unwrap().unwrap().is_active());
+}
+
+#[tokio::test]
+async fn test_remove_order_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+
+    let handler = RemoveOrderHandler::new(order_repo.clone(), Arc::new(SystemClock));
+
+    let response = handler.handle_by_id(Uuid::new_v4()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
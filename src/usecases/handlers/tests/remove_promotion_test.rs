@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::remove_promotion::RemovePromotionHandler;
+use crate::usecases::models::error::{ErrResp, MESSAGE_PROMOTION_NOT_FOUND};
+use crate::models::currency::Currency;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+use crate::repositories::promotion::PromotionRepo;
+
+#[tokio::test]
+async fn test_remove_promotion_handler_handle_success() {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = RemovePromotionHandler::new(promotion_repo.clone());
+    let promotion = Promotion::new(String::from("10% off"), Currency::Usd,
+                                   Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None).unwrap();
+    promotion_repo.add(promotion.clone());
+
+    let response = handler.handle(promotion.id()).unwrap().into_response();
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert!(promotion_repo.get_all().is_empty());
+}
+
+#[tokio::test]
+async fn test_remove_promotion_handler_handle_not_found() {
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let handler = RemovePromotionHandler::new(promotion_repo.clone());
+
+    let response = handler.handle(Uuid::new_v4()).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(ErrResp { error_message: MESSAGE_PROMOTION_NOT_FOUND.to_string() }, actual_body);
+}
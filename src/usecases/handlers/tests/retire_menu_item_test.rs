@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_NOT_FOUND};
+use crate::usecases::handlers::retire_menu_item::RetireMenuItemHandler;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::Price;
+use crate::repositories::menu::MenuRepo;
+
+#[tokio::test]
+async fn test_retire_menu_item_handler_handle_success() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = RetireMenuItemHandler::new(menu_repo.clone());
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+    let menu_id = menu.id();
+    menu_repo.add(menu);
+
+    let response = handler.handle(menu_id, menu_item.id()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let menu_arc = menu_repo.get(menu_id).unwrap();
+    assert!(menu_arc.lock().unwrap().get_menu_item(menu_item.id()).unwrap().is_retired());
+}
+
+#[tokio::test]
+async fn test_retire_menu_item_handler_handle_not_found() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = RetireMenuItemHandler::new(menu_repo.clone());
+
+    let response = handler.handle(Uuid::new_v4(), Uuid::new_v4()).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_MENU_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use uuid::Uuid;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ITEMS_PARTIALLY_SERVED, MESSAGE_ORDER_ID_NOT_FOUND};
+use crate::usecases::handlers::serve_meal_items::{ServeMealItemsHandler, ServeMealItemsReq, ServeMealItemsResp, ServeOrderMealItemsReq};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::order::{Order, OrderStatus};
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn add_order(order_repo: &OrderRepo, table_id: u32) -> (Order, MealItem, MealItem) {
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item_fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let meal_item_burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let order = Order::new(table_id, Currency::Usd, vec![meal_item_fries.clone(), meal_item_burger.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.update_order_meal_item_status(order.id(), meal_item_fries.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+    (order, meal_item_fries, meal_item_burger)
+}
+
+#[tokio::test]
+async fn test_serve_meal_items_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = ServeMealItemsHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let (order, meal_item_fries, meal_item_burger) = add_order(&order_repo, 1);
+    order_repo.update_order_meal_item_status(order.id(), meal_item_burger.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+
+    let request = ServeMealItemsReq {
+        table_id: 1,
+        meal_item_ids: vec![meal_item_fries.id(), meal_item_burger.id()],
+    };
+    let response = handler.handle(request).unwrap().into_response();
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert_eq!(OrderStatus::Served, order_repo.get_order(order.id()).unwrap().unwrap().get_order_status());
+}
+
+#[tokio::test]
+async fn test_serve_meal_items_handler_handle_by_id_partial_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = ServeMealItemsHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let (order, meal_item_fries, meal_item_burger) = add_order(&order_repo, 1);
+
+    // Given the burger is not cooked yet, only the fries are served
+    let request = ServeOrderMealItemsReq {
+        meal_item_ids: vec![meal_item_fries.id(), meal_item_burger.id()],
+    };
+    let response = handler.handle_by_id(order.id(), request).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ServeMealItemsResp = serde_json::from_slice(&body).expect("failed to parse");
+    let expected_body = ServeMealItemsResp {
+        non_servable_meal_item_ids: vec![meal_item_burger.id()],
+        message: MESSAGE_ITEMS_PARTIALLY_SERVED.to_string(),
+    };
+    assert_eq!(expected_body, actual_body);
+    assert_eq!(MealItemStatus::Served, order_repo.get_order_meal_item(order.id(), meal_item_fries.id()).unwrap().unwrap().get_status());
+    assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), meal_item_burger.id()).unwrap().unwrap().get_status());
+}
+
+#[tokio::test]
+async fn test_serve_meal_items_handler_handle_by_id_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = ServeMealItemsHandler::new(order_repo.clone(), Arc::new(SystemClock));
+
+    let request = ServeOrderMealItemsReq {
+        meal_item_ids: vec![Uuid::new_v4()],
+    };
+    let response = handler.handle_by_id(Uuid::new_v4(), request).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(ErrResp { error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string() }, actual_body);
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use crate::libraries::clock::{Clock, VirtualClock};
+use crate::libraries::job_queue::SchedulingPolicy;
+use crate::libraries::thread_pool::ThreadPool;
+use crate::usecases::handlers::add_order::{AddOrderHandler, AddOrderReq, AddOrderResp, MenuItemReq};
+use crate::models::cooking_time::{CookingTimeProfile, RandomCookingTimeEstimator};
+use crate::models::currency::Currency;
+use crate::models::meal::MealItemStatus;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::order::{OrderPriority, OrderStatus};
+use crate::models::price::Price;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::menu::MenuRepo;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::usecases::jobs::kitchen::Kitchen;
+use crate::usecases::jobs::ready_time::ReadyTimeEstimator;
+
+fn menu_item(name: &str, minutes: u32) -> MenuItem {
+    MenuItem::new(String::from(name), Price::from_minor_units(500, Currency::Usd).unwrap())
+        .with_cooking_time_profile(CookingTimeProfile::Fixed { minutes })
+}
+
+fn at(minute: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap() + TimeDelta::minutes(minute)
+}
+
+async fn add_order(handler: &AddOrderHandler, table_id: u32, menu_items: &[&MenuItem]) -> AddOrderResp {
+    let req = AddOrderReq {
+        table_id,
+        menu_items: menu_items.iter().map(|menu_item| MenuItemReq { menu_item_id: menu_item.id(), quantity: 1 }).collect(),
+        priority: OrderPriority::Normal,
+        guests: None,
+    };
+    let response = handler.handle(req).unwrap().into_response();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).expect("failed to parse")
+}
+
+fn meal_item_status(order_repo: &OrderRepo, order_id: Uuid, name: &str) -> (MealItemStatus, DateTime<Utc>) {
+    let order = order_repo.get_order(order_id).unwrap().unwrap();
+    let meal_item_arc = order.get_meal_items().into_iter()
+        .find(|meal_item_arc| meal_item_arc.lock().unwrap().get_name() == name)
+        .unwrap();
+    let meal_item = meal_item_arc.lock().unwrap();
+    (meal_item.get_status(), meal_item.update_time())
+}
+
+// Keeps the pool from being dropped if the test fails midway, as dropping it waits for the workers sleeping on the clock
+struct KeepPoolOnPanic(Option<Arc<ThreadPool>>);
+
+impl Drop for KeepPoolOnPanic {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            std::mem::forget(self.0.take());
+        }
+    }
+}
+
+// Runs a dinner shift on two chefs with a virtual clock, so twenty minutes of cooking pass without waiting for them
+#[tokio::test]
+async fn test_service_shift_on_virtual_clock() {
+    let clock = Arc::new(VirtualClock::new(at(0)));
+    let order_repo = Arc::new(OrderRepo::new());
+    let menu_repo = Arc::new(MenuRepo::new());
+    let kitchen_repo = Arc::new(KitchenRepo::new());
+    let thread_pool = Arc::new(ThreadPool::new(2, SchedulingPolicy::Fifo, clock.clone()));
+    let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+    let ready_time_estimator = Arc::new(ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), clock.clone()));
+    let handler = AddOrderHandler::new(order_repo.clone(), menu_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(RandomCookingTimeEstimator::new()), kitchen_repo, kitchen.clone(), ready_time_estimator.clone(), clock.clone());
+    let pool_guard = KeepPoolOnPanic(Some(thread_pool.clone()));
+
+    let steak = menu_item("steak", 20);
+    let fries = menu_item("fries", 5);
+    let salad = menu_item("salad", 10);
+    menu_repo.add(Menu::new(String::from("dinner"), Currency::Usd, vec![steak.clone(), fries.clone(), salad.clone()]));
+
+    // Given both chefs start on the first order, the next ones wait for the chef cooking the fries
+    let first_order = add_order(&handler, 1, &[&steak, &fries]).await.data;
+    clock.wait_for_sleepers(2);
+    let second_order = add_order(&handler, 2, &[&fries]).await.data;
+    let third_order = add_order(&handler, 3, &[&salad]).await.data;
+
+    let ready_times = ready_time_estimator.estimate();
+    assert_eq!(at(0), first_order.creation_time);
+    assert_eq!(Some(at(20)), ready_times.order(&order_repo.get_order(first_order.order_id).unwrap().unwrap()));
+    assert_eq!(Some(at(10)), second_order.estimated_ready_time);
+    assert_eq!(Some(at(20)), third_order.estimated_ready_time);
+    assert_eq!((MealItemStatus::Preparing, at(0)), meal_item_status(&order_repo, first_order.order_id, "steak"));
+    assert_eq!((MealItemStatus::Received, at(0)), meal_item_status(&order_repo, second_order.order_id, "fries"));
+
+    // Once the first fries are done, their chef takes the next ones
+    clock.advance(Duration::from_secs(5 * 60));
+    clock.wait_for_sleepers(2);
+    assert_eq!((MealItemStatus::ReadyForPickup, at(5)), meal_item_status(&order_repo, first_order.order_id, "fries"));
+    assert_eq!((MealItemStatus::Preparing, at(5)), meal_item_status(&order_repo, second_order.order_id, "fries"));
+    assert_eq!((MealItemStatus::Received, at(0)), meal_item_status(&order_repo, third_order.order_id, "salad"));
+
+    clock.advance(Duration::from_secs(5 * 60));
+    clock.wait_for_sleepers(2);
+    assert_eq!((MealItemStatus::ReadyForPickup, at(10)), meal_item_status(&order_repo, second_order.order_id, "fries"));
+    assert_eq!((MealItemStatus::Preparing, at(10)), meal_item_status(&order_repo, third_order.order_id, "salad"));
+
+    // Dropping the pool waits for the chefs to finish the meal items they are cooking
+    clock.advance(Duration::from_secs(10 * 60));
+    drop(handler);
+    drop(ready_time_estimator);
+    drop(kitchen);
+    drop(pool_guard);
+    drop(Arc::into_inner(thread_pool).expect("pool is still shared"));
+
+    assert_eq!(at(20), clock.now());
+    for (order_id, ready_time) in [(first_order.order_id, at(20)), (second_order.order_id, at(10)), (third_order.order_id, at(20))] {
+        let order = order_repo.get_order(order_id).unwrap().unwrap();
+        assert_eq!(OrderStatus::Completed, order.get_order_status());
+        assert_eq!(ready_time, order.update_time());
+    }
+    assert_eq!((MealItemStatus::ReadyForPickup, at(20)), meal_item_status(&order_repo, first_order.order_id, "steak"));
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::Filter;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::libraries::shutdown::Shutdown;
+use crate::usecases::handlers::checkout::CheckoutHandler;
+use crate::usecases::handlers::shutdown::{accepting_changes, recover_shutting_down};
+use crate::usecases::models::error::{ErrResp, MESSAGE_SHUTTING_DOWN};
+use crate::models::currency::Currency;
+use crate::models::meal::MealItem;
+use crate::models::menu::MenuItem;
+use crate::models::order::Order;
+use crate::models::price::Price;
+use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+use crate::models::tax::TaxPolicy;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::repositories::promotion::PromotionRepo;
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn routes(shutdown: Arc<Shutdown>) -> impl Filter<Extract = (impl warp::Reply,), Error = std::convert::Infallible> + Clone {
+    let change = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown))
+        .map(|| warp::reply::with_status("created", StatusCode::CREATED));
+    let read = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .map(|| "orders");
+    change.or(read).recover(recover_shutting_down).recover(|_| async { Ok::<_, std::convert::Infallible>(StatusCode::NOT_FOUND) })
+}
+
+#[tokio::test]
+async fn test_accepting_changes_before_shutdown() {
+    let shutdown = Arc::new(Shutdown::new());
+
+    let response = warp::test::request().method("POST").path("/orders").reply(&routes(shutdown)).await;
+
+    assert_eq!(StatusCode::CREATED, response.status());
+}
+
+#[tokio::test]
+async fn test_changes_refused_while_shutting_down() {
+    let shutdown = Arc::new(Shutdown::new());
+    shutdown.start_draining();
+    let routes = routes(shutdown);
+
+    let response = warp::test::request().method("POST").path("/orders").reply(&routes).await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    let body: ErrResp = serde_json::from_slice(response.body()).expect("failed to parse");
+    assert_eq!(MESSAGE_SHUTTING_DOWN, body.error_message);
+
+    // Reads are still answered, and requests matching no route are left to the other recoveries
+    let response = warp::test::request().method("GET").path("/orders").reply(&routes).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let response = warp::test::request().method("POST").path("/menus").reply(&routes).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+// Checkout prices the order again and saves it, so it is a change even though it only gives the bill
+#[tokio::test]
+async fn test_checkout_refused_while_shutting_down() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let promotion_repo = Arc::new(PromotionRepo::new());
+    let checkout_handler = Arc::new(CheckoutHandler::new(order_repo.clone(), promotion_repo.clone(), Arc::new(TaxPolicy::default()), Arc::new(SystemClock)));
+    let shutdown = Arc::new(Shutdown::new());
+    // Same route as the server's
+    let checkout = warp::post()
+        .and(warp::path("orders"))
+        .and(warp::path::param())
+        .and(warp::path("checkout"))
+        .and(warp::path::end())
+        .and(accepting_changes(shutdown.clone()))
+        .and_then(move |table_id: u32| {
+            let handler = checkout_handler.clone();
+            async move { handler.handle(table_id) }
+        })
+        .recover(recover_shutting_down);
+
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(1000, Currency::Usd).unwrap());
+    let order = Order::new(1, Currency::Usd, vec![MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now())], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    promotion_repo.add(Promotion::new(String::from("15% off"), Currency::Usd,
+                                      Reward::PercentOff { target: PromotionTarget::Order, percent: 15 }, None).unwrap());
+    shutdown.start_draining();
+
+    let response = warp::test::request().method("POST").path("/orders/1/checkout").reply(&checkout).await;
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    let body: ErrResp = serde_json::from_slice(response.body()).expect("failed to parse");
+    assert_eq!(MESSAGE_SHUTTING_DOWN, body.error_message);
+    // The order wasn't priced again
+    assert!(order_repo.get_order(order.id()).unwrap().unwrap().get_promotions().is_empty());
+}
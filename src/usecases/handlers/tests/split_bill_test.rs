@@ -0,0 +1,131 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::handlers::add_payment::{AddPaymentHandler, AddPaymentReq, AddPaymentResp};
+use crate::usecases::handlers::split_bill::{SplitBillHandler, SplitBillReq, SplitBillResp};
+use crate::usecases::models::error::{ErrResp, MESSAGE_INVALID_SPLIT, MESSAGE_ORDER_CLOSED, MESSAGE_ORDER_NOT_FOUND,
+                                     MESSAGE_SPLIT_MODE_INVALID, MESSAGE_SPLIT_REFUSED};
+use crate::models::currency::Currency;
+use crate::models::meal::MealItem;
+use crate::models::menu::MenuItem;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::models::split::{CheckStatus, NamedCheck};
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+fn add_order(order_repo: &OrderRepo) -> (Order, MealItem, MealItem) {
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let fries = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+    let menu_item = MenuItem::new(String::from("burger"), Price::from_minor_units(789, Currency::Usd).unwrap());
+    let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(15), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![fries.clone(), burger.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    (order, fries, burger)
+}
+
+async fn err_body(response: warp::reply::Response) -> ErrResp {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).expect("failed to parse")
+}
+
+#[tokio::test]
+async fn test_split_bill_handler_handle_by_meal_item() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = SplitBillHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let add_payment_handler = AddPaymentHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let (order, fries, burger) = add_order(&order_repo);
+
+    let req = SplitBillReq {
+        checks: Some(vec![
+            NamedCheck { name: String::from("ann"), meal_item_ids: vec![fries.id()] },
+            NamedCheck { name: String::from("bob"), meal_item_ids: vec![burger.id()] },
+        ]),
+        guests: None,
+    };
+    let response = handler.handle(1, req).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: SplitBillResp = serde_json::from_slice(&body).expect("failed to parse");
+    let checks: Vec<_> = actual_body.data.checks.iter()
+        .map(|check| (check.name.as_str(), check.meal_item_ids.clone(), check.total.amount.as_str(), check.status))
+        .collect();
+    assert_eq!(vec![("ann", vec![fries.id()], "345", CheckStatus::Unpaid), ("bob", vec![burger.id()], "789", CheckStatus::Unpaid)], checks);
+
+    // Ann pays her check on her own
+    let req = AddPaymentReq {
+        method: PaymentMethod::Cash,
+        amount: String::from("345"),
+        reference: None,
+        check: Some(String::from("ann")),
+    };
+    let response = add_payment_handler.handle(1, req).unwrap().into_response();
+    assert_eq!(StatusCode::CREATED, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: AddPaymentResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(CheckStatus::Paid, actual_body.data.checks[0].status);
+    assert_eq!(Some(String::from("ann")), actual_body.data.payments[0].check);
+    assert_eq!("789", actual_body.data.balance_due.amount);
+
+    // Ann's check can't be dropped once paid
+    let response = handler.handle(1, SplitBillReq { checks: None, guests: Some(3) }).unwrap().into_response();
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert_eq!(format!("{}: check 'ann' is paid in part and has to stay in the split", MESSAGE_SPLIT_REFUSED), err_body(response).await.error_message);
+    assert_eq!(2, order_repo.get_order(order.id()).unwrap().unwrap().get_sub_checks().len());
+}
+
+#[tokio::test]
+async fn test_split_bill_handler_handle_evenly() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = SplitBillHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    add_order(&order_repo);
+
+    let response = handler.handle(1, SplitBillReq { checks: None, guests: Some(3) }).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: SplitBillResp = serde_json::from_slice(&body).expect("failed to parse");
+    let checks: Vec<_> = actual_body.data.checks.iter().map(|check| (check.name.as_str(), check.subtotal.amount.as_str())).collect();
+    assert_eq!(vec![("guest-1", "378"), ("guest-2", "378"), ("guest-3", "378")], checks);
+    assert_eq!("1134", actual_body.data.total_price.amount);
+}
+
+#[tokio::test]
+async fn test_split_bill_handler_handle_invalid() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = SplitBillHandler::new(order_repo.clone(), Arc::new(SystemClock));
+    let (order, fries, _) = add_order(&order_repo);
+
+    let response = handler.handle(1, SplitBillReq { checks: None, guests: None }).unwrap().into_response();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(MESSAGE_SPLIT_MODE_INVALID, err_body(response).await.error_message);
+
+    let req = SplitBillReq {
+        checks: Some(vec![NamedCheck { name: String::from("ann"), meal_item_ids: vec![fries.id()] }]),
+        guests: None,
+    };
+    let response = handler.handle(1, req).unwrap().into_response();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert!(err_body(response).await.error_message.starts_with(MESSAGE_INVALID_SPLIT));
+
+    order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, order.get_total_price(), Utc::now())).unwrap().unwrap();
+    let response = handler.handle(1, SplitBillReq { checks: None, guests: Some(2) }).unwrap().into_response();
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert_eq!(MESSAGE_ORDER_CLOSED, err_body(response).await.error_message);
+}
+
+#[tokio::test]
+async fn test_split_bill_handler_handle_not_found() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = SplitBillHandler::new(order_repo.clone(), Arc::new(SystemClock));
+
+    let response = handler.handle(1, SplitBillReq { checks: None, guests: Some(2) }).unwrap().into_response();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(MESSAGE_ORDER_NOT_FOUND, err_body(response).await.error_message);
+}
@@ -0,0 +1,28 @@
+use warp::Filter;
+use warp::http::StatusCode;
+use crate::repositories::order::OrderStoreError;
+use crate::usecases::handlers::store::recover_store_failed;
+use crate::usecases::models::error::{ErrResp, MESSAGE_STORE_FAILED};
+
+fn routes() -> impl Filter<Extract = (impl warp::Reply,), Error = std::convert::Infallible> + Clone {
+    let orders = warp::get()
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .and_then(|| async { Err::<&str, _>(warp::reject::custom(OrderStoreError(String::from("disk I/O error")))) });
+    orders.recover(recover_store_failed).recover(|_| async { Ok::<_, std::convert::Infallible>(StatusCode::NOT_FOUND) })
+}
+
+#[tokio::test]
+async fn test_recover_store_failed() {
+    let routes = routes();
+
+    let response = warp::test::request().method("GET").path("/orders").reply(&routes).await;
+
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    let body: ErrResp = serde_json::from_slice(response.body()).expect("failed to parse");
+    assert_eq!(MESSAGE_STORE_FAILED, body.error_message);
+
+    // Other rejections are left to the next recover
+    let response = warp::test::request().method("GET").path("/menus").reply(&routes).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
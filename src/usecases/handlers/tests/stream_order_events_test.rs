@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::handlers::stream_order_events::StreamOrderEventsHandler;
+use crate::usecases::models::error::{ErrResp, MESSAGE_EVENTS_CLOSED};
+use crate::models::meal::MealItemStatus;
+use crate::models::order_event::OrderChange;
+use crate::repositories::order_event::{OrderEventRepo, EVENT_HISTORY};
+
+// Table 1 had its fries started and table 2 its order removed
+fn setup() -> (Arc<OrderEventRepo>, Uuid) {
+    let order_event_repo = Arc::new(OrderEventRepo::new(10));
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let meal_item_id = Uuid::new_v4();
+    let change = OrderChange::MealItemStatusChanged { meal_item_id, from: MealItemStatus::Received, to: MealItemStatus::Preparing };
+    order_event_repo.publish(Uuid::new_v4(), 1, vec![change], now);
+    order_event_repo.publish(Uuid::new_v4(), 2, vec![OrderChange::OrderRemoved], now);
+    (order_event_repo, meal_item_id)
+}
+
+// Epoch of the events kept, in the ids the clients resume from
+fn epoch(order_event_repo: &OrderEventRepo) -> u32 {
+    order_event_repo.kept_events()[0].id().epoch
+}
+
+// Events as (number in their id, name, data), read once the stream ends
+async fn read_events(response: warp::reply::Response) -> Vec<(String, String, serde_json::Value)> {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    text.split("\n\n")
+        .filter(|event| !event.trim().is_empty())
+        .map(|event| {
+            let field = |name: &str| event.lines()
+                .find_map(|line| line.strip_prefix(&format!("{}:", name)))
+                .unwrap_or_default()
+                .to_string();
+            let number = field("id").split_once('-').map(|(_, number)| number.to_string()).unwrap_or_default();
+            (number, field("event"), serde_json::from_str(&field("data")).unwrap())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle() {
+    let (order_event_repo, _) = setup();
+    let epoch = epoch(&order_event_repo);
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+
+    let response = handler.handle(None, None).unwrap();
+    order_event_repo.publish(Uuid::new_v4(), 3, vec![OrderChange::OrderRemoved], Utc::now());
+    order_event_repo.close();
+
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!("text/event-stream", response.headers()["content-type"]);
+    let events = read_events(response).await;
+    let ids: Vec<_> = events.iter().map(|(id, name, _)| (id.as_str(), name.as_str())).collect();
+    // Given the client has no id, it only gets the events published once it is following
+    assert_eq!(vec![("3", "order_removed")], ids);
+    let data = &events[0].2;
+    assert_eq!((format!("{}-3", epoch).as_str(), "order_removed"), (data["event_id"].as_str().unwrap(), data["type"].as_str().unwrap()));
+    assert_eq!(3, data["table_id"].as_u64().unwrap());
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle_table() {
+    let (order_event_repo, _) = setup();
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+
+    let epoch = epoch(&order_event_repo);
+    let response = handler.handle(Some(2), Some(format!("{}-0", epoch))).unwrap();
+    order_event_repo.close();
+
+    let events = read_events(response).await;
+    let ids: Vec<_> = events.iter().map(|(id, name, _)| (id.as_str(), name.as_str())).collect();
+    assert_eq!(vec![("2", "order_removed")], ids);
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle_last_event_id() {
+    let (order_event_repo, meal_item_id) = setup();
+    let epoch = epoch(&order_event_repo);
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+
+    let from_start = handler.handle(None, Some(format!("{}-0", epoch))).unwrap();
+    let resumed = handler.handle(None, Some(format!("{}-1", epoch))).unwrap();
+    // An id which isn't one is taken as none, so gets no event kept
+    let unknown = handler.handle(None, Some(String::from("abc"))).unwrap();
+    order_event_repo.close();
+
+    let ids = |events: Vec<(String, String, serde_json::Value)>| events.into_iter().map(|(id, _, _)| id).collect::<Vec<_>>();
+    let events = read_events(from_start).await;
+    let data = &events[0].2;
+    assert_eq!((format!("{}-1", epoch).as_str(), "meal_item_status_changed"), (data["event_id"].as_str().unwrap(), data["type"].as_str().unwrap()));
+    assert_eq!((1, meal_item_id.to_string()), (data["table_id"].as_u64().unwrap(), data["meal_item_id"].as_str().unwrap().to_string()));
+    assert_eq!(("Received", "Preparing"), (data["from"].as_str().unwrap(), data["to"].as_str().unwrap()));
+    assert_eq!(vec!["1", "2"], ids(events));
+    assert_eq!(vec!["2"], ids(read_events(resumed).await));
+    assert!(read_events(unknown).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle_past_history() {
+    let order_event_repo = Arc::new(OrderEventRepo::new(EVENT_HISTORY));
+    for _ in 0..EVENT_HISTORY + 2 {
+        order_event_repo.publish(Uuid::new_v4(), 1, vec![OrderChange::OrderRemoved], Utc::now());
+    }
+    let epoch = epoch(&order_event_repo);
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+
+    // Event 2 is no longer kept, event 3 is the oldest one
+    let missed = handler.handle(None, Some(format!("{}-1", epoch))).unwrap();
+    let caught_up = handler.handle(None, Some(format!("{}-2", epoch))).unwrap();
+    // Ids from before a restart are told to reload too, even with a number given again in this run
+    let restarted = handler.handle(Some(1), Some(format!("{}-3", epoch.wrapping_add(1)))).unwrap();
+    order_event_repo.close();
+
+    let events = read_events(missed).await;
+    assert_eq!(EVENT_HISTORY + 1, events.len());
+    assert_eq!((String::new(), String::from("reset")), (events[0].0.clone(), events[0].1.clone()));
+    assert_eq!("reset", events[0].2["type"]);
+    assert_eq!("3", events[1].0);
+    // Events of this run mean nothing to a client of the last one, which reloads the orders anyway
+    let events = read_events(restarted).await;
+    assert_eq!(vec![(String::new(), String::from("reset"))], events.iter().map(|(id, name, _)| (id.clone(), name.clone())).collect::<Vec<_>>());
+    let events = read_events(caught_up).await;
+    assert_eq!(EVENT_HISTORY, events.len());
+    assert_eq!("3", events[0].0);
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle_lagged() {
+    let order_event_repo = Arc::new(OrderEventRepo::new(2));
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+
+    let response = handler.handle(None, None).unwrap();
+    // More are published than are held for the client before it reads any of them
+    for _ in 0..4 {
+        order_event_repo.publish(Uuid::new_v4(), 1, vec![OrderChange::OrderRemoved], Utc::now());
+    }
+    order_event_repo.close();
+
+    let events = read_events(response).await;
+    let ids: Vec<_> = events.iter().map(|(id, name, _)| (id.as_str(), name.as_str())).collect();
+    assert_eq!(vec![("", "reset"), ("3", "order_removed"), ("4", "order_removed")], ids);
+}
+
+#[tokio::test]
+async fn test_stream_order_events_handler_handle_closed() {
+    let (order_event_repo, _) = setup();
+    let handler = StreamOrderEventsHandler::new(order_event_repo.clone());
+    order_event_repo.close();
+
+    let response = handler.handle(None, None).unwrap();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: ErrResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(MESSAGE_EVENTS_CLOSED, actual_body.error_message);
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_ITEM_NOT_FOUND};
+use crate::usecases::handlers::update_menu_item::{UpdateMenuItemHandler, UpdateMenuItemReq, UpdateMenuItemResp};
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::station::Station;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::price::Price;
+use crate::repositories::menu::MenuRepo;
+
+#[tokio::test]
+async fn test_update_menu_item_handler_handle_success() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = UpdateMenuItemHandler::new(menu_repo.clone());
+
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![menu_item.clone()]);
+    let menu_id = menu.id();
+    menu_repo.add(menu);
+
+    let req = UpdateMenuItemReq {
+        name: None,
+        price: Some(String::from("399")),
+        cooking_time_profile: Some(CookingTimeProfile::Fixed { minutes: 4 }),
+        station: Some(Station::Fryer),
+        category: None,
+    };
+
+    let response = handler.handle(menu_id, menu_item.id(), req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: UpdateMenuItemResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!("fries", actual_body.data.name);
+    assert_eq!("399", actual_body.data.price);
+    assert_eq!(CookingTimeProfile::Fixed { minutes: 4 }, actual_body.data.cooking_time_profile);
+    assert_eq!(Station::Fryer, actual_body.data.station);
+}
+
+#[tokio::test]
+async fn test_update_menu_item_handler_handle_item_not_found() {
+    let menu_repo = Arc::new(MenuRepo::new());
+
+    let handler = UpdateMenuItemHandler::new(menu_repo.clone());
+
+    let menu = Menu::new(String::from("lunch"), Currency::Usd, vec![]);
+    let menu_id = menu.id();
+    menu_repo.add(menu);
+
+    let req = UpdateMenuItemReq {
+        name: Some(String::from("fries")),
+        price: None,
+        cooking_time_profile: None,
+        station: None,
+        category: None,
+    };
+
+    let response = handler.handle(menu_id, Uuid::new_v4(), req).unwrap();
+
+    let response = response.into_response();
+
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let body_bytes = body.to_vec();
+    let actual_body: ErrResp = serde_json::from_slice(&body_bytes).expect("failed to parse");
+
+    let expected_body: ErrResp = ErrResp { error_message: MESSAGE_MENU_ITEM_NOT_FOUND.to_string() };
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(expected_body, actual_body);
+}
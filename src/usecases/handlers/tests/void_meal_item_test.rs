@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use chrono::Utc;
+use warp::reply::Reply;
+use warp::hyper::body::to_bytes;
+use warp::http::StatusCode;
+use uuid::Uuid;
+use crate::libraries::clock::SystemClock;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_ITEM_NOT_FOUND, MESSAGE_VOID_CONFLICT, MESSAGE_VOID_REASON_MISSING};
+use crate::usecases::handlers::void_meal_item::{VoidMealItemHandler, VoidMealItemReq, VoidMealItemResp};
+use crate::models::currency::Currency;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::menu::MenuItem;
+use crate::models::price::Price;
+use crate::models::order::Order;
+use crate::models::station::Station;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::promotion::PromotionRepo;
+use crate::repositories::order::{OrderRepo, OrderRepository};
+use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+use crate::models::tax::TaxPolicy;
+
+fn void_req(reason: &str) -> VoidMealItemReq {
+    VoidMealItemReq { reason: String::from(reason) }
+}
+
+async fn err_body(response: warp::reply::Response) -> ErrResp {
+    let body = to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).expect("failed to parse")
+}
+
+#[tokio::test]
+async fn test_void_meal_item_handler_handle_success() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = VoidMealItemHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(KitchenRepo::new()), Arc::new(SystemClock));
+
+    let menu_item = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(20), Utc::now());
+    let order = Order::new(3, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+    order_repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+
+    let response = handler.handle_by_id(order.id(), meal_item.id(), void_req(" guest left ")).unwrap().into_response();
+
+    assert_eq!(StatusCode::OK, response.status());
+    let body = to_bytes(response.into_body()).await.unwrap();
+    let actual_body: VoidMealItemResp = serde_json::from_slice(&body).expect("failed to parse");
+    assert_eq!(order.id(), actual_body.data.order_id);
+    assert_eq!(3, actual_body.data.table_id);
+    assert_eq!(meal_item.id(), actual_body.data.meal_item_id);
+    assert_eq!(Station::Grill, actual_body.data.station);
+    assert_eq!("guest left", actual_body.data.reason);
+    assert_eq!("2500", actual_body.data.wasted_cost.amount);
+    assert_eq!(MealItemStatus::Voided, order_repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().get_status());
+}
+
+#[tokio::test]
+async fn test_void_meal_item_handler_handle_conflict() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = VoidMealItemHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(KitchenRepo::new()), Arc::new(SystemClock));
+
+    // Given the meal item isn't being prepared yet, it has to be removed instead
+    let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+    let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(5), Utc::now());
+    let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+
+    let response = handler.handle_by_id(order.id(), meal_item.id(), void_req("guest left")).unwrap().into_response();
+
+    assert_eq!(StatusCode::CONFLICT, response.status());
+    assert_eq!(MESSAGE_VOID_CONFLICT, err_body(response).await.error_message);
+    assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().get_status());
+}
+
+#[tokio::test]
+async fn test_void_meal_item_handler_handle_invalid() {
+    let order_repo = Arc::new(OrderRepo::new());
+    let handler = VoidMealItemHandler::new(order_repo.clone(), Arc::new(PromotionRepo::new()), Arc::new(TaxPolicy::default()), Arc::new(KitchenRepo::new()), Arc::new(SystemClock));
+    let order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+    order_repo.add(order.clone()).unwrap();
+
+    let response = handler.handle_by_id(order.id(), Uuid::new_v4(), void_req("  ")).unwrap().into_response();
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    assert_eq!(MESSAGE_VOID_REASON_MISSING, err_body(response).await.error_message);
+
+    let response = handler.handle_by_id(Uuid::new_v4(), Uuid::new_v4(), void_req("guest left")).unwrap().into_response();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(MESSAGE_ORDER_ID_NOT_FOUND, err_body(response).await.error_message);
+
+    let response = handler.handle_by_id(order.id(), Uuid::new_v4(), void_req("guest left")).unwrap().into_response();
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!(MESSAGE_ORDER_ITEM_NOT_FOUND, err_body(response).await.error_message);
+}
@@ -0,0 +1,110 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::usecases::models::error::{ErrResp, MESSAGE_MENU_ITEM_NOT_FOUND, MESSAGE_MENU_NOT_FOUND};
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::price::Price;
+use crate::models::station::Station;
+use crate::repositories::menu::MenuRepo;
+use crate::usecases::handlers::add_menu::{invalid_cooking_time_profile_resp, invalid_price_resp, to_category};
+use crate::usecases::models::menu_resp::MenuItemResp;
+
+#[derive(Deserialize)]
+pub struct UpdateMenuItemReq {
+    pub name: Option<String>,
+    pub price: Option<String>,
+    pub cooking_time_profile: Option<CookingTimeProfile>,
+    pub station: Option<Station>,
+    pub category: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateMenuItemResp {
+    pub data: MenuItemResp,
+}
+
+pub struct UpdateMenuItemHandler {
+    menu_repo: Arc<MenuRepo>,
+}
+
+impl UpdateMenuItemHandler {
+    pub fn new(menu_repo: Arc<MenuRepo>) -> Self {
+        UpdateMenuItemHandler {
+            menu_repo,
+        }
+    }
+
+    pub fn handle(&self, menu_id: Uuid, menu_item_id: Uuid, req: UpdateMenuItemReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let currency = match self.menu_repo.get_currency(menu_id) {
+            Some(currency) => currency,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+
+        let price = match req.price.map(|price| Price::parse(&price, currency)).transpose() {
+            Ok(price) => price,
+            Err(err) => {
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&invalid_price_resp(err)),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        if let Some(Err(err)) = req.cooking_time_profile.map(|profile| profile.validate()) {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&invalid_cooking_time_profile_resp(err)),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let (updated, existed) = self.menu_repo.update_menu_item(menu_id, menu_item_id, req.name, price, req.cooking_time_profile, req.station,
+                                                                 to_category(req.category));
+        if !existed {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_NOT_FOUND.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        if !updated {
+            let resp = ErrResp {
+                error_message: MESSAGE_MENU_ITEM_NOT_FOUND.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+
+        if let Some(menu_item) = self.menu_repo.get(menu_id)
+            .and_then(|menu_arc| menu_arc.lock().unwrap().get_menu_item(menu_item_id)) {
+            let resp = UpdateMenuItemResp {
+                data: MenuItemResp::new(&menu_item),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::OK,
+            ));
+        }
+
+        let resp = ErrResp {
+            error_message: StatusCode::INTERNAL_SERVER_ERROR.to_string()
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ))
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::{Arc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use warp::http::StatusCode;
+use crate::models::tax::TaxPolicy;
+use crate::libraries::clock::Clock;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::order::OrderRepository;
+use crate::repositories::promotion::PromotionRepo;
+use crate::usecases::jobs::price_order::price_order;
+use crate::usecases::models::error::{ErrResp, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_ITEM_NOT_FOUND, MESSAGE_VOID_CONFLICT, MESSAGE_VOID_REASON_MISSING};
+use crate::usecases::models::waste_resp::WastedMealItemResp;
+
+#[derive(Deserialize)]
+pub struct VoidMealItemReq {
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct VoidMealItemResp {
+    pub data: WastedMealItemResp,
+}
+
+pub struct VoidMealItemHandler {
+    order_repo: Arc<dyn OrderRepository>,
+    promotion_repo: Arc<PromotionRepo>,
+    tax_policy: Arc<TaxPolicy>,
+    kitchen_repo: Arc<KitchenRepo>,
+    clock: Arc<dyn Clock>,
+}
+
+impl VoidMealItemHandler {
+    pub fn new(order_repo: Arc<dyn OrderRepository>, promotion_repo: Arc<PromotionRepo>, tax_policy: Arc<TaxPolicy>, kitchen_repo: Arc<KitchenRepo>, clock: Arc<dyn Clock>) -> Self {
+        VoidMealItemHandler {
+            order_repo,
+            promotion_repo,
+            tax_policy,
+            kitchen_repo,
+            clock,
+        }
+    }
+
+    // Voids a meal item being prepared, ex. once the guest has left. The chef cooking it stops at once,
+    // and the meal item is no longer charged but counted as waste. Meal items not started yet are removed instead.
+    pub fn handle_by_id(&self, order_id: Uuid, meal_item_id: Uuid, req: VoidMealItemReq) -> Result<impl warp::Reply, warp::Rejection> {
+        let reason = req.reason.trim().to_string();
+        if reason.is_empty() {
+            let resp = ErrResp {
+                error_message: MESSAGE_VOID_REASON_MISSING.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+
+        let order = match self.order_repo.get_order(order_id)? {
+            Some(order) => order,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_ORDER_ID_NOT_FOUND.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+
+        let now = self.clock.now();
+        let (voided, existed) = self.order_repo.void_order_meal_item(order_id, meal_item_id, reason, now)?;
+        if !existed {
+            let resp = ErrResp {
+                error_message: MESSAGE_ORDER_ITEM_NOT_FOUND.to_string(),
+            };
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&resp),
+                StatusCode::NOT_FOUND,
+            ));
+        }
+        // Only a voided meal item is counted as waste
+        let wasted_meal_item = match voided.and_then(|meal_item| WastedMealItemResp::new(&order, &meal_item)) {
+            Some(wasted_meal_item) => wasted_meal_item,
+            None => {
+                let resp = ErrResp {
+                    error_message: MESSAGE_VOID_CONFLICT.to_string(),
+                };
+                return Ok(warp::reply::with_status(
+                    warp::reply::json(&resp),
+                    StatusCode::CONFLICT,
+                ));
+            }
+        };
+
+        // The void is stored, so the chef can stop cooking the meal item
+        self.kitchen_repo.interrupt(meal_item_id);
+        price_order(&*self.order_repo, &self.promotion_repo, &self.tax_policy, order_id, now)?;
+        let resp = VoidMealItemResp {
+            data: wasted_meal_item,
+        };
+        Ok(warp::reply::with_status(
+            warp::reply::json(&resp),
+            StatusCode::OK,
+        ))
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::libraries::clock::Clock;
+use crate::libraries::job::{Job, JobInfo, JobStep};
+use crate::libraries::worker::{current_job_interrupt, current_worker_id, is_interrupted};
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::models::order::{Order, OrderPriority};
+use crate::repositories::kitchen::{Assignment, KitchenRepo};
+use crate::repositories::order::OrderRepository;
+
+// Job preparing one meal item of the order, run by one of the chef workers.
+// A meal item whose changes can't be stored is left as it was stored last, see resume_kitchen.
+pub fn cook_meal_item(order_repo: Arc<dyn OrderRepository>, kitchen_repo: Arc<KitchenRepo>, clock: Arc<dyn Clock>, order_id: Uuid, meal_item_id: Uuid) -> Job {
+    Box::new(move || {
+        let meal_item = match order_repo.get_order_meal_item(order_id, meal_item_id) {
+            Ok(Some(meal_item)) => meal_item,
+            Ok(None) => return JobStep::Done,
+            Err(err) => {
+                eprintln!("Failed to start preparing {}: {}", meal_item_id, err);
+                return JobStep::Done;
+            }
+        };
+        // If item is removed, continue without further processing
+        if meal_item.is_removed() { return JobStep::Done; }
+
+        println!("start preparing {}", meal_item_id);
+
+        // Simulates cooking time by having the chef wait, blocking it from accepting new meals until the current meal is prepared.
+        // The clock decides how long a minute of cooking really takes, ex. a second with the kitchen running 60 times faster.
+        let now = clock.now();
+        let ready_time = now + clock.kitchen_duration(cooking_duration(meal_item.cooking_time_in_min()));
+        if let Some(worker_id) = current_worker_id() {
+            kitchen_repo.assign(meal_item_id, Assignment { worker_id, station: meal_item.station(), ready_time });
+        }
+        // Set before the meal item is Preparing, so voiding it always stops the chef, see VoidMealItemHandler
+        if let Some(job_interrupt) = current_job_interrupt() {
+            kitchen_repo.set_job_interrupt(meal_item_id, job_interrupt);
+        }
+
+        // Update status as Preparing to prevent meal item being canceled
+        match order_repo.update_order_meal_item_status(order_id, meal_item_id, MealItemStatus::Preparing, now) {
+            Ok(true) => {}
+            Ok(false) => {
+                kitchen_repo.release(meal_item_id);
+                return JobStep::Done;
+            }
+            Err(err) => {
+                kitchen_repo.release(meal_item_id);
+                eprintln!("Failed to start preparing {}: {}", meal_item_id, err);
+                return JobStep::Done;
+            }
+        }
+
+        keep_cooking(order_repo, kitchen_repo, clock, order_id, meal_item_id, ready_time)
+    })
+}
+
+// The chef waits until the meal item is ready, unless a manager voids it, which interrupts the job, so the chef stops cooking it
+// at once and takes the next meal item. Once the kitchen is interrupted, the chef stops at once too and the meal item
+// goes back to Received, to be cooked from the start on the next startup, see resume_kitchen.
+fn keep_cooking(order_repo: Arc<dyn OrderRepository>, kitchen_repo: Arc<KitchenRepo>, clock: Arc<dyn Clock>, order_id: Uuid, meal_item_id: Uuid, ready_time: DateTime<Utc>) -> JobStep {
+    let remaining = (ready_time - clock.now()).to_std().unwrap_or(Duration::ZERO);
+    if remaining.is_zero() {
+        kitchen_repo.release(meal_item_id);
+        match order_repo.update_order_meal_item_status(order_id, meal_item_id, MealItemStatus::ReadyForPickup, clock.now()) {
+            Ok(_) => println!("ready for pickup {}", meal_item_id),
+            Err(err) => eprintln!("Failed to mark {} ready for pickup: {}", meal_item_id, err),
+        }
+        return JobStep::Done;
+    }
+
+    JobStep::Wait(remaining, Box::new(move || {
+        if is_interrupted() {
+            kitchen_repo.release(meal_item_id);
+            match order_repo.update_order_meal_item_status(order_id, meal_item_id, MealItemStatus::Received, clock.now()) {
+                Ok(_) => println!("stopped preparing {} as the kitchen is closing", meal_item_id),
+                Err(err) => eprintln!("Failed to put {} back as the kitchen is closing: {}", meal_item_id, err),
+            }
+            return JobStep::Done;
+        }
+        // Only a stored void interrupts the job on its own, see VoidMealItemHandler
+        if current_job_interrupt().is_some_and(|job_interrupt| job_interrupt.is_interrupted()) {
+            kitchen_repo.release(meal_item_id);
+            println!("stopped preparing {} as it was voided", meal_item_id);
+            return JobStep::Done;
+        }
+        keep_cooking(order_repo, kitchen_repo, clock, order_id, meal_item_id, ready_time)
+    }))
+}
+
+fn cooking_duration(cooking_time_in_min: u32) -> Duration {
+    Duration::from_secs(cooking_time_in_min as u64 * 60)
+}
+
+// The duration is in kitchen time, the clock cooking the meal item decides how long it really takes, see Clock::kitchen_duration
+pub fn cooking_job_info(order: &Order, meal_item: &MealItem) -> JobInfo {
+    let priority = match order.get_priority() {
+        OrderPriority::Normal => 0,
+        OrderPriority::Rush => 1,
+        OrderPriority::Vip => 2,
+    };
+    JobInfo {
+        id: meal_item.id(),
+        priority,
+        duration: cooking_duration(meal_item.cooking_time_in_min()),
+        requested_at: order.creation_time(),
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod cook_meal_item_test {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use crate::libraries::clock::{Clock, VirtualClock};
+    use crate::libraries::job_queue::SchedulingPolicy;
+    use crate::libraries::thread_pool::{ThreadPool, ThreadPoolDyn};
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::repositories::kitchen::KitchenRepo;
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::usecases::jobs::cook_meal_item::{cook_meal_item, cooking_job_info};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap() + TimeDelta::minutes(minutes)
+    }
+
+    #[test]
+    fn test_voided_meal_item_frees_its_chef() {
+        let clock = Arc::new(VirtualClock::new(at(0)));
+        let order_repo = Arc::new(OrderRepo::new());
+        let kitchen_repo = Arc::new(KitchenRepo::new());
+        let thread_pool = ThreadPool::new(1, SchedulingPolicy::Fifo, clock.clone());
+
+        let steak = MenuItem::new(String::from("steak"), Price::from_minor_units(2500, Currency::Usd).unwrap());
+        let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let steak_meal_item = MealItem::create(steak, &MockCookingTimeEstimator::new(20), clock.now());
+        let fries_meal_item = MealItem::create(fries, &MockCookingTimeEstimator::new(5), clock.now());
+        let order = Order::new(1, Currency::Usd, vec![steak_meal_item.clone(), fries_meal_item.clone()], clock.now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        for meal_item in [&steak_meal_item, &fries_meal_item] {
+            let job = cook_meal_item(order_repo.clone(), kitchen_repo.clone(), clock.clone(), order.id(), meal_item.id());
+            thread_pool.execute(job, cooking_job_info(&order, meal_item));
+        }
+        clock.wait_for_sleepers(1);
+        assert!(order_repo.void_order_meal_item(order.id(), steak_meal_item.id(), String::from("guest left"), at(0)).unwrap().0.is_some());
+        kitchen_repo.interrupt(steak_meal_item.id());
+
+        // Given voiding the steak interrupts its job, the chef takes the fries without the clock moving
+        while kitchen_repo.get_worker_id(fries_meal_item.id()).is_none() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        clock.wait_for_sleepers(1);
+        let fries_status = order_repo.get_order_meal_item(order.id(), fries_meal_item.id()).unwrap().unwrap();
+        assert_eq!((MealItemStatus::Preparing, at(0)), (fries_status.get_status(), fries_status.update_time()));
+        assert_eq!(None, kitchen_repo.get_worker_id(steak_meal_item.id()));
+
+        clock.advance(Duration::from_secs(5 * 60));
+        drop(thread_pool);
+
+        let fries_status = order_repo.get_order_meal_item(order.id(), fries_meal_item.id()).unwrap().unwrap();
+        assert_eq!((MealItemStatus::ReadyForPickup, at(5)), (fries_status.get_status(), fries_status.update_time()));
+        assert_eq!(MealItemStatus::Voided, order_repo.get_order_meal_item(order.id(), steak_meal_item.id()).unwrap().unwrap().get_status());
+        assert!(kitchen_repo.is_idle());
+    }
+}
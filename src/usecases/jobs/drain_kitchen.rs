@@ -0,0 +1,25 @@
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::libraries::job::JobInfo;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::usecases::jobs::kitchen::Kitchen;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// Lets the chefs of every station cook what is queued until the kitchen is idle or the deadline passes, whichever comes first.
+// Jobs still queued by then are checkpointed: they are taken off the queue and their meal items stay Received,
+// to be queued again on the next startup, see resume_kitchen. The chefs are interrupted too, so the meal items
+// being prepared go back to Received at once and the pools can be dropped without waiting for them to be cooked.
+// Returns the info of the checkpointed jobs.
+pub fn drain_kitchen(kitchen: &Kitchen, kitchen_repo: &KitchenRepo, deadline: Duration) -> Vec<JobInfo> {
+    let started = Instant::now();
+    while started.elapsed() < deadline {
+        if kitchen.is_queue_empty() && kitchen_repo.is_idle() {
+            return Vec::new();
+        }
+        thread::sleep(POLL_INTERVAL.min(deadline.saturating_sub(started.elapsed())));
+    }
+    let checkpointed = kitchen.cancel_queued_jobs();
+    kitchen.interrupt();
+    checkpointed
+}
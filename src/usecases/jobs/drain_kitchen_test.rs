@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod drain_kitchen_test {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use chrono::{TimeZone, Utc};
+    use crate::libraries::async_pool::AsyncPool;
+    use crate::libraries::clock::{Clock, VirtualClock};
+    use crate::libraries::job_queue::SchedulingPolicy;
+    use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+    use crate::libraries::thread_pool::{ThreadPool, ThreadPoolDyn};
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::repositories::kitchen::KitchenRepo;
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::usecases::jobs::kitchen::Kitchen;
+    use crate::usecases::jobs::cook_meal_item::{cook_meal_item, cooking_job_info};
+    use crate::usecases::jobs::drain_kitchen::drain_kitchen;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    #[test]
+    fn test_drain_idle_kitchen() {
+        let kitchen = Kitchen::new(|_| Arc::new(MockThreadPool::new()));
+
+        let checkpointed = drain_kitchen(&kitchen, &KitchenRepo::new(), Duration::from_secs(30));
+
+        assert!(checkpointed.is_empty());
+    }
+
+    #[test]
+    fn test_drain_kitchen_checkpoints_queued_jobs() {
+        let clock = Arc::new(VirtualClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap()));
+        let order_repo = Arc::new(OrderRepo::new());
+        let kitchen_repo = Arc::new(KitchenRepo::new());
+        let thread_pool: Arc<dyn ThreadPoolDyn> = Arc::new(ThreadPool::new(1, SchedulingPolicy::Fifo, clock.clone()));
+        let kitchen = Kitchen::new(|_| thread_pool.clone());
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let cooked_meal_item = MealItem::create(menu_item.clone(), &MockCookingTimeEstimator::new(10), clock.now());
+        let queued_meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), clock.now());
+        let order = Order::new(1, Currency::Usd, vec![cooked_meal_item.clone(), queued_meal_item.clone()], clock.now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        for meal_item in [&cooked_meal_item, &queued_meal_item] {
+            let job = cook_meal_item(order_repo.clone(), kitchen_repo.clone(), clock.clone(), order.id(), meal_item.id());
+            kitchen.execute(meal_item.station(), job, cooking_job_info(&order, meal_item));
+        }
+
+        // Given the only chef is busy past the deadline, the meal item still queued is checkpointed
+        clock.wait_for_sleepers(1);
+        let checkpointed = drain_kitchen(&kitchen, &kitchen_repo, Duration::from_millis(100));
+        assert_eq!(vec![queued_meal_item.id()], checkpointed.iter().map(|info| info.id).collect::<Vec<_>>());
+        assert!(thread_pool.queued_jobs().is_empty());
+
+        // The chef stops preparing the other one without the clock moving, so dropping the pool doesn't wait for it to be cooked
+        drop(kitchen);
+        drop(thread_pool);
+
+        assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), cooked_meal_item.id()).unwrap().unwrap().get_status());
+        assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), queued_meal_item.id()).unwrap().unwrap().get_status());
+        assert!(kitchen_repo.is_idle());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_drain_kitchen_stops_async_chefs() {
+        let clock = Arc::new(VirtualClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap()));
+        let order_repo = Arc::new(OrderRepo::new());
+        let kitchen_repo = Arc::new(KitchenRepo::new());
+        let async_pool: Arc<dyn ThreadPoolDyn> = Arc::new(AsyncPool::new(1, SchedulingPolicy::Fifo, clock.clone()));
+        let kitchen = Kitchen::new(|_| async_pool.clone());
+
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        let meal_item = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), clock.now());
+        let order = Order::new(1, Currency::Usd, vec![meal_item.clone()], clock.now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        let job = cook_meal_item(order_repo.clone(), kitchen_repo.clone(), clock.clone(), order.id(), meal_item.id());
+        kitchen.execute(meal_item.station(), job, cooking_job_info(&order, &meal_item));
+
+        clock.wait_for_sleepers(1);
+        // Pools of tasks are dropped away from the runtime, as in main
+        let (checkpointed, is_idle) = tokio::task::spawn_blocking(move || {
+            let checkpointed = drain_kitchen(&kitchen, &kitchen_repo, Duration::from_millis(100));
+            drop(kitchen);
+            drop(async_pool);
+            (checkpointed, kitchen_repo.is_idle())
+        }).await.unwrap();
+
+        assert!(checkpointed.is_empty());
+        assert!(is_idle);
+        assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), meal_item.id()).unwrap().unwrap().get_status());
+    }
+}
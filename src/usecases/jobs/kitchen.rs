@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use crate::libraries::job::{Job, JobInfo};
+use crate::libraries::thread_pool::ThreadPoolDyn;
+use crate::models::station::Station;
+
+// Chef pools of the kitchen, one per station, each cooking the meal items of the menu items made at its station.
+// Stations may share a pool, ex. in tests, as long as their meal items are told apart by station.
+pub struct Kitchen {
+    pools: Vec<(Station, Arc<dyn ThreadPoolDyn>)>,
+}
+
+impl Kitchen {
+    pub fn new(mut pool_of: impl FnMut(Station) -> Arc<dyn ThreadPoolDyn>) -> Self {
+        Kitchen {
+            pools: Station::ALL.into_iter().map(|station| (station, pool_of(station))).collect(),
+        }
+    }
+
+    pub fn pool(&self, station: Station) -> &Arc<dyn ThreadPoolDyn> {
+        let (_, pool) = self.pools.iter()
+            .find(|(pool_station, _)| *pool_station == station)
+            .expect("every station has a pool");
+        pool
+    }
+
+    // Stations with their pools, in the order of Station::ALL
+    pub fn pools(&self) -> &[(Station, Arc<dyn ThreadPoolDyn>)] {
+        &self.pools
+    }
+
+    // Queues the job on the pool of the station
+    pub fn execute(&self, station: Station, job: Job, info: JobInfo) {
+        self.pool(station).execute(job, info)
+    }
+
+    // Takes the jobs waiting at every station off the queues, see ThreadPoolDyn::cancel_queued_jobs
+    pub fn cancel_queued_jobs(&self) -> Vec<JobInfo> {
+        self.pools.iter().flat_map(|(_, pool)| pool.cancel_queued_jobs()).collect()
+    }
+
+    // Stops the chefs of every station waiting in their jobs, see ThreadPoolDyn::interrupt
+    pub fn interrupt(&self) {
+        for (_, pool) in self.pools.iter() {
+            pool.interrupt();
+        }
+    }
+
+    // Whether no job waits at any station
+    pub fn is_queue_empty(&self) -> bool {
+        self.pools.iter().all(|(_, pool)| pool.queued_jobs().is_empty())
+    }
+}
+
+// Parses the number of chefs of some stations, ex. "grill=3,fryer=2"; stations not listed are left out.
+// Every station listed needs a chef, or its meal items would never be cooked.
+pub fn parse_station_sizes(s: &str) -> Result<Vec<(Station, usize)>, String> {
+    s.split(',')
+        .map(|station_size| {
+            let (station, size) = station_size.split_once('=')
+                .ok_or_else(|| format!("expected station=size, got '{}'", station_size))?;
+            let station = station.trim().parse::<Station>()?;
+            let size = size.trim().parse::<usize>().ok().filter(|size| *size > 0)
+                .ok_or_else(|| format!("invalid number of chefs '{}' for station {}", size, station))?;
+            Ok((station, size))
+        })
+        .collect()
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod kitchen_test {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use chrono::Utc;
+    use crate::libraries::job::JobStep;
+    use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+    use crate::libraries::thread_pool::ThreadPoolDyn;
+    use crate::models::currency::Currency;
+    use crate::models::meal::MealItem;
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::models::station::Station;
+    use crate::usecases::jobs::cook_meal_item::cooking_job_info;
+    use crate::usecases::jobs::kitchen::{parse_station_sizes, Kitchen};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    #[test]
+    fn test_parse_station_sizes() {
+        assert_eq!(Ok(vec![(Station::Grill, 3), (Station::Fryer, 2)]), parse_station_sizes("grill=3,fryer=2"));
+        assert_eq!(Ok(vec![(Station::Bar, 1)]), parse_station_sizes(" bar = 1 "));
+        assert!(parse_station_sizes("grill").is_err());
+        assert!(parse_station_sizes("oven=2").is_err());
+        assert!(parse_station_sizes("grill=-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_station_sizes_rejects_stations_without_chefs() {
+        assert_eq!(Err(String::from("invalid number of chefs '0' for station bar")), parse_station_sizes("bar=0"));
+        assert!(parse_station_sizes("grill=3,fryer= 0").is_err());
+    }
+
+    #[test]
+    fn test_kitchen_routes_jobs_to_their_station() {
+        let pools: HashMap<Station, Arc<MockThreadPool>> = Station::ALL.into_iter()
+            .map(|station| (station, Arc::new(MockThreadPool::new())))
+            .collect();
+        let kitchen = Kitchen::new(|station| pools[&station].clone());
+
+        let fries = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()).with_station(Station::Fryer);
+        let beer = MenuItem::new(String::from("beer"), Price::from_minor_units(600, Currency::Usd).unwrap()).with_station(Station::Bar);
+        let fries_meal_item = MealItem::create(fries, &MockCookingTimeEstimator::new(5), Utc::now());
+        let beer_meal_item = MealItem::create(beer, &MockCookingTimeEstimator::new(1), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![fries_meal_item.clone(), beer_meal_item.clone()], Utc::now()).unwrap();
+        assert!(kitchen.is_queue_empty());
+        for meal_item in [&fries_meal_item, &beer_meal_item] {
+            kitchen.execute(meal_item.station(), Box::new(|| JobStep::Done), cooking_job_info(&order, meal_item));
+        }
+
+        // Given each meal item is queued at its own station, the other stations stay idle
+        assert_eq!(vec![fries_meal_item.id()], pools[&Station::Fryer].queued_jobs().iter().map(|info| info.id).collect::<Vec<_>>());
+        assert_eq!(vec![beer_meal_item.id()], pools[&Station::Bar].queued_jobs().iter().map(|info| info.id).collect::<Vec<_>>());
+        assert!(pools[&Station::Grill].queued_jobs().is_empty());
+        assert!(!kitchen.is_queue_empty());
+
+        assert_eq!(2, kitchen.cancel_queued_jobs().len());
+        assert!(kitchen.is_queue_empty());
+        for pool in pools.values() {
+            pool.wait();
+        }
+    }
+}
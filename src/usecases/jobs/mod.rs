@@ -0,0 +1,12 @@
+pub mod cook_meal_item;
+pub mod ready_time;
+pub mod drain_kitchen;
+pub mod kitchen;
+pub mod resume_kitchen;
+pub mod price_order;
+mod cook_meal_item_test;
+mod ready_time_test;
+mod drain_kitchen_test;
+mod resume_kitchen_test;
+mod kitchen_test;
+mod price_order_test;
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::models::order::Order;
+use crate::models::promotion::apply_promotions;
+use crate::models::tax::TaxPolicy;
+use crate::repositories::order::{OrderRepository, OrderStoreError};
+use crate::repositories::promotion::PromotionRepo;
+
+// Works out the promotions the order gets from those running now and takes them off it, and charges it the taxes of the policy.
+// The promotions are worked out by the repository with the order locked, so a change made meanwhile can't leave them stale.
+// Returns whether the pricing of the order changed; closed orders keep the pricing they were paid with.
+pub fn price_order(order_repo: &dyn OrderRepository, promotion_repo: &PromotionRepo, tax_policy: &TaxPolicy, order_id: Uuid, now: DateTime<Utc>) -> Result<bool, OrderStoreError> {
+    let running = promotion_repo.get_all();
+    let promotions = |order: &Order| apply_promotions(&running, order);
+    Ok(order_repo.set_order_pricing(order_id, &promotions, tax_policy.clone(), now)?.0)
+}
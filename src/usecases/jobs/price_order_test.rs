@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod price_order_test {
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::models::currency::Currency;
+    use crate::models::meal::MealItem;
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::payment::{Payment, PaymentMethod};
+    use crate::models::price::Price;
+    use crate::models::promotion::{Promotion, PromotionTarget, Reward};
+    use crate::models::tax::{Rate, Tax, TaxPolicy};
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::repositories::promotion::PromotionRepo;
+    use crate::usecases::jobs::price_order::price_order;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn usd(minor_units: i64) -> Price {
+        Price::from_minor_units(minor_units, Currency::Usd).unwrap()
+    }
+
+    #[test]
+    fn test_price_order() {
+        let order_repo = OrderRepo::new();
+        let promotion_repo = PromotionRepo::new();
+        let tax_policy = TaxPolicy::default();
+        let menu_item = MenuItem::new(String::from("burger"), usd(1000));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![burger], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+
+        assert!(!price_order(&order_repo, &promotion_repo, &tax_policy, Uuid::new_v4(), Utc::now()).unwrap());
+        assert!(!price_order(&order_repo, &promotion_repo, &tax_policy, order.id(), Utc::now()).unwrap());
+
+        let promotion = Promotion::new(String::from("10% off"), Currency::Usd,
+                                       Reward::PercentOff { target: PromotionTarget::Order, percent: 10 }, None).unwrap();
+        promotion_repo.add(promotion.clone());
+        assert!(price_order(&order_repo, &promotion_repo, &tax_policy, order.id(), Utc::now()).unwrap());
+        assert_eq!(usd(900), order_repo.get_order(order.id()).unwrap().unwrap().get_total_price());
+
+        // An order paid in full keeps the promotions it was paid with
+        order_repo.add_order_payment(order.id(), Payment::new(PaymentMethod::Cash, None, usd(900), Utc::now())).unwrap().unwrap();
+        promotion_repo.remove(promotion.id());
+        assert!(!price_order(&order_repo, &promotion_repo, &tax_policy, order.id(), Utc::now()).unwrap());
+        assert_eq!(usd(900), order_repo.get_order(order.id()).unwrap().unwrap().get_total_price());
+    }
+
+    #[test]
+    fn test_price_order_with_taxes() {
+        let order_repo = OrderRepo::new();
+        let promotion_repo = PromotionRepo::new();
+        let menu_item = MenuItem::new(String::from("burger"), usd(1000));
+        let burger = MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now());
+        let order = Order::new(1, Currency::Usd, vec![burger], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+
+        let tax_policy = TaxPolicy {
+            taxes: vec![Tax { name: String::from("sales tax"), rate: "8.875".parse::<Rate>().unwrap(), categories: None }],
+            ..TaxPolicy::default()
+        };
+        assert!(price_order(&order_repo, &promotion_repo, &tax_policy, order.id(), Utc::now()).unwrap());
+        let order = order_repo.get_order(order.id()).unwrap().unwrap();
+        assert_eq!(tax_policy, order.get_tax_policy());
+        // 8.875% of 1000 is 88.75, rounded half up to 89
+        assert_eq!(usd(1089), order.get_total_price());
+        assert!(!price_order(&order_repo, &promotion_repo, &tax_policy, order.id(), Utc::now()).unwrap());
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::libraries::clock::Clock;
+use crate::libraries::job::JobInfo;
+use crate::models::meal::MealItem;
+use crate::models::order::Order;
+use crate::models::station::Station;
+use crate::repositories::kitchen::KitchenRepo;
+use crate::usecases::jobs::kitchen::Kitchen;
+
+// Estimated time every meal item in the kitchen will be ready, as of the time it was estimated
+pub struct ReadyTimes {
+    meal_items: HashMap<Uuid, DateTime<Utc>>,
+    // Time every station is done with the meal items it has, none if it has no chefs for those waiting
+    stations: HashMap<Station, Option<DateTime<Utc>>>,
+}
+
+impl ReadyTimes {
+    // Cooked meal items were ready when they reached the pass; removed or voided ones, or those no worker will pick up,
+    // ex. restored after a restart, have no ready time
+    pub fn meal_item(&self, meal_item: &MealItem) -> Option<DateTime<Utc>> {
+        if meal_item.is_removed() || meal_item.is_voided() {
+            return None;
+        }
+        if meal_item.is_cooked() {
+            return meal_item.ready_time();
+        }
+        self.meal_items.get(&meal_item.id()).copied()
+    }
+
+    // The order is ready once its last meal item is, none if any of its meal items has no ready time; voided ones are never served
+    pub fn order(&self, order: &Order) -> Option<DateTime<Utc>> {
+        let mut ready_time = None;
+        for meal_item_arc in order.get_meal_items().iter() {
+            let meal_item = meal_item_arc.lock().unwrap();
+            if meal_item.is_removed() || meal_item.is_voided() {
+                continue;
+            }
+            let meal_item_ready_time = self.meal_item(&meal_item)?;
+            ready_time = ready_time.max(Some(meal_item_ready_time));
+        }
+        ready_time
+    }
+
+    // The station is clear once the last meal item it has is ready, right away if it has none
+    pub fn station(&self, station: Station) -> Option<DateTime<Utc>> {
+        self.stations.get(&station).copied().flatten()
+    }
+}
+
+pub struct ReadyTimeEstimator {
+    kitchen_repo: Arc<KitchenRepo>,
+    kitchen: Arc<Kitchen>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ReadyTimeEstimator {
+    pub fn new(kitchen_repo: Arc<KitchenRepo>, kitchen: Arc<Kitchen>, clock: Arc<dyn Clock>) -> Self {
+        ReadyTimeEstimator {
+            kitchen_repo,
+            kitchen,
+            clock,
+        }
+    }
+
+    // Every pool cooks on its own, so each one is replayed with its own chefs, the meal items they are preparing and its queue.
+    // Only the kitchen is read, never the orders, so estimating costs no more than the chefs and jobs there are.
+    // Stations sharing a pool share its chefs, so they are replayed together and are clear at the same time.
+    pub fn estimate(&self) -> ReadyTimes {
+        let now = self.clock.now();
+        let mut ready_times = ReadyTimes {
+            meal_items: HashMap::new(),
+            stations: HashMap::new(),
+        };
+        let pools = self.kitchen.pools();
+        for (index, (station, pool)) in pools.iter().enumerate() {
+            if let Some((first_station, _)) = pools[..index].iter().find(|(_, other_pool)| Arc::ptr_eq(other_pool, pool)) {
+                let clear_time = ready_times.stations[first_station];
+                ready_times.stations.insert(*station, clear_time);
+                continue;
+            }
+            // Meal items being prepared keep their worker busy until they are done, ex. a 15 minute dish started 5 minutes ago
+            // keeps its worker for 10 more minutes.
+            let preparing: Vec<(Uuid, DateTime<Utc>)> = pools[index..].iter()
+                .filter(|(_, other_pool)| Arc::ptr_eq(other_pool, pool))
+                .flat_map(|(pool_station, _)| self.kitchen_repo.get_preparing(*pool_station))
+                .collect();
+            // Queued jobs take as long as the clock lets the kitchen cook them
+            let queued_jobs: Vec<JobInfo> = pool.queued_jobs().into_iter()
+                .map(|info| JobInfo { duration: self.clock.kitchen_duration(info.duration), ..info })
+                .collect();
+
+            let pool_ready_times = estimate_ready_times(&preparing, &queued_jobs, pool.worker_count(), now);
+            let clear_time = if pool_ready_times.len() < preparing.len() + queued_jobs.len() {
+                None
+            } else {
+                Some(pool_ready_times.values().copied().max().unwrap_or(now))
+            };
+            ready_times.stations.insert(*station, clear_time);
+            ready_times.meal_items.extend(pool_ready_times);
+        }
+        ready_times
+    }
+}
+
+// Replays the kitchen from now on: each worker finishes the meal item it is preparing, then whichever worker is free first
+// takes the next queued job, in the order the pool would pick them
+pub fn estimate_ready_times(preparing: &[(Uuid, DateTime<Utc>)], queued_jobs: &[JobInfo], worker_count: usize, now: DateTime<Utc>) -> HashMap<Uuid, DateTime<Utc>> {
+    let mut ready_times = HashMap::new();
+    let mut free_times = vec![now; worker_count.max(preparing.len())];
+    for (free_time, (meal_item_id, done_time)) in free_times.iter_mut().zip(preparing.iter()) {
+        // A meal item taking longer than its cooking time is expected to be ready any moment
+        *free_time = (*done_time).max(now);
+        ready_times.insert(*meal_item_id, *free_time);
+    }
+    if free_times.is_empty() {
+        return ready_times;
+    }
+
+    for info in queued_jobs.iter() {
+        let free_time = free_times.iter_mut().min().unwrap();
+        *free_time += info.duration;
+        ready_times.insert(info.id, *free_time);
+    }
+    ready_times
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod ready_time_test {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use chrono::{TimeDelta, Utc};
+    use uuid::Uuid;
+    use crate::libraries::clock::SystemClock;
+    use crate::libraries::job::{JobInfo, JobStep};
+    use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+    use crate::libraries::thread_pool::ThreadPoolDyn;
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::repositories::kitchen::{Assignment, KitchenRepo};
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::usecases::jobs::cook_meal_item::cooking_job_info;
+    use crate::usecases::jobs::kitchen::Kitchen;
+    use crate::usecases::jobs::ready_time::{estimate_ready_times, ReadyTimeEstimator};
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn job_info(duration_in_sec: u64) -> JobInfo {
+        JobInfo {
+            id: Uuid::new_v4(),
+            priority: 0,
+            duration: Duration::from_secs(duration_in_sec),
+            requested_at: Utc::now(),
+        }
+    }
+
+    fn create_meal_item() -> MealItem {
+        MealItem::create(MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap()), &MockCookingTimeEstimator::new(10), Utc::now())
+    }
+
+    #[test]
+    fn test_estimate_ready_times() {
+        let now = Utc::now();
+        let preparing_id = Uuid::new_v4();
+        let long = job_info(10);
+        let short = job_info(3);
+        let other_short = job_info(4);
+
+        // Given one of two workers is busy for 5 more seconds, the long job goes to the free worker,
+        // the short one waits for the busy worker and the last one goes to whichever worker is free first
+        let ready_times = estimate_ready_times(&[(preparing_id, now + TimeDelta::seconds(5))], &[long, short, other_short], 2, now);
+
+        assert_eq!(now + TimeDelta::seconds(5), ready_times[&preparing_id]);
+        assert_eq!(now + TimeDelta::seconds(10), ready_times[&long.id]);
+        assert_eq!(now + TimeDelta::seconds(8), ready_times[&short.id]);
+        assert_eq!(now + TimeDelta::seconds(12), ready_times[&other_short.id]);
+    }
+
+    #[test]
+    fn test_estimate_ready_times_of_overdue_meal_item() {
+        let now = Utc::now();
+        let preparing_id = Uuid::new_v4();
+        let queued = job_info(3);
+
+        let ready_times = estimate_ready_times(&[(preparing_id, now - TimeDelta::seconds(5))], &[queued], 1, now);
+
+        assert_eq!(now, ready_times[&preparing_id]);
+        assert_eq!(now + TimeDelta::seconds(3), ready_times[&queued.id]);
+    }
+
+    #[test]
+    fn test_estimate_ready_times_without_workers() {
+        let queued = job_info(3);
+
+        let ready_times = estimate_ready_times(&[], &[queued], 0, Utc::now());
+
+        assert!(ready_times.is_empty());
+    }
+
+    #[test]
+    fn test_ready_time_estimator_estimate() {
+        let order_repo = Arc::new(OrderRepo::new());
+        let kitchen_repo = Arc::new(KitchenRepo::new());
+        let thread_pool = Arc::new(MockThreadPool::with_worker_count(1));
+        let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+        let estimator = ReadyTimeEstimator::new(kitchen_repo.clone(), kitchen.clone(), Arc::new(SystemClock));
+
+        let preparing_meal_item = create_meal_item();
+        let queued_meal_item = create_meal_item();
+        let completed_meal_item = create_meal_item();
+        let removed_meal_item = create_meal_item();
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![preparing_meal_item.clone(), queued_meal_item.clone(), completed_meal_item.clone(), removed_meal_item.clone()], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        order_repo.remove_order_meal_items(order.id(), vec![removed_meal_item.id()], Utc::now()).unwrap();
+        order_repo.update_order_meal_item_status(order.id(), completed_meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+        let preparing_ready_time = Utc::now() + TimeDelta::minutes(5);
+        kitchen_repo.assign(preparing_meal_item.id(), Assignment { worker_id: 0, station: preparing_meal_item.station(), ready_time: preparing_ready_time });
+        order_repo.update_order_meal_item_status(order.id(), preparing_meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+        thread_pool.execute(Box::new(|| JobStep::Done), cooking_job_info(&order, &queued_meal_item));
+
+        let ready_times = estimator.estimate();
+
+        // Given the only worker is preparing a meal item, the queued one is ready once both are cooked
+        let order = order_repo.get_order(order.id()).unwrap().unwrap();
+        let preparing_meal_item = order_repo.get_order_meal_item(order.id(), preparing_meal_item.id()).unwrap().unwrap();
+        let completed_meal_item = order_repo.get_order_meal_item(order.id(), completed_meal_item.id()).unwrap().unwrap();
+        let removed_meal_item = order_repo.get_order_meal_item(order.id(), removed_meal_item.id()).unwrap().unwrap();
+        let queued_ready_time = preparing_ready_time + cooking_job_info(&order, &queued_meal_item).duration;
+
+        assert_eq!(Some(preparing_ready_time), ready_times.meal_item(&preparing_meal_item));
+        assert_eq!(Some(queued_ready_time), ready_times.meal_item(&queued_meal_item));
+        assert_eq!(Some(completed_meal_item.update_time()), ready_times.meal_item(&completed_meal_item));
+        assert_eq!(None, ready_times.meal_item(&removed_meal_item));
+        assert_eq!(Some(queued_ready_time), ready_times.order(&order));
+        thread_pool.wait();
+    }
+
+    #[test]
+    fn test_ready_time_estimator_estimate_unqueued_meal_item() {
+        let order_repo = Arc::new(OrderRepo::new());
+        let thread_pool = Arc::new(MockThreadPool::new());
+        let kitchen = Arc::new(Kitchen::new(|_| thread_pool.clone()));
+        let estimator = ReadyTimeEstimator::new(Arc::new(KitchenRepo::new()), kitchen.clone(), Arc::new(SystemClock));
+
+        // Given the meal item was never queued, ex. restored after a restart, neither it nor its order has a ready time
+        let meal_item = create_meal_item();
+        let mut order = Order::new(1, Currency::Usd, vec![], Utc::now()).unwrap();
+        order.add_meal_items(vec![meal_item.clone()], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+
+        let ready_times = estimator.estimate();
+
+        assert_eq!(None, ready_times.meal_item(&meal_item));
+        assert_eq!(None, ready_times.order(&order));
+        assert_eq!(None, ready_times.order(&Order::new(2, Currency::Usd, vec![], Utc::now()).unwrap()));
+    }
+}
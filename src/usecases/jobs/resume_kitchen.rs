@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use crate::libraries::clock::Clock;
+use crate::models::meal::{MealItem, MealItemStatus};
+use crate::repositories::kitchen::KitchenRepo;
+use crate::repositories::order::{OrderRepository, OrderStoreError};
+use crate::usecases::jobs::cook_meal_item::{cook_meal_item, cooking_job_info};
+use crate::usecases::jobs::kitchen::Kitchen;
+
+// Queues the meal items left waiting when the process last stopped, ex. those checkpointed by drain_kitchen, at their stations, oldest first.
+// Meal items left Preparing, ex. after a crash, go back to Received and are cooked again from the start.
+// Returns the number of meal items queued.
+pub fn resume_kitchen(order_repo: Arc<dyn OrderRepository>, kitchen_repo: Arc<KitchenRepo>, kitchen: &Kitchen, clock: Arc<dyn Clock>) -> Result<usize, OrderStoreError> {
+    let mut orders = order_repo.get_active_orders()?;
+    orders.sort_by_key(|order| order.creation_time());
+
+    let mut queued = 0;
+    for order in orders {
+        let mut meal_items: Vec<MealItem> = order.get_meal_items().iter()
+            .map(|meal_item_arc| meal_item_arc.lock().unwrap().clone())
+            .filter(|meal_item| !meal_item.is_removed() && matches!(meal_item.get_status(), MealItemStatus::Received | MealItemStatus::Preparing))
+            .collect();
+        meal_items.sort_by_key(|meal_item| meal_item.creation_time());
+
+        for meal_item in meal_items {
+            if meal_item.get_status() == MealItemStatus::Preparing {
+                order_repo.update_order_meal_item_status(order.id(), meal_item.id(), MealItemStatus::Received, clock.now())?;
+            }
+            let job = cook_meal_item(order_repo.clone(), kitchen_repo.clone(), clock.clone(), order.id(), meal_item.id());
+            kitchen.execute(meal_item.station(), job, cooking_job_info(&order, &meal_item));
+            queued += 1;
+        }
+    }
+    Ok(queued)
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod resume_kitchen_test {
+    use std::sync::Arc;
+    use chrono::{TimeDelta, Utc};
+    use crate::libraries::clock::SystemClock;
+    use crate::libraries::mocks::thread_pool_mock::MockThreadPool;
+    use crate::libraries::thread_pool::ThreadPoolDyn;
+    use crate::models::currency::Currency;
+    use crate::models::meal::{MealItem, MealItemStatus};
+    use crate::models::menu::MenuItem;
+    use crate::models::order::Order;
+    use crate::models::price::Price;
+    use crate::repositories::kitchen::KitchenRepo;
+    use crate::repositories::order::{OrderRepo, OrderRepository};
+    use crate::usecases::jobs::kitchen::Kitchen;
+    use crate::usecases::jobs::resume_kitchen::resume_kitchen;
+    use crate::models::mocks::cooking_time_mock::MockCookingTimeEstimator;
+
+    fn create_meal_item(minutes_ago: i64) -> MealItem {
+        let menu_item = MenuItem::new(String::from("fries"), Price::from_minor_units(345, Currency::Usd).unwrap());
+        MealItem::create(menu_item, &MockCookingTimeEstimator::new(10), Utc::now() - TimeDelta::minutes(minutes_ago))
+    }
+
+    #[test]
+    fn test_resume_kitchen() {
+        let order_repo = Arc::new(OrderRepo::new());
+        let thread_pool = Arc::new(MockThreadPool::new());
+        let kitchen = Kitchen::new(|_| thread_pool.clone());
+
+        let received_meal_item = create_meal_item(5);
+        let preparing_meal_item = create_meal_item(10);
+        let completed_meal_item = create_meal_item(15);
+        let removed_meal_item = create_meal_item(20);
+        let order = Order::new(1, Currency::Usd, vec![received_meal_item.clone(), preparing_meal_item.clone(), completed_meal_item.clone(), removed_meal_item.clone()], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        order_repo.update_order_meal_item_status(order.id(), preparing_meal_item.id(), MealItemStatus::Preparing, Utc::now()).unwrap();
+        order_repo.update_order_meal_item_status(order.id(), completed_meal_item.id(), MealItemStatus::ReadyForPickup, Utc::now()).unwrap();
+        order_repo.remove_order_meal_items(order.id(), vec![removed_meal_item.id()], Utc::now()).unwrap();
+
+        let queued = resume_kitchen(order_repo.clone(), Arc::new(KitchenRepo::new()), &kitchen, Arc::new(SystemClock)).unwrap();
+
+        // Given the process stopped while a meal item was being prepared, it is cooked again from the start, oldest first
+        assert_eq!(2, queued);
+        assert_eq!(vec![preparing_meal_item.id(), received_meal_item.id()], thread_pool.queued_jobs().iter().map(|info| info.id).collect::<Vec<_>>());
+        assert_eq!(MealItemStatus::Received, order_repo.get_order_meal_item(order.id(), preparing_meal_item.id()).unwrap().unwrap().get_status());
+        thread_pool.wait();
+    }
+
+    #[test]
+    fn test_resume_kitchen_without_active_orders() {
+        let order_repo = Arc::new(OrderRepo::new());
+        let thread_pool = Arc::new(MockThreadPool::new());
+        let kitchen = Kitchen::new(|_| thread_pool.clone());
+
+        let order = Order::new(1, Currency::Usd, vec![create_meal_item(5)], Utc::now()).unwrap();
+        order_repo.add(order.clone()).unwrap();
+        order_repo.remove_order(order.id(), Utc::now()).unwrap();
+
+        assert_eq!(0, resume_kitchen(order_repo, Arc::new(KitchenRepo::new()), &kitchen, Arc::new(SystemClock)).unwrap());
+        assert!(thread_pool.queued_jobs().is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::order::Order;
+use crate::models::payment::{Payment, PaymentMethod};
+use crate::models::split::{CheckStatus, SubCheck};
+use crate::usecases::models::price_resp::PriceResp;
+use crate::usecases::models::promotion_resp::AppliedPromotionResp;
+use crate::usecases::models::tax_resp::TaxLineResp;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BillItemResp {
+    pub meal_item_id: Uuid,
+    pub name: String,
+    pub price: PriceResp,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PaymentResp {
+    pub payment_id: Uuid,
+    pub method: PaymentMethod,
+    pub reference: Option<String>,
+    // Check of the split bill the payment was made on
+    pub check: Option<String>,
+    pub amount: PriceResp,
+    pub time: DateTime<Utc>,
+}
+
+impl PaymentResp {
+    pub fn new(payment: &Payment) -> Self {
+        PaymentResp {
+            payment_id: payment.id(),
+            method: payment.method(),
+            reference: payment.reference(),
+            check: payment.check(),
+            amount: PriceResp::new(payment.amount()),
+            time: payment.time(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct SubCheckResp {
+    pub name: String,
+    // Empty when the bill is split evenly
+    pub meal_item_ids: Vec<Uuid>,
+    pub subtotal: PriceResp,
+    pub discount: PriceResp,
+    // Taxes added on top of the menu prices, none when the menu prices include them
+    pub tax: PriceResp,
+    pub service_charge: PriceResp,
+    pub total: PriceResp,
+    pub amount_paid: PriceResp,
+    pub balance_due: PriceResp,
+    pub status: CheckStatus,
+}
+
+impl SubCheckResp {
+    pub fn new(sub_check: &SubCheck) -> Self {
+        SubCheckResp {
+            name: sub_check.name(),
+            meal_item_ids: sub_check.meal_item_ids(),
+            subtotal: PriceResp::new(sub_check.subtotal()),
+            discount: PriceResp::new(sub_check.discount()),
+            tax: PriceResp::new(sub_check.tax()),
+            service_charge: PriceResp::new(sub_check.service_charge()),
+            total: PriceResp::new(sub_check.total()),
+            amount_paid: PriceResp::new(sub_check.amount_paid()),
+            balance_due: PriceResp::new(sub_check.balance_due()),
+            status: sub_check.status(),
+        }
+    }
+}
+
+// What the table is charged for, which are the meal items neither removed nor voided, and what it paid so far
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct BillResp {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub meal_items: Vec<BillItemResp>,
+    pub subtotal: PriceResp,
+    pub promotions: Vec<AppliedPromotionResp>,
+    pub discount: PriceResp,
+    pub taxes: Vec<TaxLineResp>,
+    pub service_charge: PriceResp,
+    // Grand total: the subtotal less the discount, plus the taxes not included in the menu prices and the service charge
+    pub total_price: PriceResp,
+    pub amount_paid: PriceResp,
+    pub balance_due: PriceResp,
+    pub payments: Vec<PaymentResp>,
+    // Checks the bill is split into, empty unless it is split
+    pub checks: Vec<SubCheckResp>,
+    pub is_closed: bool,
+    // Time the order was paid in full, none while it is open
+    pub closed_time: Option<DateTime<Utc>>,
+}
+
+impl BillResp {
+    pub fn new(order: &Order) -> Self {
+        let mut meal_items = Vec::new();
+        for meal_item_arc in order.get_meal_items() {
+            let meal_item = meal_item_arc.lock().unwrap();
+            if meal_item.is_removed() || meal_item.is_voided() {
+                continue;
+            }
+            meal_items.push((meal_item.creation_time(), BillItemResp {
+                meal_item_id: meal_item.id(),
+                name: meal_item.get_name(),
+                price: PriceResp::new(meal_item.price()),
+            }));
+        }
+        // Meal items are listed in the order they were ordered
+        meal_items.sort_by_key(|(creation_time, _)| *creation_time);
+
+        let charges = order.get_charges();
+        BillResp {
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            meal_items: meal_items.into_iter().map(|(_, meal_item)| meal_item).collect(),
+            subtotal: PriceResp::new(order.get_subtotal()),
+            promotions: order.get_promotions().iter().map(AppliedPromotionResp::new).collect(),
+            discount: PriceResp::new(order.get_discount()),
+            taxes: TaxLineResp::from_charges(&charges),
+            service_charge: PriceResp::new(charges.service_charge()),
+            total_price: PriceResp::new(order.get_total_price()),
+            amount_paid: PriceResp::new(order.get_amount_paid()),
+            balance_due: PriceResp::new(order.get_balance_due()),
+            payments: order.get_payments().iter().map(PaymentResp::new).collect(),
+            checks: order.get_sub_checks().iter().map(SubCheckResp::new).collect(),
+            is_closed: order.is_closed(),
+            closed_time: order.closed_time(),
+        }
+    }
+}
@@ -1,13 +1,53 @@
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 // can use enum
 pub const MESSAGE_ORDER_NOT_FOUND: &str = "There are no order associated with this table";
 pub const MESSAGE_ITEM_NOT_FOUND: &str = "The specified meal item can't be found for this table";
-pub const MESSAGE_ITEMS_PARTIALLY_REMOVED: &str = "If items can be removed, they are removed; otherwise, no operation since meals are either started preparing, completed, or simply not existed.";
-pub const MESSAGE_ORDER_REMOVAL_CONFLICT: &str = "Order cannot be removed as it is already started preparing, or completed";
-pub const MESSAGE_ORDER_ADD_CONFLICT: &str = "Order cannot be created since there is an ongoing order for this table";
+pub const MESSAGE_ORDER_ID_NOT_FOUND: &str = "The specified order can't be found";
+pub const MESSAGE_ORDER_ITEM_NOT_FOUND: &str = "The specified meal item can't be found for this order";
+pub const MESSAGE_ITEMS_PARTIALLY_REMOVED: &str = "If items can be removed, they are removed; otherwise, no operation since meals are either started preparing, completed, already paid for, or simply not existed.";
+pub const MESSAGE_ORDER_REMOVAL_CONFLICT: &str = "Order cannot be removed as it is already started preparing, completed, or paid for";
+pub const MESSAGE_ORDER_ADD_CONFLICT: &str = "Order cannot be created since the current order of this table is neither paid nor canceled";
+pub const MESSAGE_MENU_NOT_FOUND: &str = "The specified menu can't be found";
+pub const MESSAGE_MENU_ITEM_NOT_FOUND: &str = "The specified menu item can't be found in this menu";
+pub const MESSAGE_INVALID_PRICE: &str = "Price must be a non-negative whole number of minor units, ex. 5095 for 50.95";
+pub const MESSAGE_INVALID_COOKING_TIME_PROFILE: &str = "Cooking time profile is invalid";
+pub const MESSAGE_ORDER_PRICE_INVALID: &str = "Total price of the order can't be computed";
+pub const MESSAGE_ORDER_CURRENCY_MISMATCH: &str = "Meal items in an order must all be priced in the same currency";
+pub const MESSAGE_ORDER_EMPTY: &str = "Order must have at least one menu item";
+pub const MESSAGE_MENU_ITEMS_UNAVAILABLE: &str = "Some menu items are unknown or retired, no meal items are added";
+pub const MESSAGE_SHUTTING_DOWN: &str = "The restaurant is closing, no changes are accepted";
+pub const MESSAGE_MANAGER_ONLY: &str = "Only a manager can do this, the manager PIN is missing or wrong";
+pub const MESSAGE_VOID_REASON_MISSING: &str = "A reason is required to void a meal item";
+pub const MESSAGE_VOID_CONFLICT: &str = "Only meal items being prepared can be voided; those not started yet can be removed";
+pub const MESSAGE_ITEMS_PARTIALLY_SERVED: &str = "If items are ready for pickup, they are served; otherwise, no operation since meals are either not cooked yet, voided, or simply not existed.";
+pub const MESSAGE_ORDER_CLOSED: &str = "Order is paid in full and closed, its meal items can't be changed anymore";
+pub const MESSAGE_ORDER_NOT_OPEN: &str = "Order is paid in full or canceled, meal items can't be added to it anymore";
+pub const MESSAGE_INVALID_PAYMENT_AMOUNT: &str = "Payment amount must be a positive whole number of minor units, ex. 5095 for 50.95";
+pub const MESSAGE_PAYMENT_REFERENCE_MISSING: &str = "A reference is required for card payments";
+pub const MESSAGE_PAYMENT_REFUSED: &str = "Payment cannot be recorded";
+pub const MESSAGE_SPLIT_MODE_INVALID: &str = "Either checks or guests has to be given to split the bill";
+pub const MESSAGE_INVALID_SPLIT: &str = "Bill cannot be split this way";
+pub const MESSAGE_SPLIT_REFUSED: &str = "Bill cannot be split again";
+pub const MESSAGE_WASTE_TOTAL_INVALID: &str = "Total wasted cost can't be computed";
+pub const MESSAGE_INVALID_PROMOTION: &str = "Promotion is invalid";
+pub const MESSAGE_PROMOTION_NOT_FOUND: &str = "The specified promotion can't be found";
+pub const MESSAGE_GUESTS_INVALID: &str = "Guests must be from 1 to 100";
+pub const MESSAGE_QUANTITY_INVALID: &str = "Quantity of a menu item must be from 1 to 99";
+pub const MESSAGE_TOO_MANY_MEAL_ITEMS: &str = "At most 200 meal items can be ordered at once";
+pub const MESSAGE_RECEIPT_FORMAT_INVALID: &str = "Receipt format must be text, html or json";
+pub const MESSAGE_RECEIPT_WIDTH_INVALID: &str = "Receipt width must be 32 or 42 columns";
+pub const MESSAGE_EVENTS_CLOSED: &str = "The restaurant is closing, changes of orders are not streamed anymore";
+pub const MESSAGE_STORE_FAILED: &str = "Orders can't be read or saved right now, try again later";
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ErrResp {
     pub error_message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct UnavailableMenuItemsResp {
+    pub unavailable_menu_item_ids: Vec<Uuid>,
+    pub error_message: String,
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::cooking_time::CookingTimeProfile;
+use crate::models::currency::Currency;
+use crate::models::menu::{Menu, MenuItem};
+use crate::models::station::Station;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MenuItemResp {
+    pub menu_item_id: Uuid,
+    pub name: String,
+    pub price: String,
+    pub cooking_time_profile: CookingTimeProfile,
+    pub station: Station,
+    pub category: Option<String>,
+    pub is_retired: bool,
+}
+
+impl MenuItemResp {
+    pub fn new(menu_item: &MenuItem) -> Self {
+        MenuItemResp {
+            menu_item_id: menu_item.id(),
+            name: menu_item.get_name(),
+            price: menu_item.price().to_string(),
+            cooking_time_profile: menu_item.cooking_time_profile(),
+            station: menu_item.station(),
+            category: menu_item.category(),
+            is_retired: menu_item.is_retired(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MenuResp {
+    pub menu_id: Uuid,
+    pub name: String,
+    pub currency: Currency,
+    pub menu_items: Vec<MenuItemResp>,
+}
+
+impl MenuResp {
+    pub fn new(menu: &Menu) -> Self {
+        MenuResp {
+            menu_id: menu.id(),
+            name: menu.get_name(),
+            currency: menu.get_currency(),
+            menu_items: menu.get_menu_items().iter().map(MenuItemResp::new).collect(),
+        }
+    }
+}
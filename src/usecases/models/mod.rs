@@ -1,2 +1,11 @@
 pub mod error;
 pub mod order_resp;
+pub mod menu_resp;
+pub mod price_resp;
+pub mod waste_resp;
+pub mod order_key;
+pub mod bill_resp;
+pub mod promotion_resp;
+pub mod tax_resp;
+pub mod receipt_resp;
+pub mod order_event_resp;
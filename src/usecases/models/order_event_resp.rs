@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+use crate::models::order_event::{OrderChange, OrderEvent};
+
+// Data of a server-sent event, the fields of the change sit along with its type
+#[derive(Serialize, Debug, PartialEq)]
+pub struct OrderEventResp {
+    pub event_id: String,
+    pub order_id: Uuid,
+    pub table_id: u32,
+    #[serde(flatten)]
+    pub change: OrderChange,
+    pub time: DateTime<Utc>,
+}
+
+impl OrderEventResp {
+    pub fn new(event: &OrderEvent) -> Self {
+        OrderEventResp {
+            event_id: event.id().to_string(),
+            order_id: event.order_id(),
+            table_id: event.table_id(),
+            change: event.change(),
+            time: event.time(),
+        }
+    }
+}
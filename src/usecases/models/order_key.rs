@@ -0,0 +1,34 @@
+use uuid::Uuid;
+use crate::models::order::Order;
+use crate::repositories::order::{OrderRepository, OrderStoreError};
+use crate::usecases::models::error::{MESSAGE_ITEM_NOT_FOUND, MESSAGE_ORDER_ID_NOT_FOUND, MESSAGE_ORDER_ITEM_NOT_FOUND, MESSAGE_ORDER_NOT_FOUND};
+
+// Orders are addressed either by their id, or by a table, which refers to its current order
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrderKey {
+    Table(u32),
+    Id(Uuid),
+}
+
+impl OrderKey {
+    pub fn get_order(&self, order_repo: &dyn OrderRepository) -> Result<Option<Order>, OrderStoreError> {
+        match self {
+            OrderKey::Table(table_id) => order_repo.get_order_by_table_id(*table_id),
+            OrderKey::Id(order_id) => order_repo.get_order(*order_id),
+        }
+    }
+
+    pub fn order_not_found_message(&self) -> &'static str {
+        match self {
+            OrderKey::Table(_) => MESSAGE_ORDER_NOT_FOUND,
+            OrderKey::Id(_) => MESSAGE_ORDER_ID_NOT_FOUND,
+        }
+    }
+
+    pub fn meal_item_not_found_message(&self) -> &'static str {
+        match self {
+            OrderKey::Table(_) => MESSAGE_ITEM_NOT_FOUND,
+            OrderKey::Id(_) => MESSAGE_ORDER_ITEM_NOT_FOUND,
+        }
+    }
+}
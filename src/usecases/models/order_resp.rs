@@ -1,7 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::models::meal::MealItemStatus;
-use crate::models::order::{Order, OrderStatus};
+use crate::models::order::Order;
+use crate::usecases::jobs::ready_time::ReadyTimes;
+use crate::usecases::models::price_resp::PriceResp;
+use crate::usecases::models::promotion_resp::AppliedPromotionResp;
+use crate::usecases::models::tax_resp::TaxLineResp;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct MealItemResp {
@@ -11,29 +15,41 @@ pub struct MealItemResp {
     status: String,
     cooking_time_in_min: u32,
     is_remove: bool,
+    // Why a manager voided the meal item, none unless it is voided
+    void_reason: Option<String>,
+    // Estimated from the kitchen queue, none if the meal item is removed or waits for no worker
+    estimated_ready_time: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct OrderResp {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub priority: String,
+    pub guests: Option<u32>,
+    pub creation_time: DateTime<Utc>,
+    pub update_time: DateTime<Utc>,
     pub remaining_cooking_time_upper_bound_in_min: u32,
-    pub total_price: String,
+    // Time the last meal item is estimated to be ready, none if any of them has no estimate
+    pub estimated_ready_time: Option<DateTime<Utc>>,
+    // Price of the meal items charged, before promotions
+    pub subtotal: PriceResp,
+    // Promotions taken off the order, in the order they were applied
+    pub promotions: Vec<AppliedPromotionResp>,
+    // Sum of what the promotions saved
+    pub discount: PriceResp,
+    pub taxes: Vec<TaxLineResp>,
+    // Charged to parties of more guests than the tax policy allows, none otherwise
+    pub service_charge: PriceResp,
+    // Grand total: the subtotal less the discount, plus the taxes not included in the menu prices and the service charge
+    pub total_price: PriceResp,
     pub status: String,
     pub meal_items: Vec<MealItemResp>,
 }
 
 impl OrderResp {
-    pub fn new(order: Order, include_removed_items: bool) -> Self {
-        let mut order_resp = OrderResp {
-            total_price: order.get_total_price().to_string(),
-            remaining_cooking_time_upper_bound_in_min: 0,
-            status: OrderStatus::Received.to_string(),
-            meal_items: vec![],
-        };
-
-        let mut has_preparing = false;
-        let mut has_received = false;
-        let mut all_removed = true;
-
+    pub fn new(order: Order, include_removed_items: bool, ready_times: &ReadyTimes) -> Self {
+        let mut meal_items = vec![];
         for item_arc in order.get_meal_items().iter() {
             let item = item_arc.lock().unwrap();
 
@@ -45,39 +61,31 @@ impl OrderResp {
                     cooking_time_in_min: item.cooking_time_in_min(),
                     status: item.get_status().to_string(),
                     is_remove: item.is_removed(),
+                    void_reason: item.void_reason(),
+                    estimated_ready_time: ready_times.meal_item(&item),
                 };
-                order_resp.meal_items.push(item_resp);
-            }
-
-            if item.is_removed() {
-                continue;
-            }
-
-            all_removed = false;
-
-            match item.get_status() {
-                MealItemStatus::Received => {
-                    has_received = true;
-                    order_resp.remaining_cooking_time_upper_bound_in_min += item.cooking_time_in_min();
-                }
-                MealItemStatus::Preparing => {
-                    has_preparing = true;
-                    order_resp.remaining_cooking_time_upper_bound_in_min += item.cooking_time_in_min();
-                }
-                MealItemStatus::Completed => {}
+                meal_items.push(item_resp);
             }
         }
 
-        if all_removed {
-            order_resp.status = OrderStatus::Canceled.to_string();
-        } else if has_preparing {
-            order_resp.status = OrderStatus::Preparing.to_string();
-        } else if has_received {
-            order_resp.status = OrderStatus::Received.to_string();
-        } else {
-            order_resp.status = OrderStatus::Completed.to_string();
+        let charges = order.get_charges();
+        OrderResp {
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            priority: order.get_priority().to_string(),
+            guests: order.get_guests(),
+            creation_time: order.creation_time(),
+            update_time: order.update_time(),
+            subtotal: PriceResp::new(order.get_subtotal()),
+            promotions: order.get_promotions().iter().map(AppliedPromotionResp::new).collect(),
+            discount: PriceResp::new(order.get_discount()),
+            taxes: TaxLineResp::from_charges(&charges),
+            service_charge: PriceResp::new(charges.service_charge()),
+            total_price: PriceResp::new(order.get_total_price()),
+            remaining_cooking_time_upper_bound_in_min: order.get_remaining_cooking_time_in_min(),
+            estimated_ready_time: ready_times.order(&order),
+            status: order.get_order_status().to_string(),
+            meal_items,
         }
-
-        order_resp
     }
-}
\ No newline at end of file
+}
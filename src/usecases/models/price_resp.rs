@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use crate::models::currency::Currency;
+use crate::models::price::Price;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PriceResp {
+    // in minor units of the currency, ex. "5095" for 50.95 USD
+    pub amount: String,
+    pub currency: Currency,
+    // in major units of the currency, ex. "50.95" for USD
+    pub formatted_amount: String,
+}
+
+impl PriceResp {
+    pub fn new(price: Price) -> Self {
+        PriceResp {
+            amount: price.to_string(),
+            currency: price.currency(),
+            formatted_amount: price.to_major_units_string(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::currency::Currency;
+use crate::models::promotion::{AppliedPromotion, Promotion, PromotionTarget, Reward};
+use crate::usecases::models::price_resp::PriceResp;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromotionTargetResp {
+    MenuItem { menu_item_id: Uuid },
+    Category { category: String },
+    Order,
+}
+
+impl PromotionTargetResp {
+    pub fn new(target: &PromotionTarget) -> Self {
+        match target {
+            PromotionTarget::MenuItem(menu_item_id) => PromotionTargetResp::MenuItem { menu_item_id: *menu_item_id },
+            PromotionTarget::Category(category) => PromotionTargetResp::Category { category: category.clone() },
+            PromotionTarget::Order => PromotionTargetResp::Order,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RewardResp {
+    PercentOff { target: PromotionTargetResp, percent: u32 },
+    AmountOff { target: PromotionTargetResp, amount: PriceResp },
+    BuyXGetY { target: PromotionTargetResp, buy: u32, get: u32 },
+    ComboPrice { menu_item_ids: Vec<Uuid>, price: PriceResp },
+}
+
+impl RewardResp {
+    pub fn new(reward: &Reward) -> Self {
+        match reward {
+            Reward::PercentOff { target, percent } => RewardResp::PercentOff { target: PromotionTargetResp::new(target), percent: *percent },
+            Reward::AmountOff { target, amount } => RewardResp::AmountOff { target: PromotionTargetResp::new(target), amount: PriceResp::new(*amount) },
+            Reward::BuyXGetY { target, buy, get } => RewardResp::BuyXGetY { target: PromotionTargetResp::new(target), buy: *buy, get: *get },
+            Reward::ComboPrice { menu_item_ids, price } => RewardResp::ComboPrice { menu_item_ids: menu_item_ids.clone(), price: PriceResp::new(*price) },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PromotionResp {
+    pub promotion_id: Uuid,
+    pub name: String,
+    pub currency: Currency,
+    pub min_spend: Option<PriceResp>,
+    pub reward: RewardResp,
+}
+
+impl PromotionResp {
+    pub fn new(promotion: &Promotion) -> Self {
+        PromotionResp {
+            promotion_id: promotion.id(),
+            name: promotion.get_name(),
+            currency: promotion.get_currency(),
+            min_spend: promotion.min_spend().map(PriceResp::new),
+            reward: RewardResp::new(&promotion.reward()),
+        }
+    }
+}
+
+// A promotion taken off an order and how much it saved
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct AppliedPromotionResp {
+    pub promotion_id: Uuid,
+    pub name: String,
+    pub saved: PriceResp,
+}
+
+impl AppliedPromotionResp {
+    pub fn new(promotion: &AppliedPromotion) -> Self {
+        AppliedPromotionResp {
+            promotion_id: promotion.promotion_id(),
+            name: promotion.get_name(),
+            saved: PriceResp::new(promotion.saved()),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::receipt::{Receipt, Restaurant};
+use crate::usecases::models::bill_resp::{BillItemResp, PaymentResp};
+use crate::usecases::models::price_resp::PriceResp;
+use crate::usecases::models::promotion_resp::AppliedPromotionResp;
+use crate::usecases::models::tax_resp::TaxLineResp;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct RestaurantResp {
+    pub name: String,
+    pub address: Option<String>,
+    pub phone: Option<String>,
+}
+
+impl RestaurantResp {
+    pub fn new(restaurant: &Restaurant) -> Self {
+        RestaurantResp {
+            name: restaurant.name.clone(),
+            address: restaurant.address.clone(),
+            phone: restaurant.phone.clone(),
+        }
+    }
+}
+
+// Everything printed on the receipt of an order, for clients laying it out themselves
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ReceiptResp {
+    pub restaurant: RestaurantResp,
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub guests: Option<u32>,
+    // Meal items neither removed nor voided, in the order they were ordered
+    pub meal_items: Vec<BillItemResp>,
+    pub subtotal: PriceResp,
+    pub promotions: Vec<AppliedPromotionResp>,
+    pub discount: PriceResp,
+    pub taxes: Vec<TaxLineResp>,
+    pub service_charge: PriceResp,
+    pub total_price: PriceResp,
+    pub payments: Vec<PaymentResp>,
+    pub amount_paid: PriceResp,
+    pub balance_due: PriceResp,
+    pub creation_time: DateTime<Utc>,
+    // Time the order was paid in full, none while it is open
+    pub closed_time: Option<DateTime<Utc>>,
+    pub printed_time: DateTime<Utc>,
+}
+
+impl ReceiptResp {
+    pub fn new(receipt: &Receipt) -> Self {
+        let charges = receipt.charges();
+        ReceiptResp {
+            restaurant: RestaurantResp::new(&receipt.restaurant()),
+            order_id: receipt.order_id(),
+            table_id: receipt.table_id(),
+            guests: receipt.guests(),
+            meal_items: receipt.meal_items().iter().map(|meal_item| BillItemResp {
+                meal_item_id: meal_item.meal_item_id,
+                name: meal_item.name.clone(),
+                price: PriceResp::new(meal_item.price),
+            }).collect(),
+            subtotal: PriceResp::new(receipt.subtotal()),
+            promotions: receipt.promotions().iter().map(AppliedPromotionResp::new).collect(),
+            discount: PriceResp::new(receipt.discount()),
+            taxes: TaxLineResp::from_charges(&charges),
+            service_charge: PriceResp::new(charges.service_charge()),
+            total_price: PriceResp::new(receipt.total_price()),
+            payments: receipt.payments().iter().map(PaymentResp::new).collect(),
+            amount_paid: PriceResp::new(receipt.amount_paid()),
+            balance_due: PriceResp::new(receipt.balance_due()),
+            creation_time: receipt.creation_time(),
+            closed_time: receipt.closed_time(),
+            printed_time: receipt.printed_time(),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use crate::models::tax::{Charges, TaxLine, TaxPricing};
+use crate::usecases::models::price_resp::PriceResp;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TaxLineResp {
+    pub name: String,
+    // Percentage, ex. "8.875"
+    pub rate: String,
+    // What the meal items the tax applies to cost before it, once promotions are taken off
+    pub taxable_amount: PriceResp,
+    pub amount: PriceResp,
+    // Whether the tax is included in the menu prices rather than added on top of them
+    pub is_included: bool,
+}
+
+impl TaxLineResp {
+    pub fn new(tax_line: &TaxLine, pricing: TaxPricing) -> Self {
+        TaxLineResp {
+            name: tax_line.get_name(),
+            rate: tax_line.rate().to_string(),
+            taxable_amount: PriceResp::new(tax_line.taxable()),
+            amount: PriceResp::new(tax_line.amount()),
+            is_included: pricing == TaxPricing::Inclusive,
+        }
+    }
+
+    pub fn from_charges(charges: &Charges) -> Vec<Self> {
+        charges.tax_lines().iter().map(|tax_line| TaxLineResp::new(tax_line, charges.pricing())).collect()
+    }
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::models::meal::MealItem;
+use crate::models::order::Order;
+use crate::models::station::Station;
+use crate::usecases::models::price_resp::PriceResp;
+
+// Meal item a manager voided while it was being prepared, along with what it cost
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct WastedMealItemResp {
+    pub order_id: Uuid,
+    pub table_id: u32,
+    pub meal_item_id: Uuid,
+    pub name: String,
+    pub station: Station,
+    pub reason: String,
+    pub wasted_cost: PriceResp,
+    pub voided_time: DateTime<Utc>,
+}
+
+impl WastedMealItemResp {
+    // None unless the meal item is voided
+    pub fn new(order: &Order, meal_item: &MealItem) -> Option<Self> {
+        Some(WastedMealItemResp {
+            order_id: order.id(),
+            table_id: order.get_table_id(),
+            meal_item_id: meal_item.id(),
+            name: meal_item.get_name(),
+            station: meal_item.station(),
+            reason: meal_item.void_reason()?,
+            wasted_cost: PriceResp::new(meal_item.wasted_cost()?),
+            voided_time: meal_item.update_time(),
+        })
+    }
+}